@@ -4,40 +4,59 @@
 // @author Dennis Kuhnert <dennis.kuhnert@campus.tu-berlin.de>
 // @date 2017
 
-use bincode::{deserialize_from, serialize_into, Infinite};
-use clap::{App, AppSettings, Arg, SubCommand};
-#[cfg(feature = "libsnark")]
-use std::collections::HashMap;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::env;
 use std::fs::File;
-use std::io::{stdin, BufRead, BufReader, BufWriter, Write};
+use std::io::{stdin, BufRead, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::string::String;
-use zokrates_core::compile::compile;
+use zokrates_core::abi::Abi;
+use zokrates_core::compile::{check, compile_program, CompileErrorInner, Compiled};
+use zokrates_core::diagnostics::Diagnostic;
+use zokrates_core::flat_absy::FlatVariable;
+use zokrates_core::format;
 use zokrates_core::ir;
+use zokrates_core::ir::{r1cs_program, Artifact, Header, Witness, WitnessFormat, R1CS};
+use zokrates_core::proof_system::{ProofSystem, G16};
 #[cfg(feature = "libsnark")]
-use zokrates_core::ir::r1cs_program;
-#[cfg(feature = "libsnark")]
-use zokrates_core::proof_system::{ProofSystem, GM17, PGHR13};
-use zokrates_field::field::{Field, FieldPrime};
+use zokrates_core::proof_system::{GM17, PGHR13};
+use zokrates_field::field::{Bls12_377Field, Bls12_381Field, Field, FieldPrime};
 use zokrates_fs_resolver::resolve as fs_resolve;
 
 fn main() {
     cli().unwrap_or_else(|e| {
-        println!("{}", e);
+        eprintln!("{}", e);
         std::process::exit(1);
     })
 }
 
 fn cli() -> Result<(), String> {
     const FLATTENED_CODE_DEFAULT_PATH: &str = "out";
+    const ABI_SPEC_DEFAULT_PATH: &str = "abi.json";
     const VERIFICATION_KEY_DEFAULT_PATH: &str = "verification.key";
     const PROVING_KEY_DEFAULT_PATH: &str = "proving.key";
     const VERIFICATION_CONTRACT_DEFAULT_PATH: &str = "verifier.sol";
     const WITNESS_DEFAULT_PATH: &str = "witness";
     const VARIABLES_INFORMATION_KEY_DEFAULT_PATH: &str = "variables.inf";
     const JSON_PROOF_PATH: &str = "proof.json";
-    let default_backend = env::var("ZOKRATES_BACKEND").unwrap_or(String::from("pghr13"));
+    const R1CS_DEFAULT_PATH: &str = "out.r1cs";
+    const R1CS_FORMAT_DEFAULT: &str = "binary";
+    const R1CS_FORMATS: &[&str] = &["binary", "json"];
+    const WITNESS_FORMAT_DEFAULT: &str = "text";
+    const WITNESS_FORMATS: &[&str] = &["text", "json", "binary"];
+    const CURVE_DEFAULT: &str = "bn128";
+    const CURVES: &[&str] = &["bn128", "bls12_381", "bls12_377"];
+    const MESSAGE_FORMAT_DEFAULT: &str = "human";
+    const MESSAGE_FORMATS: &[&str] = &["human", "json"];
+    #[cfg(feature = "libsnark")]
+    const BACKEND_DEFAULT: &str = "pghr13";
+    #[cfg(not(feature = "libsnark"))]
+    const BACKEND_DEFAULT: &str = "g16";
+    // PGHR13 and GM17 proofs are only verified by the exported verifier contract
+    const VERIFY_BACKENDS: &[&str] = &["g16"];
+    let default_backend = env::var("ZOKRATES_BACKEND").unwrap_or(String::from(BACKEND_DEFAULT));
 
     // cli specification using clap library
     let matches = App::new("ZoKrates")
@@ -62,12 +81,129 @@ fn cli() -> Result<(), String> {
             .takes_value(true)
             .required(false)
             .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("abi_spec")
+            .short("s")
+            .long("abi_spec")
+            .help("Path of the ABI specification")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(ABI_SPEC_DEFAULT_PATH)
         ).arg(Arg::with_name("light")
             .long("light")
             .help("Skip logs and human readable output")
             .required(false)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve whose scalar field the program is compiled for")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+            .default_value(CURVE_DEFAULT)
+        ).arg(Arg::with_name("message-format")
+            .long("message-format")
+            .help("Format of the diagnostics reported when compilation fails")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .required(false)
+            .possible_values(MESSAGE_FORMATS)
+            .default_value(MESSAGE_FORMAT_DEFAULT)
         )
      )
+    .subcommand(SubCommand::with_name("fmt")
+        .about("Formats source code, keeping its comments")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("check")
+            .long("check")
+            .help("Fail if the source code is not formatted instead of formatting it")
+            .required(false)
+        )
+    )
+    .subcommand(SubCommand::with_name("check")
+        .about("Reports variables computed by directives which the constraints of a compiled program do not determine")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve the program was compiled for, which is read from the compiled program if not given")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+        )
+    )
+    .subcommand(SubCommand::with_name("profile")
+        .about("Reports the number of constraints each function and source line of a compiled program is responsible for")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve the program was compiled for, which is read from the compiled program if not given")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+        )
+    )
+    .subcommand(SubCommand::with_name("export-r1cs")
+        .about("Exports the constraint system of a compiled program in the iden3 .r1cs format, or as JSON")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the output file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(R1CS_DEFAULT_PATH)
+        ).arg(Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .help("Format of the output file")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .required(false)
+            .possible_values(R1CS_FORMATS)
+            .default_value(R1CS_FORMAT_DEFAULT)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve the program was compiled for, which is read from the compiled program if not given")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+        )
+    )
     .subcommand(SubCommand::with_name("setup")
         .about("Performs a trusted setup for a given constraint system")
         .arg(Arg::with_name("input")
@@ -109,7 +245,7 @@ fn cli() -> Result<(), String> {
         .arg(Arg::with_name("backend")
             .short("b")
             .long("backend")
-            .help("Backend to use in the setup. Available options are PGHR13 and GM17")
+            .help("Backend to use in the setup. Available options are PGHR13, GM17 and G16")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
@@ -138,7 +274,7 @@ fn cli() -> Result<(), String> {
         ).arg(Arg::with_name("backend")
             .short("b")
             .long("backend")
-            .help("Backend to use to export the verifier. Available options are PGHR13 and GM17")
+            .help("Backend to use to export the verifier. Available options are PGHR13, GM17 and G16")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
@@ -163,6 +299,14 @@ fn cli() -> Result<(), String> {
             .takes_value(true)
             .required(false)
             .default_value(WITNESS_DEFAULT_PATH)
+        ).arg(Arg::with_name("abi_spec")
+            .short("s")
+            .long("abi_spec")
+            .help("Path of the ABI specification")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(ABI_SPEC_DEFAULT_PATH)
         ).arg(Arg::with_name("arguments")
             .short("a")
             .long("arguments")
@@ -170,15 +314,132 @@ fn cli() -> Result<(), String> {
             .takes_value(true)
             .multiple(true) // allows multiple values
             .required(false)
+            .conflicts_with("stdin")
+        ).arg(Arg::with_name("abi")
+            .long("abi")
+            .help("Read the arguments and print the return values as JSON values of the types given by the ABI specification")
+            .required(false)
+            .conflicts_with("interactive")
+        ).arg(Arg::with_name("outputs")
+            .long("outputs")
+            .help("Path of a file to write the return values to as JSON values of the types given by the ABI specification")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("stdin")
+            .long("stdin")
+            .help("Read the arguments from stdin")
+            .required(false)
+            .conflicts_with("interactive")
         ).arg(Arg::with_name("interactive")
             .long("interactive")
             .help("Enter private inputs interactively. Public inputs still need to be passed non-interactively")
             .required(false)
+        ).arg(Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .help("Encoding of the witness file")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .required(false)
+            .possible_values(WITNESS_FORMATS)
+            .default_value(WITNESS_FORMAT_DEFAULT)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve the program was compiled for, which is read from the compiled program if not given")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+        )
+    )
+    .subcommand(SubCommand::with_name("run")
+        .about("Runs a program without compiling it, to quickly try out its logic")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("arguments")
+            .short("a")
+            .long("arguments")
+            .help("Arguments for the program's main method as a space separated list")
+            .takes_value(true)
+            .multiple(true) // allows multiple values
+            .required(false)
+            .conflicts_with("stdin")
+        ).arg(Arg::with_name("abi")
+            .long("abi")
+            .help("Read the arguments and print the return values as JSON values of the types of the main method")
+            .required(false)
+        ).arg(Arg::with_name("stdin")
+            .long("stdin")
+            .help("Read the arguments from stdin")
+            .required(false)
+        ).arg(Arg::with_name("compare")
+            .long("compare")
+            .help("Also compile the program and check that the witness has the same return values")
+            .required(false)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve whose scalar field the program is run in")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+            .default_value(CURVE_DEFAULT)
+        )
+    )
+    .subcommand(SubCommand::with_name("debug")
+        .about("Executes a program step by step, stopping at breakpoints to print the values of its variables")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("arguments")
+            .short("a")
+            .long("arguments")
+            .help("Arguments for the program's main method as a space separated list")
+            .takes_value(true)
+            .multiple(true) // allows multiple values
+            .required(false)
+        ).arg(Arg::with_name("break")
+            .short("b")
+            .long("break")
+            .help("Source line, or index of a statement prefixed with '#', to stop the execution at")
+            .value_name("BREAKPOINT")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
+        ).arg(Arg::with_name("curve")
+            .short("c")
+            .long("curve")
+            .help("Curve whose scalar field the program is compiled for")
+            .value_name("CURVE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(CURVES)
+            .default_value(CURVE_DEFAULT)
         )
     )
     .subcommand(SubCommand::with_name("generate-proof")
         .about("Calculates a proof for a given constraint system and witness.")
-        .arg(Arg::with_name("witness")
+        .arg(Arg::with_name("input")
+            .long("input")
+            .help("Path of compiled code, which the witness is checked against")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("witness")
             .short("w")
             .long("witness")
             .help("Path of the witness file")
@@ -213,184 +474,110 @@ fn cli() -> Result<(), String> {
         ).arg(Arg::with_name("backend")
             .short("b")
             .long("backend")
-            .help("Backend to use to generate the proof. Available options are PGHR13 and GM17")
+            .help("Backend to use to generate the proof. Available options are PGHR13, GM17 and G16")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
             .default_value(&default_backend)
         )
     )
+    .subcommand(SubCommand::with_name("verify")
+        .about("Verifies a given proof with the given verification key")
+        .arg(Arg::with_name("proof-path")
+            .short("j")
+            .long("proof-path")
+            .help("Path of the JSON proof file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(JSON_PROOF_PATH)
+        ).arg(Arg::with_name("verification-key-path")
+            .short("v")
+            .long("verification-key-path")
+            .help("Path of the verification key file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(VERIFICATION_KEY_DEFAULT_PATH)
+        ).arg(Arg::with_name("backend")
+            .short("b")
+            .long("backend")
+            .help("Backend to use to verify the proof. Only G16 proofs can be verified natively, PGHR13 and GM17 proofs are verified by the contract of export-verifier")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .possible_values(VERIFY_BACKENDS)
+            .case_insensitive(true)
+            .default_value(VERIFY_BACKENDS[0])
+        )
+    )
     .get_matches();
 
     match matches.subcommand() {
         ("compile", Some(sub_matches)) => {
-            println!("Compiling {}\n", sub_matches.value_of("input").unwrap());
-
-            let path = PathBuf::from(sub_matches.value_of("input").unwrap());
-
-            let location = path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .into_os_string()
-                .into_string()
-                .unwrap();
-
-            let light = sub_matches.occurrences_of("light") > 0;
-
-            let bin_output_path = Path::new(sub_matches.value_of("output").unwrap());
-
-            let hr_output_path = bin_output_path.to_path_buf().with_extension("code");
-
-            let file = File::open(path.clone()).unwrap();
-
-            let mut reader = BufReader::new(file);
-
-            let program_flattened: ir::Prog<FieldPrime> =
-                compile(&mut reader, Some(location), Some(fs_resolve))
-                    .map_err(|e| format!("Compilation failed:\n\n {}", e))?;
-
-            // number of constraints the flattened program will translate to.
-            let num_constraints = program_flattened.constraint_count();
-
-            // serialize flattened program and write to binary file
-            let mut bin_output_file = File::create(&bin_output_path)
-                .map_err(|why| format!("couldn't create {}: {}", bin_output_path.display(), why))?;
-
-            serialize_into(&mut bin_output_file, &program_flattened, Infinite)
-                .map_err(|_| "Unable to write data to file.".to_string())?;
-
-            if !light {
-                // write human-readable output file
-                let hr_output_file = File::create(&hr_output_path).map_err(|why| {
-                    format!("couldn't create {}: {}", hr_output_path.display(), why)
-                })?;
-
-                let mut hrofb = BufWriter::new(hr_output_file);
-                write!(&mut hrofb, "{}\n", program_flattened)
-                    .map_err(|_| "Unable to write data to file.".to_string())?;
-                hrofb
-                    .flush()
-                    .map_err(|_| "Unable to flush buffer.".to_string())?;
+            match sub_matches.value_of("curve").unwrap() {
+                "bn128" => cli_compile::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_compile::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_compile::<Bls12_377Field>(sub_matches)?,
+                _ => unreachable!(),
             }
-
-            if !light {
-                // debugging output
-                println!("Compiled program:\n{}", program_flattened);
+        }
+        ("fmt", Some(sub_matches)) => cli_fmt(sub_matches)?,
+        ("check", Some(sub_matches)) => {
+            match program_curve(sub_matches)?.as_str() {
+                "bn128" => cli_check::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_check::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_check::<Bls12_377Field>(sub_matches)?,
+                curve => return Err(format!("Curve {} is not supported", curve)),
             }
-
-            println!("Compiled code written to '{}'", bin_output_path.display());
-
-            if !light {
-                println!("Human readable code to '{}'", hr_output_path.display());
+        }
+        ("profile", Some(sub_matches)) => {
+            match program_curve(sub_matches)?.as_str() {
+                "bn128" => cli_profile::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_profile::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_profile::<Bls12_377Field>(sub_matches)?,
+                curve => return Err(format!("Curve {} is not supported", curve)),
+            }
+        }
+        ("export-r1cs", Some(sub_matches)) => {
+            match program_curve(sub_matches)?.as_str() {
+                "bn128" => cli_export_r1cs::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_export_r1cs::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_export_r1cs::<Bls12_377Field>(sub_matches)?,
+                curve => return Err(format!("Curve {} is not supported", curve)),
             }
-
-            println!("Number of constraints: {}", num_constraints);
         }
         ("compute-witness", Some(sub_matches)) => {
-            println!("Computing witness for:");
-
-            // read compiled program
-            let path = Path::new(sub_matches.value_of("input").unwrap());
-            let mut file = File::open(&path)
-                .map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
-
-            let program_ast: ir::Prog<FieldPrime> =
-                deserialize_from(&mut file, Infinite).map_err(|why| why.to_string())?;
-
-            // print deserialized flattened program
-            println!("{}", program_ast);
-
-            // validate #arguments
-            let cli_arguments = match sub_matches.values_of("arguments") {
-                Some(p) => p.map(|x| FieldPrime::try_from_str(x)).collect(),
-                None => Ok(vec![]),
+            match program_curve(sub_matches)?.as_str() {
+                "bn128" => cli_compute::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_compute::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_compute::<Bls12_377Field>(sub_matches)?,
+                curve => return Err(format!("Curve {} is not supported", curve)),
             }
-            .map_err(|_| "Could not parse arguments".to_string())?;
-
-            // handle interactive and non-interactive modes
-            let is_interactive = sub_matches.occurrences_of("interactive") > 0;
-
-            // in interactive mode, only public inputs are expected
-            let expected_cli_args_count = if is_interactive {
-                program_ast.public_arguments_count()
-            } else {
-                program_ast.public_arguments_count() + program_ast.private_arguments_count()
-            };
-
-            if cli_arguments.len() != expected_cli_args_count {
-                return Err(format!(
-                    "Wrong number of arguments. Given: {}, Required: {}.",
-                    cli_arguments.len(),
-                    expected_cli_args_count
-                ));
+        }
+        ("run", Some(sub_matches)) => {
+            match sub_matches.value_of("curve").unwrap() {
+                "bn128" => cli_run::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_run::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_run::<Bls12_377Field>(sub_matches)?,
+                _ => unreachable!(),
+            }
+        }
+        ("debug", Some(sub_matches)) => {
+            match sub_matches.value_of("curve").unwrap() {
+                "bn128" => cli_debug::<FieldPrime>(sub_matches)?,
+                "bls12_381" => cli_debug::<Bls12_381Field>(sub_matches)?,
+                "bls12_377" => cli_debug::<Bls12_377Field>(sub_matches)?,
+                _ => unreachable!(),
             }
-
-            let mut cli_arguments_iter = cli_arguments.into_iter();
-            let arguments: Vec<FieldPrime> = program_ast
-                .parameters()
-                .iter()
-                .map(|x| {
-                    match x.private && is_interactive {
-                        // private inputs are passed interactively when the flag is present
-                        true => loop {
-                            println!("Please enter a value for {:?}:", x.id);
-                            let mut input = String::new();
-                            let stdin = stdin();
-                            let r = stdin.lock().read_line(&mut input);
-
-                            match r {
-                                Ok(_) => {
-                                    let input = input.trim();
-                                    match FieldPrime::try_from_str(&input) {
-                                        Ok(v) => return v,
-                                        Err(_) => {
-                                            println!("Not a correct String, try again");
-                                            continue;
-                                        }
-                                    }
-                                }
-                                Err(_) => {
-                                    println!("Not a correct String, try again");
-                                    continue;
-                                }
-                            };
-                        },
-                        // otherwise, they are taken from the CLI arguments
-                        false => cli_arguments_iter.next().unwrap(),
-                    }
-                })
-                .collect();
-
-            let witness = program_ast
-                .execute(&arguments)
-                .map_err(|e| format!("Execution failed: {}", e))?;
-
-            println!("\nWitness: \n\n{}", witness.format_outputs());
-
-            // write witness to file
-            let output_path = Path::new(sub_matches.value_of("output").unwrap());
-            let output_file = File::create(&output_path)
-                .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
-
-            let mut bw = BufWriter::new(output_file);
-            write!(&mut bw, "{}", witness)
-                .map_err(|_| "Unable to write data to file.".to_string())?;
-            bw.flush()
-                .map_err(|_| "Unable to flush buffer.".to_string())?;
         }
-        #[cfg(feature = "libsnark")]
         ("setup", Some(sub_matches)) => {
             let backend = get_backend(sub_matches.value_of("backend").unwrap())?;
 
             println!("Performing setup...");
 
             let path = Path::new(sub_matches.value_of("input").unwrap());
-            let mut file = File::open(&path)
-                .map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
-
-            let program: ir::Prog<FieldPrime> =
-                deserialize_from(&mut file, Infinite).map_err(|why| format!("{:?}", why))?;
+            let program: ir::Prog<FieldPrime> = read_program(&path)?;
 
             // print deserialized flattened program
             println!("{}", program);
@@ -440,7 +627,6 @@ fn cli() -> Result<(), String> {
                 )
             );
         }
-        #[cfg(feature = "libsnark")]
         ("export-verifier", Some(sub_matches)) => {
             {
                 let backend = get_backend(sub_matches.value_of("backend").unwrap())?;
@@ -466,34 +652,31 @@ fn cli() -> Result<(), String> {
                 println!("Finished exporting verifier.");
             }
         }
-        #[cfg(feature = "libsnark")]
         ("generate-proof", Some(sub_matches)) => {
             println!("Generating proof...");
 
             let backend = get_backend(sub_matches.value_of("backend").unwrap())?;
 
+            // read compiled program
+            let path = Path::new(sub_matches.value_of("input").unwrap());
+            let program: ir::Prog<FieldPrime> = read_program(&path)?;
+
             // deserialize witness
             let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
             let witness_file = File::open(&witness_path)
                 .map_err(|why| format!("couldn't open {}: {}", witness_path.display(), why))?;
 
-            let reader = BufReader::new(witness_file);
-            let mut lines = reader.lines();
-            let mut witness_map = HashMap::new();
-
-            loop {
-                match lines.next() {
-                    Some(Ok(ref x)) => {
-                        let pairs: Vec<&str> = x.split_whitespace().collect();
-                        witness_map.insert(
-                            pairs[0].to_string(),
-                            FieldPrime::from_dec_string(pairs[1].to_string()),
-                        );
-                    }
-                    None => break,
-                    Some(Err(err)) => return Err(format!("Error reading witness: {}", err)),
-                }
-            }
+            let witness = Witness::read(&mut BufReader::new(witness_file))
+                .map_err(|why| format!("couldn't read {}: {}", witness_path.display(), why))?;
+
+            witness.check(&program).map_err(|why| {
+                format!(
+                    "{} does not match {}: {}",
+                    witness_path.display(),
+                    path.display(),
+                    why
+                )
+            })?;
 
             // determine variable order
             let var_inf_path = Path::new(sub_matches.value_of("meta-information").unwrap());
@@ -524,9 +707,15 @@ fn cli() -> Result<(), String> {
                 return Err(format!("Error reading variables"));
             }
 
-            println!("Using Witness: {:?}", witness_map);
-
-            let witness: Vec<_> = variables.iter().map(|x| witness_map[x].clone()).collect();
+            let witness = variables
+                .iter()
+                .map(|x| {
+                    x.parse::<FlatVariable>()
+                        .ok()
+                        .and_then(|v| witness.get(&v).cloned())
+                        .ok_or_else(|| format!("Witness has no value for {}", x))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
 
             // split witness into public and private inputs at offset
             let mut public_inputs: Vec<_> = witness.clone();
@@ -538,22 +727,875 @@ fn cli() -> Result<(), String> {
             let pk_path = sub_matches.value_of("provingkey").unwrap();
             let proof_path = sub_matches.value_of("proofpath").unwrap();
 
-            // run libsnark
             println!(
                 "generate-proof successful: {:?}",
                 backend.generate_proof(pk_path, proof_path, public_inputs, private_inputs)
             );
         }
+        ("verify", Some(sub_matches)) => {
+            let backend = get_backend(sub_matches.value_of("backend").unwrap())?;
+
+            println!("Performing verification...");
+
+            let vk_path = sub_matches.value_of("verification-key-path").unwrap();
+            let proof_path = sub_matches.value_of("proof-path").unwrap();
+
+            match backend.verify(vk_path, proof_path)? {
+                true => println!("PASSED"),
+                false => return Err("FAILED".to_string()),
+            }
+        }
         _ => unreachable!(),
     }
     Ok(())
 }
 
-#[cfg(feature = "libsnark")]
+// formats the diagnostics of a failed command, with the source they point to if `json` is not set,
+// or as one JSON object per line otherwise
+fn format_diagnostics(
+    heading: &str,
+    diagnostics: Vec<Diagnostic>,
+    input: &Path,
+    location: &str,
+    json: bool,
+) -> String {
+    // errors are reported against the directory of the input file, or the path of the import
+    let diagnostics: Vec<_> = diagnostics
+        .into_iter()
+        .map(|d| {
+            let file = d.file.clone().map(|file| match file == location {
+                true => input.display().to_string(),
+                false => Path::new(location).join(file).display().to_string(),
+            });
+            d.file(file)
+        })
+        .collect();
+
+    match json {
+        true => diagnostics
+            .iter()
+            .map(|d| serde_json::to_string(d).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        false => format!(
+            "{}:\n\n{}",
+            heading,
+            diagnostics
+                .iter()
+                .map(|d| {
+                    let source = d
+                        .file
+                        .as_ref()
+                        .and_then(|file| std::fs::read_to_string(file).ok());
+                    d.render(source.as_ref().map(|s| s.as_str()))
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        ),
+    }
+}
+
+fn cli_compile<T: Field + Serialize>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let json = sub_matches.value_of("message-format").unwrap() == "json";
+
+    // keep stdout parseable when diagnostics are consumed by a tool, and report progress on stderr
+    let info = |message: String| match json {
+        true => eprintln!("{}", message),
+        false => println!("{}", message),
+    };
+
+    info(format!(
+        "Compiling {}\n",
+        sub_matches.value_of("input").unwrap()
+    ));
+
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path
+        .parent()
+        .unwrap()
+        .to_path_buf()
+        .into_os_string()
+        .into_string()
+        .unwrap();
+
+    let light = sub_matches.occurrences_of("light") > 0;
+
+    let bin_output_path = Path::new(sub_matches.value_of("output").unwrap());
+
+    let hr_output_path = bin_output_path.to_path_buf().with_extension("code");
+
+    let source = std::fs::read_to_string(&path)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+
+    let abi_spec_path = Path::new(sub_matches.value_of("abi_spec").unwrap());
+
+    let Compiled {
+        program: mut program_flattened,
+        abi,
+        warnings,
+        ..
+    }: Compiled<T> = compile_program(
+        &mut source.as_bytes(),
+        Some(location.clone()),
+        Some(fs_resolve),
+    )
+    .map_err(|e| {
+        let diagnostics = format_diagnostics(
+            "Compilation failed",
+            e.diagnostics(),
+            &path,
+            &location,
+            json,
+        );
+        match json {
+            true => {
+                println!("{}", diagnostics);
+                String::from("Compilation failed")
+            }
+            false => diagnostics,
+        }
+    })?;
+
+    if !warnings.is_empty() {
+        let diagnostics = format_diagnostics(
+            "Warnings",
+            warnings.iter().map(|w| w.diagnostic()).collect(),
+            &path,
+            &location,
+            json,
+        );
+        match json {
+            true => println!("{}", diagnostics),
+            false => println!("{}\n", diagnostics),
+        }
+    }
+
+    // runtime errors are reported against the input file
+    program_flattened.attach_file(sub_matches.value_of("input").unwrap());
+
+    // number of constraints the flattened program will translate to.
+    let num_constraints = program_flattened.constraint_count();
+
+    // serialize flattened program and write to binary file
+    let bin_output_file = File::create(&bin_output_path)
+        .map_err(|why| format!("couldn't create {}: {}", bin_output_path.display(), why))?;
+
+    let artifact = Artifact::new(program_flattened, source.as_bytes());
+
+    let mut bw = BufWriter::new(bin_output_file);
+    artifact
+        .write(&mut bw)
+        .and_then(|_| bw.flush())
+        .map_err(|_| "Unable to write data to file.".to_string())?;
+
+    let program_flattened = artifact.program;
+
+    // write the interface of the program, which compute-witness reads structured inputs with
+    let abi_spec_file = File::create(&abi_spec_path)
+        .map_err(|why| format!("couldn't create {}: {}", abi_spec_path.display(), why))?;
+    let mut bw = BufWriter::new(abi_spec_file);
+    serde_json::to_writer_pretty(&mut bw, &abi)
+        .map_err(|_| "Unable to write data to file.".to_string())?;
+    bw.flush()
+        .map_err(|_| "Unable to flush buffer.".to_string())?;
+
+    if !light {
+        // write human-readable output file
+        let hr_output_file = File::create(&hr_output_path).map_err(|why| {
+            format!("couldn't create {}: {}", hr_output_path.display(), why)
+        })?;
+
+        let mut hrofb = BufWriter::new(hr_output_file);
+        write!(&mut hrofb, "{}\n", program_flattened)
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+        hrofb
+            .flush()
+            .map_err(|_| "Unable to flush buffer.".to_string())?;
+    }
+
+    if !light {
+        // debugging output
+        info(format!("Compiled program:\n{}", program_flattened));
+    }
+
+    info(format!(
+        "Compiled code written to '{}'",
+        bin_output_path.display()
+    ));
+    info(format!(
+        "ABI specification written to '{}'",
+        abi_spec_path.display()
+    ));
+
+    if !light {
+        info(format!(
+            "Human readable code to '{}'",
+            hr_output_path.display()
+        ));
+    }
+
+    info(format!("Number of constraints: {}", num_constraints));
+    Ok(())
+}
+
+fn cli_fmt(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path
+        .parent()
+        .unwrap()
+        .to_path_buf()
+        .into_os_string()
+        .into_string()
+        .unwrap();
+
+    let source = std::fs::read_to_string(&path)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+
+    // number literals are printed as written, so any field can be used to parse the source
+    let formatted = format::format::<FieldPrime>(&source).map_err(|errors| {
+        let diagnostics = errors
+            .into_iter()
+            .map(|e| {
+                CompileErrorInner::from(e)
+                    .with_context(&Some(location.clone()))
+                    .diagnostic()
+            })
+            .collect();
+        format_diagnostics("Formatting failed", diagnostics, &path, &location, false)
+    })?;
+
+    if formatted == source {
+        return Ok(());
+    }
+
+    match sub_matches.is_present("check") {
+        true => Err(format!("{} is not formatted", path.display())),
+        false => std::fs::write(&path, formatted)
+            .map_err(|why| format!("couldn't write {}: {}", path.display(), why)),
+    }
+}
+
+fn cli_check<T: Field + DeserializeOwned>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let program: ir::Prog<T> = read_program(&path)?;
+
+    let underconstrained = program.underconstrained();
+    for u in &underconstrained {
+        println!("{}", u);
+    }
+
+    match !underconstrained.is_empty() {
+        true => Err(format!("Checking {} failed", path.display())),
+        false => {
+            println!("No issue found in {}", path.display());
+            Ok(())
+        }
+    }
+}
+
+fn cli_profile<T: Field + DeserializeOwned>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let program: ir::Prog<T> = read_program(&path)?;
+
+    println!(
+        "Profile of {}, {} constraints:\n",
+        path.display(),
+        program.constraint_count()
+    );
+    print!("{}", program.profile());
+    Ok(())
+}
+
+fn cli_export_r1cs<T: Field + DeserializeOwned>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let program: ir::Prog<T> = read_program(&path)?;
+
+    let r1cs = R1CS::from_program(&program);
+
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+    let mut writer = BufWriter::new(output_file);
+
+    match sub_matches.value_of("format").unwrap() {
+        "json" => r1cs.write_json(&mut writer),
+        _ => r1cs.write(&mut writer),
+    }
+    .and_then(|_| writer.flush())
+    .map_err(|why| format!("couldn't write {}: {}", output_path.display(), why))?;
+
+    println!(
+        "Constraint system of {} constraints and {} variables written to '{}'",
+        r1cs.constraints.len(),
+        r1cs.wires.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+fn cli_compute<T: Field + DeserializeOwned>(sub_matches: &ArgMatches) -> Result<(), String> {
+    println!("Computing witness for:");
+
+    // read compiled program
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let program_ast: ir::Prog<T> = read_program(&path)?;
+
+    // print deserialized flattened program
+    println!("{}", program_ast);
+
+    // arguments are read from stdin or from the command line
+    let raw_arguments = match sub_matches.is_present("stdin") {
+        true => {
+            let mut input = String::new();
+            stdin()
+                .read_to_string(&mut input)
+                .map_err(|why| format!("couldn't read stdin: {}", why))?;
+            input
+        }
+        false => sub_matches
+            .values_of("arguments")
+            .map(|p| p.collect::<Vec<_>>().join(" "))
+            .unwrap_or(String::new()),
+    };
+
+    let is_abi = sub_matches.is_present("abi");
+
+    // the ABI specification is needed to read structured arguments or decode the return values
+    let abi_spec_path = Path::new(sub_matches.value_of("abi_spec").unwrap());
+    let abi = match is_abi || sub_matches.is_present("outputs") {
+        true => Some(read_abi(&abi_spec_path)?),
+        false => None,
+    };
+
+    let arguments: Vec<T> = match abi {
+        Some(ref abi) if is_abi => {
+            let values = serde_json::from_str(&raw_arguments)
+                .map_err(|why| format!("Could not parse arguments: {}", why))?;
+            let arguments = abi
+                .flatten_inputs(&values)
+                .map_err(|why| format!("Invalid arguments: {}", why))?;
+
+            if arguments.len() != program_ast.main.arguments.len() {
+                return Err(format!(
+                    "{} does not match {}: it describes {} field elements, the program takes {}",
+                    abi_spec_path.display(),
+                    path.display(),
+                    arguments.len(),
+                    program_ast.main.arguments.len()
+                ));
+            }
+
+            arguments
+        }
+        _ => parse_arguments(&program_ast, &raw_arguments, sub_matches)?,
+    };
+
+    let witness = program_ast
+        .execute(&arguments)
+        .map_err(|e| format!("Execution failed: {}", e))?;
+
+    let outputs = match abi {
+        Some(ref abi) => {
+            let values: Vec<T> = witness.return_values().into_iter().cloned().collect();
+            let outputs = abi.decode_outputs(&values).map_err(|why| {
+                format!(
+                    "Could not decode the return values of {}: {}",
+                    path.display(),
+                    why
+                )
+            })?;
+            Some(outputs)
+        }
+        None => None,
+    };
+
+    match outputs {
+        Some(ref outputs) if is_abi => println!("\nWitness: \n\n{}", outputs),
+        _ => println!("\nWitness: \n\n{}", witness.format_outputs()),
+    }
+
+    // write the decoded return values to file
+    if let (Some(outputs), Some(outputs_path)) = (outputs, sub_matches.value_of("outputs")) {
+        let outputs_path = Path::new(outputs_path);
+        let outputs_file = File::create(&outputs_path)
+            .map_err(|why| format!("couldn't create {}: {}", outputs_path.display(), why))?;
+
+        let mut bw = BufWriter::new(outputs_file);
+        serde_json::to_writer_pretty(&mut bw, &outputs)
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+        bw.flush()
+            .map_err(|_| "Unable to flush buffer.".to_string())?;
+    }
+
+    // write witness to file
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+
+    let format = match sub_matches.value_of("format").unwrap() {
+        "json" => WitnessFormat::Json,
+        "binary" => WitnessFormat::Binary,
+        _ => WitnessFormat::Text,
+    };
+
+    let mut bw = BufWriter::new(output_file);
+    witness
+        .write(&mut bw, format)
+        .map_err(|_| "Unable to write data to file.".to_string())?;
+    bw.flush()
+        .map_err(|_| "Unable to flush buffer.".to_string())?;
+    Ok(())
+}
+
+fn cli_run<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path
+        .parent()
+        .unwrap()
+        .to_path_buf()
+        .into_os_string()
+        .into_string()
+        .unwrap();
+
+    let source = std::fs::read_to_string(&path)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+
+    let mut program = check::<T, _, _, _>(
+        &mut source.as_bytes(),
+        Some(location.clone()),
+        Some(fs_resolve),
+    )
+    .map_err(|e| {
+        format_diagnostics(
+            "Compilation failed",
+            e.diagnostics(),
+            &path,
+            &location,
+            false,
+        )
+    })?;
+
+    // runtime errors are reported against the input file
+    program.attach_file(sub_matches.value_of("input").unwrap());
+
+    let raw_arguments = match sub_matches.is_present("stdin") {
+        true => {
+            let mut input = String::new();
+            stdin()
+                .read_to_string(&mut input)
+                .map_err(|why| format!("couldn't read stdin: {}", why))?;
+            input
+        }
+        false => sub_matches
+            .values_of("arguments")
+            .map(|p| p.collect::<Vec<_>>().join(" "))
+            .unwrap_or(String::new()),
+    };
+
+    let main = program.functions.iter().find(|f| f.id == "main").unwrap();
+    let abi = Abi::new(&main.arguments, &main.signature);
+
+    let arguments: Vec<T> = match sub_matches.is_present("abi") {
+        true => {
+            let values = serde_json::from_str(&raw_arguments)
+                .map_err(|why| format!("Could not parse arguments: {}", why))?;
+            abi.flatten_inputs(&values)
+                .map_err(|why| format!("Invalid arguments: {}", why))?
+        }
+        false => raw_arguments
+            .split_whitespace()
+            .map(|x| T::try_from_str(x))
+            .collect::<Result<_, _>>()
+            .map_err(|_| "Could not parse arguments".to_string())?,
+    };
+
+    let outputs = program
+        .execute(&arguments)
+        .map_err(|e| format!("Execution failed: {}", e))?;
+
+    match sub_matches.is_present("abi") {
+        true => {
+            let values = abi
+                .decode_outputs(&outputs)
+                .map_err(|why| format!("Could not decode the return values: {}", why))?;
+            println!("Return values:\n\n{}", values);
+        }
+        false => println!(
+            "Return values:\n\n{}",
+            outputs
+                .iter()
+                .enumerate()
+                .map(|(i, v)| format!("{} {}", FlatVariable::public(i), v))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }
+
+    if sub_matches.is_present("compare") {
+        let Compiled { mut program, .. }: Compiled<T> = compile_program(
+            &mut source.as_bytes(),
+            Some(location.clone()),
+            Some(fs_resolve),
+        )
+        .map_err(|e| {
+            format_diagnostics(
+                "Compilation failed",
+                e.diagnostics(),
+                &path,
+                &location,
+                false,
+            )
+        })?;
+        program.attach_file(sub_matches.value_of("input").unwrap());
+
+        let witness = program
+            .execute(&arguments)
+            .map_err(|e| format!("Execution of the compiled program failed: {}", e))?;
+        let compiled_outputs: Vec<T> = witness.return_values().into_iter().cloned().collect();
+
+        if compiled_outputs != outputs {
+            return Err(format!(
+                "The compiled program returned different values:\n\n{}",
+                witness.format_outputs()
+            ));
+        }
+
+        println!("\nThe compiled program returned the same values");
+    }
+
+    Ok(())
+}
+
+const DEBUG_HELP: &str = "Commands:
+  step, s [N]            execute the next N statements, 1 by default
+  continue, c            execute until the next breakpoint or the end of the program
+  break, b [BREAKPOINT]  stop at a source line, or at a statement index prefixed with '#'.
+                         Without argument, list the breakpoints
+  delete, d              remove all breakpoints
+  print, p NAME          print the value of a variable of the function the next statement is in,
+                         or of a flattened variable such as _3
+  list, l                print the next statement and the source line it originates from
+  help, h                print this message
+  quit, q                stop debugging";
+
+fn cli_debug<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path
+        .parent()
+        .unwrap()
+        .to_path_buf()
+        .into_os_string()
+        .into_string()
+        .unwrap();
+
+    let source = std::fs::read_to_string(&path)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+
+    let Compiled {
+        program,
+        source_map,
+        ..
+    }: Compiled<T> = compile_program(
+        &mut source.as_bytes(),
+        Some(location.clone()),
+        Some(fs_resolve),
+    )
+    .map_err(|e| {
+        format_diagnostics(
+            "Compilation failed",
+            e.diagnostics(),
+            &path,
+            &location,
+            false,
+        )
+    })?;
+
+    let raw_arguments = sub_matches
+        .values_of("arguments")
+        .map(|p| p.collect::<Vec<_>>().join(" "))
+        .unwrap_or(String::new());
+    let arguments = parse_arguments(&program, &raw_arguments, sub_matches)?;
+
+    let mut debugger = ir::Debugger::new(&program, &source_map, &arguments)
+        .map_err(|e| format!("Execution failed: {}", e))?;
+
+    for breakpoint in sub_matches.values_of("break").into_iter().flatten() {
+        let breakpoint = breakpoint.parse()?;
+        warn_unreachable(&debugger, breakpoint);
+        debugger.add_breakpoint(breakpoint);
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+
+    println!(
+        "Debugging {}, type `help` for the list of commands\n",
+        path.display()
+    );
+    print_debugger_position(&debugger, &lines);
+
+    let stdin = stdin();
+    loop {
+        print!("(debug) ");
+        std::io::stdout()
+            .flush()
+            .map_err(|_| "Unable to flush buffer.".to_string())?;
+
+        let mut input = String::new();
+        // the session ends with the input
+        if stdin
+            .lock()
+            .read_line(&mut input)
+            .map_err(|why| format!("couldn't read stdin: {}", why))?
+            == 0
+        {
+            println!();
+            return Ok(());
+        }
+
+        let mut words = input.split_whitespace();
+        let (command, argument) = match words.next() {
+            Some(command) => (command, words.next()),
+            None => continue,
+        };
+
+        match command {
+            "step" | "s" => {
+                let count = match argument.map(|n| n.parse::<usize>()) {
+                    None => 1,
+                    Some(Ok(count)) => count,
+                    Some(Err(_)) => {
+                        println!("Invalid number of statements {}", argument.unwrap());
+                        continue;
+                    }
+                };
+                for _ in 0..count {
+                    if debugger.is_done() {
+                        break;
+                    }
+                    if let Err(e) = debugger.step() {
+                        println!("Execution failed: {}", e);
+                        break;
+                    }
+                }
+                print_debugger_position(&debugger, &lines);
+            }
+            "continue" | "c" => {
+                match debugger.resume() {
+                    Ok(Some(breakpoint)) => println!("Stopped at {}", breakpoint),
+                    Ok(None) => {}
+                    Err(e) => println!("Execution failed: {}", e),
+                }
+                print_debugger_position(&debugger, &lines);
+            }
+            "break" | "b" => match argument {
+                Some(breakpoint) => match breakpoint.parse() {
+                    Ok(breakpoint) => {
+                        warn_unreachable(&debugger, breakpoint);
+                        debugger.add_breakpoint(breakpoint);
+                        println!("Breakpoint set at {}", breakpoint);
+                    }
+                    Err(e) => println!("{}", e),
+                },
+                None => {
+                    for breakpoint in debugger.breakpoints() {
+                        println!("{}", breakpoint);
+                    }
+                }
+            },
+            "delete" | "d" => {
+                debugger.clear_breakpoints();
+                println!("Breakpoints removed");
+            }
+            "print" | "p" => match argument {
+                Some(name) => {
+                    let mut values = debugger.variable(name);
+                    // fall back to the variables of the flattened program
+                    if values.is_empty() {
+                        if let Ok(variable) = name.parse::<FlatVariable>() {
+                            values = vec![(name.to_string(), debugger.value(&variable))];
+                        }
+                    }
+
+                    if values.is_empty() {
+                        println!("No variable {} in {}", name, debugger.function());
+                    }
+                    for (name, value) in values {
+                        match value {
+                            Some(value) => println!("{} = {}", name, value),
+                            None => println!("{} is not computed yet", name),
+                        }
+                    }
+                }
+                None => println!("Expected the name of a variable"),
+            },
+            "list" | "l" => print_debugger_position(&debugger, &lines),
+            "help" | "h" => println!("{}", DEBUG_HELP),
+            "quit" | "q" => return Ok(()),
+            _ => println!(
+                "Unknown command {}, type `help` for the list of commands",
+                command
+            ),
+        }
+    }
+}
+
+// warns about a breakpoint which no statement of a debugged program is at, such as one on an
+// empty line or past the end of the source
+fn warn_unreachable<T: Field>(debugger: &ir::Debugger<T>, breakpoint: ir::Breakpoint) {
+    if !debugger.is_reachable(breakpoint) {
+        println!(
+            "Warning: no statement is at {}, the breakpoint will not be reached",
+            breakpoint
+        );
+    }
+}
+
+// prints the next statement of a debugged program and the source line it originates from, or
+// the return values once the program is executed
+fn print_debugger_position<T: Field>(debugger: &ir::Debugger<T>, lines: &[&str]) {
+    match debugger.statement() {
+        Some(statement) => {
+            if let Some(span) = statement.span() {
+                let line = span.start.line;
+                println!(
+                    "{:>4} | {}",
+                    line,
+                    lines.get(line - 1).map(|l| l.trim_end()).unwrap_or("")
+                );
+            }
+            println!("#{}: {}", debugger.position(), statement);
+        }
+        None => {
+            println!("Execution finished, returning:");
+            for (index, _) in debugger.program().main.returns.iter().enumerate() {
+                let output = FlatVariable::public(index);
+                if let Some(value) = debugger.value(&output) {
+                    println!("{} = {}", output, value);
+                }
+            }
+        }
+    }
+}
+
+// parses space separated field elements, asking for the private ones in interactive mode
+fn parse_arguments<T: Field>(
+    program_ast: &ir::Prog<T>,
+    raw_arguments: &str,
+    sub_matches: &ArgMatches,
+) -> Result<Vec<T>, String> {
+    let cli_arguments = raw_arguments
+        .split_whitespace()
+        .map(|x| T::try_from_str(x))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Could not parse arguments".to_string())?;
+
+    // handle interactive and non-interactive modes
+    let is_interactive = sub_matches.occurrences_of("interactive") > 0;
+
+    // in interactive mode, only public inputs are expected
+    let expected_cli_args_count = if is_interactive {
+        program_ast.public_arguments_count()
+    } else {
+        program_ast.public_arguments_count() + program_ast.private_arguments_count()
+    };
+
+    if cli_arguments.len() != expected_cli_args_count {
+        return Err(format!(
+            "Wrong number of arguments. Given: {}, Required: {}.",
+            cli_arguments.len(),
+            expected_cli_args_count
+        ));
+    }
+
+    let mut cli_arguments_iter = cli_arguments.into_iter();
+    let arguments = program_ast
+        .parameters()
+        .iter()
+        .map(|x| {
+            match x.private && is_interactive {
+                // private inputs are passed interactively when the flag is present
+                true => loop {
+                    println!("Please enter a value for {:?}:", x.id);
+                    let mut input = String::new();
+                    let stdin = stdin();
+                    let r = stdin.lock().read_line(&mut input);
+
+                    match r {
+                        Ok(_) => {
+                            let input = input.trim();
+                            match T::try_from_str(&input) {
+                                Ok(v) => return v,
+                                Err(_) => {
+                                    println!("Not a correct String, try again");
+                                    continue;
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            println!("Not a correct String, try again");
+                            continue;
+                        }
+                    };
+                },
+                // otherwise, they are taken from the CLI arguments
+                false => cli_arguments_iter.next().unwrap(),
+            }
+        })
+        .collect();
+
+    Ok(arguments)
+}
+
+// reads the ABI specification written along with a compiled program
+fn read_abi(path: &Path) -> Result<Abi, String> {
+    let file =
+        File::open(path).map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))
+}
+
+// reads a compiled program, checking that it was compiled for `T`
+fn read_program<T: Field + DeserializeOwned>(path: &Path) -> Result<ir::Prog<T>, String> {
+    let file =
+        File::open(path).map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+
+    Artifact::read(&mut BufReader::new(file))
+        .map(|artifact| artifact.program)
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))
+}
+
+// reads the curve a compiled program was compiled for, which `--curve` must agree with if given
+fn program_curve(sub_matches: &ArgMatches) -> Result<String, String> {
+    let path = Path::new(sub_matches.value_of("input").unwrap());
+    let file =
+        File::open(path).map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+
+    let header = Header::read(&mut BufReader::new(file))
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))?;
+
+    match sub_matches.value_of("curve") {
+        Some(curve) if curve != header.curve => Err(format!(
+            "{} was compiled for curve {}, not for curve {}",
+            path.display(),
+            header.curve,
+            curve
+        )),
+        _ => Ok(header.curve),
+    }
+}
+
 fn get_backend(backend_str: &str) -> Result<&'static ProofSystem, String> {
     match backend_str.to_lowercase().as_ref() {
+        #[cfg(feature = "libsnark")]
         "pghr13" => Ok(&PGHR13 {}),
+        #[cfg(feature = "libsnark")]
         "gm17" => Ok(&GM17 {}),
+        "g16" => Ok(&G16 {}),
         s => Err(format!("Backend \"{}\" not supported", s)),
     }
 }
@@ -563,6 +1605,7 @@ mod tests {
     extern crate glob;
     use self::glob::glob;
     use super::*;
+    use zokrates_core::compile::compile;
     use zokrates_core::ir::r1cs_program;
 
     #[test]
@@ -597,6 +1640,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn examples_formatted() {
+        for p in glob("./examples/**/*.code").expect("Failed to read glob pattern") {
+            let path = match p {
+                Ok(x) => x,
+                Err(why) => panic!("Error: {:?}", why),
+            };
+
+            if path.to_str().unwrap().contains("error") {
+                continue;
+            }
+
+            println!("Formatting {:?}", path);
+
+            let source = std::fs::read_to_string(&path).unwrap();
+            let location = path
+                .parent()
+                .unwrap()
+                .to_path_buf()
+                .into_os_string()
+                .into_string()
+                .unwrap();
+
+            // formatting is stable
+            let formatted = format::format::<FieldPrime>(&source).unwrap();
+            assert_eq!(format::format::<FieldPrime>(&formatted).unwrap(), formatted);
+
+            // and compiles to the same program, whose hash leaves out the locations of statements
+            let compile_source = |source: &str| {
+                let mut reader = BufReader::new(source.as_bytes());
+                let program: ir::Prog<FieldPrime> =
+                    compile(&mut reader, Some(location.clone()), Some(fs_resolve)).unwrap();
+                program.hash()
+            };
+            assert_eq!(
+                compile_source(&formatted),
+                compile_source(&source),
+                "{:?}",
+                path
+            );
+        }
+    }
+
     #[test]
     fn examples_with_input_success() {
         //these examples should compile and run
@@ -78,6 +78,10 @@ mod integration {
             .join(program_name)
             .join("variables")
             .with_extension("inf");
+        let proof_path = tmp_base
+            .join(program_name)
+            .join("proof")
+            .with_extension("json");
         let verification_contract_path = tmp_base
             .join(program_name)
             .join("verifier")
@@ -161,8 +165,12 @@ mod integration {
         }
 
         #[cfg(feature = "libsnark")]
+        let backends = vec!["pghr13", "gm17", "g16"];
+        #[cfg(not(feature = "libsnark"))]
+        let backends = vec!["g16"];
+
         {
-            for backend in &["pghr13", "gm17"] {
+            for backend in &backends {
                 // SETUP
                 assert_cli::Assert::command(&[
                     "../target/release/zokrates",
@@ -199,17 +207,37 @@ mod integration {
                 assert_cli::Assert::command(&[
                     "../target/release/zokrates",
                     "generate-proof",
+                    "--input",
+                    flattened_path.to_str().unwrap(),
                     "-w",
                     witness_path.to_str().unwrap(),
                     "-p",
                     proving_key_path.to_str().unwrap(),
                     "-i",
                     variable_information_path.to_str().unwrap(),
+                    "-j",
+                    proof_path.to_str().unwrap(),
                     "--backend",
                     backend,
                 ])
                 .succeeds()
                 .unwrap();
+
+                // VERIFY
+                if *backend == "g16" {
+                    assert_cli::Assert::command(&[
+                        "../target/release/zokrates",
+                        "verify",
+                        "-v",
+                        verification_key_path.to_str().unwrap(),
+                        "-j",
+                        proof_path.to_str().unwrap(),
+                        "--backend",
+                        backend,
+                    ])
+                    .succeeds()
+                    .unwrap();
+                }
             }
         }
     }
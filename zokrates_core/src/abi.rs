@@ -0,0 +1,448 @@
+//! The interface of the `main` function of a program, which describes its inputs and outputs with
+//! their types, so that tools can pass structured values to a program and read its results.
+//!
+//! Types are written as objects with a `type` key, along with their `components` when they have
+//! any:
+//!
+//! ```json
+//! {"type": "field"}
+//! {"type": "bool"}
+//! {"type": "uint", "components": 8}
+//! {"type": "array", "components": {"size": 2, "type": "field"}}
+//! {"type": "struct", "components": {"name": "Point", "members": [{"name": "x", "type": "field"}]}}
+//! ```
+
+use num::ToPrimitive;
+use num_bigint::BigUint;
+use serde_json::Value;
+use std::fmt;
+use std::slice;
+use typed_absy::Parameter;
+use types::{Signature, Type};
+use zokrates_field::field::Field;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Abi {
+    pub inputs: Vec<AbiInput>,
+    pub outputs: Vec<AbiType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AbiInput {
+    pub name: String,
+    pub public: bool,
+    #[serde(flatten)]
+    pub ty: AbiType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "components", rename_all = "lowercase")]
+pub enum AbiType {
+    Field,
+    Bool,
+    /// An unsigned integer of the given number of bits
+    Uint(usize),
+    Array(Box<AbiArray>),
+    Struct(AbiStruct),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AbiArray {
+    pub size: usize,
+    #[serde(flatten)]
+    pub ty: AbiType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AbiStruct {
+    pub name: String,
+    pub members: Vec<AbiMember>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AbiMember {
+    pub name: String,
+    #[serde(flatten)]
+    pub ty: AbiType,
+}
+
+impl Abi {
+    pub fn new(arguments: &[Parameter], signature: &Signature) -> Self {
+        Abi {
+            inputs: arguments
+                .iter()
+                .map(|p| AbiInput {
+                    name: p.id.id.clone(),
+                    public: !p.private,
+                    ty: AbiType::from(&p.id._type),
+                })
+                .collect(),
+            outputs: signature.outputs.iter().map(AbiType::from).collect(),
+        }
+    }
+
+    /// Checks structured inputs against the ABI and flattens them into the field elements the
+    /// compiled program takes. Inputs are given either as an array in the order of the
+    /// parameters, or as an object mapping the name of each parameter to its value.
+    pub fn flatten_inputs<T: Field>(&self, inputs: &Value) -> Result<Vec<T>, String> {
+        let values: Vec<&Value> = match *inputs {
+            Value::Array(ref values) if values.len() == self.inputs.len() => {
+                values.iter().collect()
+            }
+            Value::Array(ref values) => {
+                return Err(format!(
+                    "Expected {} input{}, found {}",
+                    self.inputs.len(),
+                    if self.inputs.len() == 1 { "" } else { "s" },
+                    values.len()
+                ))
+            }
+            Value::Object(ref values) => {
+                if let Some(name) = values
+                    .keys()
+                    .find(|k| !self.inputs.iter().any(|i| i.name == **k))
+                {
+                    return Err(format!("Unknown input `{}`", name));
+                }
+                self.inputs
+                    .iter()
+                    .map(|i| {
+                        values
+                            .get(&i.name)
+                            .ok_or_else(|| format!("Missing input `{}`", i.name))
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            ref v => {
+                return Err(format!(
+                    "Expected an array or an object of inputs, found {}",
+                    v
+                ))
+            }
+        };
+
+        let mut flattened = vec![];
+        for (input, value) in self.inputs.iter().zip(values) {
+            input.ty.flatten(value, &input.name, &mut flattened)?;
+        }
+        Ok(flattened)
+    }
+
+    /// The number of field elements the inputs are flattened to
+    pub fn primitive_count(&self) -> usize {
+        self.inputs.iter().map(|i| i.ty.primitive_count()).sum()
+    }
+
+    /// Decodes the values returned by the compiled program into an array holding a JSON value of
+    /// the type of each output, in the form `flatten_inputs` reads them.
+    pub fn decode_outputs<T: Field>(&self, values: &[T]) -> Result<Value, String> {
+        let expected: usize = self.outputs.iter().map(|o| o.primitive_count()).sum();
+        if values.len() != expected {
+            return Err(format!(
+                "Expected {} return value{}, found {}",
+                expected,
+                if expected == 1 { "" } else { "s" },
+                values.len()
+            ));
+        }
+
+        let mut values = values.iter();
+        self.outputs
+            .iter()
+            .map(|o| o.decode(&mut values))
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    }
+}
+
+impl AbiType {
+    pub fn primitive_count(&self) -> usize {
+        match *self {
+            AbiType::Field | AbiType::Bool | AbiType::Uint(_) => 1,
+            AbiType::Array(ref array) => array.size * array.ty.primitive_count(),
+            AbiType::Struct(ref s) => s.members.iter().map(|m| m.ty.primitive_count()).sum(),
+        }
+    }
+
+    // appends the field elements `value` is made of to `flattened`, `path` naming the value in
+    // errors
+    fn flatten<T: Field>(
+        &self,
+        value: &Value,
+        path: &str,
+        flattened: &mut Vec<T>,
+    ) -> Result<(), String> {
+        let invalid = || format!("Expected {} for `{}`, found {}", self, path, value);
+
+        match (self, value) {
+            (&AbiType::Field, _) => {
+                let max = BigUint::from_bytes_le(&T::max_value().into_byte_vector());
+                let v = integer(value).filter(|v| *v <= max).ok_or_else(invalid)?;
+                flattened.push(T::from_byte_vector(v.to_bytes_le()));
+            }
+            (&AbiType::Bool, &Value::Bool(b)) => {
+                flattened.push(if b { T::one() } else { T::zero() })
+            }
+            (&AbiType::Uint(bits), _) => {
+                let v = integer(value)
+                    .filter(|v| v.bits() <= bits)
+                    .ok_or_else(invalid)?;
+                flattened.push(T::from_byte_vector(v.to_bytes_le()));
+            }
+            (&AbiType::Array(ref array), &Value::Array(ref values))
+                if values.len() == array.size =>
+            {
+                for (i, v) in values.iter().enumerate() {
+                    array
+                        .ty
+                        .flatten(v, &format!("{}[{}]", path, i), flattened)?;
+                }
+            }
+            (&AbiType::Struct(ref s), &Value::Object(ref values))
+                if values.len() == s.members.len() =>
+            {
+                for m in &s.members {
+                    let v = values.get(&m.name).ok_or_else(invalid)?;
+                    m.ty.flatten(v, &format!("{}.{}", path, m.name), flattened)?;
+                }
+            }
+            _ => return Err(invalid()),
+        }
+
+        Ok(())
+    }
+
+    // reads the value of this type from the next elements of `values`, which has enough of them
+    fn decode<T: Field>(&self, values: &mut slice::Iter<T>) -> Result<Value, String> {
+        match *self {
+            AbiType::Field => Ok(Value::String(values.next().unwrap().to_dec_string())),
+            AbiType::Bool => match values.next().unwrap() {
+                v if *v == T::zero() => Ok(Value::Bool(false)),
+                v if *v == T::one() => Ok(Value::Bool(true)),
+                v => Err(format!("Expected bool, found {}", v)),
+            },
+            AbiType::Uint(bits) => {
+                let v = values.next().unwrap();
+                let n = BigUint::from_bytes_le(&v.into_byte_vector());
+                match n.to_u64() {
+                    _ if n.bits() > bits => Err(format!("Expected {}, found {}", self, v)),
+                    Some(n) => Ok(Value::from(n)),
+                    None => Ok(Value::String(n.to_string())),
+                }
+            }
+            AbiType::Array(ref array) => (0..array.size)
+                .map(|_| array.ty.decode(values))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            AbiType::Struct(ref s) => s
+                .members
+                .iter()
+                .map(|m| m.ty.decode(values).map(|v| (m.name.clone(), v)))
+                .collect::<Result<_, _>>()
+                .map(Value::Object),
+        }
+    }
+}
+
+// integers are given as JSON numbers, or as decimal strings when they are too large for JSON
+fn integer(value: &Value) -> Option<BigUint> {
+    match *value {
+        Value::Number(ref n) => n.as_u64().map(BigUint::from),
+        Value::String(ref s) => BigUint::parse_bytes(s.as_bytes(), 10),
+        _ => None,
+    }
+}
+
+impl<'a> From<&'a Type> for AbiType {
+    fn from(ty: &'a Type) -> Self {
+        match *ty {
+            Type::FieldElement => AbiType::Field,
+            Type::Boolean => AbiType::Bool,
+            Type::Uint(bits) => AbiType::Uint(bits),
+            Type::Array(ref array) => AbiType::Array(box AbiArray {
+                size: array.size,
+                ty: AbiType::from(&*array.ty),
+            }),
+            Type::Struct(ref s) => AbiType::Struct(AbiStruct {
+                name: s.id.clone(),
+                members: s
+                    .members
+                    .iter()
+                    .map(|m| AbiMember {
+                        name: m.id.clone(),
+                        ty: AbiType::from(&m.ty),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AbiType::Field => write!(f, "field"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Uint(bits) => write!(f, "u{}", bits),
+            AbiType::Array(ref array) => write!(f, "{}[{}]", array.ty, array.size),
+            AbiType::Struct(ref s) => write!(f, "{}", s.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{self, json};
+    use zokrates_field::field::FieldPrime;
+
+    // def main(field a, private bool[2] b, Point p, private u8 c)
+    fn abi() -> Abi {
+        Abi {
+            inputs: vec![
+                AbiInput {
+                    name: String::from("a"),
+                    public: true,
+                    ty: AbiType::Field,
+                },
+                AbiInput {
+                    name: String::from("b"),
+                    public: false,
+                    ty: AbiType::Array(box AbiArray {
+                        size: 2,
+                        ty: AbiType::Bool,
+                    }),
+                },
+                AbiInput {
+                    name: String::from("p"),
+                    public: true,
+                    ty: AbiType::Struct(AbiStruct {
+                        name: String::from("Point"),
+                        members: vec![
+                            AbiMember {
+                                name: String::from("x"),
+                                ty: AbiType::Field,
+                            },
+                            AbiMember {
+                                name: String::from("y"),
+                                ty: AbiType::Field,
+                            },
+                        ],
+                    }),
+                },
+                AbiInput {
+                    name: String::from("c"),
+                    public: false,
+                    ty: AbiType::Uint(8),
+                },
+            ],
+            outputs: vec![AbiType::Field],
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_value(&abi()).unwrap();
+
+        assert_eq!(
+            json["inputs"][1],
+            json!({"name": "b", "public": false, "type": "array", "components": {"size": 2, "type": "bool"}})
+        );
+        assert_eq!(
+            json["inputs"][3],
+            json!({"name": "c", "public": false, "type": "uint", "components": 8})
+        );
+        assert_eq!(json["outputs"], json!([{"type": "field"}]));
+        assert_eq!(serde_json::from_value::<Abi>(json).unwrap(), abi());
+    }
+
+    #[test]
+    fn flatten() {
+        let expected: Vec<FieldPrime> = vec![42, 1, 0, 3, 4, 255]
+            .into_iter()
+            .map(FieldPrime::from)
+            .collect();
+
+        assert_eq!(
+            abi().flatten_inputs(&json!(["42", [true, false], {"x": 3, "y": "4"}, 255])),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            abi().flatten_inputs(
+                &json!({"p": {"y": 4, "x": 3}, "c": 255, "a": 42, "b": [true, false]})
+            ),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let abi = Abi {
+            inputs: vec![],
+            outputs: vec![
+                AbiType::Field,
+                AbiType::Array(box AbiArray {
+                    size: 2,
+                    ty: AbiType::Bool,
+                }),
+                AbiType::Uint(8),
+            ],
+        };
+
+        let values: Vec<FieldPrime> = vec![42, 1, 0, 255]
+            .into_iter()
+            .map(FieldPrime::from)
+            .collect();
+
+        assert_eq!(
+            abi.decode_outputs(&values),
+            Ok(json!(["42", [true, false], 255]))
+        );
+        assert_eq!(
+            abi.decode_outputs(&values[..3]),
+            Err(String::from("Expected 4 return values, found 3"))
+        );
+        let mut invalid = values.clone();
+        invalid[1] = FieldPrime::from(2);
+        assert_eq!(
+            abi.decode_outputs(&invalid),
+            Err(String::from("Expected bool, found 2"))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let flatten = |inputs: Value| abi().flatten_inputs::<FieldPrime>(&inputs);
+
+        assert_eq!(
+            flatten(json!([1, [true, false], {"x": 3, "y": 4}])),
+            Err(String::from("Expected 4 inputs, found 3"))
+        );
+        assert_eq!(
+            flatten(json!([1, [true, 0], {"x": 3, "y": 4}, 1])),
+            Err(String::from("Expected bool for `b[1]`, found 0"))
+        );
+        assert_eq!(
+            flatten(json!([1, [true, false], {"x": 3, "y": 4}, 256])),
+            Err(String::from("Expected u8 for `c`, found 256"))
+        );
+        assert_eq!(
+            flatten(json!([1, [true, false], {"x": 3}, 1])),
+            Err(String::from("Expected Point for `p`, found {\"x\":3}"))
+        );
+        assert_eq!(
+            flatten(json!([
+                "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+                [true, false],
+                {"x": 3, "y": 4},
+                1
+            ]))
+            .is_err(),
+            true
+        );
+        assert_eq!(
+            flatten(json!({"d": 1})),
+            Err(String::from("Unknown input `d`"))
+        );
+    }
+}
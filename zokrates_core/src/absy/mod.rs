@@ -12,7 +12,7 @@ pub mod variable;
 pub use absy::node::{Node, NodeValue};
 pub use absy::parameter::{Parameter, ParameterNode};
 pub use absy::variable::{Variable, VariableNode};
-use types::Signature;
+use types::{Signature, Type};
 
 use flat_absy::*;
 use imports::ImportNode;
@@ -21,10 +21,16 @@ use zokrates_field::field::Field;
 
 #[derive(Clone, PartialEq)]
 pub struct Prog<T: Field> {
+    /// Struct types declared in the program
+    pub structs: Vec<StructDefinitionNode>,
+    /// Constants declared in the program
+    pub constants: Vec<ConstantNode<T>>,
     /// Functions of the program
     pub functions: Vec<FunctionNode<T>>,
     pub imports: Vec<ImportNode>,
     pub imported_functions: Vec<FlatFunction<T>>,
+    /// Values of the constants declared in imported modules
+    pub imported_constants: Vec<(String, T)>,
 }
 
 impl<T: Field> fmt::Display for Prog<T> {
@@ -36,6 +42,24 @@ impl<T: Field> fmt::Display for Prog<T> {
                 .map(|x| format!("{}", x))
                 .collect::<Vec<_>>(),
         );
+        res.extend(
+            self.structs
+                .iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<_>>(),
+        );
+        res.extend(
+            self.imported_constants
+                .iter()
+                .map(|(id, value)| format!("const field {} = {}", id, value))
+                .collect::<Vec<_>>(),
+        );
+        res.extend(
+            self.constants
+                .iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<_>>(),
+        );
         res.extend(
             self.imported_functions
                 .iter()
@@ -56,12 +80,27 @@ impl<T: Field> fmt::Debug for Prog<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "program(\n\timports:\n\t\t{}\n\tfunctions:\n\t\t{}{}\n)",
+            "program(\n\timports:\n\t\t{}\n\tstructs:\n\t\t{}\n\tconstants:\n\t\t{}{}\n\tfunctions:\n\t\t{}{}\n)",
             self.imports
                 .iter()
                 .map(|x| format!("{:?}", x))
                 .collect::<Vec<_>>()
                 .join("\n\t\t"),
+            self.structs
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect::<Vec<_>>()
+                .join("\n\t\t"),
+            self.imported_constants
+                .iter()
+                .map(|(id, value)| format!("{} = {}", id, value))
+                .collect::<Vec<_>>()
+                .join("\n\t\t"),
+            self.constants
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect::<Vec<_>>()
+                .join("\n\t\t"),
             self.imported_functions
                 .iter()
                 .map(|x| format!("{}", x))
@@ -76,6 +115,74 @@ impl<T: Field> fmt::Debug for Prog<T> {
     }
 }
 
+#[derive(Clone, PartialEq)]
+pub struct StructDefinition {
+    /// Name of the struct type
+    pub id: String,
+    /// Members of the struct, in declaration order
+    pub fields: Vec<StructFieldNode>,
+}
+
+pub type StructDefinitionNode = Node<StructDefinition>;
+
+impl fmt::Display for StructDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "struct {} {{\n", self.id));
+        for field in &self.fields {
+            try!(write!(f, "\t{}\n", field));
+        }
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Debug for StructDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StructDefinition(id: {:?}, fields: {:?})", self.id, self.fields)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Constant<T: Field> {
+    /// Name of the constant
+    pub id: String,
+    /// Expression the constant is bound to, evaluated at compile time
+    pub expression: ExpressionNode<T>,
+}
+
+pub type ConstantNode<T> = Node<Constant<T>>;
+
+impl<T: Field> fmt::Display for Constant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "const field {} = {}", self.id, self.expression)
+    }
+}
+
+impl<T: Field> fmt::Debug for Constant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Constant(id: {:?}, expression: {:?})", self.id, self.expression)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct StructField {
+    pub id: String,
+    pub ty: Type,
+}
+
+pub type StructFieldNode = Node<StructField>;
+
+impl fmt::Display for StructField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.ty, self.id)
+    }
+}
+
+impl fmt::Debug for StructField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StructField(id: {:?}, ty: {:?})", self.id, self.ty)
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct Function<T: Field> {
     /// Name of the program
@@ -152,21 +259,22 @@ impl<T: Field> fmt::Display for Assignee<T> {
 impl<T: Field> From<ExpressionNode<T>> for AssigneeNode<T> {
     fn from(e: ExpressionNode<T>) -> Self {
         match e.value {
-            Expression::Select(box e1, box e2) => match e1 {
-                ExpressionNode {
-                    value: Expression::Identifier(id),
-                    start,
-                    end,
-                } => Node::new(
-                    e.start,
-                    e.end,
-                    Assignee::ArrayElement(
-                        box Node::new(start, end, Assignee::Identifier(id)),
-                        box e2,
-                    ),
-                ),
-                _ => panic!("only use expression to assignee for elements like foo[bar]"),
-            },
+            Expression::Select(box e1, box e2) => {
+                let array = match e1 {
+                    ExpressionNode {
+                        value: Expression::Identifier(id),
+                        start,
+                        end,
+                    } => Node::new(start, end, Assignee::Identifier(id)),
+                    // nested elements such as foo[bar][baz]
+                    e1 @ ExpressionNode {
+                        value: Expression::Select(..),
+                        ..
+                    } => AssigneeNode::from(e1),
+                    _ => panic!("only use expression to assignee for elements like foo[bar]"),
+                };
+                Node::new(e.start, e.end, Assignee::ArrayElement(box array, box e2))
+            }
             _ => panic!("only use expression to assignee for elements like foo[bar]"),
         }
     }
@@ -178,7 +286,13 @@ pub enum Statement<T: Field> {
     Declaration(VariableNode),
     Definition(AssigneeNode<T>, ExpressionNode<T>),
     Condition(ExpressionNode<T>, ExpressionNode<T>),
-    For(VariableNode, T, T, Vec<StatementNode<T>>),
+    Assertion(ExpressionNode<T>, Option<String>),
+    For(
+        VariableNode,
+        ExpressionNode<T>,
+        ExpressionNode<T>,
+        Vec<StatementNode<T>>,
+    ),
     MultipleDefinition(Vec<AssigneeNode<T>>, ExpressionNode<T>),
 }
 
@@ -191,6 +305,10 @@ impl<T: Field> fmt::Display for Statement<T> {
             Statement::Declaration(ref var) => write!(f, "{}", var),
             Statement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             Statement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            Statement::Assertion(ref e, None) => write!(f, "assert({})", e),
+            Statement::Assertion(ref e, Some(ref message)) => {
+                write!(f, "assert({}, \"{}\")", e, message)
+            }
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 try!(write!(f, "for {} in {}..{} do\n", var, start, stop));
                 for l in list {
@@ -220,6 +338,9 @@ impl<T: Field> fmt::Debug for Statement<T> {
                 write!(f, "Definition({:?}, {:?})", lhs, rhs)
             }
             Statement::Condition(ref lhs, ref rhs) => write!(f, "Condition({:?}, {:?})", lhs, rhs),
+            Statement::Assertion(ref e, ref message) => {
+                write!(f, "Assertion({:?}, {:?})", e, message)
+            }
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 try!(write!(f, "for {:?} in {:?}..{:?} do\n", var, start, stop));
                 for l in list {
@@ -236,7 +357,9 @@ impl<T: Field> fmt::Debug for Statement<T> {
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expression<T: Field> {
-    Number(T),
+    /// A decimal literal, along with its source text
+    Number(T, String),
+    HexNumber(String),
     Identifier(String),
     Add(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
     Sub(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
@@ -259,6 +382,13 @@ pub enum Expression<T: Field> {
     InlineArray(Vec<ExpressionNode<T>>),
     Select(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
     Or(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    BitAnd(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    BitOr(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    BitXor(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    LeftShift(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    RightShift(Box<ExpressionNode<T>>, Box<ExpressionNode<T>>),
+    Member(Box<ExpressionNode<T>>, String),
+    InlineStruct(String, Vec<(String, ExpressionNode<T>)>),
 }
 
 pub type ExpressionNode<T> = Node<Expression<T>>;
@@ -266,7 +396,8 @@ pub type ExpressionNode<T> = Node<Expression<T>>;
 impl<T: Field> fmt::Display for Expression<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Expression::Number(ref i) => write!(f, "{}", i),
+            Expression::Number(ref i, _) => write!(f, "{}", i),
+            Expression::HexNumber(ref i) => write!(f, "0x{}", i),
             Expression::Identifier(ref var) => write!(f, "{}", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
@@ -307,6 +438,22 @@ impl<T: Field> fmt::Display for Expression<T> {
             }
             Expression::Select(ref array, ref index) => write!(f, "{}[{}]", array, index),
             Expression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
+            Expression::BitAnd(ref lhs, ref rhs) => write!(f, "({} & {})", lhs, rhs),
+            Expression::BitOr(ref lhs, ref rhs) => write!(f, "({} | {})", lhs, rhs),
+            Expression::BitXor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
+            Expression::LeftShift(ref lhs, ref rhs) => write!(f, "({} << {})", lhs, rhs),
+            Expression::RightShift(ref lhs, ref rhs) => write!(f, "({} >> {})", lhs, rhs),
+            Expression::Member(ref s, ref id) => write!(f, "{}.{}", s, id),
+            Expression::InlineStruct(ref id, ref members) => {
+                try!(write!(f, "{} {{", id));
+                for (i, (member_id, e)) in members.iter().enumerate() {
+                    try!(write!(f, "{}: {}", member_id, e));
+                    if i < members.len() - 1 {
+                        try!(write!(f, ", "));
+                    }
+                }
+                write!(f, "}}")
+            }
         }
     }
 }
@@ -314,7 +461,8 @@ impl<T: Field> fmt::Display for Expression<T> {
 impl<T: Field> fmt::Debug for Expression<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Expression::Number(ref i) => write!(f, "Num({})", i),
+            Expression::Number(ref i, _) => write!(f, "Num({})", i),
+            Expression::HexNumber(ref i) => write!(f, "HexNum(0x{})", i),
             Expression::Identifier(ref var) => write!(f, "Ide({})", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "Add({:?}, {:?})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "Sub({:?}, {:?})", lhs, rhs),
@@ -345,6 +493,19 @@ impl<T: Field> fmt::Debug for Expression<T> {
             }
             Expression::Select(ref array, ref index) => write!(f, "{}[{}]", array, index),
             Expression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
+            Expression::BitAnd(ref lhs, ref rhs) => write!(f, "BitAnd({:?}, {:?})", lhs, rhs),
+            Expression::BitOr(ref lhs, ref rhs) => write!(f, "BitOr({:?}, {:?})", lhs, rhs),
+            Expression::BitXor(ref lhs, ref rhs) => write!(f, "BitXor({:?}, {:?})", lhs, rhs),
+            Expression::LeftShift(ref lhs, ref rhs) => {
+                write!(f, "LeftShift({:?}, {:?})", lhs, rhs)
+            }
+            Expression::RightShift(ref lhs, ref rhs) => {
+                write!(f, "RightShift({:?}, {:?})", lhs, rhs)
+            }
+            Expression::Member(ref s, ref id) => write!(f, "Member({:?}, {:?})", s, id),
+            Expression::InlineStruct(ref id, ref members) => {
+                write!(f, "InlineStruct({:?}, {:?})", id, members)
+            }
         }
     }
 }
@@ -54,6 +54,9 @@ impl<T: Field> NodeValue for Prog<T> {}
 impl NodeValue for Variable {}
 impl NodeValue for Parameter {}
 impl NodeValue for Import {}
+impl NodeValue for StructDefinition {}
+impl NodeValue for StructField {}
+impl<T: Field> NodeValue for Constant<T> {}
 
 impl<T: NodeValue> std::cmp::PartialEq for Node<T> {
     fn eq(&self, other: &Node<T>) -> bool {
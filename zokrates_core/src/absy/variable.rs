@@ -35,7 +35,7 @@ impl Variable {
     pub fn field_array<S: Into<String>>(id: S, size: usize) -> Variable {
         Variable {
             id: id.into(),
-            _type: Type::FieldElementArray(size),
+            _type: Type::array(Type::FieldElement, size),
         }
     }
 
@@ -3,19 +3,25 @@
 //! @file compile.rs
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
+use abi::Abi;
 use absy::Prog;
-use flat_absy::FlatProg;
+use diagnostics::Diagnostic;
+use flat_absy::{FlatProg, SourceMap};
 use flatten::Flattener;
 use imports::{self, Importer};
 use ir;
 use optimizer::Optimizer;
 use parser::{self, parse_program};
 use semantics::{self, Checker};
-use static_analysis::Analyse;
+use static_analysis::{self, Analyse};
+use typed_absy::{FieldElementExpression, TypedProg};
+use warnings::{self, Input, Warning, WarningKind};
 use std::fmt;
 use std::io;
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 use zokrates_field::field::Field;
+#[cfg(test)]
+use zokrates_field::field::FieldPrime;
 
 #[derive(Debug)]
 pub struct CompileErrors<T: Field>(Vec<CompileError<T>>);
@@ -45,6 +51,7 @@ pub enum CompileErrorInner<T: Field> {
     ParserError(parser::Error<T>),
     ImportError(imports::Error),
     SemanticError(semantics::Error),
+    AnalysisError(static_analysis::Error),
     ReadError(io::Error),
 }
 
@@ -77,6 +84,33 @@ impl<T: Field> CompileErrors<T> {
     }
 }
 
+impl<T: Field> CompileError<T> {
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self.value {
+            CompileErrorInner::ParserError(ref e) => {
+                Diagnostic::error("E0001", e.message()).span(Some(e.span()))
+            }
+            CompileErrorInner::ImportError(ref e) => {
+                Diagnostic::error("E0002", e.message()).span(e.pos())
+            }
+            CompileErrorInner::SemanticError(ref e) => {
+                Diagnostic::error("E0003", e.message()).span(e.pos())
+            }
+            CompileErrorInner::AnalysisError(ref e) => {
+                Diagnostic::error("E0004", e.message()).span(e.pos())
+            }
+            CompileErrorInner::ReadError(ref e) => Diagnostic::error("E0005", e.to_string()),
+        };
+        diagnostic.file(self.context.clone())
+    }
+}
+
+impl<T: Field> CompileErrors<T> {
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.0.iter().map(|e| e.diagnostic()).collect()
+    }
+}
+
 impl<T: Field> fmt::Display for CompileError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let context = match self.context {
@@ -87,6 +121,35 @@ impl<T: Field> fmt::Display for CompileError<T> {
     }
 }
 
+/// A warning about a module, which does not prevent its compilation
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileWarning {
+    context: Option<String>,
+    value: Warning,
+}
+
+impl CompileWarning {
+    pub fn kind(&self) -> &WarningKind {
+        self.value.kind()
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::warning(self.value.code(), self.value.message())
+            .span(self.value.pos())
+            .file(self.context.clone())
+    }
+}
+
+impl fmt::Display for CompileWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let context = match self.context {
+            Some(ref x) => x.clone(),
+            None => "???".to_string(),
+        };
+        write!(f, "{}:{}", context, self.value)
+    }
+}
+
 impl<T: Field> From<parser::Error<T>> for CompileErrorInner<T> {
     fn from(error: parser::Error<T>) -> Self {
         CompileErrorInner::ParserError(error)
@@ -111,11 +174,18 @@ impl<T: Field> From<semantics::Error> for CompileErrorInner<T> {
     }
 }
 
+impl<T: Field> From<static_analysis::Error> for CompileErrorInner<T> {
+    fn from(error: static_analysis::Error) -> Self {
+        CompileErrorInner::AnalysisError(error)
+    }
+}
+
 impl<T: Field> fmt::Display for CompileErrorInner<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let res = match *self {
             CompileErrorInner::ParserError(ref e) => format!("{}", e),
             CompileErrorInner::SemanticError(ref e) => format!("{}", e),
+            CompileErrorInner::AnalysisError(ref e) => format!("{}", e),
             CompileErrorInner::ReadError(ref e) => format!("{}", e),
             CompileErrorInner::ImportError(ref e) => format!("{}", e),
         };
@@ -128,51 +198,270 @@ pub fn compile<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
     location: Option<String>,
     resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
 ) -> Result<ir::Prog<T>, CompileErrors<T>> {
-    let compiled = compile_aux(reader, location, resolve_option)?;
-    Ok(ir::Prog::from(Optimizer::new().optimize_program(compiled)))
+    compile_program(reader, location, resolve_option).map(|compiled| compiled.program)
+}
+
+/// A compiled program, along with the interface of its main function, the warnings about its
+/// main module and the source variables its variables are bound to
+#[derive(Debug)]
+pub struct Compiled<T: Field> {
+    pub program: ir::Prog<T>,
+    pub abi: Abi,
+    pub warnings: Vec<CompileWarning>,
+    pub source_map: SourceMap,
+}
+
+pub fn compile_program<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
+) -> Result<Compiled<T>, CompileErrors<T>> {
+    let module = compile_module(reader, location.clone(), resolve_option)?;
+
+    // modules which only declare constants can be imported, but not compiled on their own
+    let abi = match module.abi {
+        Some(abi) => abi,
+        None => {
+            return Err(CompileErrorInner::from(semantics::Error::no_main())
+                .with_context(&location)
+                .into())
+        }
+    };
+
+    let mut optimizer = Optimizer::new();
+    let mut program = ir::Prog::from(optimizer.optimize_program(module.program));
+
+    let source_map = module
+        .source_map
+        .apply_substitution(optimizer.substitution());
+    program.attach_definitions(&source_map.definitions);
+
+    let mut found = module.warnings;
+
+    // unused inputs are already reported as such
+    let unconstrained: Vec<_> = warnings::check_constraints(&program, &module.inputs)
+        .into_iter()
+        .filter(|w| !found.iter().any(|u| u.pos() == w.pos()))
+        .collect();
+    found.extend(unconstrained);
+
+    let warnings = found
+        .into_iter()
+        .map(|value| CompileWarning {
+            context: location.clone(),
+            value,
+        })
+        .collect();
+
+    Ok(Compiled {
+        program,
+        abi,
+        warnings,
+        source_map,
+    })
+}
+
+/// Parses and checks a program without compiling it, so that it can be run by the interpreter of
+/// the typed AST. Imported modules are compiled.
+pub fn check<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
+) -> Result<TypedProg<T>, CompileErrors<T>> {
+    let program_ast = parse_module(reader, location.clone(), resolve_option)?;
+    let typed_ast = check_module(program_ast, &location)?;
+
+    match typed_ast.functions.iter().any(|f| f.id == "main") {
+        true => Ok(typed_ast),
+        false => Err(CompileErrorInner::from(semantics::Error::no_main())
+            .with_context(&location)
+            .into()),
+    }
 }
 
+/// Compiles a module, returning its flattened functions along with the values of the constants
+/// it declares
 pub fn compile_aux<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
     reader: &mut R,
     location: Option<String>,
     resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
-) -> Result<FlatProg<T>, CompileErrors<T>> {
-    let program_ast_without_imports: Prog<T> = parse_program(reader)
-        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).with_context(&location)))?;
+) -> Result<(FlatProg<T>, Vec<(String, T)>), CompileErrors<T>> {
+    compile_module(reader, location, resolve_option).map(|m| (m.program, m.constants))
+}
+
+struct CompiledModule<T: Field> {
+    program: FlatProg<T>,
+    constants: Vec<(String, T)>,
+    warnings: Vec<Warning>,
+    /// The inputs of the main function, if any
+    inputs: Vec<Input>,
+    /// The interface of the main function, if any
+    abi: Option<Abi>,
+    source_map: SourceMap,
+}
+
+// parses a module and resolves its imports
+fn parse_module<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
+) -> Result<Prog<T>, CompileErrors<T>> {
+    let program_ast_without_imports: Prog<T> = parse_program(reader).map_err(|errors| {
+        CompileErrors(
+            errors
+                .into_iter()
+                .map(|e| CompileErrorInner::from(e).with_context(&location))
+                .collect(),
+        )
+    })?;
 
-    let program_ast = Importer::new().apply_imports(
+    Importer::new().apply_imports(
         program_ast_without_imports,
         location.clone(),
         resolve_option,
-    )?;
+    )
+}
+
+fn check_module<T: Field>(
+    program_ast: Prog<T>,
+    location: &Option<String>,
+) -> Result<TypedProg<T>, CompileErrors<T>> {
+    Checker::new().check_program(program_ast).map_err(|errors| {
+        CompileErrors(
+            errors
+                .into_iter()
+                .map(|e| CompileErrorInner::from(e).with_context(location))
+                .collect(),
+        )
+    })
+}
+
+fn compile_module<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
+) -> Result<CompiledModule<T>, CompileErrors<T>> {
+    // the source is kept to locate the warnings
+    let mut source = String::new();
+    reader
+        .read_to_string(&mut source)
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).with_context(&location)))?;
+
+    let program_ast = parse_module(&mut source.as_bytes(), location.clone(), resolve_option)?;
+
+    let warnings = warnings::check_module(&program_ast, &source);
+
+    // the positions of the inputs are lost after the semantic check
+    let parameters = program_ast
+        .functions
+        .iter()
+        .find(|f| f.value.id == "main")
+        .map(|f| f.value.arguments.clone())
+        .unwrap_or(vec![]);
 
     // check semantics
-    let typed_ast = Checker::new()
-        .check_program(program_ast)
-        .map_err(|errors| {
-            CompileErrors(
-                errors
-                    .into_iter()
-                    .map(|e| CompileErrorInner::from(e).with_context(&location))
-                    .collect(),
-            )
-        })?;
+    let typed_ast = check_module(program_ast, &location)?;
+
+    let main = typed_ast.functions.iter().find(|f| f.id == "main");
+
+    let abi = main.map(|f| Abi::new(&f.arguments, &f.signature));
+
+    let inputs = main
+        .map(|f| {
+            f.arguments
+                .iter()
+                .zip(parameters.iter())
+                .map(|(a, p)| Input {
+                    id: a.id.id.clone(),
+                    pos: warnings::trim(&source, p.pos()),
+                    private: a.private,
+                    size: a.id._type.get_primitive_count(),
+                })
+                .collect()
+        })
+        .unwrap_or(vec![]);
 
     // analyse (unroll and constant propagation)
-    let typed_ast = typed_ast.analyse();
+    let typed_ast = typed_ast
+        .analyse()
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).with_context(&location)))?;
+
+    // constants have been reduced to their values during propagation
+    let constants = typed_ast
+        .constants
+        .iter()
+        .map(|c| match c.expression {
+            FieldElementExpression::Number(ref v) => (c.id.clone(), v.clone()),
+            ref e => panic!("constant {} should have been reduced, found {}", c.id, e),
+        })
+        .collect();
 
     // flatten input program
-    let program_flattened = Flattener::new(T::get_required_bits()).flatten_program(typed_ast);
+    let mut flattener = Flattener::new(T::get_required_bits());
+    let program_flattened = flattener.flatten_program(typed_ast);
 
     // analyse (constant propagation after call resolution)
-    let program_flattened = program_flattened.analyse();
+    let program_flattened = program_flattened
+        .analyse()
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).with_context(&location)))?;
+
+    Ok(CompiledModule {
+        program: program_flattened,
+        constants,
+        warnings,
+        inputs,
+        abi,
+        source_map: flattener.source_map().clone(),
+    })
+}
+
+/// Compiles `source` as the module at `./path/to/file`, whose imports resolve to a module declaring
+/// constants only
+#[cfg(test)]
+pub fn compile_str(source: &str) -> Result<Compiled<FieldPrime>, CompileErrors<FieldPrime>> {
+    compile_str_with(source, resolve_constants)
+}
+
+/// Compiles `source` as the module at `./path/to/file`, resolving its imports with `resolve`
+#[cfg(test)]
+pub fn compile_str_with<S: BufRead>(
+    source: &str,
+    resolve: fn(&Option<String>, &String) -> Result<(S, String, String), io::Error>,
+) -> Result<Compiled<FieldPrime>, CompileErrors<FieldPrime>> {
+    compile_program(
+        &mut source.as_bytes(),
+        Some(String::from("./path/to/file")),
+        Some(resolve),
+    )
+}
 
-    Ok(program_flattened)
+/// Checks `source` as the module at `./path/to/file`, like `compile_str` compiles it
+#[cfg(test)]
+pub fn check_str(source: &str) -> Result<TypedProg<FieldPrime>, CompileErrors<FieldPrime>> {
+    check(
+        &mut source.as_bytes(),
+        Some(String::from("./path/to/file")),
+        Some(resolve_constants),
+    )
+}
+
+// resolves any import to a module declaring constants only
+#[cfg(test)]
+fn resolve_constants(
+    _: &Option<String>,
+    _: &String,
+) -> Result<(&'static [u8], String, String), io::Error> {
+    Ok((
+        "const field A = 40\nconst field B = A + 2\n".as_bytes(),
+        String::from("./constants"),
+        String::from("constants"),
+    ))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use abi::AbiType;
     use std::io::{BufReader, Empty};
     use zokrates_field::field::FieldPrime;
 
@@ -224,4 +513,584 @@ mod test {
         );
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn all_parse_errors() {
+        let res = compile_str(
+            r#"
+def foo(field a) -> (field):
+	field b = a +
+	return b
+
+def main() -> (field):
+	field c = 1
+	for field i in 0..2 do
+		c = c *
+	endfor
+	c = = 2
+	return foo(c)
+"#,
+        );
+
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].to_string().starts_with("./path/to/file:3:"));
+        assert!(errors[1].to_string().starts_with("./path/to/file:9:"));
+        assert!(errors[2].to_string().starts_with("./path/to/file:11:"));
+    }
+
+    #[test]
+    fn parse_errors_across_declarations() {
+        let res = compile_str(
+            r#"
+struct Foo {
+	field a b
+}
+
+def main(field a -> (field):
+	return a +
+"#,
+        );
+
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].to_string().starts_with("./path/to/file:3:"));
+        assert!(errors[1].to_string().starts_with("./path/to/file:6:"));
+        assert!(errors[2].to_string().starts_with("./path/to/file:7:"));
+    }
+
+    #[test]
+    fn parse_errors_after_broken_return() {
+        let res = compile_str(
+            r#"
+def foo(field a) -> (field):
+	for field i in 0..2 do
+		a = a +
+	endfor
+	return a +
+def bar(field a) -> (field):
+	field b = a +
+def main() -> (field):
+	field b = 1 +
+	return bar(1)
+"#,
+        );
+
+        // the body of `bar` ends at `main`, whose return statement is not taken for its own
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].to_string().starts_with("./path/to/file:4:"));
+        assert!(errors[1].to_string().starts_with("./path/to/file:6:"));
+        assert!(errors[2].to_string().starts_with("./path/to/file:8:"));
+        assert!(errors[3].to_string().starts_with("./path/to/file:10:"));
+    }
+
+    #[test]
+    fn parse_error_without_return() {
+        let res = compile_str(
+            r#"
+def main() -> (field):
+	field a = 1
+"#,
+        );
+
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "./path/to/file:4:1\n\tExpected one of [`return`], got ``"
+        );
+    }
+
+    #[test]
+    fn parse_errors_without_endfor() {
+        let res = compile_str(
+            r#"
+def foo(field a) -> (field):
+	for field i in 0..2 do
+		a = a + 1
+def main() -> (field):
+	return 1 +
+"#,
+        );
+
+        let errors = res.unwrap_err().0;
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().starts_with("./path/to/file:5:"));
+        assert!(errors[1].to_string().starts_with("./path/to/file:6:"));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let res = compile_str(
+            r#"
+def main() -> (field):
+	field a = 1 +
+	return b
+"#,
+        );
+
+        let diagnostics = res.unwrap_err().diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "E0001");
+        assert_eq!(diagnostics[0].file, Some(String::from("./path/to/file")));
+        assert_eq!(diagnostics[0].span.as_ref().unwrap().start.line, 3);
+
+        let res = compile_str(
+            r#"
+def main() -> (field):
+	return b
+"#,
+        );
+
+        let diagnostics = res.unwrap_err().diagnostics();
+        assert_eq!(diagnostics[0].code, "E0003");
+        assert_eq!(diagnostics[0].span.as_ref().unwrap().start.line, 3);
+    }
+
+    #[test]
+    fn constants() {
+        let res = compile_str(
+            r#"
+const field N = 3
+const field M = N * N + 1 // constants can refer to previous ones
+def main() -> (field):
+	field res = 0
+	for field i in 0..3 do
+		res = res + M
+	endfor
+	return res
+"#,
+        );
+
+        let witness = res.unwrap().program.execute::<FieldPrime>(&vec![]).unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(30)]);
+    }
+
+    #[test]
+    fn imported_constants() {
+        let res = compile_str(
+            r#"
+import "./constants"
+def main() -> (field):
+	return B
+"#,
+        );
+
+        let witness = res.unwrap().program.execute::<FieldPrime>(&vec![]).unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(42)]);
+    }
+
+    #[test]
+    fn duplicate_imported_constant() {
+        let res = compile_str(
+            r#"
+import "./constants"
+const field A = 1
+def main() -> (field):
+	return A
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate definition for constant A"));
+    }
+
+    #[test]
+    fn array_size_from_constant() {
+        let res = compile_str(
+            r#"
+import "./constants"
+const field N = B - A
+struct Point {
+	field[N] coordinates
+}
+def first(Point[N] points) -> (field):
+	return points[0].coordinates[1]
+def main(field[N] a) -> (field):
+	Point[2] points = [Point { coordinates: a }, Point { coordinates: [0, 0] }]
+	return first(points)
+"#,
+        );
+
+        let witness = res
+            .unwrap()
+            .program
+            .execute::<FieldPrime>(&vec![FieldPrime::from(3), FieldPrime::from(4)])
+            .unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(4)]);
+    }
+
+    #[test]
+    fn array_size_from_undefined_constant() {
+        let res = compile_str(
+            r#"
+def main(field[N] a) -> (field):
+	return a[0]
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Undefined constant N in array size"));
+    }
+
+    #[test]
+    fn assign_to_constant() {
+        let res = compile_str(
+            r#"
+const field N = 3
+def main() -> (field):
+	N = 4
+	return N
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot assign to constant N"));
+    }
+
+    #[test]
+    fn shadow_constant() {
+        let res = compile_str(
+            r#"
+const field N = 3
+def main(field N) -> (field):
+	return N
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Cannot redefine constant N"));
+    }
+
+    #[test]
+    fn constant_calling_function() {
+        let res = compile_str(
+            r#"
+def foo() -> (field):
+	return 1
+const field N = foo()
+def main() -> (field):
+	return N
+"#,
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn constants_without_main() {
+        let res = compile_str(
+            r#"
+const field N = 3
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("No main function found"));
+    }
+
+    #[test]
+    fn loop_bound_from_constant() {
+        let res = compile_str(
+            r#"
+const field N = 2
+def main() -> (field):
+	field res = 0
+	for field i in 0..N + 1 do
+		res = res + i
+	endfor
+	return res
+"#,
+        );
+
+        let witness = res.unwrap().program.execute::<FieldPrime>(&vec![]).unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(3)]);
+    }
+
+    #[test]
+    fn loop_bound_from_parameter() {
+        let res = compile_str(
+            r#"
+const field N = 4
+def sum(field n) -> (field):
+	field res = 0
+	for field i in 0..n do
+		for field j in i..N do
+			res = res + 1
+		endfor
+	endfor
+	return res
+def main() -> (field):
+	return sum(N - 1)
+"#,
+        );
+
+        let witness = res.unwrap().program.execute::<FieldPrime>(&vec![]).unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(9)]);
+    }
+
+    #[test]
+    fn unknown_loop_bound() {
+        let res = compile_str(
+            r#"
+def main(field n) -> (field):
+	field res = 0
+	for field i in 0..n do
+		res = res + i
+	endfor
+	return res
+"#,
+        );
+
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "./path/to/file:4:2\n\tLoop bounds 0..n in function main are not known at compile time"
+        );
+    }
+
+    #[test]
+    fn shift_amount_from_parameter() {
+        let res = compile_str(
+            r#"
+def shift(u8 a, field n) -> (u8):
+	return a << n
+def main() -> (u8):
+	return shift(0x0f, 2)
+"#,
+        );
+
+        let witness = res.unwrap().program.execute::<FieldPrime>(&vec![]).unwrap();
+        assert_eq!(witness.return_values(), vec![&FieldPrime::from(0x3c)]);
+    }
+
+    #[test]
+    fn unknown_shift_amount() {
+        let res = compile_str(
+            r#"
+def main(u8 a, field n) -> (u8):
+	return a >> n
+"#,
+        );
+
+        let errors = res.unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "./path/to/file:3:2\n\tShift amount is not known at compile time"
+        );
+    }
+
+    #[test]
+    fn loop_bound_not_a_field() {
+        let res = compile_str(
+            r#"
+def main(bool b) -> (field):
+	field res = 0
+	for field i in 0..b do
+		res = res + i
+	endfor
+	return res
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Expected loop bound b to be of type field, found bool"));
+    }
+
+    #[test]
+    fn assertion() {
+        let res = compile_str(
+            r#"
+def main(field a, field b) -> (field):
+	assert(a < b && a + 1 == b)
+	assert(a * a == b, "b should be the square of a")
+	return b
+"#,
+        );
+
+        let program = res.unwrap().program;
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(1), FieldPrime::from(2)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Assertion failed at 4:2: b should be the square of a"
+        );
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(2), FieldPrime::from(4)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Expected 0 to equal 1 at 3:2"
+        );
+    }
+
+    #[test]
+    fn runtime_error_location() {
+        let res = compile_str(
+            r#"
+def check(field x) -> (field):
+	x == 1
+	return x
+
+def main(field a, field b) -> (field):
+	field c = check(a)
+	for field i in 0..2 do
+		c + i == b
+	endfor
+	return c
+"#,
+        );
+
+        let program = res.unwrap().program;
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(2), FieldPrime::from(1)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Expected 1 to equal 2 at 3:2"
+        );
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(1), FieldPrime::from(1)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Expected 1 to equal 2 at 9:3"
+        );
+    }
+
+    #[test]
+    fn solver_error() {
+        let res = compile_str(
+            r#"
+def main(private u8 a) -> (u8):
+	return a
+"#,
+        );
+
+        let program = res.unwrap().program;
+
+        // the decomposition of the inputs of main is not located in the source
+        let witness = program.execute::<FieldPrime>(&vec![FieldPrime::from(300)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Witness computation failed: 300 does not fit in 8 bits"
+        );
+    }
+
+    #[test]
+    fn runtime_error_location_after_inlining() {
+        let res = compile_str(
+            r#"
+def check(field[2] x) -> (field):
+	x[0] == x[1]
+	return x[0]
+
+def main(field a, field b) -> (field):
+	field c = check([a, b])
+	c == 2
+	return c
+"#,
+        );
+
+        let program = res.unwrap().program;
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(1), FieldPrime::from(2)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Expected 2 to equal 1 at 3:2"
+        );
+
+        let witness =
+            program.execute::<FieldPrime>(&vec![FieldPrime::from(1), FieldPrime::from(1)]);
+        assert_eq!(
+            witness.err().unwrap().to_string(),
+            "Expected 2 to equal 1 at 8:2"
+        );
+    }
+
+    #[test]
+    fn assertion_not_a_boolean() {
+        let res = compile_str(
+            r#"
+def main(field a) -> (field):
+	assert(a + 1)
+	return a
+"#,
+        );
+
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Expected assertion (a + 1) to be of type bool, found field"));
+    }
+
+    #[test]
+    fn warnings() {
+        let res = compile_str(
+            r#"
+def unused() -> (field):
+	return 1
+
+def main(private field a, private field[2] b, private field c) -> (field):
+	field d = a
+	return a + b[0]
+"#,
+        );
+
+        let warnings = res.map(|c: Compiled<FieldPrime>| c.warnings).unwrap();
+        let kinds: Vec<_> = warnings.iter().map(|w| w.kind().clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                WarningKind::UnusedFunction(String::from("unused")),
+                WarningKind::UnusedParameter(String::from("c")),
+                WarningKind::UnusedVariable(String::from("d")),
+                WarningKind::PartiallyConstrainedInput(String::from("b")),
+            ]
+        );
+
+        // warnings start at the node they are about rather than at the whitespace before it
+        let starts: Vec<_> = warnings
+            .iter()
+            .map(|w| w.diagnostic().span.unwrap().start.to_string())
+            .collect();
+        assert_eq!(starts, vec!["2:5", "5:47", "6:8", "5:27"]);
+
+        let diagnostic = warnings[3].diagnostic();
+        assert_eq!(diagnostic.code, "W0004");
+        assert_eq!(diagnostic.file, Some(String::from("./path/to/file")));
+        assert_eq!(diagnostic.span.unwrap().start.line, 5);
+    }
+
+    #[test]
+    fn abi() {
+        let res = compile_str(
+            r#"
+def main(field a, private bool[2] b) -> (field, bool):
+	return a, b[0]
+"#,
+        );
+
+        let abi = res.map(|c: Compiled<FieldPrime>| c.abi).unwrap();
+        assert_eq!(abi.inputs[0].name, "a");
+        assert!(abi.inputs[0].public);
+        assert!(!abi.inputs[1].public);
+        assert_eq!(abi.inputs[1].ty.to_string(), "bool[2]");
+        assert_eq!(abi.outputs, vec![AbiType::Field, AbiType::Bool]);
+        assert_eq!(abi.primitive_count(), 3);
+    }
 }
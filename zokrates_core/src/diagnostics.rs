@@ -0,0 +1,189 @@
+//! Module containing structured diagnostics for compilation errors, which can be rendered for
+//! humans along with the offending source or serialized for tools such as editors.
+
+use parser::Position;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DiagnosticSpan {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A message about a location in the source of a module.
+///
+/// The code identifies the kind of diagnostic:
+///
+/// * E0001 - syntax error
+/// * E0002 - import error
+/// * E0003 - semantic error
+/// * E0004 - static analysis error
+/// * E0005 - the source could not be read
+/// * E0006 - the compiler failed on the source, which is a bug of the compiler
+/// * W0001 - unused parameter
+/// * W0002 - unused variable
+/// * W0003 - function which is never called from main
+/// * W0004 - private input which does not enter any constraint
+/// * W0005 - loop variable shadowing another variable
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub file: Option<String>,
+    pub span: Option<DiagnosticSpan>,
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error<S: Into<String>, M: Into<String>>(code: S, message: M) -> Self {
+        Diagnostic {
+            file: None,
+            span: None,
+            severity: Severity::Error,
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn warning<S: Into<String>, M: Into<String>>(code: S, message: M) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(code, message)
+        }
+    }
+
+    pub fn file(self, file: Option<String>) -> Self {
+        Diagnostic { file, ..self }
+    }
+
+    pub fn span(self, span: Option<(Position, Position)>) -> Self {
+        Diagnostic {
+            span: span.map(|(start, end)| DiagnosticSpan { start, end }),
+            ..self
+        }
+    }
+
+    /// Renders the diagnostic, pointing at the offending part of `source` if it is provided
+    ///
+    /// ```text
+    /// error[E0003]: Identifier "b" is undefined
+    ///  --> ./path:3:2
+    ///   |
+    /// 3 |     b == 1
+    ///   |     ^
+    /// ```
+    pub fn render(&self, source: Option<&str>) -> String {
+        let mut res = format!("{}[{}]: {}", self.severity, self.code, self.message);
+
+        let location = match (self.file.as_ref(), self.span.as_ref()) {
+            (Some(file), Some(span)) => format!("{}:{}", file, span.start),
+            (Some(file), None) => file.clone(),
+            (None, Some(span)) => format!("{}", span.start),
+            (None, None) => return res,
+        };
+
+        let line = match (self.span.as_ref(), source) {
+            (Some(span), Some(source)) => source
+                .lines()
+                .nth(span.start.line - 1)
+                .map(|line| (span, line)),
+            _ => None,
+        };
+
+        match line {
+            Some((span, line)) => {
+                let number = span.start.line.to_string();
+                let margin = " ".repeat(number.len());
+
+                // keep the tabs of the line so that the carets are aligned with it
+                let padding: String = line
+                    .chars()
+                    .take(span.start.col - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect();
+
+                // spans over several lines are underlined until the end of the first one
+                let width = match span.end.line == span.start.line {
+                    true => span.end.col.saturating_sub(span.start.col),
+                    false => line.chars().count().saturating_sub(span.start.col - 1),
+                };
+
+                res.push_str(&format!(
+                    "\n{}--> {}\n{} |\n{} | {}\n{} | {}{}",
+                    margin,
+                    location,
+                    margin,
+                    number,
+                    line,
+                    margin,
+                    padding,
+                    "^".repeat(width.max(1))
+                ));
+            }
+            None => res.push_str(&format!("\n --> {}", location)),
+        }
+
+        res
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic() -> Diagnostic {
+        Diagnostic::error("E0003", "Identifier \"b\" is undefined")
+            .file(Some(String::from("./path")))
+            .span(Some((
+                Position { line: 2, col: 2 },
+                Position { line: 2, col: 3 },
+            )))
+    }
+
+    #[test]
+    fn render_with_source() {
+        let source = "def main() -> (field):\n\tb == 1\n\treturn 1";
+        assert_eq!(
+            diagnostic().render(Some(source)),
+            "error[E0003]: Identifier \"b\" is undefined\n --> ./path:2:2\n  |\n2 | \tb == 1\n  | \t^"
+        );
+    }
+
+    #[test]
+    fn render_without_source() {
+        assert_eq!(
+            diagnostic().render(None),
+            "error[E0003]: Identifier \"b\" is undefined\n --> ./path:2:2"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            ::serde_json::to_string(&diagnostic()).unwrap(),
+            r#"{"file":"./path","span":{"start":{"line":2,"col":2},"end":{"line":2,"col":3}},"severity":"error","code":"E0003","message":"Identifier \"b\" is undefined"}"#
+        );
+    }
+}
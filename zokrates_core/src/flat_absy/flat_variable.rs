@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::str::FromStr;
 
 // A variable in a constraint system
 // id > 0 for intermediate variables
@@ -43,6 +44,25 @@ impl fmt::Display for FlatVariable {
     }
 }
 
+impl FromStr for FlatVariable {
+    type Err = String;
+
+    /// Parses a variable from the form it is displayed in
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let index = |i: &str| {
+            i.parse::<usize>()
+                .map_err(|_| format!("Invalid variable {}", s))
+        };
+
+        match s {
+            "~one" => Ok(FlatVariable::one()),
+            s if s.starts_with("~out_") => index(&s[5..]).map(FlatVariable::public),
+            s if s.starts_with("_") => index(&s[1..]).map(FlatVariable::new),
+            s => Err(format!("Invalid variable {}", s)),
+        }
+    }
+}
+
 impl fmt::Debug for FlatVariable {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "FlatVariable(id: {})", self.id)
@@ -86,4 +106,18 @@ mod tests {
         assert_eq!(format!("{}", FlatVariable::new(0)), "_0");
         assert_eq!(format!("{}", FlatVariable::new(42)), "_42");
     }
+
+    #[test]
+    fn parse() {
+        for v in &[
+            FlatVariable::one(),
+            FlatVariable::public(3),
+            FlatVariable::new(42),
+        ] {
+            assert_eq!(format!("{}", v).parse(), Ok(*v));
+        }
+        assert!("_".parse::<FlatVariable>().is_err());
+        assert!("~out_-1".parse::<FlatVariable>().is_err());
+        assert!("one".parse::<FlatVariable>().is_err());
+    }
 }
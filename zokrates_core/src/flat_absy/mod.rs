@@ -7,12 +7,15 @@
 
 pub mod flat_parameter;
 pub mod flat_variable;
+mod source_map;
 
 pub use self::flat_parameter::FlatParameter;
 pub use self::flat_variable::FlatVariable;
+pub use self::source_map::SourceMap;
 
 use helpers::{DirectiveStatement, Executable};
-#[cfg(feature = "libsnark")]
+use parser::Position;
+#[cfg(any(feature = "libsnark", feature = "wasm"))]
 use standard;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
@@ -32,6 +35,18 @@ impl<T: Field> FlatProg<T> {
         let main = self.functions.iter().find(|x| x.id == "main").unwrap();
         main.get_witness(inputs)
     }
+
+    /// Sets the file of the spans of the program which are not located yet
+    pub fn attach_file(&mut self, file: &str) {
+        for function in self.functions.iter_mut() {
+            for statement in function.statements.iter_mut() {
+                statement.attach_file(file);
+            }
+            for span in function.source_map.definitions.values_mut() {
+                span.attach_file(file);
+            }
+        }
+    }
 }
 
 impl<T: Field> fmt::Display for FlatProg<T> {
@@ -62,7 +77,7 @@ impl<T: Field> fmt::Debug for FlatProg<T> {
     }
 }
 
-#[cfg(feature = "libsnark")]
+#[cfg(any(feature = "libsnark", feature = "wasm"))]
 impl<T: Field> From<standard::DirectiveR1CS> for FlatProg<T> {
     fn from(dr1cs: standard::DirectiveR1CS) -> Self {
         FlatProg {
@@ -81,12 +96,32 @@ pub struct FlatFunction<T: Field> {
     pub statements: Vec<FlatStatement<T>>,
     /// Typed signature
     pub signature: Signature,
+    /// Debug information relating the function to its source, which its statements do not carry
+    pub source_map: SourceMap,
 }
 
 impl<T: Field> FlatFunction<T> {
     pub fn get_witness(&self, inputs: Vec<T>) -> Result<BTreeMap<FlatVariable, T>, Error> {
-        assert!(self.arguments.len() == inputs.len());
         assert!(self.id == "main");
+        self.solve(inputs)
+    }
+
+    /// Runs the function on `inputs`, returning its return values
+    pub fn execute(&self, inputs: Vec<T>) -> Result<Vec<T>, Error> {
+        let count = self
+            .signature
+            .outputs
+            .iter()
+            .map(|t| t.get_primitive_count())
+            .sum();
+        let witness = self.solve(inputs)?;
+        Ok((0..count)
+            .map(|i| witness[&FlatVariable::public(i)].clone())
+            .collect())
+    }
+
+    fn solve(&self, inputs: Vec<T>) -> Result<BTreeMap<FlatVariable, T>, Error> {
+        assert!(self.arguments.len() == inputs.len());
         let mut witness = BTreeMap::new();
         witness.insert(FlatVariable::one(), T::one());
         for (i, arg) in self.arguments.iter().enumerate() {
@@ -104,13 +139,16 @@ impl<T: Field> FlatFunction<T> {
                     let s = expr.solve(&mut witness);
                     witness.insert(id.clone(), s);
                 }
-                FlatStatement::Condition(ref lhs, ref rhs) => {
+                FlatStatement::Condition(ref lhs, ref rhs, ref metadata) => {
                     if lhs.solve(&mut witness) != rhs.solve(&mut witness) {
                         return Err(Error {
-                            message: format!(
-                                "Condition not satisfied: {} should equal {}",
-                                lhs, rhs
-                            ),
+                            message: match metadata {
+                                Some(metadata) => metadata.describe(lhs, rhs),
+                                None => format!(
+                                    "Condition not satisfied: {} should equal {}",
+                                    lhs, rhs
+                                ),
+                            },
                         });
                     }
                 }
@@ -180,10 +218,56 @@ impl<T: Field> fmt::Debug for FlatFunction<T> {
 ///
 /// * r1cs - R1CS in standard JSON data format
 
+/// The location of a source statement, kept alongside the statements generated from it
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Span {
+    pub file: Option<String>,
+    /// The function the statement is declared in
+    pub function: Option<String>,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    /// Sets the file of the span if it is not known yet
+    pub fn attach_file(&mut self, file: &str) {
+        if self.file.is_none() {
+            self.file = Some(file.to_string());
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.file {
+            Some(ref file) => write!(f, "{}:{}", file, self.start),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+/// Describes the source statement a constraint originates from, so that it can be reported when
+/// the constraint is not satisfied
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DebugInfo {
+    pub span: Span,
+    // the message of the `assert` statement, if any
+    pub message: Option<String>,
+}
+
+impl DebugInfo {
+    pub fn describe<L: fmt::Display, R: fmt::Display>(&self, left: L, right: R) -> String {
+        match self.message {
+            Some(ref message) => format!("Assertion failed at {}: {}", self.span, message),
+            None => format!("Expected {} to equal {} at {}", left, right, self.span),
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub enum FlatStatement<T: Field> {
     Return(FlatExpressionList<T>),
-    Condition(FlatExpression<T>, FlatExpression<T>),
+    Condition(FlatExpression<T>, FlatExpression<T>, Option<DebugInfo>),
     Definition(FlatVariable, FlatExpression<T>),
     Directive(DirectiveStatement<T>),
 }
@@ -193,7 +277,7 @@ impl<T: Field> fmt::Display for FlatStatement<T> {
         match *self {
             FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             FlatStatement::Return(ref expr) => write!(f, "return {}", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            FlatStatement::Condition(ref lhs, ref rhs, _) => write!(f, "{} == {}", lhs, rhs),
             FlatStatement::Directive(ref d) => write!(f, "{}", d),
         }
     }
@@ -204,8 +288,8 @@ impl<T: Field> fmt::Debug for FlatStatement<T> {
         match *self {
             FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             FlatStatement::Return(ref expr) => write!(f, "FlatReturn({:?})", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => {
-                write!(f, "FlatCondition({:?}, {:?})", lhs, rhs)
+            FlatStatement::Condition(ref lhs, ref rhs, ref metadata) => {
+                write!(f, "FlatCondition({:?}, {:?}, {:?})", lhs, rhs, metadata)
             }
             FlatStatement::Directive(ref d) => write!(f, "{:?}", d),
         }
@@ -213,6 +297,36 @@ impl<T: Field> fmt::Debug for FlatStatement<T> {
 }
 
 impl<T: Field> FlatStatement<T> {
+    /// Attaches `span` to the statement if it does not already originate from another statement
+    pub fn attach_span(&mut self, span: &Span) {
+        match *self {
+            FlatStatement::Condition(_, _, ref mut metadata @ None) => {
+                *metadata = Some(DebugInfo {
+                    span: span.clone(),
+                    message: None,
+                })
+            }
+            FlatStatement::Directive(ref mut d) if d.span.is_none() => {
+                d.span = Some(span.clone())
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets the file of the span of the statement if it is not known yet
+    pub fn attach_file(&mut self, file: &str) {
+        match *self {
+            FlatStatement::Condition(_, _, Some(ref mut metadata)) => {
+                metadata.span.attach_file(file)
+            }
+            FlatStatement::Directive(DirectiveStatement {
+                span: Some(ref mut span),
+                ..
+            }) => span.attach_file(file),
+            _ => {}
+        }
+    }
+
     pub fn apply_recursive_substitution(
         self,
         substitution: &HashMap<FlatVariable, FlatVariable>,
@@ -240,9 +354,10 @@ impl<T: Field> FlatStatement<T> {
             FlatStatement::Return(x) => {
                 FlatStatement::Return(x.apply_substitution(substitution, should_fallback))
             }
-            FlatStatement::Condition(x, y) => FlatStatement::Condition(
+            FlatStatement::Condition(x, y, metadata) => FlatStatement::Condition(
                 x.apply_substitution(substitution, should_fallback),
                 y.apply_substitution(substitution, should_fallback),
+                metadata,
             ),
             FlatStatement::Directive(d) => {
                 let outputs = d
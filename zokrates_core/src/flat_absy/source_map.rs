@@ -0,0 +1,36 @@
+use flat_absy::flat_variable::FlatVariable;
+use flat_absy::Span;
+use std::collections::{BTreeMap, HashMap};
+
+/// Relates the variables of a flattened function to the source it is flattened from, so that
+/// its execution can be followed in terms of source variables and statements
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SourceMap {
+    /// The source variables bound to variables of the function, in the order they are bound.
+    /// Variables which are assigned several times are bound again on each assignment.
+    pub variables: Vec<(String, FlatVariable)>,
+    /// The location of the source statements defining variables
+    pub definitions: BTreeMap<FlatVariable, Span>,
+}
+
+impl SourceMap {
+    /// Renames the variables of the map, leaving out the ones which were optimized away
+    pub fn apply_substitution(self, substitution: &HashMap<FlatVariable, FlatVariable>) -> Self {
+        let mut definitions = BTreeMap::new();
+        // synonyms are renamed to the variable they are a synonym of, which is defined first
+        for (v, span) in self.definitions {
+            if let Some(v) = substitution.get(&v) {
+                definitions.entry(*v).or_insert(span);
+            }
+        }
+
+        SourceMap {
+            variables: self
+                .variables
+                .into_iter()
+                .filter_map(|(name, v)| substitution.get(&v).map(|v| (name, *v)))
+                .collect(),
+            definitions,
+        }
+    }
+}
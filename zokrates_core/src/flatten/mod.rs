@@ -28,6 +28,12 @@ pub struct Flattener {
     next_var_idx: usize,
     ///
     bijection: BiMap<String, FlatVariable>,
+    /// Debug information about the function being flattened
+    source_map: SourceMap,
+    /// Debug information about the main function, once flattened
+    main_source_map: SourceMap,
+    /// Number of calls flattened so far, for each function
+    call_count: HashMap<String, usize>,
 }
 impl Flattener {
     /// Returns a `Flattener` with fresh a fresh [substitution] and [variables].
@@ -42,6 +48,9 @@ impl Flattener {
             substitution: HashMap::new(),
             next_var_idx: 0,
             bijection: BiMap::new(),
+            source_map: SourceMap::default(),
+            main_source_map: SourceMap::default(),
+            call_count: HashMap::new(),
         }
     }
 
@@ -166,7 +175,7 @@ impl Flattener {
                     // add a directive to get the bits
                     statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
                         lhs_bits.clone(),
-                        Helper::bits(),
+                        Helper::bits(self.bits),
                         vec![lhs_id],
                     )));
 
@@ -178,6 +187,7 @@ impl Flattener {
                                 box FlatExpression::Identifier(lhs_bits[i + 2]),
                                 box FlatExpression::Identifier(lhs_bits[i + 2]),
                             ),
+                            None,
                         ));
                     }
 
@@ -197,6 +207,7 @@ impl Flattener {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(lhs_id),
                         lhs_sum,
+                        None,
                     ));
                 }
 
@@ -213,7 +224,7 @@ impl Flattener {
                     // add a directive to get the bits
                     statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
                         rhs_bits.clone(),
-                        Helper::bits(),
+                        Helper::bits(self.bits),
                         vec![rhs_id],
                     )));
 
@@ -225,6 +236,7 @@ impl Flattener {
                                 box FlatExpression::Identifier(rhs_bits[i + 2]),
                                 box FlatExpression::Identifier(rhs_bits[i + 2]),
                             ),
+                            None,
                         ));
                     }
 
@@ -244,6 +256,7 @@ impl Flattener {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(rhs_id),
                         rhs_sum,
+                        None,
                     ));
                 }
 
@@ -265,7 +278,7 @@ impl Flattener {
                 // add a directive to get the bits
                 statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
                     sub_bits.clone(),
-                    Helper::bits(),
+                    Helper::bits(self.bits),
                     vec![subtraction_result.clone()],
                 )));
 
@@ -277,6 +290,7 @@ impl Flattener {
                             box FlatExpression::Identifier(sub_bits[i]),
                             box FlatExpression::Identifier(sub_bits[i]),
                         ),
+                        None,
                     ));
                 }
 
@@ -293,7 +307,7 @@ impl Flattener {
                     );
                 }
 
-                statements_flattened.push(FlatStatement::Condition(subtraction_result, expr));
+                statements_flattened.push(FlatStatement::Condition(subtraction_result, expr, None));
 
                 FlatExpression::Identifier(sub_bits[0])
             }
@@ -301,16 +315,6 @@ impl Flattener {
                 // We know from semantic checking that lhs and rhs have the same type
                 // What the expression will flatten to depends on that type
 
-                // Wanted: (Y = (X != 0) ? 1 : 0)
-                // X = a - b
-                // # Y = if X == 0 then 0 else 1 fi
-                // # M = if X == 0 then 1 else 1/X fi
-                // Y == X * M
-                // 0 == (1-Y) * X
-
-                let name_y = self.use_sym();
-                let name_m = self.use_sym();
-
                 let x = self.flatten_field_expression(
                     functions_flattened,
                     arguments_flattened,
@@ -318,27 +322,26 @@ impl Flattener {
                     FieldElementExpression::Sub(box lhs, box rhs),
                 );
 
-                statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                    vec![name_y, name_m],
-                    Helper::Rust(RustHelper::ConditionEq),
-                    vec![x.clone()],
-                )));
-                statements_flattened.push(FlatStatement::Condition(
-                    FlatExpression::Identifier(name_y),
-                    FlatExpression::Mult(box x.clone(), box FlatExpression::Identifier(name_m)),
-                ));
-
-                let res = FlatExpression::Sub(
-                    box FlatExpression::Number(T::one()),
-                    box FlatExpression::Identifier(name_y),
+                self.flatten_is_zero(statements_flattened, x)
+            }
+            BooleanExpression::UintEq(box lhs, box rhs) => {
+                // unsigned integers are equal iff their packed values are
+                let lhs_bits = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    lhs,
+                );
+                let rhs_bits = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    rhs,
                 );
 
-                statements_flattened.push(FlatStatement::Condition(
-                    FlatExpression::Number(T::zero()),
-                    FlatExpression::Mult(box res.clone(), box x),
-                ));
+                let x = FlatExpression::Sub(box pack(lhs_bits), box pack(rhs_bits));
 
-                res
+                self.flatten_is_zero(statements_flattened, x)
             }
             BooleanExpression::Le(box lhs, box rhs) => {
                 let lt = self.flatten_boolean_expression(
@@ -427,6 +430,745 @@ impl Flattener {
                 true => T::from(1),
                 false => T::from(0),
             }),
+            BooleanExpression::Member(box s, id) => self.flatten_member(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                s,
+                &id,
+            )[0]
+            .clone(),
+            BooleanExpression::Select(box array, box index) => self.flatten_select(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                array,
+                index,
+            )[0]
+            .clone(),
+        }
+    }
+
+    /// Returns a linear expression which is 1 if `x` is 0, and 0 otherwise
+    fn flatten_is_zero<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        x: FlatExpression<T>,
+    ) -> FlatExpression<T> {
+        // Wanted: (Y = (X != 0) ? 1 : 0)
+        // X = a - b
+        // # Y = if X == 0 then 0 else 1 fi
+        // # M = if X == 0 then 1 else 1/X fi
+        // Y == X * M
+        // 0 == (1-Y) * X
+
+        let name_y = self.use_sym();
+        let name_m = self.use_sym();
+
+        statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
+            vec![name_y, name_m],
+            Helper::Rust(RustHelper::ConditionEq),
+            vec![x.clone()],
+        )));
+        statements_flattened.push(FlatStatement::Condition(
+            FlatExpression::Identifier(name_y),
+            FlatExpression::Mult(box x.clone(), box FlatExpression::Identifier(name_m)),
+            None,
+        ));
+
+        let res = FlatExpression::Sub(
+            box FlatExpression::Number(T::one()),
+            box FlatExpression::Identifier(name_y),
+        );
+
+        statements_flattened.push(FlatStatement::Condition(
+            FlatExpression::Number(T::zero()),
+            FlatExpression::Mult(box res.clone(), box x),
+            None,
+        ));
+
+        res
+    }
+
+    /// Returns `e` if it is linear, otherwise a fresh variable defined as `e`
+    fn linearize<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        e: FlatExpression<T>,
+    ) -> FlatExpression<T> {
+        match e.is_linear() {
+            true => e,
+            false => {
+                let id = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(id, e));
+                FlatExpression::Identifier(id)
+            }
+        }
+    }
+
+    /// Selects between `consequence` and `alternative` element-wise, based on the linear boolean `condition`
+    fn flatten_if_else_primitives<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        condition: FlatExpression<T>,
+        consequence: Vec<FlatExpression<T>>,
+        alternative: Vec<FlatExpression<T>>,
+    ) -> Vec<FlatExpression<T>> {
+        assert_eq!(consequence.len(), alternative.len());
+
+        // res == alternative + condition * (consequence - alternative)
+        consequence
+            .into_iter()
+            .zip(alternative.into_iter())
+            .map(|(c, a)| {
+                let c = self.linearize(statements_flattened, c);
+                let a = self.linearize(statements_flattened, a);
+                let sym = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(
+                    sym,
+                    FlatExpression::Mult(
+                        box condition.clone(),
+                        box FlatExpression::Sub(box c, box a.clone()),
+                    ),
+                ));
+                FlatExpression::Add(box a, box FlatExpression::Identifier(sym))
+            })
+            .collect()
+    }
+
+    /// Decomposes `e` into `bitwidth` boolean variables, most significant bit first
+    ///
+    /// # Remarks
+    /// * Solving fails if the value of `e` does not fit in `bitwidth` bits
+    fn flatten_bits<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        e: FlatExpression<T>,
+        bitwidth: usize,
+    ) -> Vec<FlatVariable> {
+        // the directive and the decomposition check need a linear input
+        let e = self.linearize(statements_flattened, e);
+
+        // define variables for the bits
+        let bits: Vec<FlatVariable> = (0..bitwidth).map(|_| self.use_sym()).collect();
+
+        // add a directive to get the bits
+        statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
+            bits.clone(),
+            Helper::bits(bitwidth),
+            vec![e.clone()],
+        )));
+
+        // bitness checks
+        for bit in &bits {
+            statements_flattened.push(FlatStatement::Condition(
+                FlatExpression::Identifier(*bit),
+                FlatExpression::Mult(
+                    box FlatExpression::Identifier(*bit),
+                    box FlatExpression::Identifier(*bit),
+                ),
+                None,
+            ));
+        }
+
+        // bit decomposition check
+        let sum = pack(
+            bits.iter()
+                .map(|b| FlatExpression::Identifier(*b))
+                .collect(),
+        );
+
+        statements_flattened.push(FlatStatement::Condition(e, sum, None));
+
+        bits
+    }
+
+    /// Flattens an unsigned integer expression to its bits, most significant bit first
+    ///
+    /// # Postconditions
+    ///
+    /// * each bit is a linear expression, constrained to be 0 or 1
+    fn flatten_uint_expression<T: Field>(
+        &mut self,
+        functions_flattened: &Vec<FlatFunction<T>>,
+        arguments_flattened: &Vec<FlatParameter>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        expr: UintExpression<T>,
+    ) -> Vec<FlatExpression<T>> {
+        let bitwidth = expr.bitwidth();
+
+        match expr {
+            UintExpression::Value(_, v) => (0..bitwidth)
+                .map(|i| FlatExpression::Number(T::from(((v >> (bitwidth - 1 - i)) & 1) as usize)))
+                .collect(),
+            UintExpression::Identifier(_, id) => (0..bitwidth)
+                .map(|i| {
+                    FlatExpression::Identifier(
+                        self.get_latest_var_substitution(&format!("{}_b{}", id, i)),
+                    )
+                })
+                .collect(),
+            UintExpression::Add(box left, box right) => {
+                let left_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    left,
+                );
+                let right_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    right,
+                );
+
+                // the sum fits in bitwidth + 1 bits, we drop the carry
+                let sum = FlatExpression::Add(box pack(left_flattened), box pack(right_flattened));
+                let bits = self.flatten_bits(statements_flattened, sum, bitwidth + 1);
+
+                bits.into_iter()
+                    .skip(1)
+                    .map(|b| FlatExpression::Identifier(b))
+                    .collect()
+            }
+            UintExpression::Mult(box left, box right) => {
+                let left_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    left,
+                );
+                let right_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    right,
+                );
+
+                // the product fits in 2 * bitwidth bits, we keep the lowest ones
+                let product =
+                    FlatExpression::Mult(box pack(left_flattened), box pack(right_flattened));
+                let bits = self.flatten_bits(statements_flattened, product, 2 * bitwidth);
+
+                bits.into_iter()
+                    .skip(bitwidth)
+                    .map(|b| FlatExpression::Identifier(b))
+                    .collect()
+            }
+            UintExpression::And(box left, box right) => {
+                let left_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    left,
+                );
+                let right_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    right,
+                );
+
+                left_flattened
+                    .into_iter()
+                    .zip(right_flattened.into_iter())
+                    .map(|(x, y)| match (x, y) {
+                        // masking with a constant does not require any constraint
+                        (FlatExpression::Number(n), e) | (e, FlatExpression::Number(n)) => {
+                            match n == T::zero() {
+                                true => FlatExpression::Number(T::zero()),
+                                false => e,
+                            }
+                        }
+                        (x, y) => {
+                            let name_x_and_y = self.use_sym();
+                            statements_flattened.push(FlatStatement::Definition(
+                                name_x_and_y,
+                                FlatExpression::Mult(box x, box y),
+                            ));
+                            FlatExpression::Identifier(name_x_and_y)
+                        }
+                    })
+                    .collect()
+            }
+            UintExpression::Or(box left, box right) => {
+                let left_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    left,
+                );
+                let right_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    right,
+                );
+
+                left_flattened
+                    .into_iter()
+                    .zip(right_flattened.into_iter())
+                    .map(|(x, y)| match (x, y) {
+                        (FlatExpression::Number(n), e) | (e, FlatExpression::Number(n)) => {
+                            match n == T::zero() {
+                                true => e,
+                                false => FlatExpression::Number(T::one()),
+                            }
+                        }
+                        // x | y == x + y - x * y
+                        (x, y) => {
+                            let name_x_and_y = self.use_sym();
+                            statements_flattened.push(FlatStatement::Definition(
+                                name_x_and_y,
+                                FlatExpression::Mult(box x.clone(), box y.clone()),
+                            ));
+                            FlatExpression::Sub(
+                                box FlatExpression::Add(box x, box y),
+                                box FlatExpression::Identifier(name_x_and_y),
+                            )
+                        }
+                    })
+                    .collect()
+            }
+            UintExpression::Xor(box left, box right) => {
+                let left_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    left,
+                );
+                let right_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    right,
+                );
+
+                left_flattened
+                    .into_iter()
+                    .zip(right_flattened.into_iter())
+                    .map(|(x, y)| match (x, y) {
+                        (FlatExpression::Number(x), FlatExpression::Number(y)) => {
+                            FlatExpression::Number(match x == y {
+                                true => T::zero(),
+                                false => T::one(),
+                            })
+                        }
+                        (FlatExpression::Number(n), e) | (e, FlatExpression::Number(n)) => {
+                            match n == T::zero() {
+                                true => e,
+                                false => {
+                                    FlatExpression::Sub(box FlatExpression::Number(T::one()), box e)
+                                }
+                            }
+                        }
+                        // x ^ y == x + y - 2 * x * y
+                        (x, y) => {
+                            let name_x_and_y = self.use_sym();
+                            statements_flattened.push(FlatStatement::Definition(
+                                name_x_and_y,
+                                FlatExpression::Mult(box x.clone(), box y.clone()),
+                            ));
+                            FlatExpression::Sub(
+                                box FlatExpression::Add(box x, box y),
+                                box FlatExpression::Mult(
+                                    box FlatExpression::Number(T::from(2)),
+                                    box FlatExpression::Identifier(name_x_and_y),
+                                ),
+                            )
+                        }
+                    })
+                    .collect()
+            }
+            UintExpression::LeftShift(box e, box by) => {
+                let by = match by {
+                    FieldElementExpression::Number(n) => n.to_dec_string().parse::<usize>().unwrap_or(bitwidth),
+                    _ => unreachable!("shift amounts should have been reduced to constants"),
+                };
+                let e_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    e,
+                );
+
+                // shifting is free: drop the highest bits and pad with zeros
+                e_flattened
+                    .into_iter()
+                    .skip(by)
+                    .chain((0..bitwidth).map(|_| FlatExpression::Number(T::zero())))
+                    .take(bitwidth)
+                    .collect()
+            }
+            UintExpression::RightShift(box e, box by) => {
+                let by = match by {
+                    FieldElementExpression::Number(n) => n.to_dec_string().parse::<usize>().unwrap_or(bitwidth),
+                    _ => unreachable!("shift amounts should have been reduced to constants"),
+                };
+                let e_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    e,
+                );
+
+                // shifting is free: drop the lowest bits and pad with zeros
+                let by = ::std::cmp::min(by, bitwidth);
+                (0..by)
+                    .map(|_| FlatExpression::Number(T::zero()))
+                    .chain(e_flattened.into_iter().take(bitwidth - by))
+                    .collect()
+            }
+            UintExpression::IfElse(box condition, box consequence, box alternative) => {
+                let condition_flattened = self.flatten_boolean_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    condition,
+                );
+                let consequence_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    consequence,
+                );
+                let alternative_flattened = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    alternative,
+                );
+
+                self.flatten_if_else_primitives(
+                    statements_flattened,
+                    condition_flattened,
+                    consequence_flattened,
+                    alternative_flattened,
+                )
+            }
+            UintExpression::FunctionCall(_, ref id, ref param_expressions) => {
+                let exprs_flattened = self.flatten_function_call(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    id,
+                    vec![Type::Uint(bitwidth)],
+                    param_expressions,
+                );
+                assert!(exprs_flattened.expressions.len() == 1); // outside of MultipleDefinition, FunctionCalls must return a single value
+
+                // unsigned integers are returned packed
+                self.flatten_bits(
+                    statements_flattened,
+                    exprs_flattened.expressions[0].clone(),
+                    bitwidth,
+                )
+                .into_iter()
+                .map(|b| FlatExpression::Identifier(b))
+                .collect()
+            }
+            UintExpression::Member(_, box s, id) => {
+                // unsigned integers are stored packed in structs
+                let packed = self.flatten_member(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    s,
+                    &id,
+                )[0]
+                .clone();
+
+                self.flatten_bits(statements_flattened, packed, bitwidth)
+                    .into_iter()
+                    .map(|b| FlatExpression::Identifier(b))
+                    .collect()
+            }
+            UintExpression::Select(_, box array, box index) => {
+                // unsigned integers are stored packed in arrays
+                let packed = self.flatten_select(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    array,
+                    index,
+                )[0]
+                .clone();
+
+                self.flatten_bits(statements_flattened, packed, bitwidth)
+                    .into_iter()
+                    .map(|b| FlatExpression::Identifier(b))
+                    .collect()
+            }
+        }
+    }
+
+    /// Flattens a struct expression to the primitives of its members, in declaration order
+    fn flatten_struct_expression<T: Field>(
+        &mut self,
+        functions_flattened: &Vec<FlatFunction<T>>,
+        arguments_flattened: &Vec<FlatParameter>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        expr: StructExpression<T>,
+    ) -> Vec<FlatExpression<T>> {
+        let ty = Type::Struct(expr.struct_type().clone());
+
+        match expr {
+            StructExpression::Identifier(_, id) => (0..ty.get_primitive_count())
+                .map(|i| {
+                    FlatExpression::Identifier(
+                        self.get_latest_var_substitution(&format!("{}_s{}", id, i)),
+                    )
+                })
+                .collect(),
+            StructExpression::Value(_, values) => {
+                let mut res = vec![];
+                for v in values {
+                    res.extend(self.flatten_expression(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        v,
+                    ));
+                }
+                res
+            }
+            StructExpression::FunctionCall(_, ref id, ref param_expressions) => {
+                let exprs_flattened = self.flatten_function_call(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    id,
+                    vec![ty.clone()],
+                    param_expressions,
+                );
+                assert_eq!(exprs_flattened.expressions.len(), ty.get_primitive_count());
+                exprs_flattened.expressions
+            }
+            StructExpression::IfElse(box condition, box consequence, box alternative) => {
+                let condition_flattened = self.flatten_boolean_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    condition,
+                );
+                let consequence_flattened = self.flatten_struct_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    consequence,
+                );
+                let alternative_flattened = self.flatten_struct_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    alternative,
+                );
+
+                self.flatten_if_else_primitives(
+                    statements_flattened,
+                    condition_flattened,
+                    consequence_flattened,
+                    alternative_flattened,
+                )
+            }
+            StructExpression::Member(_, box s, id) => self.flatten_member(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                s,
+                &id,
+            ),
+            StructExpression::Select(_, box array, box index) => self.flatten_select(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                array,
+                index,
+            ),
+        }
+    }
+
+    /// Flattens the member `id` of the struct `s` to its primitives
+    fn flatten_member<T: Field>(
+        &mut self,
+        functions_flattened: &Vec<FlatFunction<T>>,
+        arguments_flattened: &Vec<FlatParameter>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        s: StructExpression<T>,
+        id: &str,
+    ) -> Vec<FlatExpression<T>> {
+        let range = s.struct_type().member_range(id).unwrap();
+
+        let mut primitives = self.flatten_struct_expression(
+            functions_flattened,
+            arguments_flattened,
+            statements_flattened,
+            s,
+        );
+
+        primitives.drain(range).collect()
+    }
+
+    /// Flattens the element at `index` of `array` to its primitives
+    fn flatten_select<T: Field>(
+        &mut self,
+        functions_flattened: &Vec<FlatFunction<T>>,
+        arguments_flattened: &Vec<FlatParameter>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        array: ArrayExpression<T>,
+        index: FieldElementExpression<T>,
+    ) -> Vec<FlatExpression<T>> {
+        let size = array.size();
+        let element_count = array.inner_type().get_primitive_count();
+
+        let primitives = match index {
+            FieldElementExpression::Number(n) => {
+                assert!(n < T::from(size));
+                let n = n.to_dec_string().parse::<usize>().unwrap();
+                match array {
+                    ArrayExpression::Identifier(_, id) => (n * element_count
+                        ..(n + 1) * element_count)
+                        .map(|i| {
+                            FlatExpression::Identifier(
+                                self.get_latest_var_substitution(&format!("{}_c{}", id, i)),
+                            )
+                        })
+                        .collect(),
+                    ArrayExpression::Value(_, mut expressions) => self.flatten_expression(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        expressions.swap_remove(n),
+                    ),
+                    ArrayExpression::IfElse(box condition, box consequence, box alternative) => {
+                        // [if cond then [a, b] else [c, d]][1] == if cond then [a, b][1] else [c, d][1]
+                        let index = FieldElementExpression::Number(T::from(n));
+                        self.flatten_expression(
+                            functions_flattened,
+                            arguments_flattened,
+                            statements_flattened,
+                            TypedExpression::if_else(
+                                condition,
+                                consequence.select(index.clone()),
+                                alternative.select(index),
+                            ),
+                        )
+                    }
+                    array => {
+                        let mut primitives = self.flatten_array_expression(
+                            functions_flattened,
+                            arguments_flattened,
+                            statements_flattened,
+                            array,
+                        );
+                        primitives
+                            .drain(n * element_count..(n + 1) * element_count)
+                            .collect()
+                    }
+                }
+            }
+            e => {
+                // we have array[e] with e an arbitrary expression
+                // first we check that e is in 0..array.len(), so we check that sum(if e == i then 1 else 0) == 1
+                // here depending on the size, we could use a proper range check based on bits
+                let range_check = (0..size)
+                    .map(|i| {
+                        FieldElementExpression::IfElse(
+                            box BooleanExpression::Eq(
+                                box e.clone(),
+                                box FieldElementExpression::Number(T::from(i)),
+                            ),
+                            box FieldElementExpression::Number(T::from(1)),
+                            box FieldElementExpression::Number(T::from(0)),
+                        )
+                    })
+                    .fold(FieldElementExpression::Number(T::from(0)), |acc, e| {
+                        FieldElementExpression::Add(box acc, box e)
+                    });
+
+                let range_check_statement = TypedStatement::Condition(
+                    FieldElementExpression::Number(T::from(1)).into(),
+                    range_check.into(),
+                );
+
+                self.flatten_statement(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    range_check_statement,
+                );
+
+                // now we flatten each primitive to sum(if e == i then array[i] else 0)
+                let mut lookup = vec![FlatExpression::Number(T::from(0)); element_count];
+
+                for i in 0..size {
+                    let condition = self.flatten_boolean_expression(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        BooleanExpression::Eq(
+                            box e.clone(),
+                            box FieldElementExpression::Number(T::from(i)),
+                        ),
+                    );
+                    let condition = self.linearize(statements_flattened, condition);
+
+                    let element = self.flatten_select(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        array.clone(),
+                        FieldElementExpression::Number(T::from(i)),
+                    );
+
+                    lookup = lookup
+                        .into_iter()
+                        .zip(element.into_iter())
+                        .map(|(acc, x)| {
+                            let x = self.linearize(statements_flattened, x);
+                            let sym = self.use_sym();
+                            statements_flattened.push(FlatStatement::Definition(
+                                sym,
+                                FlatExpression::Mult(box condition.clone(), box x),
+                            ));
+                            FlatExpression::Add(box acc, box FlatExpression::Identifier(sym))
+                        })
+                        .collect();
+                }
+
+                lookup
+            }
+        };
+
+        primitives
+            .into_iter()
+            .map(|e| e.apply_recursive_substitution(&self.substitution))
+            .collect()
+    }
+
+    /// Binds the primitives of the struct `id`
+    fn define_struct<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        id: &String,
+        primitives: Vec<FlatExpression<T>>,
+    ) {
+        for (i, e) in primitives.into_iter().enumerate() {
+            let var = self.use_variable(&format!("{}_s{}", id, i));
+            statements_flattened.push(FlatStatement::Definition(var, e));
+        }
+    }
+
+    /// Binds the bits of the unsigned integer `id`
+    fn define_uint<T: Field>(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        id: &String,
+        bits: Vec<FlatExpression<T>>,
+    ) {
+        for (i, bit) in bits.into_iter().enumerate() {
+            let var = self.use_variable(&format!("{}_b{}", id, i));
+            statements_flattened.push(FlatStatement::Definition(var, bit));
         }
     }
 
@@ -490,6 +1232,23 @@ impl Flattener {
                     match stat {
                         // set return statements right sidreturne as expression result
                         FlatStatement::Return(list) => {
+                            // the variables of the called function are prefixed with the call,
+                            // like the inliner prefixes the variables of the calls it inlines
+                            let slug = format!("{}_{}", funct.id, funct.signature.to_slug());
+                            let count = *self
+                                .call_count
+                                .entry(slug.clone())
+                                .and_modify(|i| *i += 1)
+                                .or_insert(1);
+                            let call = format!("{}_{}", slug, count);
+                            for (name, v) in &funct.source_map.variables {
+                                if let Some(v) = replacement_map.get(v) {
+                                    self.source_map
+                                        .variables
+                                        .push((format!("{}_{}", call, name), *v));
+                                }
+                            }
+
                             return FlatExpressionList {
                                 expressions: list
                                     .expressions
@@ -501,13 +1260,18 @@ impl Flattener {
                         FlatStatement::Definition(var, rhs) => {
                             let new_var = self.issue_new_variable();
                             replacement_map.insert(var, new_var);
+                            // the definition is located in the called function
+                            if let Some(span) = funct.source_map.definitions.get(&var) {
+                                self.source_map.definitions.insert(new_var, span.clone());
+                            }
                             let new_rhs = rhs.apply_direct_substitution(&replacement_map);
                             statements_flattened.push(FlatStatement::Definition(new_var, new_rhs));
                         }
-                        FlatStatement::Condition(lhs, rhs) => {
+                        FlatStatement::Condition(lhs, rhs, metadata) => {
                             let new_lhs = lhs.apply_direct_substitution(&replacement_map);
                             let new_rhs = rhs.apply_direct_substitution(&replacement_map);
-                            statements_flattened.push(FlatStatement::Condition(new_lhs, new_rhs));
+                            statements_flattened
+                                .push(FlatStatement::Condition(new_lhs, new_rhs, metadata));
                         }
                         FlatStatement::Directive(d) => {
                             let new_outputs = d
@@ -529,6 +1293,7 @@ impl Flattener {
                                     outputs: new_outputs,
                                     helper: d.helper.clone(),
                                     inputs: new_inputs,
+                                    span: d.span.clone(),
                                 },
                             ))
                         }
@@ -563,7 +1328,19 @@ impl Flattener {
                 statements_flattened,
                 e,
             )],
-            TypedExpression::FieldElementArray(e) => self.flatten_field_array_expression(
+            TypedExpression::Array(e) => self.flatten_array_expression(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                e,
+            ),
+            TypedExpression::Uint(e) => vec![pack(self.flatten_uint_expression(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                e,
+            ))],
+            TypedExpression::Struct(e) => self.flatten_struct_expression(
                 functions_flattened,
                 arguments_flattened,
                 statements_flattened,
@@ -711,6 +1488,7 @@ impl Flattener {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::one()),
                     FlatExpression::Mult(box invb.into(), box new_right.clone().into()),
+                    None,
                 ));
 
                 // # c = a/b
@@ -724,6 +1502,7 @@ impl Flattener {
                 statements_flattened.push(FlatStatement::Condition(
                     new_left.into(),
                     FlatExpression::Mult(box new_right, box inverse.into()),
+                    None,
                 ));
 
                 inverse.into()
@@ -810,218 +1589,99 @@ impl Flattener {
                 assert!(exprs_flattened.expressions.len() == 1); // outside of MultipleDefinition, FunctionCalls must return a single value
                 exprs_flattened.expressions[0].clone()
             }
-            FieldElementExpression::Select(box array, box index) => {
-                match index {
-                    FieldElementExpression::Number(n) => match array {
-                        FieldElementArrayExpression::Identifier(size, id) => {
-                            assert!(n < T::from(size));
-                            FlatExpression::Identifier(
-                                self.get_latest_var_substitution(&format!("{}_c{}", id, n))
-                                    .clone(),
-                            )
-                        }
-                        FieldElementArrayExpression::Value(size, expressions) => {
-                            assert!(n < T::from(size));
-                            self.flatten_field_expression(
-                                functions_flattened,
-                                arguments_flattened,
-                                statements_flattened,
-                                expressions[n.to_dec_string().parse::<usize>().unwrap()].clone(),
-                            )
-                            .apply_recursive_substitution(&self.substitution)
-                        }
-                        FieldElementArrayExpression::FunctionCall(..) => {
-                            unimplemented!("please use intermediate variables for now")
-                        }
-                        FieldElementArrayExpression::IfElse(
-                            condition,
-                            consequence,
-                            alternative,
-                        ) => {
-                            // [if cond then [a, b] else [c, d]][1] == if cond then [a, b][1] else [c, d][1]
-                            self.flatten_field_expression(
-                                functions_flattened,
-                                arguments_flattened,
-                                statements_flattened,
-                                FieldElementExpression::IfElse(
-                                    condition,
-                                    box FieldElementExpression::Select(
-                                        consequence,
-                                        box FieldElementExpression::Number(n.clone()),
-                                    ),
-                                    box FieldElementExpression::Select(
-                                        alternative,
-                                        box FieldElementExpression::Number(n),
-                                    ),
-                                ),
-                            )
-                            .apply_recursive_substitution(&self.substitution)
-                        }
-                    },
-                    e => {
-                        let size = array.size();
-                        // we have array[e] with e an arbitrary expression
-                        // first we check that e is in 0..array.len(), so we check that sum(if e == i then 1 else 0) == 1
-                        // here depending on the size, we could use a proper range check based on bits
-                        let range_check = (0..size)
-                            .map(|i| {
-                                FieldElementExpression::IfElse(
-                                    box BooleanExpression::Eq(
-                                        box e.clone(),
-                                        box FieldElementExpression::Number(T::from(i)),
-                                    ),
-                                    box FieldElementExpression::Number(T::from(1)),
-                                    box FieldElementExpression::Number(T::from(0)),
-                                )
-                            })
-                            .fold(FieldElementExpression::Number(T::from(0)), |acc, e| {
-                                FieldElementExpression::Add(box acc, box e)
-                            });
-
-                        let range_check_statement = TypedStatement::Condition(
-                            FieldElementExpression::Number(T::from(1)).into(),
-                            range_check.into(),
-                        );
-
-                        self.flatten_statement(
-                            functions_flattened,
-                            arguments_flattened,
-                            statements_flattened,
-                            range_check_statement,
-                        );
-
-                        // now we flatten to sum(if e == i then array[i] else 0)
-                        let lookup = (0..size)
-                            .map(|i| {
-                                FieldElementExpression::IfElse(
-                                    box BooleanExpression::Eq(
-                                        box e.clone(),
-                                        box FieldElementExpression::Number(T::from(i)),
-                                    ),
-                                    box match array.clone() {
-                                        FieldElementArrayExpression::Identifier(_, id) => {
-                                            FieldElementExpression::Identifier(format!(
-                                                "{}_c{}",
-                                                id, i
-                                            ))
-                                        }
-                                        FieldElementArrayExpression::Value(size, expressions) => {
-                                            assert_eq!(size, expressions.len());
-                                            expressions[i].clone()
-                                        }
-                                        FieldElementArrayExpression::FunctionCall(..) => {
-                                            unimplemented!(
-                                                "please use intermediate variables for now"
-                                            )
-                                        }
-                                        FieldElementArrayExpression::IfElse(
-                                            condition,
-                                            consequence,
-                                            alternative,
-                                        ) => FieldElementExpression::IfElse(
-                                            condition,
-                                            box FieldElementExpression::Select(
-                                                consequence,
-                                                box FieldElementExpression::Number(T::from(i)),
-                                            ),
-                                            box FieldElementExpression::Select(
-                                                alternative,
-                                                box FieldElementExpression::Number(T::from(i)),
-                                            ),
-                                        ),
-                                    },
-                                    box FieldElementExpression::Number(T::from(0)),
-                                )
-                            })
-                            .fold(FieldElementExpression::Number(T::from(0)), |acc, e| {
-                                FieldElementExpression::Add(box acc, box e)
-                            });
-
-                        self.flatten_field_expression(
-                            functions_flattened,
-                            arguments_flattened,
-                            statements_flattened,
-                            lookup,
-                        )
-                        .apply_recursive_substitution(&self.substitution)
-                    }
-                }
-            }
+            FieldElementExpression::Member(box s, id) => self.flatten_member(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                s,
+                &id,
+            )[0]
+            .clone(),
+            FieldElementExpression::Select(box array, box index) => self.flatten_select(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                array,
+                index,
+            )[0]
+            .clone(),
         }
     }
 
-    fn flatten_field_array_expression<T: Field>(
+    /// Flattens an array expression to the primitives of its elements, in order
+    fn flatten_array_expression<T: Field>(
         &mut self,
         functions_flattened: &Vec<FlatFunction<T>>,
         arguments_flattened: &Vec<FlatParameter>,
         statements_flattened: &mut Vec<FlatStatement<T>>,
-        expr: FieldElementArrayExpression<T>,
+        expr: ArrayExpression<T>,
     ) -> Vec<FlatExpression<T>> {
+        let ty = Type::Array(expr.array_type().clone());
+
         match expr {
-            FieldElementArrayExpression::Identifier(size, x) => (0..size)
+            ArrayExpression::Identifier(_, x) => (0..ty.get_primitive_count())
                 .map(|index| {
                     FlatExpression::Identifier(
-                        self.get_latest_var_substitution(&format!("{}_c{}", x, index))
-                            .clone(),
+                        self.get_latest_var_substitution(&format!("{}_c{}", x, index)),
                     )
                 })
                 .collect(),
-            FieldElementArrayExpression::Value(size, values) => {
-                assert_eq!(size, values.len());
-                values
-                    .into_iter()
-                    .map(|v| {
-                        self.flatten_field_expression(
-                            functions_flattened,
-                            arguments_flattened,
-                            statements_flattened,
-                            v,
-                        )
-                    })
-                    .collect()
+            ArrayExpression::Value(_, values) => {
+                let mut res = vec![];
+                for v in values {
+                    res.extend(self.flatten_expression(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        v,
+                    ));
+                }
+                res
             }
-            FieldElementArrayExpression::FunctionCall(size, ref id, ref param_expressions) => {
+            ArrayExpression::FunctionCall(_, ref id, ref param_expressions) => {
                 let exprs_flattened = self.flatten_function_call(
                     functions_flattened,
                     arguments_flattened,
                     statements_flattened,
                     id,
-                    vec![Type::FieldElementArray(size)],
+                    vec![ty.clone()],
                     param_expressions,
                 );
-                assert!(exprs_flattened.expressions.len() == size); // outside of MultipleDefinition, FunctionCalls must return a single value
+                assert_eq!(exprs_flattened.expressions.len(), ty.get_primitive_count());
                 exprs_flattened.expressions
             }
-            FieldElementArrayExpression::IfElse(
-                ref condition,
-                ref consequence,
-                ref alternative,
-            ) => {
-                let size = match consequence.get_type() {
-                    Type::FieldElementArray(n) => n,
-                    _ => unreachable!(),
-                };
-                (0..size)
-                    .map(|i| {
-                        self.flatten_field_expression(
-                            functions_flattened,
-                            arguments_flattened,
-                            statements_flattened,
-                            FieldElementExpression::IfElse(
-                                condition.clone(),
-                                box FieldElementExpression::Select(
-                                    consequence.clone(),
-                                    box FieldElementExpression::Number(T::from(i)),
-                                ),
-                                box FieldElementExpression::Select(
-                                    alternative.clone(),
-                                    box FieldElementExpression::Number(T::from(i)),
-                                ),
-                            ),
-                        )
-                    })
-                    .collect()
+            ArrayExpression::IfElse(box condition, box consequence, box alternative) => {
+                let size = consequence.size();
+                let mut res = vec![];
+                for i in 0..size {
+                    let index = FieldElementExpression::Number(T::from(i));
+                    res.extend(self.flatten_expression(
+                        functions_flattened,
+                        arguments_flattened,
+                        statements_flattened,
+                        TypedExpression::if_else(
+                            condition.clone(),
+                            consequence.clone().select(index.clone()),
+                            alternative.clone().select(index),
+                        ),
+                    ));
+                }
+                res
             }
+            ArrayExpression::Member(_, box s, id) => self.flatten_member(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                s,
+                &id,
+            ),
+            ArrayExpression::Select(_, box array, box index) => self.flatten_select(
+                functions_flattened,
+                arguments_flattened,
+                statements_flattened,
+                array,
+                index,
+            ),
         }
     }
 
@@ -1060,10 +1720,33 @@ impl Flattener {
                 // declarations have already been checked
                 ()
             }
+            TypedStatement::Definition(TypedAssignee::Identifier(v), TypedExpression::Uint(e)) => {
+                // unsigned integers are kept as bits within a function
+                let bits = self.flatten_uint_expression(
+                    functions_flattened,
+                    arguments_flattened,
+                    statements_flattened,
+                    e,
+                );
+
+                let bits = bits
+                    .into_iter()
+                    .map(|e| e.apply_recursive_substitution(&self.substitution))
+                    .collect();
+
+                self.define_uint(statements_flattened, &v.id, bits);
+            }
             TypedStatement::Definition(assignee, expr) => {
                 // define n variables with n the number of primitive types for v_type
                 // assign them to the n primitive types for expr
 
+                let v = match assignee {
+                    TypedAssignee::Identifier(v) => v,
+                    TypedAssignee::ArrayElement(..) => {
+                        unreachable!("array element assignments should have been unrolled")
+                    }
+                };
+
                 let rhs = self.flatten_expression(
                     functions_flattened,
                     arguments_flattened,
@@ -1078,132 +1761,22 @@ impl Flattener {
 
                 match expr.get_type() {
                     Type::FieldElement | Type::Boolean => {
-                        match assignee {
-                            TypedAssignee::Identifier(ref v) => {
-                                let debug_name = v.clone().id;
-                                let var = self.use_variable(&debug_name);
-                                // handle return of function call
-                                let var_to_replace = self.get_latest_var_substitution(&debug_name);
-                                if !(var == var_to_replace)
-                                    && self.variables.contains(&var_to_replace)
-                                    && !self.substitution.contains_key(&var_to_replace)
-                                {
-                                    self.substitution
-                                        .insert(var_to_replace.clone(), var.clone());
-                                }
-                                statements_flattened
-                                    .push(FlatStatement::Definition(var, rhs[0].clone()));
-                            }
-                            TypedAssignee::ArrayElement(ref array, ref index) => {
-                                let expr = match expr {
-                                    TypedExpression::FieldElement(e) => e,
-                                    _ => panic!("not a field element as rhs of array element update, should have been caught at semantic")
-                                };
-                                match index {
-                                    box FieldElementExpression::Number(n) => {
-                                        match array {
-                                            box TypedAssignee::Identifier(id) => {
-                                                let debug_name = format!("{}_c{}", id.id, n);
-                                                let var = self.use_variable(&debug_name);
-                                                // handle return of function call
-                                                let var_to_replace =
-                                                    self.get_latest_var_substitution(&debug_name);
-                                                if !(var == var_to_replace)
-                                                    && self.variables.contains(&var_to_replace)
-                                                    && !self
-                                                        .substitution
-                                                        .contains_key(&var_to_replace)
-                                                {
-                                                    self.substitution.insert(
-                                                        var_to_replace.clone(),
-                                                        var.clone(),
-                                                    );
-                                                }
-                                                statements_flattened.push(
-                                                    FlatStatement::Definition(var, rhs[0].clone()),
-                                                );
-                                            }
-                                            _ => panic!("no multidimension array for now"),
-                                        }
-                                    }
-                                    box e => {
-                                        // we have array[e] with e an arbitrary expression
-                                        // first we check that e is in 0..array.len(), so we check that sum(if e == i then 1 else 0) == 1
-                                        // here depending on the size, we could use a proper range check based on bits
-                                        let size = match array.get_type() {
-                                            Type::FieldElementArray(n) => n,
-                                            _ => panic!("checker should generate array element based on non array")
-                                        };
-                                        let range_check = (0..size)
-                                            .map(|i| {
-                                                FieldElementExpression::IfElse(
-                                                    box BooleanExpression::Eq(
-                                                        box e.clone(),
-                                                        box FieldElementExpression::Number(
-                                                            T::from(i),
-                                                        ),
-                                                    ),
-                                                    box FieldElementExpression::Number(T::from(1)),
-                                                    box FieldElementExpression::Number(T::from(0)),
-                                                )
-                                            })
-                                            .fold(
-                                                FieldElementExpression::Number(T::from(0)),
-                                                |acc, e| {
-                                                    FieldElementExpression::Add(box acc, box e)
-                                                },
-                                            );
-
-                                        let range_check_statement = TypedStatement::Condition(
-                                            FieldElementExpression::Number(T::from(1)).into(),
-                                            range_check.into(),
-                                        );
-
-                                        self.flatten_statement(
-                                            functions_flattened,
-                                            arguments_flattened,
-                                            statements_flattened,
-                                            range_check_statement,
-                                        );
-
-                                        // now we redefine the whole array, updating only the piece that changed
-                                        // stat(array[i] = if e == i then `expr` else `array[i]`)
-                                        for i in 0..size {
-                                            let rhs = FieldElementExpression::IfElse(
-                                                box BooleanExpression::Eq(
-                                                    box e.clone(),
-                                                    box FieldElementExpression::Number(T::from(i)),
-                                                ),
-                                                box expr.clone(),
-                                                box e.clone(),
-                                            );
-
-                                            let rhs_flattened = self.flatten_field_expression(
-                                                functions_flattened,
-                                                arguments_flattened,
-                                                statements_flattened,
-                                                rhs,
-                                            );
-
-                                            let var =
-                                                self.use_variable(&format!("{}_c{}", array, i));
-
-                                            statements_flattened.push(FlatStatement::Definition(
-                                                var,
-                                                rhs_flattened,
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
+                        let debug_name = v.id;
+                        let var = self.use_variable(&debug_name);
+                        // handle return of function call
+                        let var_to_replace = self.get_latest_var_substitution(&debug_name);
+                        if !(var == var_to_replace)
+                            && self.variables.contains(&var_to_replace)
+                            && !self.substitution.contains_key(&var_to_replace)
+                        {
+                            self.substitution
+                                .insert(var_to_replace.clone(), var.clone());
                         }
+                        statements_flattened.push(FlatStatement::Definition(var, rhs[0].clone()));
                     }
-                    Type::FieldElementArray(..) => {
+                    Type::Array(..) => {
                         for (index, r) in rhs.into_iter().enumerate() {
-                            let debug_name = match assignee {
-                                TypedAssignee::Identifier(ref v) => format!("{}_c{}", v.id, index),
-                                _ => unimplemented!(),
-                            };
+                            let debug_name = format!("{}_c{}", v.id, index);
                             let var = self.use_variable(&debug_name);
                             // handle return of function call
                             let var_to_replace = self.get_latest_var_substitution(&debug_name);
@@ -1217,6 +1790,8 @@ impl Flattener {
                             statements_flattened.push(FlatStatement::Definition(var, r));
                         }
                     }
+                    Type::Uint(..) => unreachable!("unsigned integers are only assigned to identifiers"),
+                    Type::Struct(..) => self.define_struct(statements_flattened, &v.id, rhs),
                 }
             }
             TypedStatement::Condition(expr1, expr2) => {
@@ -1243,10 +1818,10 @@ impl Flattener {
                         );
 
                         if lhs.is_linear() {
-                            statements_flattened.push(FlatStatement::Condition(lhs, rhs));
+                            statements_flattened.push(FlatStatement::Condition(lhs, rhs, None));
                         } else if rhs.is_linear() {
                             // swap so that left side is linear
-                            statements_flattened.push(FlatStatement::Condition(rhs, lhs));
+                            statements_flattened.push(FlatStatement::Condition(rhs, lhs, None));
                         } else {
                             unimplemented!()
                         }
@@ -1270,26 +1845,24 @@ impl Flattener {
                         );
 
                         if lhs.is_linear() {
-                            statements_flattened.push(FlatStatement::Condition(lhs, rhs));
+                            statements_flattened.push(FlatStatement::Condition(lhs, rhs, None));
                         } else if rhs.is_linear() {
                             // swap so that left side is linear
-                            statements_flattened.push(FlatStatement::Condition(rhs, lhs));
+                            statements_flattened.push(FlatStatement::Condition(rhs, lhs, None));
                         } else {
                             unimplemented!()
                         }
                     }
-                    (
-                        TypedExpression::FieldElementArray(e1),
-                        TypedExpression::FieldElementArray(e2),
-                    ) => {
+                    (TypedExpression::Array(e1), TypedExpression::Array(e2)) => {
+                        // arrays are equal iff all their primitives are
                         let (lhs, rhs) = (
-                            self.flatten_field_array_expression(
+                            self.flatten_array_expression(
                                 functions_flattened,
                                 arguments_flattened,
                                 statements_flattened,
                                 e1,
                             ),
-                            self.flatten_field_array_expression(
+                            self.flatten_array_expression(
                                 functions_flattened,
                                 arguments_flattened,
                                 statements_flattened,
@@ -1297,23 +1870,66 @@ impl Flattener {
                             ),
                         );
 
+                        assert_eq!(lhs.len(), rhs.len());
+
+                        for (l, r) in lhs.into_iter().zip(rhs.into_iter()) {
+                            let l = l.apply_recursive_substitution(&self.substitution);
+                            let r = r.apply_recursive_substitution(&self.substitution);
+                            if l.is_linear() {
+                                statements_flattened.push(FlatStatement::Condition(l, r, None));
+                            } else {
+                                let r = self.linearize(statements_flattened, r);
+                                statements_flattened.push(FlatStatement::Condition(r, l, None));
+                            }
+                        }
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        let (lhs, rhs) = (
+                            pack(self.flatten_uint_expression(
+                                functions_flattened,
+                                arguments_flattened,
+                                statements_flattened,
+                                e1,
+                            ))
+                            .apply_recursive_substitution(&self.substitution),
+                            pack(self.flatten_uint_expression(
+                                functions_flattened,
+                                arguments_flattened,
+                                statements_flattened,
+                                e2,
+                            ))
+                            .apply_recursive_substitution(&self.substitution),
+                        );
+
+                        statements_flattened.push(FlatStatement::Condition(lhs, rhs, None));
+                    }
+                    (TypedExpression::Struct(e1), TypedExpression::Struct(e2)) => {
+                        // structs are equal iff all their primitives are
                         let (lhs, rhs) = (
-                            lhs.into_iter()
-                                .map(|e| e.apply_recursive_substitution(&self.substitution)),
-                            rhs.into_iter()
-                                .map(|e| e.apply_recursive_substitution(&self.substitution)),
+                            self.flatten_struct_expression(
+                                functions_flattened,
+                                arguments_flattened,
+                                statements_flattened,
+                                e1,
+                            ),
+                            self.flatten_struct_expression(
+                                functions_flattened,
+                                arguments_flattened,
+                                statements_flattened,
+                                e2,
+                            ),
                         );
 
                         assert_eq!(lhs.len(), rhs.len());
 
                         for (l, r) in lhs.into_iter().zip(rhs.into_iter()) {
+                            let l = l.apply_recursive_substitution(&self.substitution);
+                            let r = r.apply_recursive_substitution(&self.substitution);
                             if l.is_linear() {
-                                statements_flattened.push(FlatStatement::Condition(l, r));
-                            } else if r.is_linear() {
-                                // swap so that left side is linear
-                                statements_flattened.push(FlatStatement::Condition(r, l));
+                                statements_flattened.push(FlatStatement::Condition(l, r, None));
                             } else {
-                                unimplemented!()
+                                let r = self.linearize(statements_flattened, r);
+                                statements_flattened.push(FlatStatement::Condition(r, l, None));
                             }
                         }
                     }
@@ -1322,24 +1938,50 @@ impl Flattener {
                     ),
                 }
             }
-            TypedStatement::For(var, start, end, statements) => {
-                let mut current = start;
-                while current < end {
-                    statements_flattened.push(FlatStatement::Definition(
-                        self.use_variable(&var.id),
-                        FlatExpression::Number(current.clone()),
-                    ));
-                    for s in statements.clone() {
-                        self.flatten_statement(
+            TypedStatement::Assertion(e, metadata) => {
+                let (lhs, rhs) = match e {
+                    // equality of field elements can be enforced directly
+                    BooleanExpression::Eq(box e1, box e2) => (
+                        self.flatten_field_expression(
                             functions_flattened,
                             arguments_flattened,
                             statements_flattened,
-                            s,
-                        );
-                    }
-                    current = T::one() + &current;
-                }
+                            e1,
+                        ),
+                        self.flatten_field_expression(
+                            functions_flattened,
+                            arguments_flattened,
+                            statements_flattened,
+                            e2,
+                        ),
+                    ),
+                    // other expressions are required to evaluate to true
+                    e => (
+                        FlatExpression::Number(T::one()),
+                        self.flatten_boolean_expression(
+                            functions_flattened,
+                            arguments_flattened,
+                            statements_flattened,
+                            e,
+                        ),
+                    ),
+                };
+
+                let lhs = lhs.apply_recursive_substitution(&self.substitution);
+                let rhs = rhs.apply_recursive_substitution(&self.substitution);
+
+                // the left side of a condition is linear
+                let (lhs, rhs) = match (lhs.is_linear(), rhs.is_linear()) {
+                    (true, _) => (lhs, rhs),
+                    (false, true) => (rhs, lhs),
+                    (false, false) => (self.linearize(statements_flattened, lhs), rhs),
+                };
+
+                statements_flattened.push(FlatStatement::Condition(lhs, rhs, Some(metadata)));
             }
+            TypedStatement::For(..) => unreachable!("for loops should have been unrolled"),
+            // locations are attached to the flattened statements in `flatten_function`
+            TypedStatement::Span(..) => {}
             TypedStatement::MultipleDefinition(vars, rhs) => {
                 // flatten the right side to p = sum(var_i.type.primitive_count) expressions
                 // define p new variables to the right side expressions
@@ -1365,26 +2007,6 @@ impl Flattener {
                         for v in vars {
                             // determine how many field elements it carries
                             match v.get_type() {
-                                Type::FieldElementArray(size) => {
-                                    for index in 0..size {
-                                        let debug_name = format!("{}_c{}", v.id, index);
-                                        let var = self.use_variable(&debug_name);
-                                        // handle return of function call
-                                        let var_to_replace =
-                                            self.get_latest_var_substitution(&debug_name);
-                                        if !(var == var_to_replace)
-                                            && self.variables.contains(&var_to_replace)
-                                            && !self.substitution.contains_key(&var_to_replace)
-                                        {
-                                            self.substitution
-                                                .insert(var_to_replace.clone(), var.clone());
-                                        }
-                                        statements_flattened.push(FlatStatement::Definition(
-                                            var,
-                                            iterator.next().unwrap(),
-                                        ));
-                                    }
-                                }
                                 Type::Boolean | Type::FieldElement => {
                                     let debug_name = v.id;
                                     let var = self.use_variable(&debug_name);
@@ -1403,6 +2025,45 @@ impl Flattener {
                                         iterator.next().unwrap(),
                                     ));
                                 }
+                                Type::Uint(bitwidth) => {
+                                    // unsigned integers are returned packed
+                                    let bits = self
+                                        .flatten_bits(
+                                            statements_flattened,
+                                            iterator.next().unwrap(),
+                                            bitwidth,
+                                        )
+                                        .into_iter()
+                                        .map(|b| FlatExpression::Identifier(b))
+                                        .collect();
+                                    self.define_uint(statements_flattened, &v.id, bits);
+                                }
+                                ty @ Type::Struct(..) => {
+                                    let primitives = (0..ty.get_primitive_count())
+                                        .map(|_| iterator.next().unwrap())
+                                        .collect();
+                                    self.define_struct(statements_flattened, &v.id, primitives);
+                                }
+                                ty @ Type::Array(..) => {
+                                    for index in 0..ty.get_primitive_count() {
+                                        let debug_name = format!("{}_c{}", v.id, index);
+                                        let var = self.use_variable(&debug_name);
+                                        // handle return of function call
+                                        let var_to_replace =
+                                            self.get_latest_var_substitution(&debug_name);
+                                        if !(var == var_to_replace)
+                                            && self.variables.contains(&var_to_replace)
+                                            && !self.substitution.contains_key(&var_to_replace)
+                                        {
+                                            self.substitution
+                                                .insert(var_to_replace.clone(), var.clone());
+                                        }
+                                        statements_flattened.push(FlatStatement::Definition(
+                                            var,
+                                            iterator.next().unwrap(),
+                                        ));
+                                    }
+                                }
                             }
                         }
 
@@ -1429,6 +2090,7 @@ impl Flattener {
         self.substitution = HashMap::new();
 
         self.bijection = BiMap::new();
+        self.source_map = SourceMap::default();
 
         self.next_var_idx = 0;
 
@@ -1452,25 +2114,90 @@ impl Flattener {
                         private: arg.private,
                     });
                 }
-                Type::FieldElementArray(size) => {
-                    for i in 0..size {
+                Type::Uint(bitwidth) => {
+                    // unsigned integers are passed packed, and decomposed on entry
+                    let id = self.use_variable(&arg.id.id);
+                    arguments_flattened.push(FlatParameter {
+                        id: id,
+                        private: arg.private,
+                    });
+                    let bits = self
+                        .flatten_bits(
+                            &mut statements_flattened,
+                            FlatExpression::Identifier(id),
+                            bitwidth,
+                        )
+                        .into_iter()
+                        .map(|b| FlatExpression::Identifier(b))
+                        .collect();
+                    self.define_uint(&mut statements_flattened, &arg.id.id, bits);
+                }
+                Type::Struct(ty) => {
+                    // structs are passed as their primitives, unsigned integer members are range checked on entry
+                    for (i, t) in primitive_types(&Type::Struct(ty)).into_iter().enumerate() {
+                        let id = self.use_variable(&format!("{}_s{}", arg.id.id, i));
                         arguments_flattened.push(FlatParameter {
-                            id: self.use_variable(&format!("{}_c{}", arg.id.id, i)),
+                            id: id,
                             private: arg.private,
-                        })
+                        });
+                        if let Type::Uint(bitwidth) = t {
+                            self.flatten_bits(
+                                &mut statements_flattened,
+                                FlatExpression::Identifier(id),
+                                bitwidth,
+                            );
+                        }
+                    }
+                }
+                Type::Array(ty) => {
+                    // arrays are passed as their primitives, unsigned integer elements are range checked on entry
+                    for (i, t) in primitive_types(&Type::Array(ty)).into_iter().enumerate() {
+                        let id = self.use_variable(&format!("{}_c{}", arg.id.id, i));
+                        arguments_flattened.push(FlatParameter {
+                            id: id,
+                            private: arg.private,
+                        });
+                        if let Type::Uint(bitwidth) = t {
+                            self.flatten_bits(
+                                &mut statements_flattened,
+                                FlatExpression::Identifier(id),
+                                bitwidth,
+                            );
+                        }
                     }
                 }
             }
         }
 
+        // the location of the statement being flattened, as marked in the function
+        let mut span = None;
+
         // flatten statements in functions and apply substitution
         for stat in funct.statements {
-            self.flatten_statement(
-                functions_flattened,
-                &arguments_flattened,
-                &mut statements_flattened,
-                stat,
-            );
+            match stat {
+                TypedStatement::Span(s) => span = Some(s),
+                stat => {
+                    let first = statements_flattened.len();
+                    self.flatten_statement(
+                        functions_flattened,
+                        &arguments_flattened,
+                        &mut statements_flattened,
+                        stat,
+                    );
+                    // locate what the statement was flattened to
+                    if let Some(ref span) = span {
+                        for s in statements_flattened[first..].iter_mut() {
+                            s.attach_span(span);
+                            if let FlatStatement::Definition(ref v, _) = *s {
+                                self.source_map
+                                    .definitions
+                                    .entry(*v)
+                                    .or_insert_with(|| span.clone());
+                            }
+                        }
+                    }
+                }
+            }
         }
 
         FlatFunction {
@@ -1478,6 +2205,7 @@ impl Flattener {
             arguments: arguments_flattened,
             statements: statements_flattened,
             signature: funct.signature,
+            source_map: self.source_map.clone(),
         }
     }
 
@@ -1497,6 +2225,10 @@ impl Flattener {
 
         for func in prog.functions {
             let flattened_func = self.flatten_function(&mut functions_flattened, func);
+            // the other functions are inlined into main, which is all the program is made of
+            if flattened_func.id == "main" {
+                self.main_source_map = self.source_map.clone();
+            }
             functions_flattened.push(flattened_func);
         }
 
@@ -1505,6 +2237,11 @@ impl Flattener {
         }
     }
 
+    /// Returns the debug information about the main function of the last flattened program
+    pub fn source_map(&self) -> &SourceMap {
+        &self.main_source_map
+    }
+
     /// Checks if the given name is a not used variable and returns a fresh variable.
     /// # Arguments
     ///
@@ -1514,6 +2251,7 @@ impl Flattener {
         let var = self.issue_new_variable();
 
         self.bijection.insert(name.to_string(), var);
+        self.source_map.variables.push((name.to_string(), var));
         var
     }
 
@@ -1544,11 +2282,60 @@ impl Flattener {
     }
 }
 
+/// Returns the types of the primitives `ty` is flattened to
+fn primitive_types(ty: &Type) -> Vec<Type> {
+    match *ty {
+        Type::Array(ref ty) => (0..ty.size)
+            .flat_map(|_| primitive_types(&ty.ty))
+            .collect(),
+        Type::Struct(ref ty) => ty
+            .members
+            .iter()
+            .flat_map(|m| primitive_types(&m.ty))
+            .collect(),
+        ref t => vec![t.clone()],
+    }
+}
+
+/// Returns the linear combination of `bits`, most significant bit first
+fn pack<T: Field>(bits: Vec<FlatExpression<T>>) -> FlatExpression<T> {
+    let bitwidth = bits.len();
+    bits.into_iter()
+        .enumerate()
+        .fold(FlatExpression::Number(T::from(0)), |acc, (i, bit)| {
+            FlatExpression::Add(box acc, box scale(bit, T::from(2).pow(bitwidth - i - 1)))
+        })
+}
+
+/// Multiplies the linear expression `e` by `k`, keeping the result linear
+fn scale<T: Field>(e: FlatExpression<T>, k: T) -> FlatExpression<T> {
+    match e {
+        FlatExpression::Number(n) => FlatExpression::Number(n * k),
+        FlatExpression::Identifier(id) => {
+            FlatExpression::Mult(box FlatExpression::Number(k), box FlatExpression::Identifier(id))
+        }
+        FlatExpression::Add(box x, box y) => {
+            FlatExpression::Add(box scale(x, k.clone()), box scale(y, k))
+        }
+        FlatExpression::Sub(box x, box y) => {
+            FlatExpression::Sub(box scale(x, k.clone()), box scale(y, k))
+        }
+        FlatExpression::Mult(box FlatExpression::Number(n), box FlatExpression::Identifier(id))
+        | FlatExpression::Mult(box FlatExpression::Identifier(id), box FlatExpression::Number(n)) => {
+            FlatExpression::Mult(
+                box FlatExpression::Number(n * k),
+                box FlatExpression::Identifier(id),
+            )
+        }
+        e => panic!("Expected linear expression, found {}", e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use types::Signature;
-    use types::Type;
+    use types::{ArrayType, StructMember, StructType, Type};
     use zokrates_field::field::FieldPrime;
 
     #[test]
@@ -1571,6 +2358,7 @@ mod tests {
             signature: Signature::new()
                 .inputs(vec![])
                 .outputs(vec![Type::FieldElement, Type::FieldElement]),
+            source_map: SourceMap::default(),
         }];
         let arguments_flattened = vec![];
         let mut statements_flattened = vec![];
@@ -1623,6 +2411,7 @@ mod tests {
             signature: Signature::new()
                 .inputs(vec![Type::FieldElement])
                 .outputs(vec![Type::FieldElement, Type::FieldElement]),
+            source_map: SourceMap::default(),
         }];
         let statement = TypedStatement::MultipleDefinition(
             vec![
@@ -1675,6 +2464,7 @@ mod tests {
             signature: Signature::new()
                 .inputs(vec![])
                 .outputs(vec![Type::FieldElement]),
+            source_map: SourceMap::default(),
         }];
         let arguments_flattened = vec![];
         let mut statements_flattened = vec![];
@@ -1812,6 +2602,10 @@ mod tests {
                 }),
             ],
             signature: Signature::new().outputs(vec![Type::FieldElement]),
+            source_map: SourceMap {
+                variables: vec![(String::from("foo_iof_1_a"), FlatVariable::new(0))],
+                ..SourceMap::default()
+            },
         };
 
         let main_flattened = flattener.flatten_function(&mut vec![foo_flattened], main);
@@ -1895,6 +2689,13 @@ mod tests {
                 }),
             ],
             signature: Signature::new().outputs(vec![Type::FieldElement]),
+            source_map: SourceMap {
+                variables: vec![
+                    (String::from("a"), FlatVariable::new(0)),
+                    (String::from("b"), FlatVariable::new(3)),
+                ],
+                ..SourceMap::default()
+            },
         };
 
         let flattened = flattener.flatten_function(&mut vec![], function);
@@ -1977,7 +2778,9 @@ mod tests {
 
         flattener.flatten_program(TypedProg {
             functions: functions,
+            constants: vec![],
             imported_functions: vec![],
+            imported_constants: vec![],
             imports: vec![],
         });
 
@@ -2118,6 +2921,7 @@ mod tests {
                 FlatStatement::Condition(
                     FlatExpression::Number(FieldPrime::from(1)),
                     FlatExpression::Mult(box invb0.into(), box b0.into()),
+                    None,
                 ),
                 // execute div
                 FlatStatement::Directive(DirectiveStatement::new(
@@ -2128,6 +2932,7 @@ mod tests {
                 FlatStatement::Condition(
                     five.into(),
                     FlatExpression::Mult(box b0.into(), box sym_0.into()),
+                    None,
                 ),
                 // inputs to second div (res/b)
                 FlatStatement::Definition(sym_1, sym_0.into()),
@@ -2141,6 +2946,7 @@ mod tests {
                 FlatStatement::Condition(
                     FlatExpression::Number(FieldPrime::from(1)),
                     FlatExpression::Mult(box invb1.into(), box b1.into()),
+                    None,
                 ),
                 // execute div
                 FlatStatement::Directive(DirectiveStatement::new(
@@ -2151,6 +2957,7 @@ mod tests {
                 FlatStatement::Condition(
                     sym_1.into(),
                     FlatExpression::Mult(box b1.into(), box sym_2.into()),
+                    None,
                 ),
                 // result
                 FlatStatement::Definition(a, sym_2.into()),
@@ -2168,17 +2975,18 @@ mod tests {
         let mut statements_flattened = vec![];
         let statement = TypedStatement::Definition(
             TypedAssignee::Identifier(Variable::field_array("foo", 3)),
-            FieldElementArrayExpression::Value(
-                3,
+            ArrayExpression::Value(
+                ArrayType::new(Type::FieldElement, 3),
                 vec![
-                    FieldElementExpression::Number(FieldPrime::from(1)),
-                    FieldElementExpression::Number(FieldPrime::from(2)),
-                    FieldElementExpression::Number(FieldPrime::from(3)),
+                    FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(3)).into(),
                 ],
             )
             .into(),
         );
-        let expression = FieldElementArrayExpression::Identifier(3, String::from("foo"));
+        let expression =
+            ArrayExpression::Identifier(ArrayType::new(Type::FieldElement, 3), String::from("foo"));
 
         flattener.flatten_statement(
             &mut functions_flattened,
@@ -2187,7 +2995,7 @@ mod tests {
             statement,
         );
 
-        let expressions = flattener.flatten_field_array_expression(
+        let expressions = flattener.flatten_array_expression(
             &mut functions_flattened,
             &arguments_flattened,
             &mut statements_flattened,
@@ -2214,12 +3022,12 @@ mod tests {
         let mut statements_flattened = vec![];
         let statement = TypedStatement::Definition(
             TypedAssignee::Identifier(Variable::field_array("foo", 3)),
-            FieldElementArrayExpression::Value(
-                3,
+            ArrayExpression::Value(
+                ArrayType::new(Type::FieldElement, 3),
                 vec![
-                    FieldElementExpression::Number(FieldPrime::from(1)),
-                    FieldElementExpression::Number(FieldPrime::from(2)),
-                    FieldElementExpression::Number(FieldPrime::from(3)),
+                    FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(3)).into(),
                 ],
             )
             .into(),
@@ -2262,19 +3070,22 @@ mod tests {
         let mut statements_flattened = vec![];
         let statement = TypedStatement::Definition(
             TypedAssignee::Identifier(Variable::field_array("foo", 3)),
-            FieldElementArrayExpression::Value(
-                3,
+            ArrayExpression::Value(
+                ArrayType::new(Type::FieldElement, 3),
                 vec![
-                    FieldElementExpression::Number(FieldPrime::from(1)),
-                    FieldElementExpression::Number(FieldPrime::from(2)),
-                    FieldElementExpression::Number(FieldPrime::from(3)),
+                    FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(3)).into(),
                 ],
             )
             .into(),
         );
 
         let expression = FieldElementExpression::Select(
-            box FieldElementArrayExpression::Identifier(3, String::from("foo")),
+            box ArrayExpression::Identifier(
+                ArrayType::new(Type::FieldElement, 3),
+                String::from("foo"),
+            ),
             box FieldElementExpression::Number(FieldPrime::from(1)),
         );
 
@@ -2310,12 +3121,12 @@ mod tests {
         let mut statements_flattened = vec![];
         let def = TypedStatement::Definition(
             TypedAssignee::Identifier(Variable::field_array("foo", 3)),
-            FieldElementArrayExpression::Value(
-                3,
+            ArrayExpression::Value(
+                ArrayType::new(Type::FieldElement, 3),
                 vec![
-                    FieldElementExpression::Number(FieldPrime::from(1)),
-                    FieldElementExpression::Number(FieldPrime::from(2)),
-                    FieldElementExpression::Number(FieldPrime::from(3)),
+                    FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    FieldElementExpression::Number(FieldPrime::from(3)).into(),
                 ],
             )
             .into(),
@@ -2326,16 +3137,25 @@ mod tests {
             FieldElementExpression::Add(
                 box FieldElementExpression::Add(
                     box FieldElementExpression::Select(
-                        box FieldElementArrayExpression::Identifier(3, String::from("foo")),
+                        box ArrayExpression::Identifier(
+                            ArrayType::new(Type::FieldElement, 3),
+                            String::from("foo"),
+                        ),
                         box FieldElementExpression::Number(FieldPrime::from(0)),
                     ),
                     box FieldElementExpression::Select(
-                        box FieldElementArrayExpression::Identifier(3, String::from("foo")),
+                        box ArrayExpression::Identifier(
+                            ArrayType::new(Type::FieldElement, 3),
+                            String::from("foo"),
+                        ),
                         box FieldElementExpression::Number(FieldPrime::from(1)),
                     ),
                 ),
                 box FieldElementExpression::Select(
-                    box FieldElementArrayExpression::Identifier(3, String::from("foo")),
+                    box ArrayExpression::Identifier(
+                        ArrayType::new(Type::FieldElement, 3),
+                        String::from("foo"),
+                    ),
                     box FieldElementExpression::Number(FieldPrime::from(2)),
                 ),
             )
@@ -2382,23 +3202,23 @@ mod tests {
             let arguments_flattened = vec![];
             let mut statements_flattened = vec![];
 
-            let e = FieldElementArrayExpression::IfElse(
+            let e = ArrayExpression::IfElse(
                 box BooleanExpression::Eq(
                     box FieldElementExpression::Number(FieldPrime::from(1)),
                     box FieldElementExpression::Number(FieldPrime::from(1)),
                 ),
-                box FieldElementArrayExpression::Value(
-                    1,
-                    vec![FieldElementExpression::Number(FieldPrime::from(1))],
+                box ArrayExpression::Value(
+                    ArrayType::new(Type::FieldElement, 1),
+                    vec![FieldElementExpression::Number(FieldPrime::from(1)).into()],
                 ),
-                box FieldElementArrayExpression::Value(
-                    1,
-                    vec![FieldElementExpression::Number(FieldPrime::from(3))],
+                box ArrayExpression::Value(
+                    ArrayType::new(Type::FieldElement, 1),
+                    vec![FieldElementExpression::Number(FieldPrime::from(3)).into()],
                 ),
             );
 
             (
-                flattener.flatten_field_array_expression(
+                flattener.flatten_array_expression(
                     &mut functions_flattened,
                     &arguments_flattened,
                     &mut statements_flattened,
@@ -2468,4 +3288,159 @@ mod tests {
             FlatVariable::new(2)
         );
     }
+
+    #[test]
+    fn uint_definition() {
+        // u8 a = 0x0f ^ 0xff
+        // ->
+        // a_b0 = 1
+        // ...
+        // a_b4 = 0
+        // ...
+
+        let mut flattener = Flattener::new(FieldPrime::get_required_bits());
+        let mut statements_flattened = vec![];
+        let statement: TypedStatement<FieldPrime> = TypedStatement::Definition(
+            TypedAssignee::Identifier(Variable::uint("a", 8)),
+            UintExpression::Xor(
+                box UintExpression::Value(8, 0x0f),
+                box UintExpression::Value(8, 0xff),
+            )
+            .into(),
+        );
+
+        flattener.flatten_statement(&vec![], &vec![], &mut statements_flattened, statement);
+
+        // constant bits are combined without any constraint
+        assert_eq!(statements_flattened.len(), 8);
+        assert_eq!(
+            statements_flattened[0],
+            FlatStatement::Definition(
+                FlatVariable::new(0),
+                FlatExpression::Number(FieldPrime::from(1))
+            )
+        );
+        assert_eq!(
+            statements_flattened[4],
+            FlatStatement::Definition(
+                FlatVariable::new(4),
+                FlatExpression::Number(FieldPrime::from(0))
+            )
+        );
+    }
+
+    #[test]
+    fn uint_add() {
+        // u8 c = a + b
+        // the sum is decomposed into 9 bits and the carry is dropped
+
+        let mut flattener = Flattener::new(FieldPrime::get_required_bits());
+        let mut statements_flattened = vec![];
+        let definitions: Vec<TypedStatement<FieldPrime>> = vec![
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::uint("a", 8)),
+                UintExpression::Value(8, 200).into(),
+            ),
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::uint("b", 8)),
+                UintExpression::Value(8, 100).into(),
+            ),
+        ];
+
+        for s in definitions {
+            flattener.flatten_statement(&vec![], &vec![], &mut statements_flattened, s);
+        }
+
+        let statement: TypedStatement<FieldPrime> = TypedStatement::Definition(
+            TypedAssignee::Identifier(Variable::uint("c", 8)),
+            UintExpression::Add(
+                box UintExpression::Identifier(8, String::from("a")),
+                box UintExpression::Identifier(8, String::from("b")),
+            )
+            .into(),
+        );
+
+        let mut statements_flattened = vec![];
+        flattener.flatten_statement(&vec![], &vec![], &mut statements_flattened, statement);
+
+        // 1 directive, 9 bitness checks, 1 decomposition check, 8 definitions
+        assert_eq!(statements_flattened.len(), 19);
+        match statements_flattened[0] {
+            FlatStatement::Directive(ref d) => {
+                assert_eq!(d.outputs.len(), 9);
+                assert_eq!(d.helper, Helper::bits(9));
+            }
+            _ => panic!("expected a directive"),
+        };
+    }
+
+    #[test]
+    fn struct_member() {
+        // Point p = Point { x: 2, y: 3 }
+        // field a = p.y
+        // ->
+        // p_s0 = 2
+        // p_s1 = 3
+        // a = p_s1
+
+        let point = StructType::new(
+            String::from("Point"),
+            vec![
+                StructMember {
+                    id: String::from("x"),
+                    ty: Type::FieldElement,
+                },
+                StructMember {
+                    id: String::from("y"),
+                    ty: Type::FieldElement,
+                },
+            ],
+        );
+
+        let mut flattener = Flattener::new(FieldPrime::get_required_bits());
+        let mut statements_flattened = vec![];
+        let definitions: Vec<TypedStatement<FieldPrime>> = vec![
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::structure("p", point.clone())),
+                StructExpression::Value(
+                    point.clone(),
+                    vec![
+                        FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                        FieldElementExpression::Number(FieldPrime::from(3)).into(),
+                    ],
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a")),
+                FieldElementExpression::Member(
+                    box StructExpression::Identifier(point.clone(), String::from("p")),
+                    String::from("y"),
+                )
+                .into(),
+            ),
+        ];
+
+        for s in definitions {
+            flattener.flatten_statement(&vec![], &vec![], &mut statements_flattened, s);
+        }
+
+        assert_eq!(
+            statements_flattened,
+            vec![
+                FlatStatement::Definition(
+                    FlatVariable::new(0),
+                    FlatExpression::Number(FieldPrime::from(2))
+                ),
+                FlatStatement::Definition(
+                    FlatVariable::new(1),
+                    FlatExpression::Number(FieldPrime::from(3))
+                ),
+                FlatStatement::Definition(
+                    FlatVariable::new(2),
+                    FlatExpression::Identifier(FlatVariable::new(1))
+                ),
+            ]
+        );
+    }
 }
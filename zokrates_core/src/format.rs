@@ -0,0 +1,517 @@
+//! Module containing the canonical formatting of ZoKrates sources, which re-prints the parsed
+//! program while keeping the comments and the empty lines separating its parts.
+
+use absy::{
+    Assignee, AssigneeNode, ConstantNode, Expression, ExpressionNode, FunctionNode, Prog,
+    Statement, StatementNode, StructDefinitionNode,
+};
+use imports::ImportNode;
+use parser::{parse_program, Error};
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufReader;
+use zokrates_field::field::Field;
+
+/// Formats `source`, or returns the errors which prevent it from being parsed
+pub fn format<T: Field>(source: &str) -> Result<String, Vec<Error<T>>> {
+    let program = parse_program::<T, _>(&mut BufReader::new(source.as_bytes()))?;
+
+    let mut printer = Printer::new(source);
+    printer.program(&program);
+    Ok(printer.finish())
+}
+
+struct Comment {
+    text: String,
+    /// Whether the comment follows code on the same line
+    trailing: bool,
+}
+
+enum Item<'a, T: Field + 'a> {
+    Import(&'a ImportNode),
+    Struct(&'a StructDefinitionNode),
+    Constant(&'a ConstantNode<T>),
+    Function(&'a FunctionNode<T>),
+}
+
+impl<'a, T: Field> Item<'a, T> {
+    fn line(&self) -> usize {
+        match *self {
+            Item::Import(i) => i.start.line,
+            Item::Struct(s) => s.start.line,
+            Item::Constant(c) => c.start.line,
+            Item::Function(f) => f.start.line,
+        }
+    }
+}
+
+struct Printer {
+    lines: Vec<String>,
+    /// Comments of the source which are not printed yet, by line
+    comments: BTreeMap<usize, Comment>,
+    /// Lines of the source which are empty
+    empty: HashSet<usize>,
+    /// Whether the last line printed opens a block, which is never followed by an empty line
+    block_start: bool,
+}
+
+impl Printer {
+    fn new(source: &str) -> Printer {
+        let mut comments = BTreeMap::new();
+        let mut empty = HashSet::new();
+
+        for (index, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                empty.insert(index + 1);
+            } else if let Some(offset) = comment_offset(line) {
+                comments.insert(
+                    index + 1,
+                    Comment {
+                        text: line[offset..].trim_end().to_string(),
+                        trailing: !line[..offset].trim().is_empty(),
+                    },
+                );
+            }
+        }
+
+        Printer {
+            lines: vec![],
+            comments,
+            empty,
+            block_start: false,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.comments_before(usize::max_value(), 0);
+
+        while self.lines.last().map(|l| l.is_empty()).unwrap_or(false) {
+            self.lines.pop();
+        }
+
+        let mut res = self.lines.join("\n");
+        res.push('\n');
+        res
+    }
+
+    fn program<T: Field>(&mut self, program: &Prog<T>) {
+        let mut items: Vec<_> = program
+            .imports
+            .iter()
+            .map(Item::Import)
+            .chain(program.structs.iter().map(Item::Struct))
+            .chain(program.constants.iter().map(Item::Constant))
+            .chain(program.functions.iter().map(Item::Function))
+            .collect();
+        items.sort_by_key(|i| i.line());
+
+        for item in items {
+            match item {
+                Item::Import(i) => {
+                    let alias = match *i.value.get_alias() {
+                        Some(ref alias) => format!(" as {}", alias),
+                        None => String::new(),
+                    };
+                    self.code(
+                        i.start.line,
+                        0,
+                        format!("import \"{}\"{}", i.value.get_source(), alias),
+                    );
+                }
+                Item::Constant(c) => self.code(
+                    c.start.line,
+                    0,
+                    format!(
+                        "const field {} = {}",
+                        c.value.id,
+                        expression(&c.value.expression)
+                    ),
+                ),
+                Item::Struct(s) => {
+                    self.separate();
+                    self.open(s.start.line, 0, format!("struct {} {{", s.value.id));
+                    for field in &s.value.fields {
+                        self.code(field.start.line, 1, field.value.to_string());
+                    }
+                    self.close(s.end.line, 0, String::from("}"));
+                }
+                Item::Function(f) => {
+                    self.separate();
+                    self.open(
+                        f.start.line,
+                        0,
+                        format!(
+                            "def {}({}) -> ({}):",
+                            f.value.id,
+                            join(f.value.arguments.iter().map(|a| a.value.to_string())),
+                            join(f.value.signature.outputs.iter().map(|t| t.to_string()))
+                        ),
+                    );
+                    self.statements(&f.value.statements, 1);
+                }
+            }
+        }
+    }
+
+    fn statements<T: Field>(&mut self, statements: &[StatementNode<T>], depth: usize) {
+        // a single line such as `field a = 1` is parsed into several statements at its start
+        let mut index = 0;
+        while index < statements.len() {
+            let start = statements[index].start;
+            let count = statements[index..]
+                .iter()
+                .take_while(|s| s.start == start)
+                .count();
+            self.statement(&statements[index..index + count], depth);
+            index += count;
+        }
+    }
+
+    fn statement<T: Field>(&mut self, group: &[StatementNode<T>], depth: usize) {
+        let line = group[0].start.line;
+        let last = group.last().unwrap();
+
+        let declarations: Vec<_> = group
+            .iter()
+            .filter_map(|s| match s.value {
+                Statement::Declaration(ref v) => Some(&v.value),
+                _ => None,
+            })
+            .collect();
+
+        // assignees are printed with their type when they are declared by this statement
+        let declared = |a: &AssigneeNode<T>| match a.value {
+            Assignee::Identifier(ref id) => match declarations.iter().find(|v| v.id == *id) {
+                Some(v) => v.to_string(),
+                None => id.clone(),
+            },
+            _ => assignee(a),
+        };
+
+        let code = match last.value {
+            Statement::Return(ref list) => match list.value.expressions.len() {
+                0 => String::from("return"),
+                _ => format!(
+                    "return {}",
+                    join(list.value.expressions.iter().map(expression))
+                ),
+            },
+            Statement::Declaration(_) => join(declarations.iter().map(|v| v.to_string())),
+            Statement::Definition(ref lhs, ref rhs) => {
+                format!("{} = {}", declared(lhs), expression(rhs))
+            }
+            Statement::MultipleDefinition(ref lhs, ref rhs) => format!(
+                "{} = {}",
+                join(lhs.iter().map(|a| declared(a))),
+                expression(rhs)
+            ),
+            Statement::Condition(ref lhs, ref rhs) => {
+                format!("{} == {}", expression(lhs), expression(rhs))
+            }
+            Statement::Assertion(ref e, None) => format!("assert({})", expression(e)),
+            Statement::Assertion(ref e, Some(ref message)) => {
+                format!("assert({}, \"{}\")", expression(e), message)
+            }
+            Statement::For(ref var, ref from, ref to, ref body) => {
+                self.open(
+                    line,
+                    depth,
+                    format!(
+                        "for {} in {}..{} do",
+                        var.value,
+                        expression(from),
+                        expression(to)
+                    ),
+                );
+                self.statements(body, depth + 1);
+                self.close(last.end.line, depth, String::from("endfor"));
+                return;
+            }
+        };
+
+        self.code(line, depth, code);
+    }
+
+    // prints a line of code located at `line` in the source, after the comments preceding it
+    fn code(&mut self, line: usize, depth: usize, code: String) {
+        self.comments_before(line, depth);
+        self.gap(line);
+        self.push(line, depth, code);
+    }
+
+    fn open(&mut self, line: usize, depth: usize, code: String) {
+        self.code(line, depth, code);
+        self.block_start = true;
+    }
+
+    // the end of a block is never preceded by an empty line
+    fn close(&mut self, line: usize, depth: usize, code: String) {
+        self.comments_before(line, depth);
+        self.push(line, depth, code);
+    }
+
+    fn push(&mut self, line: usize, depth: usize, code: String) {
+        let trailing = self
+            .comments
+            .get(&line)
+            .map(|c| c.trailing)
+            .unwrap_or(false);
+
+        let code = match trailing {
+            true => format!("{} {}", code, self.comments.remove(&line).unwrap().text),
+            false => code,
+        };
+
+        self.lines.push(format!("{}{}", "\t".repeat(depth), code));
+        self.block_start = false;
+    }
+
+    fn comments_before(&mut self, line: usize, depth: usize) {
+        let lines: Vec<_> = self.comments.range(..line).map(|(l, _)| *l).collect();
+        for l in lines {
+            let comment = self.comments.remove(&l).unwrap();
+            self.gap(l);
+            self.lines.push(format!("{}{}", "\t".repeat(depth), comment.text));
+            self.block_start = false;
+        }
+    }
+
+    // keeps a single empty line where the source has some before `line`
+    fn gap(&mut self, line: usize) {
+        if line > 1 && self.empty.contains(&(line - 1)) && !self.block_start {
+            self.separate();
+        }
+    }
+
+    fn separate(&mut self) {
+        if self.lines.last().map(|l| !l.is_empty()).unwrap_or(false) {
+            self.lines.push(String::new());
+        }
+    }
+}
+
+// the offset of the comment of a line, ignoring slashes within strings such as import paths
+fn comment_offset(line: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (offset, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '/' if !quoted && line[offset..].starts_with("//") => return Some(offset),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn join<I: Iterator<Item = String>>(items: I) -> String {
+    items.collect::<Vec<_>>().join(", ")
+}
+
+fn assignee<T: Field>(a: &AssigneeNode<T>) -> String {
+    match a.value {
+        Assignee::Identifier(ref id) => id.clone(),
+        Assignee::ArrayElement(ref array, ref index) => {
+            format!("{}[{}]", assignee(array), expression(index))
+        }
+    }
+}
+
+// how tightly an expression binds when it is the operand of an arithmetic operator
+// conditionals and powers are always parenthesized, as the parser extends them to the right
+fn precedence<T: Field>(e: &Expression<T>) -> usize {
+    match *e {
+        Expression::IfElse(..) | Expression::Pow(..) => 0,
+        Expression::Add(..)
+        | Expression::Sub(..)
+        | Expression::BitAnd(..)
+        | Expression::BitOr(..)
+        | Expression::BitXor(..)
+        | Expression::LeftShift(..)
+        | Expression::RightShift(..) => 1,
+        Expression::Mult(..) | Expression::Div(..) => 2,
+        _ => 3,
+    }
+}
+
+fn operand<T: Field>(e: &ExpressionNode<T>, precedence_min: usize) -> String {
+    match precedence(&e.value) >= precedence_min {
+        true => expression(e),
+        false => format!("({})", expression(e)),
+    }
+}
+
+// `&&` groups to the right and binds tighter than `||`, which groups to the left
+fn boolean_operand<T: Field>(e: &ExpressionNode<T>, left_of_and: bool) -> String {
+    match e.value {
+        Expression::Or(..) => format!("({})", expression(e)),
+        Expression::And(..) if left_of_and => format!("({})", expression(e)),
+        _ => expression(e),
+    }
+}
+
+// sums group to the left
+fn sum<T: Field>(lhs: &ExpressionNode<T>, op: &str, rhs: &ExpressionNode<T>) -> String {
+    format!("{} {} {}", operand(lhs, 1), op, operand(rhs, 2))
+}
+
+// products group to the right
+fn product<T: Field>(lhs: &ExpressionNode<T>, op: &str, rhs: &ExpressionNode<T>) -> String {
+    format!("{} {} {}", operand(lhs, 3), op, operand(rhs, 2))
+}
+
+fn comparison<T: Field>(lhs: &ExpressionNode<T>, op: &str, rhs: &ExpressionNode<T>) -> String {
+    format!("{} {} {}", expression(lhs), op, expression(rhs))
+}
+
+fn expression<T: Field>(e: &ExpressionNode<T>) -> String {
+    match e.value {
+        // literals are printed as written, as their value is reduced in the field
+        Expression::Number(_, ref source) => source.clone(),
+        Expression::HexNumber(ref n) => format!("0x{}", n),
+        Expression::Identifier(ref id) => id.clone(),
+        Expression::Add(ref lhs, ref rhs) => sum(lhs, "+", rhs),
+        Expression::Sub(ref lhs, ref rhs) => sum(lhs, "-", rhs),
+        Expression::BitAnd(ref lhs, ref rhs) => sum(lhs, "&", rhs),
+        Expression::BitOr(ref lhs, ref rhs) => sum(lhs, "|", rhs),
+        Expression::BitXor(ref lhs, ref rhs) => sum(lhs, "^", rhs),
+        Expression::LeftShift(ref lhs, ref rhs) => sum(lhs, "<<", rhs),
+        Expression::RightShift(ref lhs, ref rhs) => sum(lhs, ">>", rhs),
+        Expression::Mult(ref lhs, ref rhs) => product(lhs, "*", rhs),
+        Expression::Div(ref lhs, ref rhs) => product(lhs, "/", rhs),
+        Expression::Pow(ref lhs, ref rhs) => format!("{}**{}", operand(lhs, 3), operand(rhs, 3)),
+        Expression::IfElse(ref condition, ref consequent, ref alternative) => format!(
+            "if {} then {} else {} fi",
+            expression(condition),
+            expression(consequent),
+            expression(alternative)
+        ),
+        Expression::FunctionCall(ref id, ref arguments) => {
+            format!("{}({})", id, join(arguments.iter().map(expression)))
+        }
+        Expression::Lt(ref lhs, ref rhs) => comparison(lhs, "<", rhs),
+        Expression::Le(ref lhs, ref rhs) => comparison(lhs, "<=", rhs),
+        Expression::Eq(ref lhs, ref rhs) => comparison(lhs, "==", rhs),
+        Expression::Ge(ref lhs, ref rhs) => comparison(lhs, ">=", rhs),
+        Expression::Gt(ref lhs, ref rhs) => comparison(lhs, ">", rhs),
+        Expression::And(ref lhs, ref rhs) => format!(
+            "{} && {}",
+            boolean_operand(lhs, true),
+            boolean_operand(rhs, false)
+        ),
+        Expression::Or(ref lhs, ref rhs) => {
+            format!("{} || {}", expression(lhs), boolean_operand(rhs, false))
+        }
+        Expression::Not(ref e) => format!("!({})", expression(e)),
+        Expression::InlineArray(ref elements) => {
+            format!("[{}]", join(elements.iter().map(expression)))
+        }
+        Expression::Select(ref array, ref index) => {
+            format!("{}[{}]", expression(array), expression(index))
+        }
+        Expression::Member(ref s, ref id) => format!("{}.{}", expression(s), id),
+        Expression::InlineStruct(ref id, ref members) => match members.len() {
+            0 => format!("{} {{}}", id),
+            _ => format!(
+                "{} {{ {} }}",
+                id,
+                join(
+                    members
+                        .iter()
+                        .map(|(member, e)| format!("{}: {}", member, expression(e)))
+                )
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::field::FieldPrime;
+
+    fn parse(source: &str) -> Prog<FieldPrime> {
+        parse_program(&mut BufReader::new(source.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn layout_and_comments() {
+        let source = r#"// header
+
+
+import   "./foo.code"  as foo
+const field N=2
+struct Bar {
+    field a; field[2] b
+} // trailing
+def main(private field a,field b)->(field): // entry
+  field c=a+  b // sum
+
+
+  // loop
+  for field i in 0..N do
+
+     c = c*i
+  endfor
+  assert(c==a, "c // should be a")
+  return c
+// end
+"#;
+
+        let expected = r#"// header
+
+import "./foo.code" as foo
+const field N = 2
+
+struct Bar {
+	field a
+	field[2] b
+} // trailing
+
+def main(private field a, field b) -> (field): // entry
+	field c = a + b // sum
+
+	// loop
+	for field i in 0..N do
+		c = c * i
+	endfor
+	assert(c == a, "c // should be a")
+	return c
+// end
+"#;
+
+        let formatted = format::<FieldPrime>(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format::<FieldPrime>(&formatted).unwrap(), formatted);
+        assert_eq!(parse(&formatted), parse(source));
+    }
+
+    #[test]
+    fn parentheses() {
+        let source = r#"def main(field a, field b) -> (field):
+	field c = (a + b) * (a - b) / b
+	field d = a - (b - c) + (a * b) * c
+	field e = (if a < b then a else b fi) + (a + b)**2
+	assert(!(a < b) && (a == b || b == c) || a > c && b > c)
+	a, field f = foo(a * (b + c), [a, b])
+	return d + e
+"#;
+
+        let formatted = format::<FieldPrime>(source).unwrap();
+        assert_eq!(parse(&formatted), parse(source));
+        assert_eq!(format::<FieldPrime>(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn number_literals() {
+        // the modulus of the field would be printed as `0` if literals were printed by value
+        let source = r#"def main() -> (field):
+	return 007 + 21888242871839275222246405745257275088548364400416034343698204186575808495617 + 0x00ff
+"#;
+
+        assert_eq!(format::<FieldPrime>(source).unwrap(), source);
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(format::<FieldPrime>("def main() -> (field):\n\treturn 1 +\n").is_err());
+    }
+}
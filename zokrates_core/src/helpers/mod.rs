@@ -9,7 +9,7 @@ pub use self::libsnark_gadget::LibsnarkGadgetHelper;
 pub use self::rust::RustHelper;
 #[cfg(feature = "wasm")]
 pub use self::wasm::WasmHelper;
-use flat_absy::{FlatExpression, FlatVariable};
+use flat_absy::{FlatExpression, FlatVariable, Span};
 use std::fmt;
 use zokrates_field::field::Field;
 
@@ -18,6 +18,7 @@ pub struct DirectiveStatement<T: Field> {
     pub inputs: Vec<FlatExpression<T>>,
     pub outputs: Vec<FlatVariable>,
     pub helper: Helper,
+    pub span: Option<Span>,
 }
 
 impl<T: Field> DirectiveStatement<T> {
@@ -33,6 +34,7 @@ impl<T: Field> DirectiveStatement<T> {
             helper,
             inputs: inputs.into_iter().map(|i| i.into()).collect(),
             outputs,
+            span: None,
         }
     }
 }
@@ -72,8 +74,12 @@ impl Helper {
         Helper::Wasm(WasmHelper::from_hex(WasmHelper::IDENTITY_WASM))
     }
 
-    pub fn bits() -> Self {
-        Helper::Wasm(WasmHelper::from(WasmHelper::BITS_WASM))
+    pub fn bits(bits: usize) -> Self {
+        // the wasm helper only decomposes into 254 bits
+        match bits {
+            254 => Helper::Wasm(WasmHelper::from(WasmHelper::BITS_WASM)),
+            bits => Helper::Rust(RustHelper::Bits(bits)),
+        }
     }
 }
 
@@ -83,8 +89,8 @@ impl Helper {
         Helper::Rust(RustHelper::Identity)
     }
 
-    pub fn bits() -> Self {
-        Helper::Rust(RustHelper::Bits)
+    pub fn bits(bits: usize) -> Self {
+        Helper::Rust(RustHelper::Bits(bits))
     }
 }
 
@@ -6,7 +6,7 @@ use zokrates_field::field::Field;
 pub enum RustHelper {
     Identity,
     ConditionEq,
-    Bits,
+    Bits(usize),
     Div,
 }
 
@@ -15,7 +15,7 @@ impl fmt::Display for RustHelper {
         match *self {
             RustHelper::Identity => write!(f, "Identity"),
             RustHelper::ConditionEq => write!(f, "ConditionEq"),
-            RustHelper::Bits => write!(f, "Bits"),
+            RustHelper::Bits(..) => write!(f, "Bits"),
             RustHelper::Div => write!(f, "Div"),
         }
     }
@@ -26,7 +26,7 @@ impl Signed for RustHelper {
         match self {
             RustHelper::Identity => (1, 1),
             RustHelper::ConditionEq => (1, 2),
-            RustHelper::Bits => (1, 254),
+            RustHelper::Bits(bits) => (1, *bits),
             RustHelper::Div => (2, 1),
         }
     }
@@ -40,11 +40,10 @@ impl<T: Field> Executable<T> for RustHelper {
                 true => Ok(vec![T::zero(), T::one()]),
                 false => Ok(vec![T::one(), T::one() / inputs[0].clone()]),
             },
-            RustHelper::Bits => {
+            RustHelper::Bits(bits) => {
                 let mut num = inputs[0].clone();
                 let mut res = vec![];
-                let bits = 254;
-                for i in (0..bits).rev() {
+                for i in (0..*bits).rev() {
                     if T::from(2).pow(i) <= num {
                         num = num - T::from(2).pow(i);
                         res.push(T::one());
@@ -52,8 +51,10 @@ impl<T: Field> Executable<T> for RustHelper {
                         res.push(T::zero());
                     }
                 }
-                assert_eq!(num, T::zero());
-                Ok(res)
+                match num == T::zero() {
+                    true => Ok(res),
+                    false => Err(format!("{} does not fit in {} bits", inputs[0], bits)),
+                }
             }
             RustHelper::Div => Ok(vec![inputs[0].clone() / inputs[1].clone()]),
         }
@@ -68,7 +69,7 @@ mod tests {
     #[test]
     fn bits_of_one() {
         let inputs = vec![FieldPrime::from(1)];
-        let res = RustHelper::Bits.execute(&inputs).unwrap();
+        let res = RustHelper::Bits(254).execute(&inputs).unwrap();
         assert_eq!(res[253], FieldPrime::from(1));
         for i in 0..252 {
             assert_eq!(res[i], FieldPrime::from(0));
@@ -78,7 +79,7 @@ mod tests {
     #[test]
     fn bits_of_42() {
         let inputs = vec![FieldPrime::from(42)];
-        let res = RustHelper::Bits.execute(&inputs).unwrap();
+        let res = RustHelper::Bits(254).execute(&inputs).unwrap();
         assert_eq!(res[253], FieldPrime::from(0));
         assert_eq!(res[252], FieldPrime::from(1));
         assert_eq!(res[251], FieldPrime::from(0));
@@ -87,4 +88,11 @@ mod tests {
         assert_eq!(res[248], FieldPrime::from(1));
         assert_eq!(res[247], FieldPrime::from(0));
     }
+
+    #[test]
+    fn bits_overflow() {
+        let inputs = vec![FieldPrime::from(256)];
+        let res = RustHelper::Bits(8).execute(&inputs);
+        assert!(res.is_err());
+    }
 }
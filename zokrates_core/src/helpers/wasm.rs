@@ -44,6 +44,36 @@ impl WasmHelper {
     }
 }
 
+impl WasmHelper {
+    /// Instantiates a module provided by the user, checking that it exports the symbols a helper
+    /// needs instead of panicking when it is invalid
+    pub fn try_from_bytes(code: Vec<u8>) -> Result<Self, String> {
+        let parsed: parity_wasm::elements::Module = parity_wasm::deserialize_buffer(&code)
+            .map_err(|e| format!("Error decoding buffer: {}", e))?;
+        if parsed.start_section().is_some() {
+            return Err(String::from("Module should not have a start function"));
+        }
+
+        let module = wasmi::Module::from_buffer(code.clone())
+            .map_err(|e| format!("Error decoding buffer: {}", e))?;
+        let modinst = ModuleInstance::new(&module, &ImportsBuilder::default())
+            .map_err(|e| format!("Failed to instantiate module: {}", e))?
+            .assert_no_start();
+
+        for symbol in &["min_inputs", "min_outputs", "field_size"] {
+            get_export::<i32>(symbol, &modinst)?;
+        }
+        for symbol in &["get_inputs_off", "solve"] {
+            modinst
+                .export_by_name(symbol)
+                .and_then(|e| e.as_func().cloned())
+                .ok_or(format!("Could not find exported function `{}` in module", symbol))?;
+        }
+
+        Ok(WasmHelper(Rc::new(modinst), code))
+    }
+}
+
 impl<U: Into<Vec<u8>>> From<U> for WasmHelper {
     fn from(code: U) -> Self {
         let code_vec = code.into();
@@ -339,6 +369,20 @@ mod tests {
         WasmHelper::from_hex(&WasmHelper::IDENTITY_WASM[..20]);
     }
 
+    #[test]
+    fn try_from_bytes() {
+        let code: Vec<u8> = FromHex::from_hex(WasmHelper::IDENTITY_WASM).unwrap();
+        assert!(WasmHelper::try_from_bytes(code).is_ok());
+
+        assert_eq!(
+            WasmHelper::try_from_bytes(remove_export(WasmHelper::IDENTITY_WASM, "solve")).err(),
+            Some(String::from(
+                "Could not find exported function `solve` in module"
+            ))
+        );
+        assert!(WasmHelper::try_from_bytes(vec![0, 97, 115]).is_err());
+    }
+
     #[test]
     fn validate_exports() {
         /* Test identity without the `solve` export */
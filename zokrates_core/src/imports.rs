@@ -14,6 +14,9 @@ use std::io;
 use std::io::BufRead;
 use zokrates_field::field::Field;
 
+/// Extension of the constraint systems which can be imported as functions
+const R1CS_EXTENSION: &str = ".r1cs.json";
+
 pub struct CompiledImport<T: Field> {
     pub flat_func: FlatFunction<T>,
 }
@@ -49,6 +52,14 @@ impl Error {
     fn with_pos(self, pos: Option<(Position, Position)>) -> Error {
         Error { pos, ..self }
     }
+
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl fmt::Display for Error {
@@ -134,6 +145,7 @@ impl Importer {
         resolve_option: Option<fn(&Option<String>, &String) -> Result<(S, String, String), E>>,
     ) -> Result<Prog<T>, CompileErrors<T>> {
         let mut origins: Vec<CompiledImport<T>> = vec![];
+        let mut constants: Vec<(String, T)> = vec![];
 
         for import in destination.imports.iter() {
             let pos = import.pos();
@@ -142,7 +154,7 @@ impl Importer {
             if import.source.starts_with("LIBSNARK") {
                 #[cfg(feature = "libsnark")]
                 {
-                    use helpers::LibsnarkGadgetHelper;
+                    use helpers::{Helper, LibsnarkGadgetHelper};
                     use libsnark::get_sha256round_constraints;
                     use serde_json::from_str;
                     use standard::{DirectiveR1CS, R1CS};
@@ -152,7 +164,7 @@ impl Importer {
                             let r1cs: R1CS = from_str(&get_sha256round_constraints()).unwrap();
                             let dr1cs: DirectiveR1CS = DirectiveR1CS {
                                 r1cs,
-                                directive: LibsnarkGadgetHelper::Sha256Round,
+                                helper: Helper::LibsnarkGadget(LibsnarkGadgetHelper::Sha256Round),
                             };
                             let compiled = FlatProg::from(dr1cs);
                             let alias = match import.alias {
@@ -207,14 +219,36 @@ impl Importer {
                 // to resolve imports, we need a resolver
                 match resolve_option {
                     Some(resolve) => match resolve(&location, &import.source) {
+                        Ok((reader, _, auto_alias)) if import.source.ends_with(R1CS_EXTENSION) => {
+                            let compiled = import_r1cs(reader, &import.source, &location, resolve)
+                                .map_err(|e| {
+                                    CompileErrors::from(
+                                        CompileErrorInner::ImportError(e.with_pos(Some(pos)))
+                                            .with_context(&location),
+                                    )
+                                })?;
+                            let alias = match import.alias {
+                                Some(ref alias) => alias.clone(),
+                                None => auto_alias.trim_end_matches(".r1cs").to_string(),
+                            };
+                            origins.push(CompiledImport::new(compiled, alias));
+                        }
                         Ok((mut reader, location, auto_alias)) => {
-                            let compiled = compile_aux(&mut reader, Some(location), resolve_option)
-                                .map_err(|e| e.with_context(Some(import.source.clone())))?;
+                            let (mut compiled, imported_constants) =
+                                compile_aux(&mut reader, Some(location), resolve_option)
+                                    .map_err(|e| e.with_context(Some(import.source.clone())))?;
+                            // runtime errors in the module are reported against its source
+                            compiled.attach_file(&import.source);
                             let alias = match import.alias {
                                 Some(ref alias) => alias.clone(),
                                 None => auto_alias,
                             };
-                            origins.push(CompiledImport::new(compiled, alias));
+                            // modules which only declare constants have no main to import
+                            if compiled.functions.iter().any(|f| f.id == "main") {
+                                origins.push(CompiledImport::new(compiled, alias));
+                            }
+                            // constants are imported under their own names
+                            constants.extend(imported_constants);
                         }
                         Err(err) => {
                             return Err(CompileErrorInner::ImportError(
@@ -236,13 +270,84 @@ impl Importer {
         }
 
         Ok(Prog {
+            structs: destination.structs.clone(),
+            constants: destination.constants.clone(),
             imports: vec![],
             functions: destination.clone().functions,
             imported_functions: origins.into_iter().map(|o| o.flat_func).collect(),
+            imported_constants: constants,
         })
     }
 }
 
+/// Imports a constraint system in the JSON format of `standard::R1CS`, whose witness is computed
+/// by the wasm module of the same name, such as `foo.wasm` for `foo.r1cs.json`
+#[cfg(feature = "wasm")]
+fn import_r1cs<T: Field, S: BufRead, E: Into<Error>>(
+    reader: S,
+    source: &String,
+    location: &Option<String>,
+    resolve: fn(&Option<String>, &String) -> Result<(S, String, String), E>,
+) -> Result<FlatProg<T>, Error> {
+    use helpers::{Helper, Signed, WasmHelper};
+    use serde_json;
+    use standard::{DirectiveR1CS, R1CS};
+
+    let r1cs: R1CS = serde_json::from_reader(reader)
+        .map_err(|e| Error::new(format!("Invalid R1CS in {}: {}", source, e)))?;
+    r1cs.validate::<T>()
+        .map_err(|e| Error::new(format!("Invalid R1CS in {}: {}", source, e)))?;
+
+    let generator = format!("{}.wasm", &source[..source.len() - R1CS_EXTENSION.len()]);
+
+    let (mut reader, _, _) = resolve(location, &generator).map_err(|e| {
+        let e: Error = e.into();
+        Error::new(format!(
+            "Could not find the witness generator {} of {}: {}",
+            generator,
+            source,
+            e.message()
+        ))
+    })?;
+    let mut code = vec![];
+    reader.read_to_end(&mut code)?;
+
+    let helper = WasmHelper::try_from_bytes(code)
+        .map_err(|e| Error::new(format!("Invalid witness generator {}: {}", generator, e)))?;
+
+    // the generator computes the whole witness, including ~one
+    let (inputs, outputs) = helper.get_signature();
+    if (inputs, outputs) != (r1cs.inputs.len(), r1cs.variable_count) {
+        return Err(Error::new(format!(
+            "Witness generator {} computes {} variables from {} inputs, but {} expects {} variables from {} inputs",
+            generator,
+            outputs,
+            inputs,
+            source,
+            r1cs.variable_count,
+            r1cs.inputs.len()
+        )));
+    }
+
+    Ok(FlatProg::from(DirectiveR1CS {
+        r1cs,
+        helper: Helper::Wasm(helper),
+    }))
+}
+
+#[cfg(not(feature = "wasm"))]
+fn import_r1cs<T: Field, S: BufRead, E: Into<Error>>(
+    _: S,
+    source: &String,
+    _: &Option<String>,
+    _: fn(&Option<String>, &String) -> Result<(S, String, String), E>,
+) -> Result<FlatProg<T>, Error> {
+    Err(Error::new(format!(
+        "Cannot import {}: importing constraint systems requires the wasm feature",
+        source
+    )))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -269,4 +374,93 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "wasm")]
+    mod r1cs {
+        use super::*;
+        use compile::compile_str_with;
+        use helpers::WasmHelper;
+        use rustc_hex::FromHex;
+        use std::io::Cursor;
+        use std::path::Path;
+        use zokrates_field::field::FieldPrime;
+
+        // `id` is a gadget whose only variable is its input, computed by the identity module
+        fn resolve(
+            _: &Option<String>,
+            source: &String,
+        ) -> Result<(Cursor<Vec<u8>>, String, String), io::Error> {
+            let content: Vec<u8> = match source.as_str() {
+                "./id.r1cs.json" | "./nogenerator.r1cs.json" => {
+                    r#"{"variable_count": 1, "inputs": [0], "outputs": [0], "constraints": []}"#
+                        .as_bytes()
+                        .to_vec()
+                }
+                "./wide.r1cs.json" => {
+                    r#"{"variable_count": 2, "inputs": [1], "outputs": [1], "constraints": []}"#
+                        .as_bytes()
+                        .to_vec()
+                }
+                "./id.wasm" | "./wide.wasm" => {
+                    FromHex::from_hex(WasmHelper::IDENTITY_WASM).unwrap()
+                }
+                _ => return Err(io::Error::new(io::ErrorKind::NotFound, "Not found")),
+            };
+            // like the file system resolver, the alias is the file name without its extension
+            let alias = Path::new(source).file_stem().unwrap().to_string_lossy().to_string();
+            Ok((Cursor::new(content), String::from("."), alias))
+        }
+
+        #[test]
+        fn import() {
+            let program = compile_str_with(
+                r#"
+import "./id.r1cs.json"
+def main(field a) -> (field):
+	return id(a)
+"#,
+                resolve,
+            )
+            .unwrap()
+            .program;
+
+            let witness = program.execute(&vec![FieldPrime::from(1)]).unwrap();
+            assert_eq!(witness.return_values(), vec![&FieldPrime::from(1)]);
+
+            // the first variable of the gadget is ~one
+            assert!(program.execute(&vec![FieldPrime::from(2)]).is_err());
+        }
+
+        #[test]
+        fn generator_mismatch() {
+            let res = compile_str_with(
+                r#"
+import "./wide.r1cs.json" as wide
+def main(field a) -> (field):
+	return wide(a)
+"#,
+                resolve,
+            );
+
+            assert!(res.unwrap_err().to_string().contains(
+                "Witness generator ./wide.wasm computes 1 variables from 1 inputs, but ./wide.r1cs.json expects 2 variables from 1 inputs"
+            ));
+        }
+
+        #[test]
+        fn generator_not_found() {
+            let res = compile_str_with(
+                r#"
+import "./nogenerator.r1cs.json"
+def main(field a) -> (field):
+	return nogenerator(a)
+"#,
+                resolve,
+            );
+
+            assert!(res.unwrap_err().to_string().contains(
+                "Could not find the witness generator ./nogenerator.wasm of ./nogenerator.r1cs.json"
+            ));
+        }
+    }
 }
@@ -0,0 +1,221 @@
+//! The file format of compiled programs, which wraps a program with a header describing how it
+//! was compiled, so that programs compiled for another curve or by an incompatible version of
+//! ZoKrates are rejected with a clear error:
+//!
+//! ```text
+//! "zkir" | format version: u32 | header and program, as encoded by bincode
+//! header: curve | version of the compiler | SHA-256 hash of the source of the main module
+//! ```
+
+use bincode;
+use bincode::Infinite;
+use ir::Prog;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{self, Read, Write};
+use zokrates_field::field::Field;
+
+/// The version of the format of compiled programs this crate reads and writes
+pub const ARTIFACT_VERSION: u32 = 1;
+
+const MAGIC: &[u8] = b"zkir";
+const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Header {
+    /// The curve the program was compiled for, as named by `Field::name`
+    pub curve: String,
+    /// The version of the compiler
+    pub compiler: String,
+    /// The SHA-256 hash of the source of the main module, in hexadecimal
+    pub source: String,
+}
+
+/// A compiled program, along with the header it is stored with
+#[derive(Debug, Clone)]
+pub struct Artifact<T: Field> {
+    pub header: Header,
+    pub program: Prog<T>,
+}
+
+impl Header {
+    /// Reads the header of a compiled program, which tells the curve to read the program for
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, ArtifactError> {
+        let mut magic = [0u8; 4];
+        let mut version = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .and_then(|_| reader.read_exact(&mut version))
+            .map_err(|_| ArtifactError::Magic)?;
+
+        if magic != MAGIC {
+            return Err(ArtifactError::Magic);
+        }
+
+        let version = u32::from_le_bytes(version);
+        if version != ARTIFACT_VERSION {
+            return Err(ArtifactError::Version(version));
+        }
+
+        bincode::deserialize_from(reader, Infinite)
+            .map_err(|e| ArtifactError::Format(e.to_string()))
+    }
+}
+
+impl<T: Field> Artifact<T> {
+    /// Wraps a program compiled for the field `T` by this compiler from `source`
+    pub fn new(program: Prog<T>, source: &[u8]) -> Self {
+        Artifact {
+            header: Header {
+                curve: String::from(T::name()),
+                compiler: String::from(COMPILER_VERSION),
+                source: format!("{:x}", Sha256::digest(source)),
+            },
+            program,
+        }
+    }
+}
+
+impl<T: Field + Serialize> Artifact<T> {
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&ARTIFACT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(writer, &(&self.header, &self.program), Infinite)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+    }
+}
+
+impl<T: Field + DeserializeOwned> Artifact<T> {
+    /// Reads a program compiled for the field `T`
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, ArtifactError> {
+        let header = Header::read(reader)?;
+
+        if header.curve != T::name() {
+            return Err(ArtifactError::Curve {
+                expected: String::from(T::name()),
+                found: header.curve,
+            });
+        }
+
+        let program = bincode::deserialize_from(reader, Infinite).map_err(|e| {
+            match header.compiler == COMPILER_VERSION {
+                true => ArtifactError::Format(e.to_string()),
+                false => ArtifactError::Compiler(header.compiler.clone()),
+            }
+        })?;
+
+        Ok(Artifact { header, program })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArtifactError {
+    /// The file does not start with the magic bytes of compiled programs
+    Magic,
+    /// The file was written in another version of the format
+    Version(u32),
+    /// The program was compiled for another curve
+    Curve { expected: String, found: String },
+    /// The program could not be read, and was compiled by another version of the compiler
+    Compiler(String),
+    Format(String),
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ArtifactError::Magic => write!(
+                f,
+                "Not a compiled program, or compiled by a version of ZoKrates older than {}",
+                COMPILER_VERSION
+            ),
+            ArtifactError::Version(version) => write!(
+                f,
+                "Compiled program format version {} is not supported, expected version {}",
+                version, ARTIFACT_VERSION
+            ),
+            ArtifactError::Curve {
+                ref expected,
+                ref found,
+            } => write!(
+                f,
+                "Program was compiled for curve {}, not for curve {}",
+                found, expected
+            ),
+            ArtifactError::Compiler(ref version) => write!(
+                f,
+                "Program was compiled by ZoKrates {}, which is not compatible with ZoKrates {}",
+                version, COMPILER_VERSION
+            ),
+            ArtifactError::Format(ref e) => write!(f, "Invalid compiled program: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flat_absy::FlatVariable;
+    use ir::Function;
+    use zokrates_field::field::{Bls12_381Field, FieldPrime};
+
+    fn program() -> Prog<FieldPrime> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                statements: vec![],
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::new(0).into()],
+            },
+            private: vec![false],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let artifact = Artifact::new(program(), b"def main(field a) -> (field): return a");
+
+        let mut bytes = vec![];
+        artifact.write(&mut bytes).unwrap();
+        assert_eq!(&bytes[..8], b"zkir\x01\x00\x00\x00");
+
+        let read = Artifact::<FieldPrime>::read(&mut &bytes[..]).unwrap();
+        assert_eq!(read.header, artifact.header);
+        assert_eq!(read.header.curve, "bn128");
+        assert_eq!(read.program.hash(), program().hash());
+
+        // the header can be read on its own, before choosing the field to read the program in
+        assert_eq!(Header::read(&mut &bytes[..]).unwrap(), artifact.header);
+    }
+
+    #[test]
+    fn mismatch() {
+        let mut bytes = vec![];
+        Artifact::new(program(), b"").write(&mut bytes).unwrap();
+
+        assert_eq!(
+            Artifact::<Bls12_381Field>::read(&mut &bytes[..]).unwrap_err(),
+            ArtifactError::Curve {
+                expected: String::from("bls12_381"),
+                found: String::from("bn128"),
+            }
+        );
+
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert_eq!(
+            Artifact::<FieldPrime>::read(&mut &version[..]).unwrap_err(),
+            ArtifactError::Version(2)
+        );
+
+        // programs written before the header was introduced are plain bincode
+        let mut legacy = vec![];
+        bincode::serialize_into(&mut legacy, &program(), Infinite).unwrap();
+        assert_eq!(
+            Artifact::<FieldPrime>::read(&mut &legacy[..]).unwrap_err(),
+            ArtifactError::Magic
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! Execution of a program one statement at a time, stopping at breakpoints to inspect the values
+//! of its variables, named after the variables of the source program.
+//!
+//! Source variables are flattened to one variable per primitive value: the elements of an array
+//! `a` are bound as `a_c0`, `a_c1`..., the members of a struct `p` as `p_s0`, `p_s1`... and the
+//! bits of an unsigned integer `x` as `x_b0`, `x_b1`... Each assignment to a variable binds a new
+//! version of it, suffixed with its number, which is left out of the names shown.
+
+use flat_absy::{FlatVariable, SourceMap};
+use ir::{Error, Execution, ExecutionResult, Prog, Statement};
+use std::fmt;
+use std::str::FromStr;
+use zokrates_field::field::Field;
+
+/// A location to stop the execution at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Breakpoint {
+    /// Before the statement of the given index
+    Statement(usize),
+    /// Before the statements originating from the given source line
+    Line(usize),
+}
+
+impl FromStr for Breakpoint {
+    type Err = String;
+
+    /// Parses a source line, or the index of a statement prefixed with `#`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid breakpoint {}, expected a line or #statement", s);
+        match s.starts_with('#') {
+            true => s[1..]
+                .parse()
+                .map(Breakpoint::Statement)
+                .map_err(|_| invalid()),
+            false => s.parse().map(Breakpoint::Line).map_err(|_| invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Breakpoint::Statement(index) => write!(f, "statement #{}", index),
+            Breakpoint::Line(line) => write!(f, "line {}", line),
+        }
+    }
+}
+
+pub struct Debugger<'a, T: Field> {
+    execution: Execution<'a, T>,
+    source_map: &'a SourceMap,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl<'a, T: Field> Debugger<'a, T> {
+    /// Starts debugging `program` on `inputs`, before its first statement
+    pub fn new<U: Into<T> + Clone>(
+        program: &'a Prog<T>,
+        source_map: &'a SourceMap,
+        inputs: &[U],
+    ) -> Result<Self, Error> {
+        Ok(Debugger {
+            execution: Execution::new(program, inputs)?,
+            source_map,
+            breakpoints: vec![],
+        })
+    }
+
+    pub fn program(&self) -> &'a Prog<T> {
+        self.execution.program()
+    }
+
+    /// The index of the next statement to execute
+    pub fn position(&self) -> usize {
+        self.execution.position()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.execution.is_done()
+    }
+
+    /// The next statement to execute, if any
+    pub fn statement(&self) -> Option<&'a Statement<T>> {
+        self.execution
+            .program()
+            .main
+            .statements
+            .get(self.position())
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    /// Whether any statement of the program is at `breakpoint`, as breakpoints on lines which
+    /// are not compiled to statements are never reached
+    pub fn is_reachable(&self, breakpoint: Breakpoint) -> bool {
+        let statements = &self.execution.program().main.statements;
+        match breakpoint {
+            Breakpoint::Statement(index) => index < statements.len(),
+            Breakpoint::Line(l) => statements
+                .iter()
+                .filter_map(|s| s.span())
+                .any(|s| s.start.line == l),
+        }
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Executes the next statement, if any
+    pub fn step(&mut self) -> Result<(), Error> {
+        self.execution.step()
+    }
+
+    /// Executes statements until the next one is at a breakpoint, returning that breakpoint, or
+    /// until the end of the program. At least one statement is executed, so that execution
+    /// can resume from a breakpoint.
+    pub fn resume(&mut self) -> Result<Option<Breakpoint>, Error> {
+        while !self.is_done() {
+            self.step()?;
+            if let Some(breakpoint) = self.breakpoint() {
+                return Ok(Some(breakpoint));
+            }
+        }
+        Ok(None)
+    }
+
+    // the breakpoint the next statement is at, if any. Execution stops at a line when entering
+    // it, rather than before each of the statements it is flattened to.
+    fn breakpoint(&self) -> Option<Breakpoint> {
+        let statements = &self.execution.program().main.statements;
+        let position = self.position();
+        let line = |index: usize| {
+            statements
+                .get(index)
+                .and_then(|s| s.span())
+                .map(|s| s.start.line)
+        };
+
+        self.breakpoints.iter().cloned().find(|b| match *b {
+            Breakpoint::Statement(index) => index == position,
+            Breakpoint::Line(l) => {
+                line(position) == Some(l) && (position == 0 || line(position - 1) != Some(l))
+            }
+        })
+    }
+
+    /// Returns the value of `variable`, if it was computed already
+    pub fn value(&self, variable: &FlatVariable) -> Option<&T> {
+        self.execution.value(variable)
+    }
+
+    /// The function the next statement is located in, `main` if it is not located
+    pub fn function(&self) -> &'a str {
+        self.statement()
+            .and_then(|s| s.span())
+            .and_then(|s| s.function.as_ref())
+            .map(|f| f.as_str())
+            .unwrap_or("main")
+    }
+
+    /// Returns the current values of the source variable `name` of the function the next
+    /// statement is located in, or of the variables it is flattened to if it is not a primitive,
+    /// along with their names. Values which are not computed yet are `None`.
+    pub fn variable(&self, name: &str) -> Vec<(String, Option<&T>)> {
+        let function = self.function();
+        let source_name =
+            |binding: &str| local_binding(binding, function).and_then(|b| source_name(b, name));
+
+        let mut names: Vec<String> = vec![];
+        for (n, _) in &self.source_map.variables {
+            if let Some(n) = source_name(n) {
+                if !names.contains(&n) {
+                    names.push(n);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .map(|n| {
+                // variables which are assigned several times take the value of the last
+                // assignment executed so far
+                let value = self
+                    .source_map
+                    .variables
+                    .iter()
+                    .rev()
+                    .filter(|(m, _)| source_name(m).as_ref() == Some(&n))
+                    .filter_map(|(_, v)| self.value(v))
+                    .next();
+                (n, value)
+            })
+            .collect()
+    }
+
+    /// Executes the remaining statements regardless of breakpoints, returning the witness
+    pub fn finish(self) -> ExecutionResult<T> {
+        self.execution.finish()
+    }
+}
+
+// the binding `binding` without the calls it is inlined from, if it binds a variable of
+// `function`: `cube_ifof_2_y_0` binds `y_0` in the second call to `cube`, and the variables of
+// `main` are bound as they are
+fn local_binding<'b>(binding: &'b str, function: &str) -> Option<&'b str> {
+    if function == "main" {
+        return Some(binding);
+    }
+
+    let prefix = format!("{}_", function);
+    binding
+        .match_indices(&prefix)
+        .filter(|&(i, _)| i == 0 || binding[..i].ends_with('_'))
+        .filter_map(|(i, _)| {
+            // the signature and the index of the call follow the name of the function
+            let mut parts = binding[i + prefix.len()..].splitn(3, '_');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(signature), Some(index), Some(rest))
+                    if signature.starts_with('i') && is_number(index) =>
+                {
+                    Some(rest)
+                }
+                _ => None,
+            }
+        })
+        .next()
+}
+
+// the name of the binding `binding` of the source variable `name` with its versions left out,
+// if it is one: `b_1_c0` is the first element of the second version of `b`, named `b_c0`
+fn source_name(binding: &str, name: &str) -> Option<String> {
+    if !binding.starts_with(name) {
+        return None;
+    }
+
+    let mut parts = binding[name.len()..].split('_');
+    // the binding is either `name` itself or followed by `_`
+    if parts.next() != Some("") {
+        return None;
+    }
+
+    let mut components = String::new();
+    for part in parts {
+        match (components.is_empty(), is_number(part)) {
+            // versions come before components
+            (true, true) => {}
+            _ if part.len() > 1
+                && part.starts_with(|c| c == 'c' || c == 's' || c == 'b')
+                && is_number(&part[1..]) =>
+            {
+                components.push('_');
+                components.push_str(part);
+            }
+            _ => return None,
+        }
+    }
+
+    Some(format!("{}{}", name, components))
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_digit(10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compile::compile_str;
+    use zokrates_field::field::FieldPrime;
+
+    const SOURCE: &str = r#"
+def main(field a, field[2] b) -> (field):
+	field c = a * b[0]
+	c = c * b[1]
+	return c + 1
+"#;
+
+    #[test]
+    fn breakpoints() {
+        assert_eq!("3".parse(), Ok(Breakpoint::Line(3)));
+        assert_eq!("#12".parse(), Ok(Breakpoint::Statement(12)));
+        assert!("c".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn source_names() {
+        assert_eq!(source_name("c", "c"), Some(String::from("c")));
+        assert_eq!(source_name("c_1", "c"), Some(String::from("c")));
+        assert_eq!(source_name("b_0_c1", "b"), Some(String::from("b_c1")));
+        assert_eq!(source_name("p_2_c0_s1", "p"), Some(String::from("p_c0_s1")));
+        assert_eq!(source_name("cd_0", "c"), None);
+        assert_eq!(source_name("c_0_x", "c"), None);
+
+        // variables of called functions are prefixed with the calls they are inlined from
+        assert_eq!(local_binding("c_1", "main"), Some("c_1"));
+        assert_eq!(local_binding("cube_ifof_2_y_0", "cube"), Some("y_0"));
+        assert_eq!(
+            local_binding("sum_if[2]of_1_cube_ifof_1_y_0", "cube"),
+            Some("y_0")
+        );
+        assert_eq!(local_binding("cube_ifof_2_y_0", "sum"), None);
+        assert_eq!(local_binding("y_0", "cube"), None);
+    }
+
+    #[test]
+    fn step_through() {
+        let compiled = compile_str(SOURCE).unwrap();
+        let inputs = vec![2, 3, 5];
+        let mut debugger =
+            Debugger::<FieldPrime>::new(&compiled.program, &compiled.source_map, &inputs).unwrap();
+
+        assert!(debugger.is_reachable(Breakpoint::Line(4)));
+        assert!(!debugger.is_reachable(Breakpoint::Line(1)));
+        assert!(!debugger.is_reachable(Breakpoint::Line(99)));
+        assert!(!debugger.is_reachable(Breakpoint::Statement(99)));
+
+        debugger.add_breakpoint(Breakpoint::Line(4));
+        assert_eq!(debugger.resume(), Ok(Some(Breakpoint::Line(4))));
+        assert_eq!(debugger.statement().unwrap().span().unwrap().start.line, 4);
+
+        let six = FieldPrime::from(6);
+        assert_eq!(
+            debugger.variable("c"),
+            vec![(String::from("c"), Some(&six))]
+        );
+        assert_eq!(
+            debugger.variable("b"),
+            vec![
+                (String::from("b_c0"), Some(&FieldPrime::from(3))),
+                (String::from("b_c1"), Some(&FieldPrime::from(5)))
+            ]
+        );
+
+        debugger.step().unwrap();
+        let thirty = FieldPrime::from(30);
+        assert_eq!(
+            debugger.variable("c"),
+            vec![(String::from("c"), Some(&thirty))]
+        );
+
+        assert_eq!(debugger.resume(), Ok(None));
+        assert!(debugger.is_done());
+        assert_eq!(debugger.finish(), compiled.program.execute(&inputs));
+    }
+
+    #[test]
+    fn called_functions() {
+        let source = r#"
+def cube(field x) -> (field):
+	field y = x * x
+	y = y * x
+	return y
+
+def main(field a) -> (field):
+	field z = cube(a)
+	return cube(z)
+"#;
+        let compiled = compile_str(source).unwrap();
+        let inputs = vec![2];
+        let mut debugger =
+            Debugger::<FieldPrime>::new(&compiled.program, &compiled.source_map, &inputs).unwrap();
+
+        debugger.add_breakpoint(Breakpoint::Line(4));
+        assert_eq!(debugger.resume(), Ok(Some(Breakpoint::Line(4))));
+        assert_eq!(debugger.function(), "cube");
+        assert_eq!(
+            debugger.variable("x"),
+            vec![(String::from("x"), Some(&FieldPrime::from(2)))]
+        );
+        assert_eq!(
+            debugger.variable("y"),
+            vec![(String::from("y"), Some(&FieldPrime::from(4)))]
+        );
+        // the variables of the caller are out of scope
+        assert_eq!(debugger.variable("z"), vec![]);
+
+        // the second call shows its own values
+        assert_eq!(debugger.resume(), Ok(Some(Breakpoint::Line(4))));
+        assert_eq!(
+            debugger.variable("x"),
+            vec![(String::from("x"), Some(&FieldPrime::from(8)))]
+        );
+        assert_eq!(
+            debugger.variable("y"),
+            vec![(String::from("y"), Some(&FieldPrime::from(64)))]
+        );
+
+        assert_eq!(debugger.resume(), Ok(None));
+        assert_eq!(debugger.function(), "main");
+        assert_eq!(
+            debugger.variable("z"),
+            vec![(String::from("z"), Some(&FieldPrime::from(8)))]
+        );
+    }
+}
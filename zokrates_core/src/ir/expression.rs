@@ -48,6 +48,14 @@ impl<T: Field> LinComb<T> {
     pub fn one() -> LinComb<T> {
         Self::summand(1, FlatVariable::one())
     }
+
+    /// Returns the variable this combination is made of, if it is a single variable
+    pub fn single_variable(&self) -> Option<FlatVariable> {
+        match self.0.iter().next() {
+            Some((v, c)) if self.0.len() == 1 && *c == T::one() => Some(*v),
+            _ => None,
+        }
+    }
 }
 
 impl<T: Field> fmt::Display for LinComb<T> {
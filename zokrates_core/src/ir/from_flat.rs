@@ -49,7 +49,7 @@ impl<T: Field> From<FlatProg<T>> for Prog<T> {
         // contrary to other functions, we need to make sure that return values are identifiers, so we define new (public) variables
         let definitions =
             main.returns.iter().enumerate().map(|(index, e)| {
-                Statement::Constraint(e.clone(), FlatVariable::public(index).into())
+                Statement::Constraint(e.clone(), FlatVariable::public(index).into(), None)
             });
 
         // update the main function with the extra definition statements and replace the return values
@@ -105,19 +105,21 @@ impl<T: Field> From<FlatExpression<T>> for LinComb<T> {
 impl<T: Field> From<FlatStatement<T>> for Statement<T> {
     fn from(flat_statement: FlatStatement<T>) -> Statement<T> {
         match flat_statement {
-            FlatStatement::Condition(linear, quadratic) => match quadratic {
+            FlatStatement::Condition(linear, quadratic, metadata) => match quadratic {
                 FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
                     QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
                     linear.into(),
+                    metadata,
                 ),
-                e => Statement::Constraint(LinComb::from(e).into(), linear.into()),
+                e => Statement::Constraint(LinComb::from(e).into(), linear.into(), metadata),
             },
             FlatStatement::Definition(var, quadratic) => match quadratic {
                 FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
                     QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
                     var.into(),
+                    None,
                 ),
-                e => Statement::Constraint(LinComb::from(e).into(), var.into()),
+                e => Statement::Constraint(LinComb::from(e).into(), var.into(), None),
             },
             FlatStatement::Directive(ds) => Statement::Directive(ds.into()),
             _ => panic!("return should be handled at the function level"),
@@ -131,6 +133,7 @@ impl<T: Field> From<helpers::DirectiveStatement<T>> for DirectiveStatement<T> {
             inputs: ds.inputs.into_iter().map(|i| i.into()).collect(),
             helper: ds.helper,
             outputs: ds.outputs,
+            span: ds.span,
         }
     }
 }
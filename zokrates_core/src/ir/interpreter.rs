@@ -1,16 +1,73 @@
+//! Execution of programs, and the witness files which store the resulting assignment of their
+//! variables.
+//!
+//! Witness files start with a header made of the version of the format, the hash of the program
+//! the witness was computed for and the number of variables, followed by the value of each
+//! variable. They come in three encodings:
+//!
+//! ```text
+//! text:   # ZoKrates witness v1
+//!         # program <hash>
+//!         # variables <count>
+//!         for each variable, a line <variable> <value>
+//! json:   {"version": 1, "program": "<hash>", "variables": <count>, "values": {"<variable>": "<value>"}}
+//! binary: "zkwt" | version: u32 | program, count and values of the variables, as encoded by bincode
+//! ```
+//!
+//! Values are written in decimal, except in the binary encoding where they are little-endian bytes.
+
+use bincode;
+use bincode::Infinite;
 use helpers::Executable;
 use ir::*;
-use std::collections::BTreeMap;
+use num_bigint::BigUint;
+use serde_json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Read, Write};
 use zokrates_field::field::Field;
 
 pub type ExecutionResult<T> = Result<Witness<T>, Error>;
 
-pub struct Witness<T: Field>(BTreeMap<FlatVariable, T>);
+/// The version of the witness format this crate reads and writes
+pub const WITNESS_VERSION: u32 = 1;
+
+const MAGIC: &[u8] = b"zkwt";
+const TEXT_HEADER: &str = "# ZoKrates witness v";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Witness<T: Field> {
+    /// The hash of the program the witness was computed for
+    program: String,
+    values: BTreeMap<FlatVariable, T>,
+}
+
+/// The encodings of witness files
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WitnessFormat {
+    Text,
+    Json,
+    Binary,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonWitness {
+    version: u32,
+    program: String,
+    variables: usize,
+    values: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryWitness {
+    program: String,
+    variables: u64,
+    values: Vec<(FlatVariable, Vec<u8>)>,
+}
 
 impl<T: Field> Witness<T> {
     pub fn return_values(&self) -> Vec<&T> {
         let out = self
-            .0
+            .values
             .iter()
             .filter(|(k, _)| k.is_output())
             .collect::<HashMap<_, _>>();
@@ -20,7 +77,7 @@ impl<T: Field> Witness<T> {
     }
 
     pub fn format_outputs(&self) -> String {
-        self.0
+        self.values
             .iter()
             .filter_map(|(variable, value)| match variable {
                 variable if variable.is_output() => Some(format!("{} {}", variable, value)),
@@ -29,14 +86,233 @@ impl<T: Field> Witness<T> {
             .collect::<Vec<String>>()
             .join("\n")
     }
+
+    /// The hash of the program the witness was computed for
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    pub fn get(&self, variable: &FlatVariable) -> Option<&T> {
+        self.values.get(variable)
+    }
+
+    /// Checks that the witness was computed for `program` and assigns all of its variables
+    pub fn check(&self, program: &Prog<T>) -> Result<(), WitnessError> {
+        let hash = program.hash();
+        if self.program != hash {
+            return Err(WitnessError::Program {
+                expected: hash,
+                found: self.program.clone(),
+            });
+        }
+
+        match program
+            .variables()
+            .into_iter()
+            .find(|v| !self.values.contains_key(v))
+        {
+            Some(v) => Err(WitnessError::Missing(v)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W, format: WitnessFormat) -> io::Result<()> {
+        match format {
+            WitnessFormat::Text => write!(writer, "{}", self),
+            WitnessFormat::Json => {
+                let json = JsonWitness {
+                    version: WITNESS_VERSION,
+                    program: self.program.clone(),
+                    variables: self.values.len(),
+                    values: self
+                        .values
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_dec_string()))
+                        .collect(),
+                };
+                serde_json::to_writer_pretty(writer, &json)?;
+                Ok(())
+            }
+            WitnessFormat::Binary => {
+                let binary = BinaryWitness {
+                    program: self.program.clone(),
+                    variables: self.values.len() as u64,
+                    values: self
+                        .values
+                        .iter()
+                        .map(|(k, v)| (*k, v.into_byte_vector()))
+                        .collect(),
+                };
+                writer.write_all(MAGIC)?;
+                writer.write_all(&WITNESS_VERSION.to_le_bytes())?;
+                let bytes = bincode::serialize(&binary, Infinite)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+                writer.write_all(&bytes)
+            }
+        }
+    }
+
+    /// Reads a witness in any of the encodings, which is detected from the content
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, WitnessError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+
+        if bytes.starts_with(MAGIC) {
+            return Self::read_binary(&bytes[MAGIC.len()..]);
+        }
+
+        let text = String::from_utf8(bytes)
+            .map_err(|_| WitnessError::Format(String::from("Unknown witness encoding")))?;
+
+        match text.trim_start().starts_with('{') {
+            true => Self::read_json(&text),
+            false => Self::read_text(&text),
+        }
+    }
+
+    fn read_text(text: &str) -> Result<Self, WitnessError> {
+        let mut lines = text.lines().enumerate();
+
+        let mut header = |key: &str| -> Result<String, WitnessError> {
+            match lines.next() {
+                Some((_, line)) if line.starts_with(key) => Ok(line[key.len()..].trim().into()),
+                _ => Err(WitnessError::Format(format!(
+                    "Expected witness header line `{}`",
+                    key.trim()
+                ))),
+            }
+        };
+
+        let version = header(TEXT_HEADER)?;
+        check_version(
+            version
+                .parse()
+                .map_err(|_| WitnessError::Format(format!("Invalid version {}", version)))?,
+        )?;
+        let program = header("# program ")?;
+        let count = header("# variables ")?;
+        let count = count
+            .parse()
+            .map_err(|_| WitnessError::Format(format!("Invalid variable count {}", count)))?;
+
+        let values = lines
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| -> Result<(FlatVariable, T), WitnessError> {
+                let invalid =
+                    || WitnessError::Format(format!("Invalid line {}: {}", index + 1, line));
+                let mut parts = line.split_whitespace();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(variable), Some(value), None) => Ok((
+                        variable.parse::<FlatVariable>().map_err(|_| invalid())?,
+                        parse_value(value).ok_or_else(invalid)?,
+                    )),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Self::new(program, count, values)
+    }
+
+    fn read_json(text: &str) -> Result<Self, WitnessError> {
+        let json: JsonWitness =
+            serde_json::from_str(text).map_err(|e| WitnessError::Format(e.to_string()))?;
+
+        check_version(json.version)?;
+
+        let values = json
+            .values
+            .iter()
+            .map(
+                |(variable, value)| -> Result<(FlatVariable, T), WitnessError> {
+                    let invalid = || {
+                        WitnessError::Format(format!("Invalid value {} for {}", value, variable))
+                    };
+                    Ok((
+                        variable
+                            .parse::<FlatVariable>()
+                            .map_err(WitnessError::Format)?,
+                        parse_value(value).ok_or_else(invalid)?,
+                    ))
+                },
+            )
+            .collect::<Result<_, _>>()?;
+
+        Self::new(json.program, json.variables, values)
+    }
+
+    fn read_binary(bytes: &[u8]) -> Result<Self, WitnessError> {
+        if bytes.len() < 4 {
+            return Err(WitnessError::Format(String::from(
+                "Truncated witness header",
+            )));
+        }
+        let mut version = [0u8; 4];
+        version.copy_from_slice(&bytes[..4]);
+        check_version(u32::from_le_bytes(version))?;
+
+        let binary: BinaryWitness =
+            bincode::deserialize(&bytes[4..]).map_err(|e| WitnessError::Format(e.to_string()))?;
+
+        let values = binary
+            .values
+            .into_iter()
+            .map(|(variable, value)| {
+                field_element(BigUint::from_bytes_le(&value))
+                    .map(|value| (variable, value))
+                    .ok_or_else(|| {
+                        WitnessError::Format(format!("Value of {} is not in the field", variable))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Self::new(binary.program, binary.variables as usize, values)
+    }
+
+    fn new(
+        program: String,
+        count: usize,
+        values: BTreeMap<FlatVariable, T>,
+    ) -> Result<Self, WitnessError> {
+        match values.len() == count {
+            true => Ok(Witness { program, values }),
+            false => Err(WitnessError::Format(format!(
+                "Expected {} variables, found {}",
+                count,
+                values.len()
+            ))),
+        }
+    }
+}
+
+fn check_version(version: u32) -> Result<(), WitnessError> {
+    match version == WITNESS_VERSION {
+        true => Ok(()),
+        false => Err(WitnessError::Version(version)),
+    }
+}
+
+// values out of the field are rejected, as building field elements from them would reduce them
+fn field_element<T: Field>(value: BigUint) -> Option<T> {
+    match value <= BigUint::from_bytes_le(&T::max_value().into_byte_vector()) {
+        true => Some(T::from_byte_vector(value.to_bytes_le())),
+        false => None,
+    }
+}
+
+fn parse_value<T: Field>(value: &str) -> Option<T> {
+    BigUint::parse_bytes(value.as_bytes(), 10).and_then(field_element)
 }
 
 impl<T: Field> fmt::Display for Witness<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}{}", TEXT_HEADER, WITNESS_VERSION)?;
+        writeln!(f, "# program {}", self.program)?;
+        writeln!(f, "# variables {}", self.values.len())?;
         write!(
             f,
             "{}",
-            self.0
+            self.values
                 .iter()
                 .map(|(k, v)| format!("{} {}", k, v.to_dec_string()))
                 .collect::<Vec<_>>()
@@ -47,52 +323,36 @@ impl<T: Field> fmt::Display for Witness<T> {
 
 impl<T: Field> Prog<T> {
     pub fn execute<U: Into<T> + Clone>(&self, inputs: &Vec<U>) -> ExecutionResult<T> {
+        Execution::new(self, inputs)?.finish()
+    }
+
+    // the variables the constraints and directives of the program refer to
+    fn variables(&self) -> BTreeSet<FlatVariable> {
         let main = &self.main;
-        self.check_inputs(&inputs)?;
-        let mut witness = BTreeMap::new();
-        witness.insert(FlatVariable::one(), T::one());
-        for (arg, value) in main.arguments.iter().zip(inputs.iter()) {
-            witness.insert(arg.clone(), value.clone().into());
-        }
+        let mut variables: BTreeSet<FlatVariable> = main.arguments.iter().cloned().collect();
+        variables.insert(FlatVariable::one());
+        variables.extend((0..main.returns.len()).map(FlatVariable::public));
 
         for statement in &main.statements {
             match statement {
-                Statement::Constraint(quad, lin) => match lin.is_assignee(&witness) {
-                    true => {
-                        let val = quad.evaluate(&witness);
-                        witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
+                Statement::Constraint(quad, lin, _) => {
+                    for l in &[&quad.left, &quad.right, lin] {
+                        variables.extend(l.0.keys().cloned());
                     }
-                    false => {
-                        let lhs_value = quad.evaluate(&witness);
-                        let rhs_value = lin.evaluate(&witness);
-                        if lhs_value != rhs_value {
-                            return Err(Error::UnsatisfiedConstraint {
-                                left: lhs_value.to_dec_string(),
-                                right: rhs_value.to_dec_string(),
-                            });
-                        }
+                }
+                Statement::Directive(d) => {
+                    for i in &d.inputs {
+                        variables.extend(i.0.keys().cloned());
                     }
-                },
-                Statement::Directive(ref d) => {
-                    let input_values: Vec<T> =
-                        d.inputs.iter().map(|i| i.evaluate(&witness)).collect();
-                    match d.helper.execute(&input_values) {
-                        Ok(res) => {
-                            for (i, o) in d.outputs.iter().enumerate() {
-                                witness.insert(o.clone(), res[i].clone());
-                            }
-                            continue;
-                        }
-                        Err(_) => return Err(Error::Solver),
-                    };
+                    variables.extend(d.outputs.iter().cloned());
                 }
             }
         }
 
-        Ok(Witness(witness))
+        variables
     }
 
-    fn check_inputs<U>(&self, inputs: &Vec<U>) -> Result<(), Error> {
+    fn check_inputs<U>(&self, inputs: &[U]) -> Result<(), Error> {
         if self.main.arguments.len() == inputs.len() {
             Ok(())
         } else {
@@ -104,6 +364,111 @@ impl<T: Field> Prog<T> {
     }
 }
 
+/// A program being executed one statement at a time
+pub struct Execution<'a, T: Field> {
+    program: &'a Prog<T>,
+    witness: BTreeMap<FlatVariable, T>,
+    /// The index of the next statement to execute
+    position: usize,
+}
+
+impl<'a, T: Field> Execution<'a, T> {
+    /// Starts executing `program` on `inputs`, before its first statement
+    pub fn new<U: Into<T> + Clone>(program: &'a Prog<T>, inputs: &[U]) -> Result<Self, Error> {
+        program.check_inputs(inputs)?;
+
+        let mut witness = BTreeMap::new();
+        witness.insert(FlatVariable::one(), T::one());
+        for (arg, value) in program.main.arguments.iter().zip(inputs.iter()) {
+            witness.insert(arg.clone(), value.clone().into());
+        }
+
+        Ok(Execution {
+            program,
+            witness,
+            position: 0,
+        })
+    }
+
+    pub fn program(&self) -> &'a Prog<T> {
+        self.program
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.position == self.program.main.statements.len()
+    }
+
+    /// Returns the value of `variable`, if it was computed already
+    pub fn value(&self, variable: &FlatVariable) -> Option<&T> {
+        self.witness.get(variable)
+    }
+
+    /// Executes the next statement, if any
+    pub fn step(&mut self) -> Result<(), Error> {
+        let statement = match self.program.main.statements.get(self.position) {
+            Some(statement) => statement,
+            None => return Ok(()),
+        };
+
+        let witness = &mut self.witness;
+
+        match statement {
+            Statement::Constraint(quad, lin, metadata) => match lin.is_assignee(witness) {
+                true => {
+                    let val = quad.evaluate(witness);
+                    witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
+                }
+                false => {
+                    let lhs_value = quad.evaluate(witness);
+                    let rhs_value = lin.evaluate(witness);
+                    if lhs_value != rhs_value {
+                        return Err(Error::UnsatisfiedConstraint {
+                            left: lhs_value.to_dec_string(),
+                            right: rhs_value.to_dec_string(),
+                            metadata: metadata.clone(),
+                        });
+                    }
+                }
+            },
+            Statement::Directive(ref d) => {
+                let input_values: Vec<T> = d.inputs.iter().map(|i| i.evaluate(witness)).collect();
+                match d.helper.execute(&input_values) {
+                    Ok(res) => {
+                        for (i, o) in d.outputs.iter().enumerate() {
+                            witness.insert(o.clone(), res[i].clone());
+                        }
+                    }
+                    Err(message) => {
+                        return Err(Error::Solver {
+                            message,
+                            span: d.span.clone(),
+                        })
+                    }
+                };
+            }
+        }
+
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Executes the remaining statements, returning the witness of the program
+    pub fn finish(mut self) -> ExecutionResult<T> {
+        while !self.is_done() {
+            self.step()?;
+        }
+
+        Ok(Witness {
+            program: self.program.hash(),
+            values: self.witness,
+        })
+    }
+}
+
 impl<T: Field> LinComb<T> {
     fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> T {
         self.0
@@ -127,8 +492,15 @@ impl<T: Field> QuadComb<T> {
 
 #[derive(PartialEq, Serialize, Deserialize)]
 pub enum Error {
-    UnsatisfiedConstraint { left: String, right: String },
-    Solver,
+    UnsatisfiedConstraint {
+        left: String,
+        right: String,
+        metadata: Option<DebugInfo>,
+    },
+    Solver {
+        message: String,
+        span: Option<Span>,
+    },
     WrongInputCount { expected: usize, received: usize },
 }
 
@@ -138,8 +510,22 @@ impl fmt::Display for Error {
             Error::UnsatisfiedConstraint {
                 ref left,
                 ref right,
+                metadata: Some(ref metadata),
+            } => write!(f, "{}", metadata.describe(left, right)),
+            Error::UnsatisfiedConstraint {
+                ref left,
+                ref right,
+                metadata: None,
             } => write!(f, "Expected {} to equal {}", left, right),
-            Error::Solver => write!(f, ""),
+            Error::Solver {
+                ref message,
+                span: Some(ref span),
+            } => write!(f, "Witness computation failed at {}: {}", span, message),
+            // the directives decomposing the inputs of main are not located
+            Error::Solver {
+                ref message,
+                span: None,
+            } => write!(f, "Witness computation failed: {}", message),
             Error::WrongInputCount { expected, received } => write!(
                 f,
                 "Program takes {} input{} but was passed {} value{}",
@@ -157,3 +543,140 @@ impl fmt::Debug for Error {
         write!(f, "{}", self)
     }
 }
+
+#[derive(Debug, PartialEq)]
+pub enum WitnessError {
+    Io(String),
+    /// The witness file is malformed
+    Format(String),
+    /// The witness file was written in another version of the format
+    Version(u32),
+    /// The witness was computed for another program
+    Program {
+        expected: String,
+        found: String,
+    },
+    /// A variable of the program has no value in the witness
+    Missing(FlatVariable),
+}
+
+impl From<io::Error> for WitnessError {
+    fn from(e: io::Error) -> Self {
+        WitnessError::Io(e.to_string())
+    }
+}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WitnessError::Io(ref e) => write!(f, "{}", e),
+            WitnessError::Format(ref e) => write!(f, "Invalid witness: {}", e),
+            WitnessError::Version(version) => write!(
+                f,
+                "Witness format version {} is not supported, expected version {}",
+                version, WITNESS_VERSION
+            ),
+            WitnessError::Program {
+                ref expected,
+                ref found,
+            } => write!(
+                f,
+                "Witness was computed for program {}, not for program {}",
+                found, expected
+            ),
+            WitnessError::Missing(ref v) => write!(f, "Witness has no value for {}", v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::field::FieldPrime;
+
+    // def main(private field a) -> (field): return a * a
+    fn program() -> Prog<FieldPrime> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                statements: vec![Statement::Constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                    FlatVariable::public(0).into(),
+                    None,
+                )],
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0).into()],
+            },
+            private: vec![true],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let witness = program().execute(&vec![FieldPrime::from(3)]).unwrap();
+
+        for format in &[
+            WitnessFormat::Text,
+            WitnessFormat::Json,
+            WitnessFormat::Binary,
+        ] {
+            let mut bytes = vec![];
+            witness.write(&mut bytes, *format).unwrap();
+            assert_eq!(Witness::read(&mut &bytes[..]), Ok(witness.clone()));
+        }
+    }
+
+    #[test]
+    fn text() {
+        let witness = program().execute(&vec![FieldPrime::from(3)]).unwrap();
+
+        assert_eq!(
+            witness.to_string(),
+            format!(
+                "# ZoKrates witness v1\n# program {}\n# variables 3\n~out_0 9\n~one 1\n_0 3",
+                program().hash()
+            )
+        );
+
+        let read = |text: &str| Witness::<FieldPrime>::read(&mut text.as_bytes());
+
+        assert_eq!(
+            read("# ZoKrates witness v2\n# program 00\n# variables 0"),
+            Err(WitnessError::Version(2))
+        );
+        assert!(read("# ZoKrates witness v1\n# program 00\n# variables 2\n~one 1").is_err());
+        assert!(read("# ZoKrates witness v1\n# program 00\n# variables 1\n~one one").is_err());
+        // values are not reduced modulo the prime
+        assert!(read(&format!(
+            "# ZoKrates witness v1\n# program 00\n# variables 1\n_0 {}",
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn check() {
+        let witness = program().execute(&vec![FieldPrime::from(3)]).unwrap();
+        assert_eq!(witness.check(&program()), Ok(()));
+
+        let mut public = program();
+        public.private = vec![false];
+        assert_eq!(
+            witness.check(&public),
+            Err(WitnessError::Program {
+                expected: public.hash(),
+                found: program().hash(),
+            })
+        );
+
+        let mut incomplete = witness.clone();
+        incomplete.values.remove(&FlatVariable::new(0));
+        assert_eq!(
+            incomplete.check(&program()),
+            Err(WitnessError::Missing(FlatVariable::new(0)))
+        );
+    }
+}
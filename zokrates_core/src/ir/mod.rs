@@ -1,24 +1,37 @@
 use flat_absy::flat_parameter::FlatParameter;
 use flat_absy::FlatVariable;
+use flat_absy::{DebugInfo, Span};
 use helpers::Helper;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::mem;
 use zokrates_field::field::Field;
 
+mod artifact;
+mod debugger;
 mod expression;
 mod from_flat;
 mod interpreter;
+mod profile;
+mod r1cs;
+mod underconstrained;
 
 use self::expression::LinComb;
 use self::expression::QuadComb;
 
+pub use self::artifact::{Artifact, ArtifactError, Header};
+pub use self::debugger::{Breakpoint, Debugger};
 pub use self::interpreter::Error;
-pub use self::interpreter::ExecutionResult;
+pub use self::interpreter::{Execution, ExecutionResult};
+pub use self::interpreter::{Witness, WitnessError, WitnessFormat};
+pub use self::profile::{Count, Profile};
+pub use self::r1cs::R1CS;
+pub use self::underconstrained::Underconstrained;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Statement<T: Field> {
-    Constraint(QuadComb<T>, LinComb<T>),
+    Constraint(QuadComb<T>, LinComb<T>, Option<DebugInfo>),
     Directive(DirectiveStatement<T>),
 }
 
@@ -27,6 +40,7 @@ pub struct DirectiveStatement<T: Field> {
     pub inputs: Vec<LinComb<T>>,
     pub outputs: Vec<FlatVariable>,
     pub helper: Helper,
+    pub span: Option<Span>,
 }
 
 impl<T: Field> fmt::Display for DirectiveStatement<T> {
@@ -49,10 +63,20 @@ impl<T: Field> fmt::Display for DirectiveStatement<T> {
     }
 }
 
+impl<T: Field> Statement<T> {
+    /// The location of the source statement this statement originates from, if known
+    pub fn span(&self) -> Option<&Span> {
+        match *self {
+            Statement::Constraint(_, _, ref metadata) => metadata.as_ref().map(|m| &m.span),
+            Statement::Directive(ref d) => d.span.as_ref(),
+        }
+    }
+}
+
 impl<T: Field> fmt::Display for Statement<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Statement::Constraint(ref quad, ref lin) => write!(f, "{} == {}", quad, lin),
+            Statement::Constraint(ref quad, ref lin, _) => write!(f, "{} == {}", quad, lin),
             Statement::Directive(ref s) => write!(f, "{}", s),
         }
     }
@@ -99,6 +123,38 @@ pub struct Prog<T: Field> {
 }
 
 impl<T: Field> Prog<T> {
+    /// Sets the file of the spans of the program which are not located yet
+    pub fn attach_file(&mut self, file: &str) {
+        for statement in self.main.statements.iter_mut() {
+            match *statement {
+                Statement::Constraint(_, _, Some(ref mut metadata)) => {
+                    metadata.span.attach_file(file)
+                }
+                Statement::Directive(DirectiveStatement {
+                    span: Some(ref mut span),
+                    ..
+                }) => span.attach_file(file),
+                _ => {}
+            }
+        }
+    }
+
+    /// Locates the constraints defining variables, which flattening does not locate
+    pub fn attach_definitions(&mut self, definitions: &BTreeMap<FlatVariable, Span>) {
+        let mut definitions = definitions.clone();
+        for statement in self.main.statements.iter_mut() {
+            if let Statement::Constraint(_, ref lin, ref mut metadata @ None) = *statement {
+                // a variable is defined by the first constraint it is the only term of the output of
+                if let Some(span) = lin.single_variable().and_then(|v| definitions.remove(&v)) {
+                    *metadata = Some(DebugInfo {
+                        span,
+                        message: None,
+                    });
+                }
+            }
+        }
+    }
+
     pub fn constraint_count(&self) -> usize {
         self.main
             .statements
@@ -129,6 +185,27 @@ impl<T: Field> Prog<T> {
             })
             .collect()
     }
+
+    /// Returns the SHA-256 hash of the program in hexadecimal, computed from its human-readable
+    /// form and the visibility of its arguments
+    pub fn hash(&self) -> String {
+        let mut hasher = Hasher(Sha256::new());
+        fmt::write(&mut hasher, format_args!("{}", self)).unwrap();
+        for private in &self.private {
+            hasher.0.update(&[*private as u8]);
+        }
+        format!("{:x}", hasher.0.finalize())
+    }
+}
+
+// feeds formatted text to a hash function, so that programs do not need to be printed in memory
+struct Hasher(Sha256);
+
+impl fmt::Write for Hasher {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s.as_bytes());
+        Ok(())
+    }
 }
 
 impl<T: Field> fmt::Display for Prog<T> {
@@ -196,7 +273,7 @@ pub fn r1cs_program<T: Field>(
 
     // first pass through statements to populate `variables`
     for (quad, lin) in main.statements.iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, _) => Some((quad, lin)),
         Statement::Directive(..) => None,
     }) {
         for (k, _) in &quad.left.0 {
@@ -216,7 +293,7 @@ pub fn r1cs_program<T: Field>(
 
     // second pass to convert program to raw sparse vectors
     for (quad, lin) in main.statements.into_iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, _) => Some((quad, lin)),
         Statement::Directive(..) => None,
     }) {
         a.push(
@@ -266,6 +343,7 @@ mod tests {
                     FlatVariable::new(42).into(),
                 ),
                 FlatVariable::new(42).into(),
+                None,
             );
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
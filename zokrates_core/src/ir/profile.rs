@@ -0,0 +1,209 @@
+//! Attribution of the constraints and directives of a program to the functions and source lines
+//! they originate from, to find out which parts of the source make the program expensive.
+
+use ir::{Prog, Statement};
+use std::collections::BTreeMap;
+use std::fmt;
+use zokrates_field::field::Field;
+
+// the width of the longest bar of a histogram
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// The number of constraints and directives originating from a part of the source
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Count {
+    pub constraints: usize,
+    pub directives: usize,
+}
+
+impl Count {
+    fn add<T: Field>(&mut self, statement: &Statement<T>) {
+        match *statement {
+            Statement::Constraint(..) => self.constraints += 1,
+            Statement::Directive(..) => self.directives += 1,
+        }
+    }
+}
+
+/// The statements of a program, counted by the function and the line of the source statement
+/// they originate from
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    /// The statements originating from each function, by file and name of the function
+    pub functions: BTreeMap<(Option<String>, Option<String>), Count>,
+    /// The statements originating from each line, by file and line
+    pub lines: BTreeMap<(Option<String>, usize), Count>,
+    /// The statements which are not located, such as the definitions of the return values
+    pub unlocated: Count,
+}
+
+impl<T: Field> Prog<T> {
+    /// Counts the constraints and directives of the program by the function and the source line
+    /// they originate from. The statements a function call is compiled to are attributed to the
+    /// statements of the called function, whether or not the call was inlined.
+    pub fn profile(&self) -> Profile {
+        let mut profile = Profile::default();
+
+        for statement in &self.main.statements {
+            match statement.span() {
+                Some(span) => {
+                    profile
+                        .functions
+                        .entry((span.file.clone(), span.function.clone()))
+                        .or_insert_with(Count::default)
+                        .add(statement);
+                    profile
+                        .lines
+                        .entry((span.file.clone(), span.start.line))
+                        .or_insert_with(Count::default)
+                        .add(statement);
+                }
+                None => profile.unlocated.add(statement),
+            }
+        }
+
+        profile
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let functions = self
+            .functions
+            .iter()
+            .map(|(&(ref file, ref function), count)| {
+                let function = function.as_ref().map(|f| f.as_str()).unwrap_or("<unknown>");
+                let name = match *file {
+                    Some(ref file) => format!("{}:{}", file, function),
+                    None => function.to_string(),
+                };
+                (name, *count)
+            })
+            .collect();
+
+        let lines = self
+            .lines
+            .iter()
+            .map(|(&(ref file, line), count)| {
+                let name = match *file {
+                    Some(ref file) => format!("{}:{}", file, line),
+                    None => format!("line {}", line),
+                };
+                (name, *count)
+            })
+            .collect();
+
+        histogram(f, "function", functions)?;
+        write!(f, "\n")?;
+        histogram(f, "line", lines)?;
+
+        if self.unlocated != Count::default() {
+            write!(
+                f,
+                "\nNot located: {} constraint{}, {} directive{}\n",
+                self.unlocated.constraints,
+                if self.unlocated.constraints == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                self.unlocated.directives,
+                if self.unlocated.directives == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// writes the counts of `entries` from the most to the least constraints, with a bar proportional
+// to their number of constraints
+fn histogram(
+    f: &mut fmt::Formatter,
+    heading: &str,
+    mut entries: Vec<(String, Count)>,
+) -> fmt::Result {
+    entries.sort_by(|(a, a_count), (b, b_count)| {
+        b_count
+            .constraints
+            .cmp(&a_count.constraints)
+            .then_with(|| a.cmp(b))
+    });
+
+    let max = entries.first().map(|(_, c)| c.constraints).unwrap_or(0);
+    let width = entries
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    write!(
+        f,
+        "{:>11} {:>10}  {}\n",
+        "constraints", "directives", heading
+    )?;
+
+    for (name, count) in entries {
+        // entries with constraints always get a bar, however short
+        let bar = match max {
+            0 => 0,
+            max => (count.constraints * HISTOGRAM_WIDTH + max - 1) / max,
+        };
+        write!(
+            f,
+            "{:>11} {:>10}  {:width$}  {}\n",
+            count.constraints,
+            count.directives,
+            name,
+            "#".repeat(bar),
+            width = width
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compile::compile_str;
+
+    #[test]
+    fn compiled() {
+        let program = compile_str(
+            r#"
+def mul(field a, field b) -> (field):
+	field c = a * b
+	return c
+
+def main(field a, field b) -> (field):
+	field c = mul(a, b)
+	field d = a * c
+	return d
+"#,
+        )
+        .unwrap()
+        .program;
+
+        let profile = program.profile();
+
+        let count = |constraints, directives| Count {
+            constraints,
+            directives,
+        };
+        let function = |name: &str| (None, Some(String::from(name)));
+
+        assert_eq!(profile.functions.get(&function("mul")), Some(&count(1, 0)));
+        assert_eq!(profile.functions.get(&function("main")), Some(&count(1, 0)));
+        assert_eq!(profile.lines.get(&(None, 3)), Some(&count(1, 0)));
+        assert_eq!(profile.lines.get(&(None, 8)), Some(&count(1, 0)));
+        assert_eq!(
+            profile.unlocated.constraints,
+            program.constraint_count() - 2
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Export of the constraint system of a program in the `.r1cs` format of iden3, which circom and
+//! snarkjs read, and in the JSON form snarkjs prints it in.
+//!
+//! The binary format is made of little-endian integers:
+//!
+//! ```text
+//! "r1cs" | version: u32 = 1 | number of sections: u32 = 3
+//! for each section: type: u32 | size in bytes: u64 | content
+//!
+//! header (type 1): field element size in bytes: u32 | prime | wires: u32 | public outputs: u32
+//!                  | public inputs: u32 | private inputs: u32 | labels: u64 | constraints: u32
+//! constraints (type 2): for each constraint, A, B and C such that A * B = C, each made of
+//!                       their number of terms: u32 | for each term: wire: u32 | coefficient
+//! wire to label map (type 3): for each wire, label: u64
+//! ```
+//!
+//! Wires are ordered as `~one`, then the outputs, the public inputs, the private inputs and the
+//! other variables. Labels identify wires, so that the map is the identity.
+
+use flat_absy::FlatVariable;
+use ir::expression::LinComb;
+use ir::{Prog, Statement};
+use num::One;
+use num_bigint::BigUint;
+use serde_json;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use zokrates_field::field::Field;
+
+const MAGIC: &[u8] = b"r1cs";
+const VERSION: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const CONSTRAINTS_SECTION: u32 = 2;
+const MAP_SECTION: u32 = 3;
+
+type Terms<T> = Vec<(usize, T)>;
+
+/// The constraint system of a program, with its variables numbered as wires
+#[derive(Debug, Clone, PartialEq)]
+pub struct R1CS<T: Field> {
+    /// The variable of each wire
+    pub wires: Vec<FlatVariable>,
+    pub public_outputs: usize,
+    pub public_inputs: usize,
+    pub private_inputs: usize,
+    /// The terms of A, B and C for each constraint A * B = C
+    pub constraints: Vec<(Terms<T>, Terms<T>, Terms<T>)>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonR1CS {
+    n8: usize,
+    prime: String,
+    n_vars: usize,
+    n_outputs: usize,
+    n_pub_inputs: usize,
+    n_prv_inputs: usize,
+    n_labels: usize,
+    n_constraints: usize,
+    constraints: Vec<Vec<BTreeMap<String, String>>>,
+    map: Vec<usize>,
+    /// Not part of the snarkjs format: the name of the variable of each wire
+    variables: Vec<String>,
+}
+
+impl<T: Field> R1CS<T> {
+    pub fn from_program(prog: &Prog<T>) -> Self {
+        let mut wires: Vec<FlatVariable> = vec![FlatVariable::one()];
+
+        let outputs = prog.main.returns.len();
+        wires.extend((0..outputs).map(FlatVariable::public));
+
+        let arguments = || prog.main.arguments.iter().zip(prog.private.iter());
+        wires.extend(arguments().filter(|(_, p)| !**p).map(|(a, _)| *a));
+        wires.extend(arguments().filter(|(_, p)| **p).map(|(a, _)| *a));
+
+        let public_inputs = prog.public_arguments_count();
+        let private_inputs = prog.private_arguments_count();
+
+        let mut indices: HashMap<FlatVariable, usize> =
+            wires.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        let mut terms = |lin: &LinComb<T>| -> Terms<T> {
+            let mut terms: Terms<T> = lin
+                .0
+                .iter()
+                .filter(|(_, c)| **c != T::zero())
+                .map(|(v, c)| {
+                    let index = indices.len();
+                    let index = *indices.entry(*v).or_insert_with(|| {
+                        wires.push(*v);
+                        index
+                    });
+                    (index, c.clone())
+                })
+                .collect();
+            terms.sort_by_key(|(index, _)| *index);
+            terms
+        };
+
+        let constraints = prog
+            .main
+            .statements
+            .iter()
+            .filter_map(|s| match *s {
+                Statement::Constraint(ref quad, ref lin, _) => {
+                    Some((terms(&quad.left), terms(&quad.right), terms(lin)))
+                }
+                Statement::Directive(..) => None,
+            })
+            .collect();
+
+        R1CS {
+            wires,
+            public_outputs: outputs,
+            public_inputs,
+            private_inputs,
+            constraints,
+        }
+    }
+
+    /// Writes the constraint system in the binary `.r1cs` format
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&3u32.to_le_bytes())?;
+
+        let mut header = vec![];
+        header.extend(&(field_size::<T>() as u32).to_le_bytes());
+        header.extend(prime::<T>().to_bytes_le());
+        header.resize(4 + field_size::<T>(), 0);
+        header.extend(&(self.wires.len() as u32).to_le_bytes());
+        header.extend(&(self.public_outputs as u32).to_le_bytes());
+        header.extend(&(self.public_inputs as u32).to_le_bytes());
+        header.extend(&(self.private_inputs as u32).to_le_bytes());
+        header.extend(&(self.wires.len() as u64).to_le_bytes());
+        header.extend(&(self.constraints.len() as u32).to_le_bytes());
+        write_section(writer, HEADER_SECTION, &header)?;
+
+        let mut constraints = vec![];
+        for &(ref a, ref b, ref c) in &self.constraints {
+            for terms in &[a, b, c] {
+                constraints.extend(&(terms.len() as u32).to_le_bytes());
+                for (wire, coefficient) in terms.iter() {
+                    constraints.extend(&(*wire as u32).to_le_bytes());
+                    constraints.extend(field_bytes(coefficient));
+                }
+            }
+        }
+        write_section(writer, CONSTRAINTS_SECTION, &constraints)?;
+
+        let map: Vec<u8> = (0..self.wires.len() as u64)
+            .flat_map(|label| label.to_le_bytes().to_vec())
+            .collect();
+        write_section(writer, MAP_SECTION, &map)
+    }
+
+    /// Writes the constraint system in the JSON format of `snarkjs r1cs export json`
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let terms = |terms: &Terms<T>| -> BTreeMap<String, String> {
+            terms
+                .iter()
+                .map(|(wire, c)| (wire.to_string(), c.to_dec_string()))
+                .collect()
+        };
+
+        let json = JsonR1CS {
+            n8: field_size::<T>(),
+            prime: prime::<T>().to_string(),
+            n_vars: self.wires.len(),
+            n_outputs: self.public_outputs,
+            n_pub_inputs: self.public_inputs,
+            n_prv_inputs: self.private_inputs,
+            n_labels: self.wires.len(),
+            n_constraints: self.constraints.len(),
+            constraints: self
+                .constraints
+                .iter()
+                .map(|&(ref a, ref b, ref c)| vec![terms(a), terms(b), terms(c)])
+                .collect(),
+            map: (0..self.wires.len()).collect(),
+            variables: self.wires.iter().map(|v| v.to_string()).collect(),
+        };
+
+        serde_json::to_writer_pretty(writer, &json)?;
+        Ok(())
+    }
+}
+
+fn write_section<W: Write>(writer: &mut W, section: u32, content: &[u8]) -> io::Result<()> {
+    writer.write_all(&section.to_le_bytes())?;
+    writer.write_all(&(content.len() as u64).to_le_bytes())?;
+    writer.write_all(content)
+}
+
+// the number of bytes of a field element, rounded up to 64 bit words
+fn field_size<T: Field>() -> usize {
+    (T::get_required_bits() + 63) / 64 * 8
+}
+
+fn prime<T: Field>() -> BigUint {
+    BigUint::from_bytes_le(&T::max_value().into_byte_vector()) + BigUint::one()
+}
+
+fn field_bytes<T: Field>(e: &T) -> Vec<u8> {
+    let mut bytes = e.into_byte_vector();
+    bytes.resize(field_size::<T>(), 0);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::expression::QuadComb;
+    use ir::Function;
+    use zokrates_field::field::FieldPrime;
+
+    // def main(field a, private field b) -> (field): return a * b
+    fn program() -> Prog<FieldPrime> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                statements: vec![
+                    Statement::Constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(1).into(),
+                        ),
+                        FlatVariable::new(2).into(),
+                        None,
+                    ),
+                    Statement::Constraint(
+                        LinComb::from(FlatVariable::new(2)).into(),
+                        FlatVariable::public(0).into(),
+                        None,
+                    ),
+                ],
+                arguments: vec![FlatVariable::new(1), FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0).into()],
+            },
+            private: vec![true, false],
+        }
+    }
+
+    #[test]
+    fn wires() {
+        let r1cs = R1CS::from_program(&program());
+
+        assert_eq!(
+            r1cs.wires,
+            vec![
+                FlatVariable::one(),
+                FlatVariable::public(0),
+                FlatVariable::new(0),
+                FlatVariable::new(1),
+                FlatVariable::new(2),
+            ]
+        );
+        assert_eq!(r1cs.public_inputs, 1);
+        assert_eq!(r1cs.private_inputs, 1);
+        assert_eq!(
+            r1cs.constraints[0],
+            (
+                vec![(2, FieldPrime::from(1))],
+                vec![(3, FieldPrime::from(1))],
+                vec![(4, FieldPrime::from(1))]
+            )
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let mut bytes = vec![];
+        R1CS::from_program(&program()).write(&mut bytes).unwrap();
+
+        assert_eq!(&bytes[0..4], b"r1cs");
+        assert_eq!(&bytes[4..12], &[1, 0, 0, 0, 3, 0, 0, 0]);
+
+        // header section, of 32 + 32 bytes
+        assert_eq!(&bytes[12..24], &[1, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(&bytes[24..28], &[32, 0, 0, 0]);
+        assert_eq!(
+            BigUint::from_bytes_le(&bytes[28..60]).to_string(),
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        );
+        // 5 wires, 1 output, 1 public input, 1 private input, 5 labels and 2 constraints
+        assert_eq!(
+            &bytes[60..88],
+            &[5, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0]
+        );
+
+        // constraints section, with 6 combinations of a single term of 4 + 32 bytes
+        let size = 6 * (4 + 4 + 32);
+        assert_eq!(&bytes[88..92], &[2, 0, 0, 0]);
+        assert_eq!(&bytes[92..100], &(size as u64).to_le_bytes());
+
+        // map section
+        let map = 100 + size;
+        assert_eq!(&bytes[map..map + 12], &[3, 0, 0, 0, 40, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes.len(), map + 12 + 40);
+    }
+
+    #[test]
+    fn json() {
+        let mut bytes = vec![];
+        R1CS::from_program(&program())
+            .write_json(&mut bytes)
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["nVars"], 5);
+        assert_eq!(json["nPubInputs"], 1);
+        assert_eq!(json["constraints"][1][0]["0"], "1");
+        assert_eq!(json["constraints"][1][2]["1"], "1");
+        assert_eq!(json["variables"][1], "~out_0");
+    }
+}
@@ -0,0 +1,300 @@
+//! Detection of the variables computed by directives which the constraints of a program do not
+//! pin down, letting a prover choose their value freely.
+
+use flat_absy::{FlatVariable, Span};
+use ir::expression::{LinComb, QuadComb};
+use ir::{Prog, Statement};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use zokrates_field::field::Field;
+
+/// A variable computed by a directive which is not determined by the constraints it appears in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Underconstrained {
+    pub variable: FlatVariable,
+    /// The helper of the directive computing the variable
+    pub helper: String,
+    pub span: Option<Span>,
+    /// The number of constraints the variable appears in
+    pub constraints: usize,
+}
+
+impl fmt::Display for Underconstrained {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} computed by {}{} appears in {} constraint{} which do not determine it",
+            self.variable,
+            self.helper,
+            self.span
+                .as_ref()
+                .map(|s| format!(" at {}", s))
+                .unwrap_or(String::new()),
+            self.constraints,
+            if self.constraints == 1 { "" } else { "s" }
+        )
+    }
+}
+
+impl<T: Field> Prog<T> {
+    /// Returns the variables computed by directives which appear in constraints, but are not
+    /// determined by them given the arguments of the program.
+    ///
+    /// Variables are determined one constraint at a time, starting from the arguments: a
+    /// constraint determines its only unknown variable if it is linear in it, and a linear
+    /// combination of unknown boolean variables with distinct coefficients, such as a bit
+    /// decomposition, determines all of them. This is an approximation, which assumes that
+    /// known factors are not zero and reports variables which are only determined by several
+    /// constraints taken together.
+    pub fn underconstrained(&self) -> Vec<Underconstrained> {
+        let constraints: Vec<(&QuadComb<T>, &LinComb<T>)> = self
+            .main
+            .statements
+            .iter()
+            .filter_map(|s| match *s {
+                Statement::Constraint(ref quad, ref lin, _) => Some((quad, lin)),
+                Statement::Directive(..) => None,
+            })
+            .collect();
+
+        let boolean: HashSet<FlatVariable> = constraints
+            .iter()
+            .filter_map(|&(quad, lin)| booleanity(quad, lin))
+            .collect();
+
+        // the constraints each variable appears in
+        let mut occurrences: HashMap<FlatVariable, Vec<usize>> = HashMap::new();
+        for (i, &(quad, lin)) in constraints.iter().enumerate() {
+            for v in variables(&quad.left)
+                .chain(variables(&quad.right))
+                .chain(variables(lin))
+            {
+                let occurrences = occurrences.entry(v).or_insert_with(Vec::new);
+                if occurrences.last() != Some(&i) {
+                    occurrences.push(i);
+                }
+            }
+        }
+
+        let mut known: HashSet<FlatVariable> = self.main.arguments.iter().cloned().collect();
+        known.insert(FlatVariable::one());
+
+        // constraints are visited again whenever one of their variables gets determined
+        let mut pending: VecDeque<usize> = (0..constraints.len()).collect();
+        let mut queued = vec![true; constraints.len()];
+
+        while let Some(i) = pending.pop_front() {
+            queued[i] = false;
+            let (quad, lin) = constraints[i];
+
+            for v in determined(quad, lin, &known, &boolean) {
+                known.insert(v);
+                for &j in &occurrences[&v] {
+                    if !queued[j] {
+                        queued[j] = true;
+                        pending.push_back(j);
+                    }
+                }
+            }
+        }
+
+        self.main
+            .statements
+            .iter()
+            .filter_map(|s| match *s {
+                Statement::Directive(ref d) => Some(d),
+                Statement::Constraint(..) => None,
+            })
+            .flat_map(|d| {
+                d.outputs
+                    .iter()
+                    // outputs which appear in no constraint do not take part in the proof
+                    .filter(|o| !known.contains(o))
+                    .filter_map(|o| occurrences.get(o).map(|c| (o, c.len())))
+                    .map(|(o, constraints)| Underconstrained {
+                        variable: *o,
+                        helper: format!("{}", d.helper),
+                        span: d.span.clone(),
+                        constraints,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+fn variables<'a, T: Field>(lin: &'a LinComb<T>) -> impl Iterator<Item = FlatVariable> + 'a {
+    lin.0
+        .iter()
+        .filter(|(_, c)| **c != T::zero())
+        .map(|(v, _)| *v)
+}
+
+fn coefficient<T: Field>(lin: &LinComb<T>, v: &FlatVariable) -> T {
+    lin.0.get(v).cloned().unwrap_or(T::zero())
+}
+
+// the value of `lin` if it only depends on ~one
+fn constant<T: Field>(lin: &LinComb<T>) -> Option<T> {
+    match variables(lin).all(|v| v == FlatVariable::one()) {
+        true => Some(coefficient(lin, &FlatVariable::one())),
+        false => None,
+    }
+}
+
+// the variable of a constraint which only holds if it is 0 or 1, such as `b * b == b`
+fn booleanity<T: Field>(quad: &QuadComb<T>, lin: &LinComb<T>) -> Option<FlatVariable> {
+    let mut vars: Vec<_> = variables(&quad.left)
+        .chain(variables(&quad.right))
+        .chain(variables(lin))
+        .filter(|v| *v != FlatVariable::one())
+        .collect();
+    vars.sort();
+    vars.dedup();
+
+    let b = match vars.as_slice() {
+        [b] => *b,
+        _ => return None,
+    };
+
+    let one = FlatVariable::one();
+    let (a1, a0) = (coefficient(&quad.left, &b), coefficient(&quad.left, &one));
+    let (b1, b0) = (coefficient(&quad.right, &b), coefficient(&quad.right, &one));
+    let (c1, c0) = (coefficient(lin, &b), coefficient(lin, &one));
+
+    // (a1 * b + a0) * (b1 * b + b0) - (c1 * b + c0) vanishes on 0 and 1 only
+    let square = a1.clone() * &b1;
+    let linear = a1 * &b0 + a0.clone() * &b1 - c1;
+    let constant = a0 * &b0 - c0;
+
+    match square != T::zero() && constant == T::zero() && square + linear == T::zero() {
+        true => Some(b),
+        false => None,
+    }
+}
+
+// the unknown variables of a constraint which it determines
+fn determined<T: Field>(
+    quad: &QuadComb<T>,
+    lin: &LinComb<T>,
+    known: &HashSet<FlatVariable>,
+    boolean: &HashSet<FlatVariable>,
+) -> Vec<FlatVariable> {
+    let unknown = |lin: &LinComb<T>| -> Vec<FlatVariable> {
+        variables(lin).filter(|v| !known.contains(v)).collect()
+    };
+
+    let (left, right, output) = (unknown(&quad.left), unknown(&quad.right), unknown(lin));
+
+    let mut all: Vec<_> = left.iter().chain(&right).chain(&output).cloned().collect();
+    all.sort();
+    all.dedup();
+
+    // variables on both sides of the product appear squared
+    if !left.is_empty() && !right.is_empty() {
+        return vec![];
+    }
+
+    if all.len() <= 1 {
+        return all;
+    }
+
+    if !all.iter().all(|v| boolean.contains(v)) {
+        return vec![];
+    }
+
+    // the other side of the product has to be constant for the constraint to be linear
+    let (factor, side) = match left.is_empty() {
+        true => (constant(&quad.left), &quad.right),
+        false => (constant(&quad.right), &quad.left),
+    };
+
+    let factor = match factor {
+        Some(factor) => factor,
+        None => return vec![],
+    };
+
+    let coefficients: Vec<T> = all
+        .iter()
+        .map(|v| factor.clone() * &coefficient(side, v) - coefficient(lin, v))
+        .collect();
+
+    let distinct: HashSet<&T> = coefficients.iter().collect();
+
+    match distinct.len() == coefficients.len() && !distinct.contains(&T::zero()) {
+        true => all,
+        false => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compile::compile_str;
+    use helpers::{Helper, RustHelper};
+    use ir::{DirectiveStatement, Function};
+    use zokrates_field::field::FieldPrime;
+
+    #[test]
+    fn compiled() {
+        let program = compile_str(
+            r#"
+def main(field a, field b) -> (field):
+	field c = if a < b then a / b else 1 fi
+	field d = if a == b then 0 else c fi
+	return d
+"#,
+        )
+        .unwrap()
+        .program;
+
+        assert_eq!(program.underconstrained(), vec![]);
+    }
+
+    #[test]
+    fn unchecked_directive() {
+        // _1 is computed from _0 by a directive, which should then be checked with _1 == _0
+        let statements = vec![
+            Statement::Directive(DirectiveStatement {
+                inputs: vec![FlatVariable::new(0).into()],
+                outputs: vec![FlatVariable::new(1)],
+                helper: Helper::Rust(RustHelper::Identity),
+                span: None,
+            }),
+            Statement::Constraint(
+                LinComb::from(FlatVariable::new(1)).into(),
+                FlatVariable::public(0).into(),
+                None,
+            ),
+        ];
+
+        let program = |statements| Prog::<FieldPrime> {
+            main: Function {
+                id: String::from("main"),
+                statements,
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0).into()],
+            },
+            private: vec![true],
+        };
+
+        assert_eq!(
+            program(statements.clone()).underconstrained(),
+            vec![Underconstrained {
+                variable: FlatVariable::new(1),
+                helper: String::from("Rust::Identity"),
+                span: None,
+                constraints: 1,
+            }]
+        );
+
+        let mut checked = statements;
+        checked.push(Statement::Constraint(
+            LinComb::from(FlatVariable::new(0)).into(),
+            FlatVariable::new(1).into(),
+            None,
+        ));
+
+        assert_eq!(program(checked).underconstrained(), vec![]);
+    }
+}
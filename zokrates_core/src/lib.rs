@@ -1,5 +1,11 @@
 #![feature(box_patterns, box_syntax)]
 
+extern crate ark_bn254;
+extern crate ark_ff;
+extern crate ark_groth16;
+extern crate ark_relations;
+extern crate ark_serialize;
+extern crate rand;
 extern crate num;
 extern crate num_bigint;
 extern crate reduce; // better reduce function than Iter.fold
@@ -16,27 +22,31 @@ extern crate regex;
 extern crate rustc_hex;
 #[cfg(feature = "wasm")]
 extern crate serde_bytes;
+extern crate sha2;
 #[cfg(feature = "wasm")]
 extern crate wasmi;
 extern crate zokrates_field;
 
 mod flatten;
 mod helpers;
-mod imports;
 mod optimizer;
-mod parser;
-mod semantics;
-#[cfg(feature = "libsnark")]
+#[cfg(any(feature = "libsnark", feature = "wasm"))]
 mod standard;
 mod static_analysis;
 mod typed_absy;
 mod types;
 
+pub mod abi;
 pub mod absy;
 pub mod compile;
+pub mod diagnostics;
 pub mod flat_absy;
+pub mod format;
+pub mod imports;
 pub mod ir;
 #[cfg(feature = "libsnark")]
 pub mod libsnark;
-#[cfg(feature = "libsnark")]
+pub mod parser;
 pub mod proof_system;
+pub mod semantics;
+pub mod warnings;
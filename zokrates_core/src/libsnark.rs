@@ -82,7 +82,9 @@ mod tests {
             let r1cs: standard::R1CS = serde_json::from_str(&constraints).unwrap();
             let _prog: FlatProg<FieldPrime> = FlatProg::from(standard::DirectiveR1CS {
                 r1cs,
-                directive: helpers::LibsnarkGadgetHelper::Sha256Round,
+                helper: helpers::Helper::LibsnarkGadget(
+                    helpers::LibsnarkGadgetHelper::Sha256Round,
+                ),
             });
         }
     }
@@ -37,6 +37,11 @@ impl Optimizer {
         }
     }
 
+    /// Returns the renaming of the variables of the optimized function
+    pub fn substitution(&self) -> &HashMap<FlatVariable, FlatVariable> {
+        &self.substitution
+    }
+
     pub fn optimize_program<T: Field>(&mut self, prog: FlatProg<T>) -> FlatProg<T> {
         let optimized_program = FlatProg {
             functions: prog
@@ -158,6 +163,7 @@ mod tests {
                 inputs: vec![Type::FieldElement],
                 outputs: vec![Type::FieldElement],
             },
+            source_map: SourceMap::default(),
         };
 
         let optimized: FlatFunction<FieldPrime> = FlatFunction {
@@ -173,6 +179,7 @@ mod tests {
                 inputs: vec![Type::FieldElement],
                 outputs: vec![Type::FieldElement],
             },
+            source_map: SourceMap::default(),
         };
 
         let mut optimizer = Optimizer::new();
@@ -213,6 +220,7 @@ mod tests {
                 inputs: vec![Type::FieldElement],
                 outputs: vec![Type::FieldElement, Type::FieldElement],
             },
+            source_map: SourceMap::default(),
         };
 
         let optimized: FlatFunction<FieldPrime> = FlatFunction {
@@ -231,6 +239,7 @@ mod tests {
                 inputs: vec![Type::FieldElement],
                 outputs: vec![Type::FieldElement, Type::FieldElement],
             },
+            source_map: SourceMap::default(),
         };
 
         let mut optimizer = Optimizer::new();
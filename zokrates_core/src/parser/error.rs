@@ -10,16 +10,24 @@ pub struct Error<T: Field> {
     pub pos: Position,
 }
 
-impl<T: Field> fmt::Display for Error<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}\n\tExpected one of {:?}, got {:?}",
+impl<T: Field> Error<T> {
+    /// The span of the unexpected token, which ends at `pos`
+    pub fn span(&self) -> (Position, Position) {
+        (
             self.pos.col(-(self.got.to_string().len() as isize)),
-            self.expected,
-            self.got
+            self.pos,
         )
     }
+
+    pub fn message(&self) -> String {
+        format!("Expected one of {:?}, got {:?}", self.expected, self.got)
+    }
+}
+
+impl<T: Field> fmt::Display for Error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n\t{}", self.span().0, self.message())
+    }
 }
 
 impl<T: Field> fmt::Debug for Error<T> {
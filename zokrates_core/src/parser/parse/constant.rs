@@ -0,0 +1,105 @@
+use zokrates_field::field::Field;
+
+use parser::tokenize::{next_token, Position, Token};
+use parser::Error;
+
+use super::expression::parse_expr;
+
+use absy::{Constant, ConstantNode, Node};
+use types::Type;
+
+// parse a constant declaration such as `const field N = 42`, starting after the `const` keyword
+pub fn parse_constant<T: Field>(
+    input: &String,
+    pos: &Position,
+) -> Result<(ConstantNode<T>, Position), Error<T>> {
+    let (s, p) = match next_token::<T>(input, pos) {
+        (Token::Type(Type::FieldElement), s1, p1) => (s1, p1),
+        (t1, _, p1) => {
+            return Err(Error {
+                expected: vec![Token::Type(Type::FieldElement)],
+                got: t1,
+                pos: p1,
+            });
+        }
+    };
+
+    let (id, s, p) = match next_token::<T>(&s, &p) {
+        (Token::Ide(x), s2, p2) => (x, s2, p2),
+        (t2, _, p2) => {
+            return Err(Error {
+                expected: vec![Token::ErrIde],
+                got: t2,
+                pos: p2,
+            });
+        }
+    };
+
+    let (s, p) = match next_token::<T>(&s, &p) {
+        (Token::Eq, s3, p3) => (s3, p3),
+        (t3, _, p3) => {
+            return Err(Error {
+                expected: vec![Token::Eq],
+                got: t3,
+                pos: p3,
+            });
+        }
+    };
+
+    let (expression, s, p) = parse_expr(&s, &p)?;
+
+    match next_token::<T>(&s, &p) {
+        (Token::InlineComment(_), ..) => {}
+        (Token::Unknown(ref t5), ..) if t5 == "" => {}
+        (t5, _, p5) => {
+            return Err(Error {
+                expected: vec![Token::Unknown("".to_string())],
+                got: t5,
+                pos: p5,
+            });
+        }
+    }
+
+    Ok((Node::new(*pos, p, Constant { id, expression }), p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use absy::Expression;
+    use zokrates_field::field::FieldPrime;
+
+    #[test]
+    fn literal() {
+        let pos = Position { line: 1, col: 6 };
+        let string = String::from(" field N = 42 // the answer");
+        let (c, _) = parse_constant::<FieldPrime>(&string, &pos).unwrap();
+        assert_eq!(c.value.id, "N");
+        assert_eq!(
+            c.value.expression.value,
+            Expression::Number(FieldPrime::from(42), String::from("42"))
+        );
+    }
+
+    #[test]
+    fn expression() {
+        let pos = Position { line: 1, col: 6 };
+        let string = String::from(" field M = N * 2");
+        let (c, _) = parse_constant::<FieldPrime>(&string, &pos).unwrap();
+        assert_eq!(c.value.id, "M");
+        assert_eq!(
+            c.value.expression.value,
+            Expression::Mult(
+                box Expression::Identifier(String::from("N")).into(),
+                box Expression::Number(FieldPrime::from(2), String::from("2")).into()
+            )
+        );
+    }
+
+    #[test]
+    fn not_a_field() {
+        let pos = Position { line: 1, col: 6 };
+        let string = String::from(" bool B = 1 == 1");
+        assert!(parse_constant::<FieldPrime>(&string, &pos).is_err());
+    }
+}
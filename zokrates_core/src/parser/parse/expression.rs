@@ -49,7 +49,7 @@ fn parse_prim_cond<T: Field>(
     pos: &Position,
 ) -> Result<(ExpressionNode<T>, String, Position), Error<T>> {
     match parse_expr(input, pos) {
-        Ok((e2, s2, p2)) => match next_token(&s2, &p2) {
+        Ok((e2, s2, p2)) => match next_token::<T>(&s2, &p2) {
             (Token::Lt, s3, p3) => match parse_expr(&s3, &p3) {
                 Ok((e4, s4, p4)) => {
                     Ok((Node::new(*pos, p4, Expression::Lt(box e2, box e4)), s4, p4))
@@ -80,11 +80,8 @@ fn parse_prim_cond<T: Field>(
                 }
                 Err(err) => Err(err),
             },
-            (t3, _, p3) => Err(Error {
-                expected: vec![Token::Lt, Token::Le, Token::Eqeq, Token::Ge, Token::Gt],
-                got: t3,
-                pos: p3,
-            }),
+            // an expression which is not compared, such as a boolean variable
+            _ => Ok((e2, s2, p2)),
         },
         Err(err) => Err(err),
     }
@@ -161,7 +158,7 @@ fn parse_bexpr1<T: Field>(
     }
 }
 
-fn parse_bexpr<T: Field>(
+pub fn parse_bexpr<T: Field>(
     input: &String,
     pos: &Position,
 ) -> Result<(ExpressionNode<T>, String, Position), Error<T>> {
@@ -209,13 +206,15 @@ fn parse_bexpr<T: Field>(
                 Err(err) => Err(err),
             },
         },
-        (Token::Ide(_), _, _) | (Token::Num(_), _, _) => match parse_prim_cond(input, pos) {
-            Ok((e2, s2, p2)) => match parse_bterm1(e2, s2, p2) {
-                Ok((e3, s3, p3)) => parse_bexpr1(e3, s3, p3),
+        (Token::Ide(_), _, _) | (Token::Num(..), _, _) | (Token::HexNum(_), _, _) => {
+            match parse_prim_cond(input, pos) {
+                Ok((e2, s2, p2)) => match parse_bterm1(e2, s2, p2) {
+                    Ok((e3, s3, p3)) => parse_bexpr1(e3, s3, p3),
+                    Err(err) => Err(err),
+                },
                 Err(err) => Err(err),
-            },
-            Err(err) => Err(err),
-        },
+            }
+        }
         (t1, _, p1) => Err(Error {
             expected: vec![Token::Open, Token::ErrIde, Token::ErrNum],
             got: t1,
@@ -250,11 +249,14 @@ fn parse_factor1<T: Field>(
         Ok((e1, s1, p1)) => match parse_expr1(e1, s1, p1) {
             Ok((e2, s2, p2)) => match next_token::<T>(&s2, &p2) {
                 (Token::Pow, s3, p3) => match next_token(&s3, &p3) {
-                    (Token::Num(x), s4, p4) => Ok((
+                    (Token::Num(x, source), s4, p4) => Ok((
                         Node::new(
                             pos,
                             p4,
-                            Expression::Pow(box e2, box Node::new(p3, p4, Expression::Number(x))),
+                            Expression::Pow(
+                                box e2,
+                                box Node::new(p3, p4, Expression::Number(x, source)),
+                            ),
                         ),
                         s4,
                         p4,
@@ -286,11 +288,119 @@ fn parse_identified1<T: Field>(
     match next_token::<T>(&input, &position) {
         (Token::Open, s1, p1) => parse_function_call(x, s1, p1),
         (Token::LeftBracket, s1, p1) => parse_array_select(x, s1, p1),
-        _ => Ok((
+        (Token::LeftBrace, s1, p1) => parse_inline_struct(x, s1, p1),
+        _ => parse_accesses(
             Node::new(ide_initial_pos, position, Expression::Identifier(x)),
             input,
             position,
-        )),
+        ),
+    }
+}
+
+// parse a (possibly empty) chain of member accesses and selects such as `.a[1].b` following an expression
+pub fn parse_accesses<T: Field>(
+    expr: ExpressionNode<T>,
+    input: String,
+    pos: Position,
+) -> Result<(ExpressionNode<T>, String, Position), Error<T>> {
+    match next_token::<T>(&input, &pos) {
+        (Token::Dot, s1, p1) => match next_token::<T>(&s1, &p1) {
+            (Token::Ide(id), s2, p2) => parse_accesses(
+                Node::new(expr.start, p2, Expression::Member(box expr, id)),
+                s2,
+                p2,
+            ),
+            (t2, _, p2) => Err(Error {
+                expected: vec![Token::ErrIde],
+                got: t2,
+                pos: p2,
+            }),
+        },
+        (Token::LeftBracket, s1, p1) => {
+            let (e2, s2, p2) = parse_expr(&s1, &p1)?;
+            match next_token::<T>(&s2, &p2) {
+                (Token::RightBracket, s3, p3) => parse_accesses(
+                    Node::new(expr.start, p3, Expression::Select(box expr, box e2)),
+                    s3,
+                    p3,
+                ),
+                (t3, _, p3) => Err(Error {
+                    expected: vec![Token::RightBracket],
+                    got: t3,
+                    pos: p3,
+                }),
+            }
+        }
+        _ => Ok((expr, input, pos)),
+    }
+}
+
+// parse the members of an inline struct such as `Foo { a: 1, b: 2 }`, starting after the `{`
+pub fn parse_inline_struct<T: Field>(
+    ide: String,
+    input: String,
+    pos: Position,
+) -> Result<(ExpressionNode<T>, String, Position), Error<T>> {
+    // backtrack to the beginning of the struct identifier, plus one because we're after the `{`
+    let start_pos = Position {
+        col: pos.col - ide.len() - 1,
+        ..pos
+    };
+
+    let mut members = Vec::new();
+    let mut s: String = input;
+    let mut p: Position = pos;
+
+    loop {
+        match next_token::<T>(&s, &p) {
+            (Token::RightBrace, s1, p1) => {
+                return Ok((
+                    Node::new(start_pos, p1, Expression::InlineStruct(ide, members)),
+                    s1,
+                    p1,
+                ));
+            }
+            (Token::Ide(id), s1, p1) => match next_token::<T>(&s1, &p1) {
+                (Token::Colon, s2, p2) => {
+                    let (e3, s3, p3) = parse_expr(&s2, &p2)?;
+                    members.push((id, e3));
+                    match next_token::<T>(&s3, &p3) {
+                        (Token::Comma, s4, p4) => {
+                            s = s4;
+                            p = p4;
+                        }
+                        (Token::RightBrace, s4, p4) => {
+                            return Ok((
+                                Node::new(start_pos, p4, Expression::InlineStruct(ide, members)),
+                                s4,
+                                p4,
+                            ));
+                        }
+                        (t4, _, p4) => {
+                            return Err(Error {
+                                expected: vec![Token::Comma, Token::RightBrace],
+                                got: t4,
+                                pos: p4,
+                            });
+                        }
+                    }
+                }
+                (t2, _, p2) => {
+                    return Err(Error {
+                        expected: vec![Token::Colon],
+                        got: t2,
+                        pos: p2,
+                    });
+                }
+            },
+            (t1, _, p1) => {
+                return Err(Error {
+                    expected: vec![Token::ErrIde, Token::RightBrace],
+                    got: t1,
+                    pos: p1,
+                });
+            }
+        }
     }
 }
 
@@ -315,8 +425,11 @@ fn parse_factor<T: Field>(
             Ok((e2, s2, p2)) => parse_factor1(e2, s2, p2),
             e => e,
         },
-        (Token::Num(x), s1, p1) => {
-            parse_factor1(Node::new(*pos, p1, Expression::Number(x)), s1, p1)
+        (Token::Num(x, source), s1, p1) => {
+            parse_factor1(Node::new(*pos, p1, Expression::Number(x, source)), s1, p1)
+        }
+        (Token::HexNum(x), s1, p1) => {
+            parse_factor1(Node::new(*pos, p1, Expression::HexNumber(x)), s1, p1)
         }
         (t1, _, p1) => Err(Error {
             expected: vec![Token::If, Token::Open, Token::ErrIde, Token::ErrNum],
@@ -384,6 +497,46 @@ pub fn parse_expr1<T: Field>(
             ),
             Err(err) => Err(err),
         },
+        (Token::BitAnd, s1, p1) => match parse_term(&s1, &p1) {
+            Ok((e2, s2, p2)) => parse_expr1(
+                Node::new(expr.start, p2, Expression::BitAnd(box expr, box e2)),
+                s2,
+                p2,
+            ),
+            Err(err) => Err(err),
+        },
+        (Token::BitOr, s1, p1) => match parse_term(&s1, &p1) {
+            Ok((e2, s2, p2)) => parse_expr1(
+                Node::new(expr.start, p2, Expression::BitOr(box expr, box e2)),
+                s2,
+                p2,
+            ),
+            Err(err) => Err(err),
+        },
+        (Token::BitXor, s1, p1) => match parse_term(&s1, &p1) {
+            Ok((e2, s2, p2)) => parse_expr1(
+                Node::new(expr.start, p2, Expression::BitXor(box expr, box e2)),
+                s2,
+                p2,
+            ),
+            Err(err) => Err(err),
+        },
+        (Token::LeftShift, s1, p1) => match parse_term(&s1, &p1) {
+            Ok((e2, s2, p2)) => parse_expr1(
+                Node::new(expr.start, p2, Expression::LeftShift(box expr, box e2)),
+                s2,
+                p2,
+            ),
+            Err(err) => Err(err),
+        },
+        (Token::RightShift, s1, p1) => match parse_term(&s1, &p1) {
+            Ok((e2, s2, p2)) => parse_expr1(
+                Node::new(expr.start, p2, Expression::RightShift(box expr, box e2)),
+                s2,
+                p2,
+            ),
+            Err(err) => Err(err),
+        },
         (Token::Pow, s1, p1) => match parse_term(&s1, &p1) {
             Ok((e, s2, p2)) => match parse_term1(
                 Node::new(expr.start, p2, Expression::Pow(box expr, box e)),
@@ -526,18 +679,22 @@ pub fn parse_array_select<T: Field>(
     match next_token::<T>(&input, &pos) {
         (_, _, _) => match parse_expr(&input, &pos) {
             Ok((e1, s1, p1)) => match next_token::<T>(&s1, &p1) {
-                (Token::RightBracket, s2, p2) => parse_term1(
-                    Node::new(
-                        pos,
-                        p2,
-                        Expression::Select(
-                            box Node::new(start_pos, p1, Expression::Identifier(ide)),
-                            box e1,
+                (Token::RightBracket, s2, p2) => {
+                    // further dimensions and members, such as `a[0][1].b`
+                    let (e2, s2, p2) = parse_accesses(
+                        Node::new(
+                            pos,
+                            p2,
+                            Expression::Select(
+                                box Node::new(start_pos, p1, Expression::Identifier(ide)),
+                                box e1,
+                            ),
                         ),
-                    ),
-                    s2,
-                    p2,
-                ),
+                        s2,
+                        p2,
+                    )?;
+                    parse_term1(e2, s2, p2)
+                }
                 (t2, _, p2) => Err(Error {
                     expected: vec![Token::RightBracket],
                     got: t2,
@@ -576,8 +733,14 @@ pub fn parse_expr<T: Field>(
             },
             e => e,
         },
-        (Token::Num(x), s1, p1) => {
-            match parse_term1(Node::new(*pos, p1, Expression::Number(x)), s1, p1) {
+        (Token::Num(x, source), s1, p1) => {
+            match parse_term1(Node::new(*pos, p1, Expression::Number(x, source)), s1, p1) {
+                Ok((e2, s2, p2)) => parse_expr1(e2, s2, p2),
+                Err(err) => Err(err),
+            }
+        }
+        (Token::HexNum(x), s1, p1) => {
+            match parse_term1(Node::new(*pos, p1, Expression::HexNumber(x)), s1, p1) {
                 Ok((e2, s2, p2)) => parse_expr1(e2, s2, p2),
                 Err(err) => Err(err),
             }
@@ -605,11 +768,11 @@ mod tests {
             let string = String::from("1 + 2 + 3");
             let expr = Expression::Add(
                 box Expression::Add(
-                    box Expression::Number(FieldPrime::from(1)).into(),
-                    box Expression::Number(FieldPrime::from(2)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
+                    box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -624,11 +787,11 @@ mod tests {
             let string = String::from("1 - 2 - 3");
             let expr = Expression::Sub(
                 box Expression::Sub(
-                    box Expression::Number(FieldPrime::from(1)).into(),
-                    box Expression::Number(FieldPrime::from(2)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
+                    box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -642,7 +805,7 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             let string = String::from("1 - f(a)");
             let expr = Expression::Sub(
-                box Expression::Number(FieldPrime::from(1)).into(),
+                box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                 box Expression::FunctionCall(
                     String::from("f"),
                     vec![Expression::Identifier(String::from("a")).into()],
@@ -662,11 +825,11 @@ mod tests {
             let string = String::from("1 - f() - 3");
             let expr = Expression::Sub(
                 box Expression::Sub(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::FunctionCall(String::from("f"), vec![]).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -681,11 +844,11 @@ mod tests {
             let string = String::from("1 + f() + 3");
             let expr = Expression::Add(
                 box Expression::Add(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::FunctionCall(String::from("f"), vec![]).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -700,15 +863,15 @@ mod tests {
             let string = String::from("1 - f[2] - 3");
             let expr = Expression::Sub(
                 box Expression::Sub(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::Select(
                         box Expression::Identifier(String::from("f")).into(),
-                        box Expression::Number(FieldPrime::from(2)).into(),
+                        box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
                     )
                     .into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -723,15 +886,38 @@ mod tests {
             let string = String::from("1 + f[2] + 3");
             let expr = Expression::Add(
                 box Expression::Add(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::Select(
                         box Expression::Identifier(String::from("f")).into(),
-                        box Expression::Number(FieldPrime::from(2)).into(),
+                        box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
+                    )
+                    .into(),
+                )
+                .into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
+            )
+            .into();
+            assert_eq!(
+                Ok((expr, String::from(""), pos.col(string.len() as isize))),
+                parse_expr(&string, &pos)
+            );
+        }
+
+        #[test]
+        fn parse_bitwise() {
+            let pos = Position { line: 45, col: 121 };
+            let string = String::from("a & 0xff ^ b << 3");
+            let expr = Expression::LeftShift(
+                box Expression::BitXor(
+                    box Expression::BitAnd(
+                        box Expression::Identifier(String::from("a")).into(),
+                        box Expression::HexNumber(String::from("ff")).into(),
                     )
                     .into(),
+                    box Expression::Identifier(String::from("b")).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -746,11 +932,11 @@ mod tests {
             let string = String::from("1 - f - 3");
             let expr = Expression::Sub(
                 box Expression::Sub(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::Identifier(String::from("f")).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -765,11 +951,11 @@ mod tests {
             let string = String::from("1 + f + 3");
             let expr = Expression::Add(
                 box Expression::Add(
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                     box Expression::Identifier(String::from("f")).into(),
                 )
                 .into(),
-                box Expression::Number(FieldPrime::from(3)).into(),
+                box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into();
             assert_eq!(
@@ -814,7 +1000,7 @@ mod tests {
                 box Expression::And(
                     box Expression::Gt(
                         box Expression::Mult(
-                            box Expression::Number(FieldPrime::from(2)).into(),
+                            box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
                             box Expression::Identifier(String::from("a")).into(),
                         )
                         .into(),
@@ -851,8 +1037,8 @@ mod tests {
             let expr = Expression::Select::<FieldPrime>(
                 box Expression::Identifier(String::from("foo")).into(),
                 box Expression::Add(
-                    box Expression::Number(FieldPrime::from(42)).into(),
-                    box Expression::Number(FieldPrime::from(33)).into(),
+                    box Expression::Number(FieldPrime::from(42), String::from("42")).into(),
+                    box Expression::Number(FieldPrime::from(33), String::from("33")).into(),
                 )
                 .into(),
             )
@@ -891,7 +1077,7 @@ mod tests {
                 .into(),
                 box Expression::Gt(
                     box Expression::Mult(
-                        box Expression::Number(FieldPrime::from(2)).into(),
+                        box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
                         box Expression::Identifier(String::from("a")).into(),
                     )
                     .into(),
@@ -917,19 +1103,19 @@ mod tests {
         let string = String::from("2 == 3 || 4 == 5 && 6 == 7");
         let expr = Or::<FieldPrime>(
             box Eq(
-                box Number(FieldPrime::from(2)).into(),
-                box Number(FieldPrime::from(3)).into(),
+                box Number(FieldPrime::from(2), String::from("2")).into(),
+                box Number(FieldPrime::from(3), String::from("3")).into(),
             )
             .into(),
             box And(
                 box Eq(
-                    box Number(FieldPrime::from(4)).into(),
-                    box Number(FieldPrime::from(5)).into(),
+                    box Number(FieldPrime::from(4), String::from("4")).into(),
+                    box Number(FieldPrime::from(5), String::from("5")).into(),
                 )
                 .into(),
                 box Eq(
-                    box Number(FieldPrime::from(6)).into(),
-                    box Number(FieldPrime::from(7)).into(),
+                    box Number(FieldPrime::from(6), String::from("6")).into(),
+                    box Number(FieldPrime::from(7), String::from("7")).into(),
                 )
                 .into(),
             )
@@ -952,10 +1138,10 @@ mod tests {
                 box Eq(
                     box Add(
                         box Identifier(String::from("a")).into(),
-                        box Number(FieldPrime::from(2)).into(),
+                        box Number(FieldPrime::from(2), String::from("2")).into(),
                     )
                     .into(),
-                    box Number(FieldPrime::from(3)).into(),
+                    box Number(FieldPrime::from(3), String::from("3")).into(),
                 )
                 .into(),
                 box Or(
@@ -963,18 +1149,18 @@ mod tests {
                         box Add(
                             box Mult(
                                 box Identifier(String::from("a")).into(),
-                                box Number(FieldPrime::from(2)).into(),
+                                box Number(FieldPrime::from(2), String::from("2")).into(),
                             )
                             .into(),
-                            box Number(FieldPrime::from(3)).into(),
+                            box Number(FieldPrime::from(3), String::from("3")).into(),
                         )
                         .into(),
-                        box Number(FieldPrime::from(2)).into(),
+                        box Number(FieldPrime::from(2), String::from("2")).into(),
                     )
                     .into(),
                     box Lt(
                         box Identifier(String::from("a")).into(),
-                        box Number(FieldPrime::from(3)).into(),
+                        box Number(FieldPrime::from(3), String::from("3")).into(),
                     )
                     .into(),
                 )
@@ -982,8 +1168,8 @@ mod tests {
             )
             .into(),
             box Lt(
-                box Number(FieldPrime::from(1)).into(),
-                box Number(FieldPrime::from(2)).into(),
+                box Number(FieldPrime::from(1), String::from("1")).into(),
+                box Number(FieldPrime::from(2), String::from("2")).into(),
             )
             .into(),
         )
@@ -1020,10 +1206,10 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             let string = String::from("(5 + a * 6)");
             let expr = Expression::Add(
-                box Expression::Number(FieldPrime::from(5)).into(),
+                box Expression::Number(FieldPrime::from(5), String::from("5")).into(),
                 box Expression::Mult(
                     box Expression::Identifier(String::from("a")).into(),
-                    box Expression::Number(FieldPrime::from(6)).into(),
+                    box Expression::Number(FieldPrime::from(6), String::from("6")).into(),
                 )
                 .into(),
             )
@@ -1047,7 +1233,7 @@ mod tests {
         fn num() {
             let pos = Position { line: 45, col: 121 };
             let string = String::from("234");
-            let expr = Expression::Number(FieldPrime::from(234)).into();
+            let expr = Expression::Number(FieldPrime::from(234), String::from("234")).into();
             assert_eq!(
                 Ok((expr, String::from(""), pos.col(string.len() as isize))),
                 parse_factor(&string, &pos)
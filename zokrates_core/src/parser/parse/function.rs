@@ -2,11 +2,13 @@ use zokrates_field::field::Field;
 
 use std::io::prelude::*;
 use std::io::Lines;
+use std::iter::Peekable;
 
 use parser::tokenize::{next_token, Position, Token};
 use parser::Error;
 
-use super::statement::parse_statement;
+use super::statement::{is_declaration, parse_statement};
+use super::struct_definition::parse_struct_type;
 
 use absy::{
     Function, FunctionNode, Node, Parameter, ParameterNode, Statement, Variable, VariableNode,
@@ -125,19 +127,24 @@ fn parse_function_argument_variable<T: Field>(
     let s4 = input;
     let p4 = pos;
 
-    match next_token::<T>(&s4, &p4) {
-        (Token::Type(t), s5, p5) => match next_token(&s5, &p5) {
-            (Token::Ide(x), s6, p6) => Ok((Node::new(*pos, p6, Variable::new(x, t)), s6, p6)),
-            (t6, _, p6) => Err(Error {
-                expected: vec![Token::Ide(String::from("identifier"))],
-                got: t6,
-                pos: p6,
-            }),
-        },
-        (t5, _, p5) => Err(Error {
-            expected: vec![Token::Type(Type::FieldElement)],
-            got: t5,
-            pos: p5,
+    let (t, s5, p5) = match next_token::<T>(&s4, &p4) {
+        (Token::Type(t), s5, p5) => (t, s5, p5),
+        (Token::Ide(x), s5, p5) => parse_struct_type::<T>(x, s5, p5),
+        (t5, _, p5) => {
+            return Err(Error {
+                expected: vec![Token::Type(Type::FieldElement)],
+                got: t5,
+                pos: p5,
+            });
+        }
+    };
+
+    match next_token(&s5, &p5) {
+        (Token::Ide(x), s6, p6) => Ok((Node::new(*pos, p6, Variable::new(x, t)), s6, p6)),
+        (t6, _, p6) => Err(Error {
+            expected: vec![Token::Ide(String::from("identifier"))],
+            got: t6,
+            pos: p6,
         }),
     }
 }
@@ -170,7 +177,7 @@ fn parse_function_arguments<T: Field>(
                     }
                 }
             }
-            (Token::Type(_), _, _) => {
+            (Token::Type(_), _, _) | (Token::Ide(_), _, _) => {
                 let (var, s2, p2) = parse_function_argument_variable::<T>(&s, &p)?;
                 args.push(Node::new(p, p2, Parameter::public(var)));
                 match next_token::<T>(&s2, &p2) {
@@ -213,24 +220,9 @@ fn parse_function_return_types<T: Field>(
     let mut p = pos;
 
     loop {
-        match next_token(&s, &p) {
-            (Token::Type(t), s1, p1) => {
-                types.push(t);
-                match next_token::<T>(&s1, &p1) {
-                    (Token::Comma, s3, p3) => {
-                        s = s3;
-                        p = p3;
-                    }
-                    (Token::Close, _, _) => return Ok((types, s1, p1)),
-                    (t3, _, p3) => {
-                        return Err(Error {
-                            expected: vec![Token::Comma, Token::Close],
-                            got: t3,
-                            pos: p3,
-                        });
-                    }
-                }
-            }
+        let (t, s1, p1) = match next_token(&s, &p) {
+            (Token::Type(t), s1, p1) => (t, s1, p1),
+            (Token::Ide(x), s1, p1) => parse_struct_type::<T>(x, s1, p1),
             (Token::Close, _, _) => return Ok((vec![], s, p)),
             (t4, _, p4) => {
                 return Err(Error {
@@ -243,24 +235,74 @@ fn parse_function_return_types<T: Field>(
                     pos: p4,
                 });
             }
+        };
+
+        types.push(t);
+        match next_token::<T>(&s1, &p1) {
+            (Token::Comma, s3, p3) => {
+                s = s3;
+                p = p3;
+            }
+            (Token::Close, _, _) => return Ok((types, s1, p1)),
+            (t3, _, p3) => {
+                return Err(Error {
+                    expected: vec![Token::Comma, Token::Close],
+                    got: t3,
+                    pos: p3,
+                });
+            }
         }
     }
 }
 
 pub fn parse_function<T: Field, R: BufRead>(
-    mut lines: &mut Lines<R>,
+    mut lines: &mut Peekable<Lines<R>>,
     input: &String,
     pos: &Position,
-) -> Result<(FunctionNode<T>, Position), Error<T>> {
+) -> Result<(FunctionNode<T>, Position), (Vec<Error<T>>, Position)> {
     let mut current_line = pos.line;
+    let mut errors = Vec::new();
 
-    let (id, args, sig) = parse_function_header(input, pos)?;
+    // the body is parsed even if the header is invalid, so that its errors are reported too
+    let header = match parse_function_header(input, pos) {
+        Ok(header) => Some(header),
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    };
 
     current_line += 1;
 
     // parse function body
     let mut stats = Vec::new();
     loop {
+        // a declaration is left for the program to parse, the function misses its return statement
+        if let Some(Ok(ref x)) = lines.peek() {
+            if is_declaration::<T>(x, current_line) {
+                // the return statement may be the one which could not be parsed
+                if errors.len() == 0 {
+                    let (t, ..) = next_token::<T>(
+                        x,
+                        &Position {
+                            line: current_line,
+                            col: 1,
+                        },
+                    );
+                    errors.push(Error {
+                        expected: vec![Token::Return],
+                        got: t,
+                        pos: Position {
+                            line: current_line,
+                            col: 1,
+                        },
+                    });
+                }
+                current_line -= 1;
+                break;
+            }
+        }
+
         match lines.next() {
             Some(Ok(ref x)) if x.trim().starts_with("//") || x.trim() == "" => {} // skip
             Some(Ok(ref x)) => match parse_statement(
@@ -284,24 +326,47 @@ pub fn parse_function<T: Field, R: BufRead>(
                         }
                     }
                 }
-                Err(err) => return Err(err),
+                // parsing resumes after the statement
+                Err((mut errs, pos)) => {
+                    current_line = pos.line;
+                    errors.append(&mut errs);
+                }
             },
-            None => panic!("Function {} does not return before program ends", id),
+            // the function does not return before the program ends
+            None => {
+                if errors.len() == 0 {
+                    errors.push(Error {
+                        expected: vec![Token::Return],
+                        got: Token::Unknown("".to_string()),
+                        pos: Position {
+                            line: current_line,
+                            col: 1,
+                        },
+                    });
+                }
+                break;
+            }
             Some(Err(err)) => panic!("Error while reading function statements: {}", err),
         }
         current_line += 1;
     }
 
-    match stats.last().clone().unwrap().value {
-        Statement::Return(_) => {}
-        ref x => panic!("Last function statement not Return: {}", x),
-    }
-
     let next_pos = Position {
         line: current_line,
         col: 1,
     };
 
+    if errors.len() > 0 {
+        return Err((errors, next_pos));
+    }
+
+    let (id, args, sig) = header.unwrap();
+
+    match stats.last().clone().unwrap().value {
+        Statement::Return(_) => {}
+        ref x => panic!("Last function statement not Return: {}", x),
+    }
+
     Ok((
         Node::new(
             *pos,
@@ -74,6 +74,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unterminated_quoted_path() {
+        let pos = Position { line: 45, col: 121 };
+        let string = String::from("\"./foo");
+        assert_eq!(
+            Err(Error {
+                expected: vec![Token::Path("./path/to/program.code".to_string())],
+                got: Token::Unknown("./foo".to_string()),
+                pos: pos.col(1 as isize),
+            }),
+            parse_import::<FieldPrime>(&string, &pos)
+        );
+    }
+
     #[test]
     fn import() {
         let pos = Position { line: 45, col: 121 };
@@ -1,8 +1,10 @@
+mod constant;
 mod expression;
 mod expression_list;
 mod function;
 mod import;
 mod program;
 mod statement;
+mod struct_definition;
 
 pub use self::program::parse_program;
@@ -5,20 +5,31 @@ use std::io::prelude::*;
 use parser::error::Error;
 use parser::tokenize::{next_token, Position, Token};
 
+use super::constant::parse_constant;
 use super::function::parse_function;
 use super::import::parse_import;
+use super::statement::is_declaration;
+use super::struct_definition::parse_struct;
 
 use absy::Prog;
 
-pub fn parse_program<T: Field, R: BufRead>(reader: &mut R) -> Result<Prog<T>, Error<T>> {
+pub fn parse_program<T: Field, R: BufRead>(reader: &mut R) -> Result<Prog<T>, Vec<Error<T>>> {
     let mut current_line = 1;
-    let mut lines = reader.lines();
+    let mut lines = reader.lines().peekable();
     let mut functions = Vec::new();
     let mut imports = Vec::new();
+    let mut structs = Vec::new();
+    let mut constants = Vec::new();
+    let mut errors = Vec::new();
+    // after an error, lines are skipped until the next top-level declaration
+    let mut recovering = false;
 
     loop {
         match lines.next() {
             Some(Ok(ref x)) if x.trim().starts_with("//") || x.trim() == "" => current_line += 1,
+            Some(Ok(ref x)) if recovering && !is_declaration::<T>(x, current_line) => {
+                current_line += 1
+            }
             Some(Ok(ref x)) => match next_token(
                 x,
                 &Position {
@@ -31,23 +42,64 @@ pub fn parse_program<T: Field, R: BufRead>(reader: &mut R) -> Result<Prog<T>, Er
                         imports.push(import);
                         current_line = p2.line; // this is the line of the import statement
                         current_line += 1;
+                        recovering = false;
+                    }
+                    Err(err) => {
+                        current_line = err.pos.line + 1;
+                        errors.push(err);
+                        recovering = true;
+                    }
+                },
+                (Token::Struct, ref s1, ref p1) => match parse_struct(&mut lines, s1, p1) {
+                    Ok((definition, p2)) => {
+                        structs.push(definition);
+                        current_line = p2.line; // this is the line of the closing brace
+                        current_line += 1;
+                        recovering = false;
+                    }
+                    // the error is on the last line read
+                    Err(err) => {
+                        current_line = err.pos.line + 1;
+                        errors.push(err);
+                        recovering = true;
+                    }
+                },
+                (Token::Const, ref s1, ref p1) => match parse_constant(s1, p1) {
+                    Ok((constant, p2)) => {
+                        constants.push(constant);
+                        current_line = p2.line; // this is the line of the constant declaration
+                        current_line += 1;
+                        recovering = false;
+                    }
+                    Err(err) => {
+                        current_line = err.pos.line + 1;
+                        errors.push(err);
+                        recovering = true;
                     }
-                    Err(err) => return Err(err),
                 },
                 (Token::Def, ref s1, ref p1) => match parse_function(&mut lines, s1, p1) {
                     Ok((function, p2)) => {
                         functions.push(function);
                         current_line = p2.line; // this is the line of the return statement
                         current_line += 1;
+                        recovering = false;
+                    }
+                    // errors in the body are recovered from, so the whole function was read
+                    Err((mut errs, p2)) => {
+                        current_line = p2.line;
+                        current_line += 1;
+                        errors.append(&mut errs);
+                        recovering = false;
                     }
-                    Err(err) => return Err(err),
                 },
                 (t1, _, p1) => {
-                    return Err(Error {
+                    errors.push(Error {
                         expected: vec![Token::Def],
                         got: t1,
                         pos: p1,
                     });
+                    current_line += 1;
+                    recovering = true;
                 }
             },
             None => break,
@@ -55,9 +107,16 @@ pub fn parse_program<T: Field, R: BufRead>(reader: &mut R) -> Result<Prog<T>, Er
         }
     }
 
+    if errors.len() > 0 {
+        return Err(errors);
+    }
+
     Ok(Prog {
+        structs,
+        constants,
         functions,
         imports,
         imported_functions: vec![],
+        imported_constants: vec![],
     })
 }
@@ -2,31 +2,300 @@ use zokrates_field::field::Field;
 
 use std::io::prelude::*;
 use std::io::Lines;
+use std::iter::Peekable;
 
-use parser::tokenize::{next_token, Position, Token};
+use parser::tokenize::{next_token, parse_quoted_string, Position, Token};
 use parser::Error;
 
 use parser::tokenize::skip_whitespaces;
 
 use super::expression::{
-    parse_array_select, parse_expr, parse_expr1, parse_function_call, parse_term1,
+    parse_accesses, parse_array_select, parse_bexpr, parse_expr, parse_expr1, parse_function_call,
+    parse_term1,
 };
 use super::expression_list::parse_expression_list;
+use super::struct_definition::parse_struct_type;
 
 use absy::{
-    Assignee, AssigneeNode, Expression, Node, Statement, StatementNode, Variable, VariableNode,
+    Assignee, AssigneeNode, Expression, ExpressionNode, Node, Statement, StatementNode, Variable,
+    VariableNode,
 };
 use types::Type;
 
+// parse the statement starting on `input`, reading the following lines if it is a loop
+// after an error, parsing resumes at the next statement: a loop is read up to its `endfor`, and the
+// errors in its body are reported as well. The position of the last line read is returned either way
 pub fn parse_statement<T: Field, R: BufRead>(
-    lines: &mut Lines<R>,
+    lines: &mut Peekable<Lines<R>>,
+    input: &String,
+    pos: &Position,
+) -> Result<(Vec<StatementNode<T>>, String, Position), (Vec<Error<T>>, Position)> {
+    // statements are located at their first token, which is where runtime errors are reported
+    let start = Position {
+        line: pos.line,
+        col: pos.col + skip_whitespaces(input),
+    };
+
+    let (statements, s, p) = match next_token::<T>(input, pos) {
+        (Token::For, s1, p1) => parse_for(lines, start, &s1, &p1)?,
+        _ => parse_line_statement(input, pos).map_err(|e| (vec![e], *pos))?,
+    };
+
+    Ok((
+        statements
+            .into_iter()
+            .map(|statement| Node::new(start, statement.end, statement.value))
+            .collect(),
+        s,
+        p,
+    ))
+}
+
+/// Returns whether `line` starts a top-level declaration, which ends any function or loop before it
+pub fn is_declaration<T: Field>(line: &String, current_line: usize) -> bool {
+    match next_token::<T>(
+        line,
+        &Position {
+            line: current_line,
+            col: 1,
+        },
+    ) {
+        (Token::Import, ..) | (Token::Struct, ..) | (Token::Const, ..) | (Token::Def, ..) => true,
+        _ => false,
+    }
+}
+
+// parse a loop such as `for field i in 0..n do`, starting after `for`, then its body up to `endfor`
+fn parse_for<T: Field, R: BufRead>(
+    lines: &mut Peekable<Lines<R>>,
+    start: Position,
+    input: &String,
+    pos: &Position,
+) -> Result<(Vec<StatementNode<T>>, String, Position), (Vec<Error<T>>, Position)> {
+    // the body is parsed even if the header is invalid, so that its errors are reported too
+    let (header, mut errors) = match parse_for_header(input, pos) {
+        Ok(header) => (Some(header), vec![]),
+        Err(e) => (None, vec![e]),
+    };
+
+    let mut current_line = pos.line;
+    let mut statements = Vec::new();
+    loop {
+        // a declaration is left for the program to parse, the loop misses its end
+        if let Some(Ok(ref x)) = lines.peek() {
+            if is_declaration::<T>(x, current_line + 1) {
+                let (t, ..) = next_token::<T>(
+                    x,
+                    &Position {
+                        line: current_line + 1,
+                        col: 1,
+                    },
+                );
+                errors.push(Error {
+                    expected: vec![Token::Endfor],
+                    got: t,
+                    pos: Position {
+                        line: current_line + 1,
+                        col: 1,
+                    },
+                });
+                return Err((
+                    errors,
+                    Position {
+                        line: current_line,
+                        col: 1,
+                    },
+                ));
+            }
+        }
+
+        current_line += 1;
+        match lines.next() {
+            Some(Ok(ref x)) if x.trim().starts_with("//") || x.trim() == "" => {} // skip
+            Some(Ok(ref x)) if x.trim().starts_with("endfor") => {
+                let offset = skip_whitespaces(x);
+                let s8 = x[offset + 6..].to_string();
+                let p8 = Position {
+                    line: current_line,
+                    col: offset + 7,
+                };
+                match next_token::<T>(&s8, &p8) {
+                    (Token::InlineComment(_), ref s9, _) => assert_eq!(s9, ""),
+                    (Token::Unknown(ref t9), ref s9, _) if t9 == "" => assert_eq!(s9, ""),
+                    (t9, _, p9) => errors.push(Error {
+                        expected: vec![Token::Unknown("".to_string())],
+                        got: t9,
+                        pos: p9,
+                    }),
+                }
+                return match header {
+                    Some((var, from, to)) if errors.len() == 0 => Ok((
+                        vec![Node::new(
+                            start,
+                            p8,
+                            Statement::For(var, from, to, statements),
+                        )],
+                        s8,
+                        p8,
+                    )),
+                    _ => Err((errors, p8)),
+                };
+            }
+            Some(Ok(ref x)) if !x.trim().starts_with("return") => match parse_statement(
+                lines,
+                x,
+                &Position {
+                    line: current_line,
+                    col: 1,
+                },
+            ) {
+                Ok((mut statement, _, p)) => {
+                    current_line = p.line;
+                    statements.append(&mut statement);
+                }
+                Err((mut e, p)) => {
+                    current_line = p.line;
+                    errors.append(&mut e);
+                }
+            },
+            Some(Err(err)) => panic!("Error while reading Definitions: {}", err),
+            Some(Ok(ref x)) => {
+                let (t, ..) = next_token(
+                    x,
+                    &Position {
+                        line: current_line,
+                        col: 1,
+                    },
+                );
+                errors.push(Error {
+                    expected: vec![
+                        Token::ErrIde,
+                        Token::ErrNum,
+                        Token::If,
+                        Token::Open,
+                        Token::Hash,
+                        Token::For,
+                        Token::Endfor,
+                    ],
+                    got: t,
+                    pos: Position {
+                        line: current_line,
+                        col: 1,
+                    },
+                });
+            }
+            None => {
+                errors.push(Error {
+                    expected: vec![
+                        Token::ErrIde,
+                        Token::ErrNum,
+                        Token::If,
+                        Token::Open,
+                        Token::Hash,
+                        Token::For,
+                    ],
+                    got: Token::Unknown("".to_string()),
+                    pos: Position {
+                        line: current_line,
+                        col: 1,
+                    },
+                });
+                return Err((
+                    errors,
+                    Position {
+                        line: current_line,
+                        col: 1,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+// parse the header of a loop such as `for field i in 0..n do`, starting after `for`
+fn parse_for_header<T: Field>(
+    input: &String,
+    pos: &Position,
+) -> Result<(VariableNode, ExpressionNode<T>, ExpressionNode<T>), Error<T>> {
+    let (t, s0, p0) = match next_token(input, pos) {
+        (Token::Type(t), s0, p0) => (t, s0, p0),
+        (t0, _, p0) => {
+            return Err(Error {
+                expected: vec![Token::Type(Type::FieldElement)],
+                got: t0,
+                pos: p0,
+            })
+        }
+    };
+    let (x2, s2, p2) = match next_token(&s0, &p0) {
+        (Token::Ide(x2), s2, p2) => (x2, s2, p2),
+        (t2, _, p2) => {
+            return Err(Error {
+                expected: vec![Token::ErrIde],
+                got: t2,
+                pos: p2,
+            })
+        }
+    };
+    let (s3, p3) = match next_token(&s2, &p2) {
+        (Token::In, s3, p3) => (s3, p3),
+        (t3, _, p3) => {
+            return Err(Error {
+                expected: vec![Token::In],
+                got: t3,
+                pos: p3,
+            })
+        }
+    };
+    let (x4, s4, p4) = parse_expr(&s3, &p3)?;
+    let (s5, p5) = match next_token(&s4, &p4) {
+        (Token::Dotdot, s5, p5) => (s5, p5),
+        (t5, _, p5) => {
+            return Err(Error {
+                expected: vec![Token::Dotdot],
+                got: t5,
+                pos: p5,
+            })
+        }
+    };
+    let (x6, s6, p6) = parse_expr(&s5, &p5)?;
+    let (s7, p7) = match next_token(&s6, &p6) {
+        (Token::Do, s7, p7) => (s7, p7),
+        (t7, _, p7) => {
+            return Err(Error {
+                expected: vec![Token::Do],
+                got: t7,
+                pos: p7,
+            })
+        }
+    };
+    match next_token(&s7, &p7) {
+        (Token::InlineComment(_), ref s8, _) => assert_eq!(s8, ""),
+        (Token::Unknown(ref t8), ref s8, _) if t8 == "" => assert_eq!(s8, ""),
+        (t8, _, p8) => {
+            return Err(Error {
+                expected: vec![Token::Unknown("".to_string())],
+                got: t8,
+                pos: p8,
+            })
+        }
+    }
+
+    Ok((Node::new(*pos, p3, Variable::new(x2, t)), x4, x6))
+}
+
+// parse a statement which fits on the line `input`
+fn parse_line_statement<T: Field>(
     input: &String,
     pos: &Position,
 ) -> Result<(Vec<StatementNode<T>>, String, Position), Error<T>> {
     match next_token::<T>(input, pos) {
         (Token::Type(t), s1, p1) => parse_declaration_definition(t, s1, p1),
         (Token::Ide(x1), s1, p1) => parse_statement1(x1, s1, p1),
-        (Token::If, ..) | (Token::Open, ..) | (Token::Num(_), ..) => match parse_expr(input, pos) {
+        (Token::If, ..)
+        | (Token::Open, ..)
+        | (Token::Num(..), ..)
+        | (Token::HexNum(_), ..) => match parse_expr(input, pos) {
             Ok((e2, s2, p2)) => match next_token(&s2, &p2) {
                 (Token::Eqeq, s3, p3) => match parse_expr(&s3, &p3) {
                     Ok((e4, s4, p4)) => match next_token(&s4, &p4) {
@@ -62,134 +331,7 @@ pub fn parse_statement<T: Field, R: BufRead>(
             },
             Err(err) => Err(err),
         },
-        (Token::For, s1, p1) => {
-            match next_token(&s1, &p1) {
-                (Token::Type(t), s0, p0) => {
-                    match next_token(&s0, &p0) {
-                        (Token::Ide(x2), s2, p2) => {
-                            match next_token(&s2, &p2) {
-                                (Token::In, s3, p3) => {
-                                    match next_token(&s3, &p3) {
-                                        (Token::Num(x4), s4, p4) => {
-                                            match next_token(&s4, &p4) {
-                                                (Token::Dotdot, s5, p5) => {
-                                                    match next_token(&s5, &p5) {
-                                                        (Token::Num(x6), s6, p6) => {
-                                                            match next_token(&s6, &p6) {
-                                                                (Token::Do, s7, p7) => {
-                                                                    match next_token(&s7, &p7) {
-                                                                        (
-                                                                            Token::InlineComment(_),
-                                                                            ref s8,
-                                                                            _,
-                                                                        ) => {
-                                                                            assert_eq!(s8, "");
-                                                                        }
-                                                                        (
-                                                                            Token::Unknown(ref t8),
-                                                                            ref s8,
-                                                                            _,
-                                                                        )
-                                                                            if t8 == "" =>
-                                                                        {
-                                                                            assert_eq!(s8, "");
-                                                                        }
-                                                                        (t8, _, p8) => {
-                                                                            return Err(Error {
-                                                                                expected: vec![
-                                                                                    Token::Unknown(
-                                                                                        "".to_string(),
-                                                                                    ),
-                                                                                ],
-                                                                                got: t8,
-                                                                                pos: p8,
-                                                                            })
-                                                                        }
-                                                                    }
-                                                                    let mut current_line = p7.line;
-                                                                    let mut statements = Vec::new();
-                                                                    loop {
-                                                                        current_line += 1;
-                                                                        match lines.next() {
-                                                    Some(Ok(ref x)) if x.trim().starts_with("//") || x.trim() == "" => {}, // skip
-                                                    Some(Ok(ref x)) if x.trim().starts_with("endfor") => {
-                                                        let offset = skip_whitespaces(x);
-                                                        let s8 = x[offset + 6..].to_string();
-                                                        let p8 = Position{ line: current_line, col: offset + 7 };
-                                                        match next_token(&s8, &p8) {
-                                                            (Token::InlineComment(_), ref s9, _) => {
-                                                                assert_eq!(s9, "");
-                                                                return Ok((vec![Node::new(*pos, p8, Statement::For(Node::new(p1, p3, Variable::new(x2, t)), x4, x6, statements))], s8, p8))
-                                                            }
-                                                            (Token::Unknown(ref t9), ref s9, _) if t9 == "" => {
-                                                                assert_eq!(s9, "");
-                                                                return Ok((vec![Node::new(*pos, p8, Statement::For(Node::new(p1, p3, Variable::new(x2, t)), x4, x6, statements))], s8, p8))
-                                                            },
-                                                            (t9, _, p9) => return Err(Error { expected: vec![Token::Unknown("".to_string())], got: t9 , pos: p9 }),
-                                                        }
-                                                    },
-                                                    Some(Ok(ref x)) if !x.trim().starts_with("return") => match parse_statement(lines, x, &Position { line: current_line, col: 1 }) {
-                                                        Ok((mut statement, ..)) => statements.append(&mut statement),
-                                                        Err(err) => return Err(err),
-                                                    },
-                                                    Some(Err(err)) => panic!("Error while reading Definitions: {}", err),
-                                                    Some(Ok(ref x)) => {
-                                                        let (t, ..) = next_token(x, &Position{ line: current_line, col: 1 });
-                                                        return Err(Error { expected: vec![Token::ErrIde, Token::ErrNum, Token::If, Token::Open, Token::Hash, Token::For, Token::Endfor], got: t , pos:  Position{ line: current_line, col: 1 } })
-                                                    },
-                                                    None => return Err(Error { expected: vec![Token::ErrIde, Token::ErrNum, Token::If, Token::Open, Token::Hash, Token::For], got: Token::Unknown("".to_string()) , pos:  Position{ line: current_line, col: 1 } }),
-                                                }
-                                                                    }
-                                                                }
-                                                                (t7, _, p7) => Err(Error {
-                                                                    expected: vec![Token::Do],
-                                                                    got: t7,
-                                                                    pos: p7,
-                                                                }),
-                                                            }
-                                                        }
-                                                        (t6, _, p6) => Err(Error {
-                                                            expected: vec![Token::ErrNum],
-                                                            got: t6,
-                                                            pos: p6,
-                                                        }),
-                                                    }
-                                                }
-                                                (t5, _, p5) => Err(Error {
-                                                    expected: vec![Token::Dotdot],
-                                                    got: t5,
-                                                    pos: p5,
-                                                }),
-                                            }
-                                        }
-                                        (t4, _, p4) => Err(Error {
-                                            expected: vec![Token::ErrNum],
-                                            got: t4,
-                                            pos: p4,
-                                        }),
-                                    }
-                                }
-                                (t3, _, p3) => Err(Error {
-                                    expected: vec![Token::In],
-                                    got: t3,
-                                    pos: p3,
-                                }),
-                            }
-                        }
-                        (t2, _, p2) => Err(Error {
-                            expected: vec![Token::ErrIde],
-                            got: t2,
-                            pos: p2,
-                        }),
-                    }
-                }
-                (t0, _, p0) => Err(Error {
-                    expected: vec![Token::Type(Type::FieldElement)],
-                    got: t0,
-                    pos: p0,
-                }),
-            }
-        }
+        (Token::Assert, s1, p1) => parse_assertion(s1, p1),
         (Token::Return, s1, p1) => match parse_expression_list(s1, p1) {
             Ok((e2, s2, p2)) => match next_token(&s2, &p2) {
                 (Token::InlineComment(_), ref s3, _) => {
@@ -219,6 +361,7 @@ pub fn parse_statement<T: Field, R: BufRead>(
                 Token::ErrNum,
                 Token::If,
                 Token::Open,
+                Token::Assert,
                 Token::Return,
             ],
             got: t1,
@@ -227,6 +370,83 @@ pub fn parse_statement<T: Field, R: BufRead>(
     }
 }
 
+// parse an assertion such as `assert(a == b, "a should equal b")`, starting after `assert`
+fn parse_assertion<T: Field>(
+    input: String,
+    pos: Position,
+) -> Result<(Vec<StatementNode<T>>, String, Position), Error<T>> {
+    let (s, p) = match next_token::<T>(&input, &pos) {
+        (Token::Open, s1, p1) => (s1, p1),
+        (t1, _, p1) => {
+            return Err(Error {
+                expected: vec![Token::Open],
+                got: t1,
+                pos: p1,
+            });
+        }
+    };
+
+    let (e, s, p) = parse_bexpr(&s, &p)?;
+
+    // the message is optional
+    let (message, s, p) = match next_token::<T>(&s, &p) {
+        (Token::Close, s3, p3) => (None, s3, p3),
+        (Token::Comma, s3, p3) => match next_token::<T>(&s3, &p3) {
+            (Token::DoubleQuote, s4, p4) => match parse_quoted_string::<T>(&s4, &p4) {
+                (Token::Str(message), s5, p5) => match next_token::<T>(&s5, &p5) {
+                    (Token::Close, s6, p6) => (Some(message), s6, p6),
+                    (t6, _, p6) => {
+                        return Err(Error {
+                            expected: vec![Token::Close],
+                            got: t6,
+                            pos: p6,
+                        });
+                    }
+                },
+                (t5, _, p5) => {
+                    return Err(Error {
+                        expected: vec![Token::DoubleQuote],
+                        got: t5,
+                        pos: p5,
+                    });
+                }
+            },
+            (t4, _, p4) => {
+                return Err(Error {
+                    expected: vec![Token::DoubleQuote],
+                    got: t4,
+                    pos: p4,
+                });
+            }
+        },
+        (t3, _, p3) => {
+            return Err(Error {
+                expected: vec![Token::Comma, Token::Close],
+                got: t3,
+                pos: p3,
+            });
+        }
+    };
+
+    match next_token::<T>(&s, &p) {
+        (Token::InlineComment(_), ..) => {}
+        (Token::Unknown(ref t7), ..) if t7 == "" => {}
+        (t7, _, p7) => {
+            return Err(Error {
+                expected: vec![Token::Unknown("".to_string())],
+                got: t7,
+                pos: p7,
+            });
+        }
+    }
+
+    Ok((
+        vec![Node::new(pos, p, Statement::Assertion(e, message))],
+        s,
+        p,
+    ))
+}
+
 fn parse_definition1<T: Field>(
     x: AssigneeNode<T>,
     input: String,
@@ -469,6 +689,13 @@ fn parse_statement1<T: Field>(
         col: pos.col - ide.len(),
         ..pos
     };
+
+    // a struct type, possibly an array of structs, followed by the declared variable
+    let (ty, s0, p0) = parse_struct_type::<T>(ide.clone(), input.clone(), pos);
+    if let (Token::Ide(_), ..) = next_token::<T>(&s0, &p0) {
+        return parse_declaration_definition(ty, s0, p0);
+    }
+
     match next_token::<T>(&input, &pos) {
         (Token::Eq, s1, p1) => parse_definition1(
             Node::new(ide_start_position, pos, Assignee::Identifier(ide)),
@@ -591,56 +818,59 @@ fn parse_statement1<T: Field>(
             },
             Err(err) => Err(err),
         },
-        _ => match parse_term1(
-            Node::new(ide_start_position, pos, Expression::Identifier(ide)),
-            input,
-            pos,
-        ) {
-            Ok((e2, s2, p2)) => match parse_expr1(e2, s2, p2) {
-                Ok((e3, s3, p3)) => match next_token(&s3, &p3) {
-                    (Token::Eqeq, s4, p4) => match parse_expr(&s4, &p4) {
-                        Ok((e5, s5, p5)) => match next_token(&s5, &p5) {
-                            (Token::InlineComment(_), ref s6, _) => {
-                                assert_eq!(s6, "");
-                                Ok((
-                                    vec![Node::new(pos, p5, Statement::Condition(e3, e5))],
-                                    s5,
-                                    p5,
-                                ))
-                            }
-                            (Token::Unknown(ref t6), ref s6, _) if t6 == "" => {
-                                assert_eq!(s6, "");
-                                Ok((
-                                    vec![Node::new(pos, p5, Statement::Condition(e3, e5))],
-                                    s5,
-                                    p5,
-                                ))
-                            }
-                            (t6, _, p6) => Err(Error {
-                                expected: vec![
-                                    Token::Add,
-                                    Token::Sub,
-                                    Token::Pow,
-                                    Token::Mult,
-                                    Token::Div,
-                                    Token::Unknown("".to_string()),
-                                ],
-                                got: t6,
-                                pos: p6,
-                            }),
+        _ => {
+            let (e1, s1, p1) = parse_accesses(
+                Node::new(ide_start_position, pos, Expression::Identifier(ide)),
+                input,
+                pos,
+            )?;
+            match parse_term1(e1, s1, p1) {
+                Ok((e2, s2, p2)) => match parse_expr1(e2, s2, p2) {
+                    Ok((e3, s3, p3)) => match next_token(&s3, &p3) {
+                        (Token::Eqeq, s4, p4) => match parse_expr(&s4, &p4) {
+                            Ok((e5, s5, p5)) => match next_token(&s5, &p5) {
+                                (Token::InlineComment(_), ref s6, _) => {
+                                    assert_eq!(s6, "");
+                                    Ok((
+                                        vec![Node::new(pos, p5, Statement::Condition(e3, e5))],
+                                        s5,
+                                        p5,
+                                    ))
+                                }
+                                (Token::Unknown(ref t6), ref s6, _) if t6 == "" => {
+                                    assert_eq!(s6, "");
+                                    Ok((
+                                        vec![Node::new(pos, p5, Statement::Condition(e3, e5))],
+                                        s5,
+                                        p5,
+                                    ))
+                                }
+                                (t6, _, p6) => Err(Error {
+                                    expected: vec![
+                                        Token::Add,
+                                        Token::Sub,
+                                        Token::Pow,
+                                        Token::Mult,
+                                        Token::Div,
+                                        Token::Unknown("".to_string()),
+                                    ],
+                                    got: t6,
+                                    pos: p6,
+                                }),
+                            },
+                            Err(err) => Err(err),
                         },
-                        Err(err) => Err(err),
+                        (t4, _, p4) => Err(Error {
+                            expected: vec![Token::Eqeq],
+                            got: t4,
+                            pos: p4,
+                        }),
                     },
-                    (t4, _, p4) => Err(Error {
-                        expected: vec![Token::Eqeq],
-                        got: t4,
-                        pos: p4,
-                    }),
+                    Err(err) => Err(err),
                 },
                 Err(err) => Err(err),
-            },
-            Err(err) => Err(err),
-        },
+            }
+        }
     }
 }
 
@@ -698,12 +928,28 @@ fn parse_comma_separated_identifier_list_rec<T: Field>(
             }),
         },
         (Token::Ide(id), s1, p1) => {
-            acc.push(Node::new(pos, p1, Assignee::Identifier(id)));
-            match next_token::<T>(&s1, &p1) {
-                (Token::Comma, s2, p2) => {
-                    parse_comma_separated_identifier_list_rec(s2, p2, &mut acc, &mut decl)
+            // a struct type, possibly an array of structs, followed by the declared variable
+            let (ty, s2, p2) = parse_struct_type::<T>(id.clone(), s1.clone(), p1);
+            match next_token::<T>(&s2, &p2) {
+                (Token::Ide(id2), s3, p3) => {
+                    acc.push(Node::new(p2, p3, Assignee::Identifier(id2.clone())));
+                    decl.push(Node::new(pos, p3, Variable::new(id2, ty)));
+                    match next_token::<T>(&s3, &p3) {
+                        (Token::Comma, s4, p4) => {
+                            parse_comma_separated_identifier_list_rec(s4, p4, &mut acc, &mut decl)
+                        }
+                        (..) => Ok((acc.to_vec(), decl.to_vec(), s3, p3)),
+                    }
+                }
+                _ => {
+                    acc.push(Node::new(pos, p1, Assignee::Identifier(id)));
+                    match next_token::<T>(&s1, &p1) {
+                        (Token::Comma, s2, p2) => {
+                            parse_comma_separated_identifier_list_rec(s2, p2, &mut acc, &mut decl)
+                        }
+                        (..) => Ok((acc.to_vec(), decl.to_vec(), s1, p1)),
+                    }
                 }
-                (..) => Ok((acc.to_vec(), decl.to_vec(), s1, p1)),
             }
         }
         (t1, _, p1) => Err(Error {
@@ -727,7 +973,7 @@ mod tests {
             let string = String::from("() == 1");
             let cond = Statement::Condition(
                 Expression::FunctionCall(String::from("foo"), vec![]).into(),
-                Expression::Number(FieldPrime::from(1)).into(),
+                Expression::Number(FieldPrime::from(1), String::from("1")).into(),
             )
             .into();
             assert_eq!(
@@ -747,10 +993,10 @@ mod tests {
                         box Expression::FunctionCall(String::from("g"), vec![]).into(),
                     )
                     .into(),
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                 )
                 .into(),
-                Expression::Number(FieldPrime::from(1)).into(),
+                Expression::Number(FieldPrime::from(1), String::from("1")).into(),
             )
             .into();
             assert_eq!(
@@ -768,16 +1014,16 @@ mod tests {
                     box Expression::Sub(
                         box Expression::Select(
                             box Expression::Identifier(String::from("foo")).into(),
-                            box Expression::Number(FieldPrime::from(3)).into(),
+                            box Expression::Number(FieldPrime::from(3), String::from("3")).into(),
                         )
                         .into(),
                         box Expression::FunctionCall(String::from("g"), vec![]).into(),
                     )
                     .into(),
-                    box Expression::Number(FieldPrime::from(1)).into(),
+                    box Expression::Number(FieldPrime::from(1), String::from("1")).into(),
                 )
                 .into(),
-                Expression::Number(FieldPrime::from(1)).into(),
+                Expression::Number(FieldPrime::from(1), String::from("1")).into(),
             )
             .into();
             assert_eq!(
@@ -786,4 +1032,65 @@ mod tests {
             );
         }
     }
+
+    mod parse_assertion {
+        use super::*;
+
+        #[test]
+        fn with_message() {
+            let pos = Position { line: 45, col: 121 };
+            let string = String::from("(a == b, \"a should equal b\") // comment");
+            let assertion: StatementNode<FieldPrime> = Statement::Assertion(
+                Expression::Eq(
+                    box Expression::Identifier(String::from("a")).into(),
+                    box Expression::Identifier(String::from("b")).into(),
+                )
+                .into(),
+                Some(String::from("a should equal b")),
+            )
+            .into();
+            assert_eq!(
+                Ok((
+                    vec![assertion],
+                    String::from(" // comment"),
+                    pos.col("(a == b, \"a should equal b\")".len() as isize)
+                )),
+                parse_assertion(string, pos)
+            );
+        }
+
+        #[test]
+        fn boolean_variable() {
+            let pos = Position { line: 45, col: 121 };
+            let string = String::from("(b && c < 2)");
+            let assertion = Statement::Assertion(
+                Expression::And(
+                    box Expression::Identifier(String::from("b")).into(),
+                    box Expression::Lt(
+                        box Expression::Identifier(String::from("c")).into(),
+                        box Expression::Number(FieldPrime::from(2), String::from("2")).into(),
+                    )
+                    .into(),
+                )
+                .into(),
+                None,
+            )
+            .into();
+            assert_eq!(
+                Ok((
+                    vec![assertion],
+                    String::from(""),
+                    pos.col(string.len() as isize)
+                )),
+                parse_assertion(string, pos)
+            );
+        }
+
+        #[test]
+        fn unterminated_message() {
+            let pos = Position { line: 45, col: 121 };
+            let string = String::from("(a == b, \"a should equal b)");
+            assert!(parse_assertion::<FieldPrime>(string, pos).is_err());
+        }
+    }
 }
@@ -0,0 +1,259 @@
+use zokrates_field::field::Field;
+
+use std::io::prelude::*;
+use std::io::Lines;
+use std::iter::Peekable;
+
+use parser::tokenize::{next_token, Position, Token};
+use parser::Error;
+
+use absy::{Node, StructDefinition, StructDefinitionNode, StructField, StructFieldNode};
+use types::{ArrayType, StructType, Type};
+
+// array sizes are either literals or the names of constants
+enum ArraySize {
+    Value(usize),
+    Constant(String),
+}
+
+impl ArraySize {
+    fn array_of(self, ty: Type) -> Type {
+        match self {
+            ArraySize::Value(size) => Type::array(ty, size),
+            ArraySize::Constant(id) => Type::Array(ArrayType::with_size_constant(ty, id)),
+        }
+    }
+}
+
+// parse the array dimensions following the name of a struct type, such as `[2]` in `Foo[2] foo`
+pub fn parse_struct_type<T: Field>(
+    id: String,
+    input: String,
+    pos: Position,
+) -> (Type, String, Position) {
+    let mut dimensions = vec![];
+    let mut s = input;
+    let mut p = pos;
+
+    loop {
+        match next_token::<T>(&s, &p) {
+            (Token::LeftBracket, s1, p1) => {
+                let (size, s2, p2) = match next_token::<T>(&s1, &p1) {
+                    (Token::Num(n, _), s2, p2) => (
+                        ArraySize::Value(n.to_dec_string().parse::<usize>().unwrap()),
+                        s2,
+                        p2,
+                    ),
+                    (Token::Ide(id), s2, p2) => (ArraySize::Constant(id), s2, p2),
+                    _ => break,
+                };
+                match next_token::<T>(&s2, &p2) {
+                    (Token::RightBracket, s3, p3) => {
+                        dimensions.push(size);
+                        s = s3;
+                        p = p3;
+                    }
+                    _ => break,
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let ty = dimensions
+        .into_iter()
+        .rev()
+        .fold(Type::Struct(StructType::new(id, vec![])), |ty, size| {
+            size.array_of(ty)
+        });
+
+    (ty, s, p)
+}
+
+fn parse_struct_field<T: Field>(
+    ty: Type,
+    input: &String,
+    pos: &Position,
+    start: &Position,
+) -> Result<(StructFieldNode, String, Position), Error<T>> {
+    match next_token::<T>(input, pos) {
+        (Token::Ide(id), s1, p1) => match next_token::<T>(&s1, &p1) {
+            // a member is followed by a separator, the closing brace or the end of the line
+            (Token::Semicolon, ..)
+            | (Token::RightBrace, ..)
+            | (Token::InlineComment(_), ..) => {
+                Ok((Node::new(*start, p1, StructField { id, ty }), s1, p1))
+            }
+            (Token::Unknown(ref t2), ..) if t2 == "" => {
+                Ok((Node::new(*start, p1, StructField { id, ty }), s1, p1))
+            }
+            (t2, _, p2) => Err(Error {
+                expected: vec![Token::Semicolon, Token::RightBrace],
+                got: t2,
+                pos: p2,
+            }),
+        },
+        (t1, _, p1) => Err(Error {
+            expected: vec![Token::ErrIde],
+            got: t1,
+            pos: p1,
+        }),
+    }
+}
+
+pub fn parse_struct<T: Field, R: BufRead>(
+    lines: &mut Peekable<Lines<R>>,
+    input: &String,
+    pos: &Position,
+) -> Result<(StructDefinitionNode, Position), Error<T>> {
+    let (id, s, p) = match next_token::<T>(input, pos) {
+        (Token::Ide(x), s1, p1) => (x, s1, p1),
+        (t1, _, p1) => {
+            return Err(Error {
+                expected: vec![Token::ErrIde],
+                got: t1,
+                pos: p1,
+            });
+        }
+    };
+
+    let (mut s, mut p) = match next_token::<T>(&s, &p) {
+        (Token::LeftBrace, s2, p2) => (s2, p2),
+        (t2, _, p2) => {
+            return Err(Error {
+                expected: vec![Token::LeftBrace],
+                got: t2,
+                pos: p2,
+            });
+        }
+    };
+
+    let mut fields = Vec::new();
+
+    loop {
+        match next_token::<T>(&s, &p) {
+            (Token::RightBrace, s1, p1) => {
+                match next_token::<T>(&s1, &p1) {
+                    (Token::InlineComment(_), ..) => {}
+                    (Token::Unknown(ref t2), ..) if t2 == "" => {}
+                    (t2, _, p2) => {
+                        return Err(Error {
+                            expected: vec![Token::Unknown("".to_string())],
+                            got: t2,
+                            pos: p2,
+                        });
+                    }
+                }
+                return Ok((Node::new(*pos, p1, StructDefinition { id, fields }), p1));
+            }
+            (Token::Semicolon, s1, p1) => {
+                s = s1;
+                p = p1;
+            }
+            (Token::Type(ty), s1, p1) => {
+                let (field, s2, p2) = parse_struct_field(ty, &s1, &p1, &p)?;
+                fields.push(field);
+                s = s2;
+                p = p2;
+            }
+            // members can themselves be structs
+            (Token::Ide(x), s1, p1) => {
+                let (ty, s1, p1) = parse_struct_type::<T>(x, s1, p1);
+                let (field, s2, p2) = parse_struct_field(ty, &s1, &p1, &p)?;
+                fields.push(field);
+                s = s2;
+                p = p2;
+            }
+            (Token::InlineComment(_), ..) => {
+                s = String::from("");
+            }
+            (Token::Unknown(ref t1), ..) if t1 == "" => {
+                // the definition continues on the next line
+                let line = p.line + 1;
+                match lines.next() {
+                    Some(Ok(x)) => {
+                        s = x;
+                        p = Position { line, col: 1 };
+                    }
+                    Some(Err(err)) => panic!("Error while reading struct definition: {}", err),
+                    None => {
+                        return Err(Error {
+                            expected: vec![Token::RightBrace],
+                            got: Token::Unknown("".to_string()),
+                            pos: p,
+                        });
+                    }
+                }
+            }
+            (t1, _, p1) => {
+                return Err(Error {
+                    expected: vec![Token::Type(Type::FieldElement), Token::RightBrace],
+                    got: t1,
+                    pos: p1,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+    use zokrates_field::field::FieldPrime;
+
+    #[test]
+    fn single_line() {
+        let pos = Position { line: 1, col: 7 };
+        let string = String::from(" Foo { field a; bool b }");
+        let mut lines = BufReader::new("".as_bytes()).lines().peekable();
+        let (s, _) = parse_struct::<FieldPrime, _>(&mut lines, &string, &pos).unwrap();
+        assert_eq!(s.value.id, "Foo");
+        assert_eq!(
+            s.value
+                .fields
+                .iter()
+                .map(|f| (f.value.id.clone(), f.value.ty.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (String::from("a"), Type::FieldElement),
+                (String::from("b"), Type::Boolean)
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_line() {
+        let pos = Position { line: 1, col: 7 };
+        let string = String::from(" Foo {");
+        let mut lines =
+            BufReader::new("\tfield[2] a // comment\n\n\tBar b\n}\ndef main()".as_bytes())
+                .lines()
+                .peekable();
+        let (s, p) = parse_struct::<FieldPrime, _>(&mut lines, &string, &pos).unwrap();
+        assert_eq!(
+            s.value
+                .fields
+                .iter()
+                .map(|f| (f.value.id.clone(), f.value.ty.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (String::from("a"), Type::array(Type::FieldElement, 2)),
+                (
+                    String::from("b"),
+                    Type::Struct(StructType::new(String::from("Bar"), vec![]))
+                )
+            ]
+        );
+        assert_eq!(p.line, 5);
+        assert_eq!(lines.next().unwrap().unwrap(), "def main()");
+    }
+
+    #[test]
+    fn missing_separator() {
+        let pos = Position { line: 1, col: 7 };
+        let string = String::from(" Foo { field a field b }");
+        let mut lines = BufReader::new("".as_bytes()).lines().peekable();
+        assert!(parse_struct::<FieldPrime, _>(&mut lines, &string, &pos).is_err());
+    }
+}
@@ -34,17 +34,31 @@ pub enum Token<T: Field> {
     Mult,
     Div,
     Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    LeftShift,
+    RightShift,
     Private,
     Ide(String),
-    Num(T),
+    Num(T, String),
+    HexNum(String),
     Unknown(String),
     InlineComment(String),
     Import,
     DoubleQuote,
     Path(String),
+    Str(String),
     As,
     LeftBracket,
     RightBracket,
+    LeftBrace,
+    RightBrace,
+    Dot,
+    Semicolon,
+    Struct,
+    Const,
+    Assert,
     // following used for error messages
     ErrIde,
     ErrNum,
@@ -85,14 +99,21 @@ impl<T: Field> fmt::Display for Token<T> {
             Token::Mult => write!(f, "*"),
             Token::Div => write!(f, "/"),
             Token::Pow => write!(f, "**"),
+            Token::BitAnd => write!(f, "&"),
+            Token::BitOr => write!(f, "|"),
+            Token::BitXor => write!(f, "^"),
+            Token::LeftShift => write!(f, "<<"),
+            Token::RightShift => write!(f, ">>"),
             Token::Private => write!(f, "private"),
             Token::Ide(ref x) => write!(f, "{}", x),
-            Token::Num(ref x) => write!(f, "{}", x),
+            Token::Num(_, ref x) => write!(f, "{}", x),
+            Token::HexNum(ref x) => write!(f, "0x{}", x),
             Token::Unknown(ref x) => write!(f, "{}", x),
             Token::InlineComment(ref x) => write!(f, "// {}", x),
             Token::Import => write!(f, "import"),
             Token::DoubleQuote => write!(f, "\""),
             Token::Path(ref x) => write!(f, "\"{}\"", x),
+            Token::Str(ref x) => write!(f, "\"{}\"", x),
             Token::As => write!(f, "as"),
             Token::ErrIde => write!(f, "identifier"),
             Token::ErrNum => write!(f, "number"),
@@ -100,6 +121,13 @@ impl<T: Field> fmt::Display for Token<T> {
             Token::Arrow => write!(f, "->"),
             Token::LeftBracket => write!(f, "["),
             Token::RightBracket => write!(f, "]"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
+            Token::Dot => write!(f, "."),
+            Token::Semicolon => write!(f, ";"),
+            Token::Struct => write!(f, "struct"),
+            Token::Const => write!(f, "const"),
+            Token::Assert => write!(f, "assert"),
         }
     }
 }
@@ -1,6 +1,6 @@
 use super::position::Position;
 use super::token::Token;
-use types::Type;
+use types::{ArrayType, Type};
 use zokrates_field::field::Field;
 
 pub fn parse_num<T: Field>(input: &String, pos: &Position) -> (Token<T>, String, Position) {
@@ -16,7 +16,10 @@ pub fn parse_num<T: Field>(input: &String, pos: &Position) -> (Token<T>, String,
     }
     assert!(end > 0);
     (
-        Token::Num(T::try_from_str(&input[0..end]).unwrap()),
+        Token::Num(
+            T::try_from_str(&input[0..end]).unwrap(),
+            input[0..end].to_string(),
+        ),
         input[end..].to_string(),
         Position {
             line: pos.line,
@@ -55,39 +58,34 @@ pub fn parse_ide<T: Field>(input: &String, pos: &Position) -> (Token<T>, String,
         "private" => Token::Private,
         "def" => Token::Def,
         "return" => Token::Return,
-        "field" => match input.chars().nth(end) {
-            Some('[') => {
-                let size_start = end + 1;
-                let mut size_len = 0;
-                loop {
-                    match input.chars().nth(size_start + size_len) {
-                        Some(x) => match x {
-                            '0'...'9' => size_len += 1,
-                            _ => break,
-                        },
-                        None => break,
-                    }
-                }
-                assert!(size_len > 0);
-                let size_end = size_start + size_len;
-                match input.chars().nth(size_end) {
-                    Some(']') => {
-                        end = size_end + 1;
-                        Token::Type(Type::FieldElementArray(
-                            input[size_start..(size_start + size_len)]
-                                .parse::<usize>()
-                                .unwrap(),
-                        ))
-                    }
-                    _ => panic!(),
-                }
-            }
-            _ => Token::Type(Type::FieldElement),
-        },
+        "struct" => Token::Struct,
+        "const" => Token::Const,
+        "assert" => Token::Assert,
+        "field" => Token::Type(Type::FieldElement),
         "bool" => Token::Type(Type::Boolean),
+        "u8" => Token::Type(Type::Uint(8)),
+        "u16" => Token::Type(Type::Uint(16)),
+        "u32" => Token::Type(Type::Uint(32)),
+        "u64" => Token::Type(Type::Uint(64)),
         _ => Token::Ide(input[0..end].to_string()),
     };
 
+    // primitive types can be followed by array dimensions, such as `field[3][4]`
+    // malformed dimensions yield an unknown token so that the parser reports them
+    let token = match token {
+        Token::Type(ty) => match parse_array_dimensions(ty, input, end) {
+            Some((ty, array_end)) => {
+                end = array_end;
+                Token::Type(ty)
+            }
+            None => {
+                end = input.len();
+                Token::Unknown(input.clone())
+            }
+        },
+        t => t,
+    };
+
     (
         token,
         input[end..].to_string(),
@@ -98,6 +96,77 @@ pub fn parse_ide<T: Field>(input: &String, pos: &Position) -> (Token<T>, String,
     )
 }
 
+// parse the `[size]` suffixes of an array type starting at `end`, the outermost dimension coming first
+// sizes are either literals or the names of constants
+fn parse_array_dimensions(ty: Type, input: &String, mut end: usize) -> Option<(Type, usize)> {
+    let mut dimensions = vec![];
+    while let Some('[') = input.chars().nth(end) {
+        let size_start = end + 1;
+        let mut size_len = 0;
+        loop {
+            match input.chars().nth(size_start + size_len) {
+                Some(x) => match x {
+                    '0'...'9' => size_len += 1,
+                    'a'...'z' | 'A'...'Z' | '_' => size_len += 1,
+                    _ => break,
+                },
+                None => break,
+            }
+        }
+        let size_end = size_start + size_len;
+        match input.chars().nth(size_end) {
+            Some(']') if size_len > 0 => {
+                end = size_end + 1;
+                dimensions.push(input[size_start..size_end].to_string());
+            }
+            _ => return None,
+        }
+    }
+
+    let ty = dimensions
+        .into_iter()
+        .rev()
+        .fold(ty, |ty, size| match size.parse::<usize>() {
+            Ok(size) => Type::array(ty, size),
+            Err(_) => Type::Array(ArrayType::with_size_constant(ty, size)),
+        });
+
+    Some((ty, end))
+}
+
+pub fn parse_hex_num<T: Field>(input: &String, pos: &Position) -> (Token<T>, String, Position) {
+    assert!(input.starts_with("0x"));
+    let mut end = 2;
+    loop {
+        match input.chars().nth(end) {
+            Some(x) => match x {
+                '0'...'9' | 'a'...'f' | 'A'...'F' => end += 1,
+                _ => break,
+            },
+            None => break,
+        }
+    }
+    // a prefix without digits yields an unknown token so that the parser reports it
+    if end == 2 {
+        return (
+            Token::Unknown(String::from("0x")),
+            input[end..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + end,
+            },
+        );
+    }
+    (
+        Token::HexNum(input[2..end].to_string()),
+        input[end..].to_string(),
+        Position {
+            line: pos.line,
+            col: pos.col + end,
+        },
+    )
+}
+
 pub fn skip_whitespaces(input: &String) -> usize {
     let mut i = 0;
     loop {
@@ -119,7 +188,8 @@ pub fn parse_quoted_path<T: Field>(input: &String, pos: &Position) -> (Token<T>,
                     _ => continue,
                 }
             }
-            None => panic!("Invalid import path, should end with '\"'"),
+            // an unterminated path yields an unknown token so that the parser reports it
+            None => return (Token::Unknown(input.clone()), String::from(""), *pos),
         }
     }
     (
@@ -132,6 +202,25 @@ pub fn parse_quoted_path<T: Field>(input: &String, pos: &Position) -> (Token<T>,
     )
 }
 
+// parse a string literal starting after its opening quote, such as the message of an assertion
+// an unterminated literal yields an unknown token so that the parser reports it
+pub fn parse_quoted_string<T: Field>(
+    input: &String,
+    pos: &Position,
+) -> (Token<T>, String, Position) {
+    match input.find('"') {
+        Some(end) => (
+            Token::Str(input[0..end].to_string()),
+            input[end + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + end + 1,
+            },
+        ),
+        None => (Token::Unknown(input.clone()), String::from(""), *pos),
+    }
+}
+
 pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String, Position) {
     let offset = skip_whitespaces(input);
     match input.chars().nth(offset) {
@@ -202,6 +291,14 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                     col: pos.col + offset + 2,
                 },
             ),
+            Some('<') => (
+                Token::LeftShift,
+                input[offset + 2..].to_string(),
+                Position {
+                    line: pos.line,
+                    col: pos.col + offset + 2,
+                },
+            ),
             _ => (
                 Token::Lt,
                 input[offset + 1..].to_string(),
@@ -220,6 +317,14 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                     col: pos.col + offset + 2,
                 },
             ),
+            Some('>') => (
+                Token::RightShift,
+                input[offset + 2..].to_string(),
+                Position {
+                    line: pos.line,
+                    col: pos.col + offset + 2,
+                },
+            ),
             _ => (
                 Token::Gt,
                 input[offset + 1..].to_string(),
@@ -239,7 +344,7 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                 },
             ),
             _ => (
-                Token::Unknown(String::from("&")),
+                Token::BitAnd,
                 input[offset + 1..].to_string(),
                 Position {
                     line: pos.line,
@@ -257,7 +362,7 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                 },
             ),
             _ => (
-                Token::Unknown(String::from("|")),
+                Token::BitOr,
                 input[offset + 1..].to_string(),
                 Position {
                     line: pos.line,
@@ -265,6 +370,14 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                 },
             ),
         },
+        Some('^') => (
+            Token::BitXor,
+            input[offset + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + offset + 1,
+            },
+        ),
         Some('!') => (
             Token::Not,
             input[offset + 1..].to_string(),
@@ -341,6 +454,30 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                 col: pos.col + offset + 1,
             },
         ),
+        Some('{') => (
+            Token::LeftBrace,
+            input[offset + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + offset + 1,
+            },
+        ),
+        Some('}') => (
+            Token::RightBrace,
+            input[offset + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + offset + 1,
+            },
+        ),
+        Some(';') => (
+            Token::Semicolon,
+            input[offset + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + offset + 1,
+            },
+        ),
         Some('/') => match input.chars().nth(offset + 1) {
             Some('/') => (
                 Token::InlineComment(input[offset + 2..].to_string()),
@@ -367,6 +504,21 @@ pub fn next_token<T: Field>(input: &String, pos: &Position) -> (Token<T>, String
                 col: pos.col + offset + 2,
             },
         ),
+        Some('.') => (
+            Token::Dot,
+            input[offset + 1..].to_string(),
+            Position {
+                line: pos.line,
+                col: pos.col + offset + 1,
+            },
+        ),
+        Some(_) if input[offset..].starts_with("0x") => parse_hex_num(
+            &input[offset..].to_string(),
+            &Position {
+                line: pos.line,
+                col: pos.col + offset,
+            },
+        ),
         Some(x) => match x {
             '0'...'9' => parse_num(
                 &input[offset..].to_string(),
@@ -434,12 +586,25 @@ mod tests {
             );
         }
 
+        #[test]
+        fn uint() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                (
+                    Token::Type::<FieldPrime>(Type::Uint(32)),
+                    String::from(" a"),
+                    pos.col(3)
+                ),
+                parse_ide(&"u32 a".to_string(), &pos)
+            );
+        }
+
         #[test]
         fn field_array() {
             let pos = Position { line: 45, col: 121 };
             assert_eq!(
                 (
-                    Token::Type::<FieldPrime>(Type::FieldElementArray(123)),
+                    Token::Type::<FieldPrime>(Type::array(Type::FieldElement, 123)),
                     String::from(" "),
                     pos.col(10)
                 ),
@@ -452,7 +617,7 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             assert_eq!(
                 (
-                    Token::Type::<FieldPrime>(Type::FieldElementArray(1)),
+                    Token::Type::<FieldPrime>(Type::array(Type::FieldElement, 1)),
                     String::from(" "),
                     pos.col(8)
                 ),
@@ -460,25 +625,83 @@ mod tests {
             );
         }
 
-        #[should_panic]
+        #[test]
+        fn nested_array() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                (
+                    Token::Type::<FieldPrime>(Type::array(Type::array(Type::Boolean, 4), 3)),
+                    String::from(" a"),
+                    pos.col(10)
+                ),
+                parse_ide(&"bool[3][4] a".to_string(), &pos)
+            );
+            assert_eq!(
+                (
+                    Token::Type::<FieldPrime>(Type::array(Type::Uint(8), 2)),
+                    String::from(" a"),
+                    pos.col(5)
+                ),
+                parse_ide(&"u8[2] a".to_string(), &pos)
+            );
+        }
+
+        #[test]
+        fn constant_array() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                (
+                    Token::Type::<FieldPrime>(Type::array(
+                        Type::Array(ArrayType::with_size_constant(
+                            Type::FieldElement,
+                            String::from("N")
+                        )),
+                        2
+                    )),
+                    String::from(" a"),
+                    pos.col(11)
+                ),
+                parse_ide(&"field[2][N] a".to_string(), &pos)
+            );
+        }
+
         #[test]
         fn field_array_no_size() {
             let pos = Position { line: 45, col: 121 };
-            parse_ide::<FieldPrime>(&"field[] ".to_string(), &pos);
+            assert_eq!(
+                (
+                    Token::Unknown(String::from("field[] ")),
+                    String::from(""),
+                    pos.col(8)
+                ),
+                parse_ide::<FieldPrime>(&"field[] ".to_string(), &pos)
+            );
         }
 
-        #[should_panic]
         #[test]
         fn field_array_unclosed() {
             let pos = Position { line: 45, col: 121 };
-            parse_ide::<FieldPrime>(&"field[123 ".to_string(), &pos);
+            assert_eq!(
+                (
+                    Token::Unknown(String::from("field[123 ")),
+                    String::from(""),
+                    pos.col(10)
+                ),
+                parse_ide::<FieldPrime>(&"field[123 ".to_string(), &pos)
+            );
         }
 
-        #[should_panic]
         #[test]
         fn field_array_empty_unclosed() {
             let pos = Position { line: 45, col: 121 };
-            parse_ide::<FieldPrime>(&"field[ ".to_string(), &pos);
+            assert_eq!(
+                (
+                    Token::Unknown(String::from("field[ a)")),
+                    String::from(""),
+                    pos.col(9)
+                ),
+                parse_ide::<FieldPrime>(&"field[ a)".to_string(), &pos)
+            );
         }
 
         #[test]
@@ -495,6 +718,153 @@ mod tests {
         }
     }
 
+    mod operators {
+        use super::*;
+
+        #[test]
+        fn bitwise() {
+            let pos = Position { line: 45, col: 121 };
+            let (t, s, p) = next_token::<FieldPrime>(&"a & b".to_string(), &pos);
+            assert_eq!(t, Token::Ide(String::from("a")));
+            let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+            assert_eq!(t, Token::BitAnd);
+            assert_eq!(s, " b");
+            assert_eq!(p, pos.col(3));
+            assert_eq!(
+                next_token::<FieldPrime>(&" | b".to_string(), &pos).0,
+                Token::BitOr
+            );
+            assert_eq!(
+                next_token::<FieldPrime>(&" ^ b".to_string(), &pos).0,
+                Token::BitXor
+            );
+        }
+
+        #[test]
+        fn shifts() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                next_token::<FieldPrime>(&"<< 3".to_string(), &pos),
+                (Token::LeftShift, String::from(" 3"), pos.col(2))
+            );
+            assert_eq!(
+                next_token::<FieldPrime>(&">> 3".to_string(), &pos),
+                (Token::RightShift, String::from(" 3"), pos.col(2))
+            );
+            assert_eq!(
+                next_token::<FieldPrime>(&"<= 3".to_string(), &pos).0,
+                Token::Le
+            );
+        }
+    }
+
+    mod structs {
+        use super::*;
+
+        #[test]
+        fn declaration() {
+            let pos = Position { line: 45, col: 121 };
+            let (t, s, p) = next_token::<FieldPrime>(&"struct Foo {".to_string(), &pos);
+            assert_eq!(t, Token::Struct);
+            let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+            assert_eq!(t, Token::Ide(String::from("Foo")));
+            let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+            assert_eq!(t, Token::LeftBrace);
+            assert_eq!(s, "");
+            assert_eq!(p, pos.col(12));
+        }
+
+        #[test]
+        fn member_access() {
+            let pos = Position { line: 45, col: 121 };
+            let (t, s, p) = next_token::<FieldPrime>(&"a.b".to_string(), &pos);
+            assert_eq!(t, Token::Ide(String::from("a")));
+            let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+            assert_eq!(t, Token::Dot);
+            assert_eq!(
+                next_token::<FieldPrime>(&s, &p),
+                (Token::Ide(String::from("b")), String::from(""), pos.col(3))
+            );
+            assert_eq!(
+                next_token::<FieldPrime>(&"..".to_string(), &pos).0,
+                Token::Dotdot
+            );
+        }
+    }
+
+    #[test]
+    fn constant() {
+        let pos = Position { line: 45, col: 121 };
+        let (t, s, p) = next_token::<FieldPrime>(&"const field N = 3".to_string(), &pos);
+        assert_eq!(t, Token::Const);
+        let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+        assert_eq!(t, Token::Type(Type::FieldElement));
+        let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+        assert_eq!(t, Token::Ide(String::from("N")));
+        assert_eq!(
+            next_token::<FieldPrime>(&s, &p),
+            (Token::Eq, String::from(" 3"), pos.col(15))
+        );
+    }
+
+    #[test]
+    fn assertion() {
+        let pos = Position { line: 45, col: 121 };
+        let (t, s, p) = next_token::<FieldPrime>(&"assert(a == 1, \"a is 1\")".to_string(), &pos);
+        assert_eq!(t, Token::Assert);
+        let (_, s, p) = next_token::<FieldPrime>(&s, &p);
+        let (_, s, p) = next_token::<FieldPrime>(&s, &p);
+        let (_, s, p) = next_token::<FieldPrime>(&s, &p);
+        let (_, s, p) = next_token::<FieldPrime>(&s, &p);
+        let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+        assert_eq!(t, Token::Comma);
+        let (t, s, p) = next_token::<FieldPrime>(&s, &p);
+        assert_eq!(t, Token::DoubleQuote);
+        assert_eq!(
+            parse_quoted_string::<FieldPrime>(&s, &p),
+            (Token::Str(String::from("a is 1")), String::from(")"), pos.col(23))
+        );
+    }
+
+    #[test]
+    fn unterminated_string() {
+        let pos = Position { line: 45, col: 121 };
+        assert_eq!(
+            parse_quoted_string::<FieldPrime>(&"a is 1)".to_string(), &pos).0,
+            Token::Unknown(String::from("a is 1)"))
+        );
+    }
+
+    mod parse_hex_num {
+        use super::*;
+
+        #[test]
+        fn u32() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                (
+                    Token::<FieldPrime>::HexNum(String::from("deadBEEF")),
+                    String::from(" + 1"),
+                    pos.col(10)
+                ),
+                next_token(&"0xdeadBEEF + 1".to_string(), &pos)
+            );
+        }
+
+        #[test]
+        fn no_digits() {
+            let pos = Position { line: 45, col: 121 };
+            assert_eq!(
+                (
+                    Token::<FieldPrime>::Unknown(String::from("0x")),
+                    String::from(")"),
+                    pos.col(2)
+                ),
+                parse_hex_num(&"0x)".to_string(), &pos)
+            );
+        }
+    }
+
     mod parse_num {
         use super::*;
 
@@ -503,7 +873,7 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             assert_eq!(
                 (
-                    Token::Num(FieldPrime::from(12234)),
+                    Token::Num(FieldPrime::from(12234), String::from("12234")),
                     String::from(""),
                     pos.col(5)
                 ),
@@ -516,7 +886,7 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             assert_eq!(
                 (
-                    Token::Num(FieldPrime::from(354)),
+                    Token::Num(FieldPrime::from(354), String::from("354")),
                     String::from("+879"),
                     pos.col(3)
                 ),
@@ -529,7 +899,7 @@ mod tests {
             let pos = Position { line: 45, col: 121 };
             assert_eq!(
                 (
-                    Token::Num(FieldPrime::from(354)),
+                    Token::Num(FieldPrime::from(354), String::from("354")),
                     String::from(" "),
                     pos.col(3)
                 ),
@@ -0,0 +1,679 @@
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bincode::{deserialize_from, serialize_into, Infinite};
+use flat_absy::flat_variable::FlatVariable;
+use num_bigint::BigUint;
+use proof_system::utils::SOLIDITY_PAIRING_LIB;
+use proof_system::ProofSystem;
+use rand::rngs::OsRng;
+use regex::Regex;
+use serde_json::{self, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::str::FromStr;
+
+use zokrates_field::field::{Field, FieldPrime};
+
+pub struct G16 {}
+
+impl G16 {
+    pub fn new() -> G16 {
+        G16 {}
+    }
+}
+
+// The constraint system as produced by `ir::r1cs_program`. Groth16 proving keys do not embed the
+// constraints, so we store them next to the key in order to synthesize the witness at proving time.
+#[derive(Serialize, Deserialize)]
+struct R1CS {
+    num_variables: usize,
+    num_inputs: usize,
+    a: Vec<Vec<(usize, FieldPrime)>>,
+    b: Vec<Vec<(usize, FieldPrime)>>,
+    c: Vec<Vec<(usize, FieldPrime)>>,
+}
+
+// A circuit over the R1CS. Variable 0 is `~one`, variables `1..=num_inputs` are public and the
+// rest are private. The witness is only available when generating a proof.
+struct Computation<'a> {
+    r1cs: &'a R1CS,
+    witness: Option<Vec<Fr>>,
+}
+
+fn to_fr(value: &FieldPrime) -> Fr {
+    Fr::from_le_bytes_mod_order(&value.into_byte_vector())
+}
+
+fn to_linear_combination(
+    row: &Vec<(usize, FieldPrime)>,
+    variables: &Vec<Variable>,
+) -> LinearCombination<Fr> {
+    LinearCombination(
+        row.iter()
+            .map(|&(index, ref coeff)| (to_fr(coeff), variables[index]))
+            .collect(),
+    )
+}
+
+impl<'a> ConstraintSynthesizer<Fr> for Computation<'a> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let witness = self.witness;
+        let value = |index: usize| {
+            witness
+                .as_ref()
+                .map(|w| w[index])
+                .ok_or(SynthesisError::AssignmentMissing)
+        };
+
+        let mut variables = vec![Variable::One];
+        for index in 1..self.r1cs.num_variables {
+            let variable = match index <= self.r1cs.num_inputs {
+                true => cs.new_input_variable(|| value(index))?,
+                false => cs.new_witness_variable(|| value(index))?,
+            };
+            variables.push(variable);
+        }
+
+        for ((a, b), c) in self
+            .r1cs
+            .a
+            .iter()
+            .zip(self.r1cs.b.iter())
+            .zip(self.r1cs.c.iter())
+        {
+            cs.enforce_constraint(
+                to_linear_combination(a, &variables),
+                to_linear_combination(b, &variables),
+                to_linear_combination(c, &variables),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn fq_to_hex(value: &Fq) -> String {
+    format!(
+        "0x{}",
+        value
+            .into_bigint()
+            .to_bytes_be()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    )
+}
+
+fn fq2_to_hex(value: &Fq2) -> (String, String) {
+    // Ethereum's precompiles expect the imaginary part first
+    (fq_to_hex(&value.c1), fq_to_hex(&value.c0))
+}
+
+fn g1_to_hex(point: &G1Affine) -> String {
+    format!("{}, {}", fq_to_hex(&point.x), fq_to_hex(&point.y))
+}
+
+fn g2_to_hex(point: &G2Affine) -> String {
+    let (x1, x0) = fq2_to_hex(&point.x);
+    let (y1, y0) = fq2_to_hex(&point.y);
+    format!("[{}, {}], [{}, {}]", x1, x0, y1, y0)
+}
+
+fn g1_to_json(point: &G1Affine) -> String {
+    format!(
+        "[\"{}\", \"{}\"]",
+        fq_to_hex(&point.x),
+        fq_to_hex(&point.y)
+    )
+}
+
+fn g2_to_json(point: &G2Affine) -> String {
+    let (x1, x0) = fq2_to_hex(&point.x);
+    let (y1, y0) = fq2_to_hex(&point.y);
+    format!("[[\"{}\", \"{}\"], [\"{}\", \"{}\"]]", x1, x0, y1, y0)
+}
+
+// coordinates are rejected rather than reduced when they are not smaller than the modulus, so
+// that each point has a single encoding
+fn hex_to_fq(hex: &str) -> Option<Fq> {
+    let hex = hex.trim();
+    let hex = hex.trim_start_matches("0x");
+    let n = BigUint::parse_bytes(hex.as_bytes(), 16)?;
+    match n < BigUint::from_bytes_be(&Fq::MODULUS.to_bytes_be()) {
+        true => Some(Fq::from_be_bytes_mod_order(&n.to_bytes_be())),
+        false => None,
+    }
+}
+
+fn hex_to_fq2(c1: &str, c0: &str) -> Option<Fq2> {
+    Some(Fq2::new(hex_to_fq(c0)?, hex_to_fq(c1)?))
+}
+
+fn to_g1(x: Fq, y: Fq) -> Option<G1Affine> {
+    let point = G1Affine::new_unchecked(x, y);
+    match point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() {
+        true => Some(point),
+        false => None,
+    }
+}
+
+fn to_g2(x: Fq2, y: Fq2) -> Option<G2Affine> {
+    let point = G2Affine::new_unchecked(x, y);
+    match point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() {
+        true => Some(point),
+        false => None,
+    }
+}
+
+// parses `0x.., 0x..` as written by `g1_to_hex`
+fn g1_from_hex(s: &str) -> Option<G1Affine> {
+    let coordinates: Vec<&str> = s.split(",").collect();
+    match coordinates.len() {
+        2 => to_g1(hex_to_fq(coordinates[0])?, hex_to_fq(coordinates[1])?),
+        _ => None,
+    }
+}
+
+// parses `[0x.., 0x..], [0x.., 0x..]` as written by `g2_to_hex`
+fn g2_from_hex(s: &str) -> Option<G2Affine> {
+    let coordinates: Vec<&str> = s
+        .split(|c| c == ',' || c == '[' || c == ']')
+        .map(|c| c.trim())
+        .filter(|c| !c.is_empty())
+        .collect();
+    match coordinates.len() {
+        4 => to_g2(
+            hex_to_fq2(coordinates[0], coordinates[1])?,
+            hex_to_fq2(coordinates[2], coordinates[3])?,
+        ),
+        _ => None,
+    }
+}
+
+fn g1_from_json(value: &Value) -> Option<G1Affine> {
+    let coordinates = value.as_array()?;
+    match coordinates.len() {
+        2 => to_g1(
+            hex_to_fq(coordinates[0].as_str()?)?,
+            hex_to_fq(coordinates[1].as_str()?)?,
+        ),
+        _ => None,
+    }
+}
+
+fn g2_from_json(value: &Value) -> Option<G2Affine> {
+    let coordinates = value.as_array()?;
+    match coordinates.len() {
+        2 => {
+            let x = coordinates[0].as_array()?;
+            let y = coordinates[1].as_array()?;
+            match (x.len(), y.len()) {
+                (2, 2) => to_g2(
+                    hex_to_fq2(x[0].as_str()?, x[1].as_str()?)?,
+                    hex_to_fq2(y[0].as_str()?, y[1].as_str()?)?,
+                ),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn read_verification_key(reader: BufReader<File>) -> Result<VerifyingKey<Bn254>, String> {
+    let mut entries = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line.map_err(|why| format!("Error reading verification key: {}", why))?;
+        let split: Vec<&str> = line.split("=").collect();
+        if split.len() != 2 {
+            return Err(format!("Invalid line in verification key: {}", line));
+        }
+        entries.insert(split[0].trim().to_string(), split[1].trim().to_string());
+    }
+
+    let get = |key: &str| {
+        entries
+            .get(key)
+            .ok_or(format!("Missing {} in verification key", key))
+    };
+    let invalid = |key: &str| format!("Invalid point for {} in verification key", key);
+
+    let gamma_abc_count: usize = get("vk.gammaABC.len()")?
+        .parse()
+        .map_err(|_| invalid("vk.gammaABC.len()"))?;
+
+    let gamma_abc_g1 = (0..gamma_abc_count)
+        .map(|i| {
+            let key = format!("vk.gammaABC[{}]", i);
+            g1_from_hex(get(&key)?).ok_or(invalid(&key))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(VerifyingKey {
+        alpha_g1: g1_from_hex(get("vk.alpha")?).ok_or(invalid("vk.alpha"))?,
+        beta_g2: g2_from_hex(get("vk.beta")?).ok_or(invalid("vk.beta"))?,
+        gamma_g2: g2_from_hex(get("vk.gamma")?).ok_or(invalid("vk.gamma"))?,
+        delta_g2: g2_from_hex(get("vk.delta")?).ok_or(invalid("vk.delta"))?,
+        gamma_abc_g1,
+    })
+}
+
+fn read_proof(reader: BufReader<File>) -> Result<(Proof<Bn254>, Vec<Fr>), String> {
+    let json: Value = serde_json::from_reader(reader)
+        .map_err(|why| format!("Error reading proof: {}", why))?;
+
+    let proof = Proof {
+        a: g1_from_json(&json["proof"]["A"]).ok_or("Invalid point A in proof".to_string())?,
+        b: g2_from_json(&json["proof"]["B"]).ok_or("Invalid point B in proof".to_string())?,
+        c: g1_from_json(&json["proof"]["C"]).ok_or("Invalid point C in proof".to_string())?,
+    };
+
+    let inputs = json["input"]
+        .as_array()
+        .ok_or("Missing inputs in proof".to_string())?
+        .iter()
+        .map(|i| {
+            let decimal = match *i {
+                Value::String(ref s) => s.clone(),
+                Value::Number(ref n) => n.to_string(),
+                _ => return Err(format!("Invalid input in proof: {}", i)),
+            };
+            Fr::from_str(&decimal).map_err(|_| format!("Invalid input in proof: {}", i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((proof, inputs))
+}
+
+impl ProofSystem for G16 {
+    fn setup(
+        &self,
+        variables: Vec<FlatVariable>,
+        a: Vec<Vec<(usize, FieldPrime)>>,
+        b: Vec<Vec<(usize, FieldPrime)>>,
+        c: Vec<Vec<(usize, FieldPrime)>>,
+        num_inputs: usize,
+        pk_path: &str,
+        vk_path: &str,
+    ) -> bool {
+        let r1cs = R1CS {
+            num_variables: variables.len(),
+            num_inputs,
+            a,
+            b,
+            c,
+        };
+
+        let computation = Computation {
+            r1cs: &r1cs,
+            witness: None,
+        };
+
+        let pk = match Groth16::<Bn254>::generate_random_parameters_with_reduction(
+            computation,
+            &mut OsRng,
+        ) {
+            Ok(pk) => pk,
+            Err(e) => {
+                println!("setup failed: {}", e);
+                return false;
+            }
+        };
+
+        // write the proving key, followed by the constraint system
+        let mut pk_writer = match File::create(pk_path) {
+            Ok(file) => BufWriter::new(file),
+            Err(why) => {
+                println!("couldn't create {}: {}", pk_path, why);
+                return false;
+            }
+        };
+
+        if pk.serialize_uncompressed(&mut pk_writer).is_err()
+            || serialize_into(&mut pk_writer, &r1cs, Infinite).is_err()
+            || pk_writer.flush().is_err()
+        {
+            println!("Unable to write proving key to {}", pk_path);
+            return false;
+        }
+
+        // write the verification key in the same text format as the libsnark backends
+        let vk = &pk.vk;
+
+        let mut vk_text = String::new();
+        vk_text.push_str(&format!("vk.alpha = {}\n", g1_to_hex(&vk.alpha_g1)));
+        vk_text.push_str(&format!("vk.beta = {}\n", g2_to_hex(&vk.beta_g2)));
+        vk_text.push_str(&format!("vk.gamma = {}\n", g2_to_hex(&vk.gamma_g2)));
+        vk_text.push_str(&format!("vk.delta = {}\n", g2_to_hex(&vk.delta_g2)));
+        vk_text.push_str(&format!("vk.gammaABC.len() = {}\n", vk.gamma_abc_g1.len()));
+        for (i, point) in vk.gamma_abc_g1.iter().enumerate() {
+            vk_text.push_str(&format!("vk.gammaABC[{}] = {}\n", i, g1_to_hex(point)));
+        }
+
+        File::create(vk_path)
+            .and_then(|mut file| file.write_all(vk_text.as_bytes()))
+            .is_ok()
+    }
+
+    fn generate_proof(
+        &self,
+        pk_path: &str,
+        proof_path: &str,
+        public_inputs: Vec<FieldPrime>,
+        private_inputs: Vec<FieldPrime>,
+    ) -> bool {
+        let mut pk_reader = match File::open(pk_path) {
+            Ok(file) => BufReader::new(file),
+            Err(why) => {
+                println!("couldn't open {}: {}", pk_path, why);
+                return false;
+            }
+        };
+
+        let pk = match ProvingKey::<Bn254>::deserialize_uncompressed_unchecked(&mut pk_reader) {
+            Ok(pk) => pk,
+            Err(_) => {
+                println!("Unable to read proving key from {}", pk_path);
+                return false;
+            }
+        };
+
+        let r1cs: R1CS = match deserialize_from(&mut pk_reader, Infinite) {
+            Ok(r1cs) => r1cs,
+            Err(_) => {
+                println!("Unable to read constraint system from {}", pk_path);
+                return false;
+            }
+        };
+
+        // public inputs start with `~one`, which is not part of the instance
+        let inputs: Vec<FieldPrime> = public_inputs[1..].to_vec();
+
+        let computation = Computation {
+            r1cs: &r1cs,
+            witness: Some(
+                public_inputs
+                    .iter()
+                    .chain(private_inputs.iter())
+                    .map(to_fr)
+                    .collect(),
+            ),
+        };
+
+        let proof =
+            match Groth16::<Bn254>::create_random_proof_with_reduction(computation, &pk, &mut OsRng)
+            {
+                Ok(proof) => proof,
+                Err(e) => {
+                    println!("generate-proof failed: {}", e);
+                    return false;
+                }
+            };
+
+        println!("Proof:");
+        println!("A = Pairing.G1Point({});", g1_to_hex(&proof.a));
+        println!("B = Pairing.G2Point({});", g2_to_hex(&proof.b));
+        println!("C = Pairing.G1Point({});", g1_to_hex(&proof.c));
+
+        let proof_json = format!(
+            "{{\n\t\"proof\":\n\t{{\n\t\t\"A\":{},\n\t\t\"B\":\n\t\t\t{},\n\t\t\n\t\t\"C\":{}\n\t}},\n\t\"input\":[{}]\n}}\n",
+            g1_to_json(&proof.a),
+            g2_to_json(&proof.b),
+            g1_to_json(&proof.c),
+            inputs
+                .iter()
+                .map(|i| format!("\"{}\"", i.to_dec_string()))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+
+        File::create(proof_path)
+            .and_then(|mut file| file.write_all(proof_json.as_bytes()))
+            .is_ok()
+    }
+
+    fn export_solidity_verifier(&self, reader: BufReader<File>) -> String {
+        let mut lines = reader.lines();
+
+        let mut template_text = String::from(CONTRACT_TEMPLATE);
+        let gamma_abc_template = String::from("vk.gammaABC[index] = Pairing.G1Point(points);"); //copy this for each entry
+
+        //replace things in template
+        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
+        let vk_gamma_abc_len_regex = Regex::new(r#"(<%vk_gammaABC_length%>)"#).unwrap();
+        let vk_gamma_abc_index_regex = Regex::new(r#"index"#).unwrap();
+        let vk_gamma_abc_points_regex = Regex::new(r#"points"#).unwrap();
+        let vk_gamma_abc_repeat_regex = Regex::new(r#"(<%vk_gammaABC_pts%>)"#).unwrap();
+        let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
+
+        for _ in 0..4 {
+            let current_line: String = lines
+                .next()
+                .expect("Unexpected end of file in verification key!")
+                .unwrap();
+            let current_line_split: Vec<&str> = current_line.split("=").collect();
+            assert_eq!(current_line_split.len(), 2);
+            template_text = vk_regex
+                .replace(template_text.as_str(), current_line_split[1].trim())
+                .into_owned();
+        }
+
+        let current_line: String = lines
+            .next()
+            .expect("Unexpected end of file in verification key!")
+            .unwrap();
+        let current_line_split: Vec<&str> = current_line.split("=").collect();
+        assert_eq!(current_line_split.len(), 2);
+        let gamma_abc_count: i32 = current_line_split[1].trim().parse().unwrap();
+
+        template_text = vk_gamma_abc_len_regex
+            .replace(
+                template_text.as_str(),
+                format!("{}", gamma_abc_count).as_str(),
+            )
+            .into_owned();
+        template_text = vk_input_len_regex
+            .replace(
+                template_text.as_str(),
+                format!("{}", gamma_abc_count - 1).as_str(),
+            )
+            .into_owned();
+
+        let mut gamma_abc_repeat_text = String::new();
+        for x in 0..gamma_abc_count {
+            let mut curr_template = gamma_abc_template.clone();
+            let current_line: String = lines
+                .next()
+                .expect("Unexpected end of file in verification key!")
+                .unwrap();
+            let current_line_split: Vec<&str> = current_line.split("=").collect();
+            assert_eq!(current_line_split.len(), 2);
+            curr_template = vk_gamma_abc_index_regex
+                .replace(curr_template.as_str(), format!("{}", x).as_str())
+                .into_owned();
+            curr_template = vk_gamma_abc_points_regex
+                .replace(curr_template.as_str(), current_line_split[1].trim())
+                .into_owned();
+            gamma_abc_repeat_text.push_str(curr_template.as_str());
+            if x < gamma_abc_count - 1 {
+                gamma_abc_repeat_text.push_str("\n        ");
+            }
+        }
+        template_text = vk_gamma_abc_repeat_regex
+            .replace(template_text.as_str(), gamma_abc_repeat_text.as_str())
+            .into_owned();
+
+        format!("{}{}", SOLIDITY_PAIRING_LIB, template_text)
+    }
+
+    fn verify(&self, vk_path: &str, proof_path: &str) -> Result<bool, String> {
+        let vk = File::open(vk_path)
+            .map_err(|why| format!("couldn't open {}: {}", vk_path, why))
+            .and_then(|file| read_verification_key(BufReader::new(file)))?;
+
+        let (proof, inputs) = File::open(proof_path)
+            .map_err(|why| format!("couldn't open {}: {}", proof_path, why))
+            .and_then(|file| read_proof(BufReader::new(file)))?;
+
+        if inputs.len() + 1 != vk.gamma_abc_g1.len() {
+            return Err(format!(
+                "Wrong number of inputs. Given: {}, Required: {}.",
+                inputs.len(),
+                vk.gamma_abc_g1.len() - 1
+            ));
+        }
+
+        Ok(
+            Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&vk), &proof, &inputs)
+                .unwrap_or(false),
+        )
+    }
+}
+
+const CONTRACT_TEMPLATE: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gammaABC;
+    }
+    struct Proof {
+        Pairing.G1Point A;
+        Pairing.G2Point B;
+        Pairing.G1Point C;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey vk) {
+        vk.a = Pairing.G1Point(<%vk_a%>);
+        vk.b = Pairing.G2Point(<%vk_b%>);
+        vk.gamma = Pairing.G2Point(<%vk_gamma%>);
+        vk.delta = Pairing.G2Point(<%vk_delta%>);
+        vk.gammaABC = new Pairing.G1Point[](<%vk_gammaABC_length%>);
+        <%vk_gammaABC_pts%>
+    }
+    function verify(uint[] input, Proof proof) internal returns (uint) {
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.gammaABC.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = Pairing.G1Point(0, 0);
+        for (uint i = 0; i < input.length; i++)
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gammaABC[i + 1], input[i]));
+        vk_x = Pairing.addition(vk_x, vk.gammaABC[0]);
+        /**
+         * e(A, B) = e(G^{alpha}, H^{beta}) * e(vk_x, H^{gamma}) * e(C, H^{delta})
+         */
+        if(!Pairing.pairingProd4(
+             proof.A, proof.B,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.C), vk.delta,
+             Pairing.negate(vk.a), vk.b)) return 1;
+        return 0;
+    }
+    event Verified(string s);
+    function verifyTx(
+            uint[2] a,
+            uint[2][2] b,
+            uint[2] c,
+            uint[<%vk_input_length%>] input
+        ) public returns (bool r) {
+        Proof memory proof;
+        proof.A = Pairing.G1Point(a[0], a[1]);
+        proof.B = Pairing.G2Point([b[0][0], b[0][1]], [b[1][0], b[1][1]]);
+        proof.C = Pairing.G1Point(c[0], c[1]);
+        uint[] memory inputValues = new uint[](input.length);
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }
+        if (verify(inputValues, proof) == 0) {
+            emit Verified("Transaction successfully verified.");
+            return true;
+        } else {
+            return false;
+        }
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn non_canonical_coordinates() {
+        let modulus = BigUint::from_bytes_be(&Fq::MODULUS.to_bytes_be());
+        let hex = |n: BigUint| format!("0x{}", n.to_str_radix(16));
+
+        assert!(hex_to_fq(&hex(modulus.clone() - BigUint::from(1u32))).is_some());
+        assert_eq!(hex_to_fq(&hex(modulus.clone())), None);
+        assert_eq!(hex_to_fq(&hex(modulus + BigUint::from(1u32))), None);
+    }
+
+    #[test]
+    fn setup_prove_and_verify() {
+        // ~one, ~out_0 (public), _0 (private)
+        // _0 * _0 == ~out_0
+        let variables = vec![
+            FlatVariable::one(),
+            FlatVariable::public(0),
+            FlatVariable::new(0),
+        ];
+        let a = vec![vec![(2, FieldPrime::from(1))]];
+        let b = vec![vec![(2, FieldPrime::from(1))]];
+        let c = vec![vec![(1, FieldPrime::from(1))]];
+
+        let dir = env::temp_dir().join("zokrates_g16_setup_prove_and_verify");
+        fs::create_dir_all(&dir).unwrap();
+        let pk_path = dir.join("proving.key");
+        let vk_path = dir.join("verification.key");
+        let proof_path = dir.join("proof.json");
+
+        let g16 = G16::new();
+
+        assert!(g16.setup(
+            variables,
+            a,
+            b,
+            c,
+            1,
+            pk_path.to_str().unwrap(),
+            vk_path.to_str().unwrap()
+        ));
+
+        assert!(g16.generate_proof(
+            pk_path.to_str().unwrap(),
+            proof_path.to_str().unwrap(),
+            vec![FieldPrime::from(1), FieldPrime::from(9)],
+            vec![FieldPrime::from(3)]
+        ));
+
+        let verifier =
+            g16.export_solidity_verifier(BufReader::new(File::open(&vk_path).unwrap()));
+        assert!(verifier.contains("vk.gammaABC[1] = Pairing.G1Point(0x"));
+        assert!(verifier.contains("uint[1] input"));
+
+        assert_eq!(
+            g16.verify(vk_path.to_str().unwrap(), proof_path.to_str().unwrap()),
+            Ok(true)
+        );
+
+        // the same proof does not verify for a different output
+        let proof = fs::read_to_string(&proof_path).unwrap();
+        fs::write(&proof_path, proof.replace("\"9\"", "\"10\"")).unwrap();
+        assert_eq!(
+            g16.verify(vk_path.to_str().unwrap(), proof_path.to_str().unwrap()),
+            Ok(false)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
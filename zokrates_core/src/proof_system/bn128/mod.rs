@@ -1,5 +1,11 @@
+mod g16;
+#[cfg(feature = "libsnark")]
 mod gm17;
+#[cfg(feature = "libsnark")]
 mod pghr13;
 
+pub use self::g16::G16;
+#[cfg(feature = "libsnark")]
 pub use self::gm17::GM17;
+#[cfg(feature = "libsnark")]
 pub use self::pghr13::PGHR13;
@@ -186,6 +186,11 @@ impl ProofSystem for PGHR13 {
             SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, template_text
         )
     }
+    fn verify(&self, _vk_path: &str, _proof_path: &str) -> Result<bool, String> {
+        Err(String::from(
+            "Native verification is not supported by the PGHR13 backend, please use the exported verifier contract",
+        ))
+    }
 }
 
 const CONTRACT_TEMPLATE: &str = r#"contract Verifier {
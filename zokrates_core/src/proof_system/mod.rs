@@ -4,7 +4,10 @@ mod utils;
 use std::fs::File;
 use zokrates_field::field::FieldPrime;
 
+pub use self::bn128::G16;
+#[cfg(feature = "libsnark")]
 pub use self::bn128::GM17;
+#[cfg(feature = "libsnark")]
 pub use self::bn128::PGHR13;
 use flat_absy::flat_variable::FlatVariable;
 use std::io::BufReader;
@@ -30,4 +33,6 @@ pub trait ProofSystem {
     ) -> bool;
 
     fn export_solidity_verifier(&self, reader: BufReader<File>) -> String;
+
+    fn verify(&self, vk_path: &str, proof_path: &str) -> Result<bool, String>;
 }
@@ -1,9 +1,14 @@
+#[cfg(feature = "libsnark")]
 use flat_absy::flat_variable::FlatVariable;
+#[cfg(feature = "libsnark")]
 use std::cmp::max;
+#[cfg(feature = "libsnark")]
 use std::ffi::CString;
+#[cfg(feature = "libsnark")]
 use zokrates_field::field::Field;
 
 // utility function. Converts a Fields vector-based byte representation to fixed size array.
+#[cfg(feature = "libsnark")]
 fn vec_as_u8_32_array(vec: &Vec<u8>) -> [u8; 32] {
     assert!(vec.len() <= 32);
     let mut array = [0u8; 32];
@@ -14,6 +19,7 @@ fn vec_as_u8_32_array(vec: &Vec<u8>) -> [u8; 32] {
 }
 
 // proof-system-independent preparation for the setup phase
+#[cfg(feature = "libsnark")]
 pub fn prepare_setup<T: Field>(
     variables: Vec<FlatVariable>,
     a: Vec<Vec<(usize, T)>>,
@@ -146,6 +152,7 @@ pub fn prepare_setup<T: Field>(
 }
 
 // proof-system-independent preparation for proof generation
+#[cfg(feature = "libsnark")]
 pub fn prepare_generate_proof<T: Field>(
     pk_path: &str,
     proof_path: &str,
@@ -180,6 +187,7 @@ pub fn prepare_generate_proof<T: Field>(
     )
 }
 
+#[cfg(feature = "libsnark")]
 pub const SOLIDITY_G2_ADDITION_LIB: &str = r#"// This file is LGPL3 Licensed
 
 pragma solidity ^0.4.19;
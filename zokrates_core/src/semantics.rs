@@ -8,15 +8,17 @@
 
 use absy::variable::Variable;
 use absy::*;
-use std::collections::HashSet;
+use flat_absy::{DebugInfo, Span};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use typed_absy::*;
 use types::Signature;
 use zokrates_field::field::Field;
 
 use parser::Position;
+use static_analysis::constant_values;
 
-use types::Type;
+use types::{ArrayType, StructMember, StructType, Type};
 
 use std::hash::{Hash, Hasher};
 
@@ -26,6 +28,23 @@ pub struct Error {
     message: String,
 }
 
+impl Error {
+    pub fn no_main() -> Error {
+        Error {
+            pos: None,
+            message: format!("No main function found"),
+        }
+    }
+
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let location = self
@@ -123,7 +142,13 @@ pub struct FunctionDeclaration {
 pub struct Checker {
     scope: HashSet<ScopedVariable>,
     functions: HashSet<FunctionDeclaration>,
+    types: HashMap<String, StructType>,
+    constants: HashSet<String>,
+    // the values of the constants in decimal, as array sizes can refer to them
+    constant_values: HashMap<String, String>,
     level: usize,
+    // the function being checked, which the spans of its statements refer to
+    function: Option<String>,
 }
 
 impl Checker {
@@ -131,11 +156,56 @@ impl Checker {
         Checker {
             scope: HashSet::new(),
             functions: HashSet::new(),
+            types: HashMap::new(),
+            constants: HashSet::new(),
+            constant_values: HashMap::new(),
             level: 0,
+            function: None,
         }
     }
 
     pub fn check_program<T: Field>(&mut self, prog: Prog<T>) -> Result<TypedProg<T>, Vec<Error>> {
+        let mut errors = vec![];
+        let mut checked_functions = vec![];
+        let mut checked_constants = vec![];
+
+        for (id, _) in &prog.imported_constants {
+            match self.declare_constant(id.clone(), None) {
+                Ok(()) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        // constants can only refer to constants declared before them, and are checked before any
+        // function is declared so that they cannot call functions
+        for c in prog.constants {
+            let id = c.value.id.clone();
+            match self.check_constant(c) {
+                Ok(c) => checked_constants.push(c),
+                Err(e) => {
+                    errors.push(e);
+                    // still declare the constant so that it is not reported as undefined
+                    self.declare_constant(id, None).ok();
+                }
+            }
+        }
+
+        // the values of the constants are only known if they are all valid
+        if errors.len() > 0 {
+            return Err(errors);
+        }
+
+        self.constant_values = prog
+            .imported_constants
+            .iter()
+            .cloned()
+            .chain(constant_values(
+                checked_constants.clone(),
+                &prog.imported_constants,
+            ))
+            .map(|(id, value)| (id, value.to_dec_string()))
+            .collect();
+
         for func in &prog.imported_functions {
             self.functions.insert(FunctionDeclaration {
                 id: func.id.clone(),
@@ -143,10 +213,31 @@ impl Checker {
             });
         }
 
-        let mut errors = vec![];
-        let mut checked_functions = vec![];
+        // structs can only refer to structs declared before them
+        for s in prog.structs {
+            match self.check_struct_definition(s) {
+                Ok(()) => {}
+                Err(e) => errors.push(e),
+            }
+        }
 
         for func in prog.functions {
+            // resolve the struct types used in the function before checking it
+            let unresolved = FunctionDeclaration {
+                id: func.value.id.clone(),
+                signature: func.value.signature.clone(),
+            };
+
+            let func = match self.check_function_types(func) {
+                Ok(func) => func,
+                Err(e) => {
+                    errors.push(e);
+                    // still declare the function so that it is not reported as missing
+                    self.functions.insert(unresolved);
+                    continue;
+                }
+            };
+
             self.enter_scope();
 
             let dec = FunctionDeclaration {
@@ -166,10 +257,13 @@ impl Checker {
             self.exit_scope();
         }
 
-        match self.check_single_main() {
-            Ok(()) => {}
-            Err(e) => errors.push(e),
-        };
+        // modules which only declare constants are meant to be imported and do not need a main
+        if self.functions.len() > 0 || checked_constants.len() == 0 {
+            match self.check_single_main() {
+                Ok(()) => {}
+                Err(e) => errors.push(e),
+            };
+        }
 
         if errors.len() > 0 {
             return Err(errors);
@@ -177,7 +271,9 @@ impl Checker {
 
         Ok(TypedProg {
             functions: checked_functions,
+            constants: checked_constants,
             imported_functions: prog.imported_functions,
+            imported_constants: prog.imported_constants,
             imports: prog.imports.into_iter().map(|i| i.value).collect(),
         })
     }
@@ -185,10 +281,7 @@ impl Checker {
     fn check_single_main(&mut self) -> Result<(), Error> {
         match self.functions.iter().filter(|fun| fun.id == "main").count() {
             1 => Ok(()),
-            0 => Err(Error {
-                pos: None,
-                message: format!("No main function found"),
-            }),
+            0 => Err(Error::no_main()),
             n => Err(Error {
                 pos: None,
                 message: format!("Only one main function allowed, found {}", n),
@@ -196,6 +289,203 @@ impl Checker {
         }
     }
 
+    fn check_constant<T: Field>(&mut self, c: ConstantNode<T>) -> Result<TypedConstant<T>, Error> {
+        let pos = c.pos();
+        let c = c.value;
+
+        let expression = match self.check_expression(&c.expression)? {
+            TypedExpression::FieldElement(e) => e,
+            e => {
+                return Err(Error {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected constant {} to be of type field, found {}",
+                        c.id,
+                        e.get_type()
+                    ),
+                });
+            }
+        };
+
+        self.declare_constant(c.id.clone(), Some(pos))?;
+
+        Ok(TypedConstant {
+            id: c.id,
+            expression,
+        })
+    }
+
+    fn declare_constant(
+        &mut self,
+        id: String,
+        pos: Option<(Position, Position)>,
+    ) -> Result<(), Error> {
+        match self.insert_scope(Variable::field_element(id.clone())) {
+            true => {
+                self.constants.insert(id);
+                Ok(())
+            }
+            false => Err(Error {
+                pos,
+                message: format!("Duplicate definition for constant {}", id),
+            }),
+        }
+    }
+
+    // constants are visible in every function, they cannot be shadowed nor reassigned
+    fn check_not_constant(&self, id: &String, pos: (Position, Position)) -> Result<(), Error> {
+        match self.constants.contains(id) {
+            true => Err(Error {
+                pos: Some(pos),
+                message: format!("Cannot redefine constant {}", id),
+            }),
+            false => Ok(()),
+        }
+    }
+
+    fn check_struct_definition(&mut self, s: StructDefinitionNode) -> Result<(), Error> {
+        let pos = s.pos();
+        let s = s.value;
+
+        if self.types.contains_key(&s.id) {
+            return Err(Error {
+                pos: Some(pos),
+                message: format!("Duplicate definition for type {}", s.id),
+            });
+        }
+
+        let mut members: Vec<StructMember> = vec![];
+
+        for field in s.fields {
+            let field_pos = field.pos();
+            let field = field.value;
+
+            if members.iter().any(|m| m.id == field.id) {
+                return Err(Error {
+                    pos: Some(field_pos),
+                    message: format!("Duplicate member {} in struct {}", field.id, s.id),
+                });
+            }
+
+            members.push(StructMember {
+                ty: self.check_type(field.ty, field_pos)?,
+                id: field.id,
+            });
+        }
+
+        self.types.insert(s.id.clone(), StructType::new(s.id, members));
+        Ok(())
+    }
+
+    // struct types are parsed by name only, look up their definition
+    fn check_type(&self, ty: Type, pos: (Position, Position)) -> Result<Type, Error> {
+        match ty {
+            Type::Array(array_type) => {
+                let size = match array_type.size_constant {
+                    Some(ref id) => self.check_array_size(id, pos)?,
+                    None => array_type.size,
+                };
+                Ok(Type::array(self.check_type(*array_type.ty, pos)?, size))
+            }
+            Type::Struct(ty) => match self.types.get(&ty.id) {
+                Some(ty) => Ok(Type::Struct(ty.clone())),
+                None => Err(Error {
+                    pos: Some(pos),
+                    message: format!("Undefined type {}", ty.id),
+                }),
+            },
+            ty => Ok(ty),
+        }
+    }
+
+    // array sizes can be given by constants, whose values are known once they are checked
+    fn check_array_size(&self, id: &String, pos: (Position, Position)) -> Result<usize, Error> {
+        match self.constant_values.get(id) {
+            Some(value) => value.parse::<usize>().map_err(|_| Error {
+                pos: Some(pos),
+                message: format!(
+                    "Expected array size {} to be a valid size, found {}",
+                    id, value
+                ),
+            }),
+            None => Err(Error {
+                pos: Some(pos),
+                message: format!("Undefined constant {} in array size", id),
+            }),
+        }
+    }
+
+    fn check_function_types<T: Field>(
+        &self,
+        funct_node: FunctionNode<T>,
+    ) -> Result<FunctionNode<T>, Error> {
+        let pos = funct_node.pos();
+        let mut funct = funct_node.value;
+
+        for arg in funct.arguments.iter_mut() {
+            let arg_pos = arg.value.id.pos();
+            arg.value.id.value._type = self.check_type(arg.value.id.value._type.clone(), arg_pos)?;
+        }
+
+        for ty in funct
+            .signature
+            .inputs
+            .iter_mut()
+            .chain(funct.signature.outputs.iter_mut())
+        {
+            *ty = self.check_type(ty.clone(), pos)?;
+        }
+
+        let mut statements = vec![];
+        for stat in funct.statements {
+            statements.push(self.check_statement_types(stat)?);
+        }
+        funct.statements = statements;
+
+        Ok(Node::new(pos.0, pos.1, funct))
+    }
+
+    fn check_statement_types<T: Field>(
+        &self,
+        stat: StatementNode<T>,
+    ) -> Result<StatementNode<T>, Error> {
+        let pos = stat.pos();
+        let stat = match stat.value {
+            Statement::Declaration(mut var) => {
+                var.value._type = self.check_type(var.value._type.clone(), var.pos())?;
+                Statement::Declaration(var)
+            }
+            Statement::For(var, from, to, statements) => {
+                let mut checked_statements = vec![];
+                for stat in statements {
+                    checked_statements.push(self.check_statement_types(stat)?);
+                }
+                Statement::For(var, from, to, checked_statements)
+            }
+            s => s,
+        };
+        Ok(Node::new(pos.0, pos.1, stat))
+    }
+
+    // loop bounds can be any field expression, they are required to reduce to constants during
+    // static analysis
+    fn check_loop_bound<T: Field>(
+        &mut self,
+        bound: &ExpressionNode<T>,
+    ) -> Result<FieldElementExpression<T>, Error> {
+        match self.check_expression(bound)? {
+            TypedExpression::FieldElement(e) => Ok(e),
+            e => Err(Error {
+                pos: Some(bound.pos()),
+                message: format!(
+                    "Expected loop bound {} to be of type field, found {}",
+                    bound,
+                    e.get_type()
+                ),
+            }),
+        }
+    }
+
     fn check_for_var(&self, var: &VariableNode) -> Result<(), Error> {
         match var.value.get_type() {
             Type::FieldElement => Ok(()),
@@ -245,14 +535,21 @@ impl Checker {
         }
 
         for arg in funct.arguments.clone() {
+            match self.check_not_constant(&arg.value.id.value.id, arg.value.id.pos()) {
+                Ok(()) => {}
+                Err(e) => errors.push(e),
+            };
             self.insert_scope(arg.value.id.value);
         }
 
         let mut statements_checked = vec![];
 
+        self.function = Some(funct.id.clone());
+
         for stat in funct.statements.iter() {
             match self.check_statement(stat, &funct.signature.outputs) {
                 Ok(statement) => {
+                    statements_checked.push(TypedStatement::Span(span(stat, &self.function)));
                     statements_checked.push(statement);
                 }
                 Err(e) => {
@@ -285,8 +582,13 @@ impl Checker {
         match stat.value {
             Statement::Return(ref list) => {
                 let mut expression_list_checked = vec![];
-                for e in list.value.expressions.clone() {
+                for (i, e) in list.value.expressions.iter().enumerate() {
                     let e_checked = self.check_expression(&e)?;
+                    // decimal literals can be returned as unsigned integers
+                    let e_checked = match header_return_types.get(i) {
+                        Some(ty) => coerce_literals(e_checked, ty),
+                        None => e_checked,
+                    };
                     expression_list_checked.push(e_checked);
                 }
 
@@ -315,13 +617,19 @@ impl Checker {
                     }),
                 }
             }
-            Statement::Declaration(ref var) => match self.insert_scope(var.clone().value) {
-                true => Ok(TypedStatement::Declaration(var.value.clone().into())),
-                false => Err(Error {
-                    pos: Some(stat.pos()),
-                    message: format!("Duplicate declaration for variable named {}", var.value.id),
-                }),
-            },
+            Statement::Declaration(ref var) => {
+                self.check_not_constant(&var.value.id, var.pos())?;
+                match self.insert_scope(var.clone().value) {
+                    true => Ok(TypedStatement::Declaration(var.value.clone().into())),
+                    false => Err(Error {
+                        pos: Some(stat.pos()),
+                        message: format!(
+                            "Duplicate declaration for variable named {}",
+                            var.value.id
+                        ),
+                    }),
+                }
+            }
             Statement::Definition(ref assignee, ref expr) => {
                 // we create multidef when rhs is a function call to benefit from inference
                 // check rhs is not a function call here
@@ -332,13 +640,16 @@ impl Checker {
 
                 // check the expression to be assigned
                 let checked_expr = self.check_expression(&expr)?;
-                let expression_type = checked_expr.get_type();
 
                 // check that the assignee is declared and is well formed
                 let var = self.check_assignee(&assignee)?;
 
                 let var_type = var.get_type();
 
+                // decimal literals can be assigned to unsigned integers
+                let checked_expr = coerce_literals(checked_expr, &var_type);
+                let expression_type = checked_expr.get_type();
+
                 // make sure the assignee has the same type as the rhs
                 match var_type == expression_type {
                     true => Ok(TypedStatement::Definition(var, checked_expr)),
@@ -355,6 +666,8 @@ impl Checker {
                 let checked_lhs = self.check_expression(&lhs)?;
                 let checked_rhs = self.check_expression(&rhs)?;
 
+                let (checked_lhs, checked_rhs) = uint_literals(checked_lhs, checked_rhs);
+
                 match (checked_lhs.clone(), checked_rhs.clone()) {
                     (ref r, ref l) if r.get_type() == l.get_type() => {
                         Ok(TypedStatement::Condition(checked_lhs, checked_rhs))
@@ -371,10 +684,32 @@ impl Checker {
                     }),
                 }
             }
+            Statement::Assertion(ref e, ref message) => match self.check_expression(&e)? {
+                TypedExpression::Boolean(checked_e) => Ok(TypedStatement::Assertion(
+                    checked_e,
+                    DebugInfo {
+                        span: span(stat, &self.function),
+                        message: message.clone(),
+                    },
+                )),
+                checked_e => Err(Error {
+                    pos: Some(stat.pos()),
+                    message: format!(
+                        "Expected assertion {} to be of type bool, found {}",
+                        e,
+                        checked_e.get_type()
+                    ),
+                }),
+            },
             Statement::For(ref var, ref from, ref to, ref statements) => {
+                // bounds are checked outside of the loop scope
+                let from = self.check_loop_bound(from)?;
+                let to = self.check_loop_bound(to)?;
+
                 self.enter_scope();
 
                 self.check_for_var(&var)?;
+                self.check_not_constant(&var.value.id, var.pos())?;
 
                 self.insert_scope(var.clone().value);
 
@@ -382,14 +717,15 @@ impl Checker {
 
                 for stat in statements {
                     let checked_stat = self.check_statement(stat, header_return_types)?;
+                    checked_statements.push(TypedStatement::Span(span(stat, &self.function)));
                     checked_statements.push(checked_stat);
                 }
 
                 self.exit_scope();
                 Ok(TypedStatement::For(
                     var.value.clone().into(),
-                    from.clone(),
-                    to.clone(),
+                    from,
+                    to,
                     checked_statements,
                 ))
             }
@@ -403,6 +739,7 @@ impl Checker {
                         for assignee in assignees {
                             let (name, t) = match assignee.value {
                     			Assignee::Identifier(ref name) => {
+                    				self.check_not_constant(name, assignee.pos())?;
                     				Ok((name, match self.get_scope(&name) {
 					            		None => None,
 					            		Some(sv) => Some(sv.id.get_type())
@@ -467,6 +804,10 @@ impl Checker {
         // check that the assignee is declared
         match assignee.value {
             Assignee::Identifier(ref variable_name) => match self.get_scope(&variable_name) {
+                Some(_) if self.constants.contains(variable_name) => Err(Error {
+                    pos: Some(assignee.pos()),
+                    message: format!("Cannot assign to constant {}", variable_name),
+                }),
                 Some(var) => Ok(TypedAssignee::Identifier(var.id.clone().into())),
                 None => Err(Error {
                     pos: Some(assignee.pos()),
@@ -490,6 +831,19 @@ impl Checker {
                     }),
                 }?;
 
+                match checked_assignee.get_type() {
+                    Type::Array(..) => {}
+                    ty => {
+                        return Err(Error {
+                            pos: Some(assignee.pos()),
+                            message: format!(
+                                "Cannot access element {} of {} of type {}",
+                                index, assignee, ty
+                            ),
+                        });
+                    }
+                };
+
                 Ok(TypedAssignee::ArrayElement(
                     box checked_assignee,
                     box checked_typed_index,
@@ -511,8 +865,14 @@ impl Checker {
                         Type::FieldElement => {
                             Ok(FieldElementExpression::Identifier(name.to_string()).into())
                         }
-                        Type::FieldElementArray(n) => {
-                            Ok(FieldElementArrayExpression::Identifier(n, name.to_string()).into())
+                        Type::Array(ty) => {
+                            Ok(ArrayExpression::Identifier(ty, name.to_string()).into())
+                        }
+                        Type::Uint(bitwidth) => {
+                            Ok(UintExpression::Identifier(bitwidth, name.to_string()).into())
+                        }
+                        Type::Struct(ty) => {
+                            Ok(StructExpression::Identifier(ty, name.to_string()).into())
                         }
                     },
                     None => Err(Error {
@@ -530,15 +890,18 @@ impl Checker {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Add(box e1, box e2).into())
                     }
-                    (t1, t2) => Err(Error {
-                        pos: Some(expr.pos()),
-
-                        message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
-                            t1.get_type(),
-                            t2.get_type()
-                        ),
-                    }),
+                    (t1, t2) => match uint_operands(t1, t2) {
+                        Ok((e1, e2)) => Ok(UintExpression::Add(box e1, box e2).into()),
+                        Err((t1, t2)) => Err(Error {
+                            pos: Some(expr.pos()),
+
+                            message: format!(
+                                "Expected only field elements or unsigned integers of the same type, found {:?}, {:?}",
+                                t1.get_type(),
+                                t2.get_type()
+                            ),
+                        }),
+                    },
                 }
             }
             &Expression::Sub(ref e1, ref e2) => {
@@ -568,15 +931,18 @@ impl Checker {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Mult(box e1, box e2).into())
                     }
-                    (t1, t2) => Err(Error {
-                        pos: Some(expr.pos()),
-
-                        message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
-                            t1.get_type(),
-                            t2.get_type()
-                        ),
-                    }),
+                    (t1, t2) => match uint_operands(t1, t2) {
+                        Ok((e1, e2)) => Ok(UintExpression::Mult(box e1, box e2).into()),
+                        Err((t1, t2)) => Err(Error {
+                            pos: Some(expr.pos()),
+
+                            message: format!(
+                                "Expected only field elements or unsigned integers of the same type, found {:?}, {:?}",
+                                t1.get_type(),
+                                t2.get_type()
+                            ),
+                        }),
+                    },
                 }
             }
             &Expression::Div(ref e1, ref e2) => {
@@ -622,6 +988,9 @@ impl Checker {
                 let consequence_checked = self.check_expression(&consequence)?;
                 let alternative_checked = self.check_expression(&alternative)?;
 
+                let (consequence_checked, alternative_checked) =
+                    uint_literals(consequence_checked, alternative_checked);
+
                 match condition_checked {
                     TypedExpression::Boolean(condition) => {
                         let consequence_type = consequence_checked.get_type();
@@ -631,8 +1000,14 @@ impl Checker {
                                 (TypedExpression::FieldElement(consequence), TypedExpression::FieldElement(alternative)) => {
                                     Ok(FieldElementExpression::IfElse(box condition, box consequence, box alternative).into())
                                 },
-                                (TypedExpression::FieldElementArray(consequence), TypedExpression::FieldElementArray(alternative)) => {
-                                    Ok(FieldElementArrayExpression::IfElse(box condition, box consequence, box alternative).into())
+                                (TypedExpression::Array(consequence), TypedExpression::Array(alternative)) => {
+                                    Ok(ArrayExpression::IfElse(box condition, box consequence, box alternative).into())
+                                },
+                                (TypedExpression::Uint(consequence), TypedExpression::Uint(alternative)) => {
+                                    Ok(UintExpression::IfElse(box condition, box consequence, box alternative).into())
+                                },
+                                (TypedExpression::Struct(consequence), TypedExpression::Struct(alternative)) => {
+                                    Ok(StructExpression::IfElse(box condition, box consequence, box alternative).into())
                                 },
                                 _ => unimplemented!()
                             }
@@ -651,7 +1026,21 @@ impl Checker {
                     }),
                 }
             }
-            &Expression::Number(ref n) => Ok(FieldElementExpression::Number(n.clone()).into()),
+            &Expression::Number(ref n, _) => Ok(FieldElementExpression::Number(n.clone()).into()),
+            &Expression::HexNumber(ref digits) => match digits.len() {
+                2 | 4 | 8 | 16 => Ok(UintExpression::Value(
+                    digits.len() * 4,
+                    u64::from_str_radix(digits, 16).unwrap(),
+                )
+                .into()),
+                n => Err(Error {
+                    pos: Some(expr.pos()),
+                    message: format!(
+                        "Hexadecimal literal 0x{} should have 2, 4, 8 or 16 digits, found {}",
+                        digits, n
+                    ),
+                }),
+            },
             &Expression::FunctionCall(ref fun_id, ref arguments) => {
                 // check the arguments
                 let mut arguments_checked = vec![];
@@ -683,14 +1072,24 @@ impl Checker {
                                     arguments_checked,
                                 )
                                 .into()),
-                                Type::FieldElementArray(size) => {
-                                    Ok(FieldElementArrayExpression::FunctionCall(
-                                        size,
-                                        f.id.clone(),
-                                        arguments_checked,
-                                    )
-                                    .into())
-                                }
+                                Type::Array(ref ty) => Ok(ArrayExpression::FunctionCall(
+                                    ty.clone(),
+                                    f.id.clone(),
+                                    arguments_checked,
+                                )
+                                .into()),
+                                Type::Uint(bitwidth) => Ok(UintExpression::FunctionCall(
+                                    bitwidth,
+                                    f.id.clone(),
+                                    arguments_checked,
+                                )
+                                .into()),
+                                Type::Struct(ref ty) => Ok(StructExpression::FunctionCall(
+                                    ty.clone(),
+                                    f.id.clone(),
+                                    arguments_checked,
+                                )
+                                .into()),
                                 _ => unimplemented!(),
                             },
                             n => Err(Error {
@@ -755,10 +1154,15 @@ impl Checker {
             &Expression::Eq(ref e1, ref e2) => {
                 let e1_checked = self.check_expression(&e1)?;
                 let e2_checked = self.check_expression(&e2)?;
-                match (e1_checked, e2_checked) {
+                match uint_literals(e1_checked, e2_checked) {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Eq(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(ref e1), TypedExpression::Uint(ref e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::UintEq(box e1.clone(), box e2.clone()).into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(expr.pos()),
                         message: format!(
@@ -812,11 +1216,8 @@ impl Checker {
             &Expression::Select(ref array, ref index) => {
                 let array = self.check_expression(&array)?;
                 let index = self.check_expression(&index)?;
-                match (array.clone(), index.clone()) {
-                    (
-                        TypedExpression::FieldElementArray(ref a),
-                        TypedExpression::FieldElement(ref i),
-                    ) => Ok(FieldElementExpression::Select(box a.clone(), box i.clone()).into()),
+                match (array, index) {
+                    (TypedExpression::Array(a), TypedExpression::FieldElement(i)) => Ok(a.select(i)),
                     (a, e) => Err(Error {
                         pos: Some(expr.pos()),
                         message: format!(
@@ -840,37 +1241,112 @@ impl Checker {
                 // we infer the type to be the type of the first element
                 let inferred_type = expressions_checked.get(0).unwrap().get_type();
 
-                match inferred_type {
-                    Type::FieldElement => {
-                        // we check all expressions have that same type
-                        let mut unwrapped_expressions = vec![];
-
-                        for e in expressions_checked {
-                            let unwrapped_e = match e {
-                                TypedExpression::FieldElement(e) => Ok(e),
-                                e => Err(Error {
-                                    pos: Some(expr.pos()),
-
-                                    message: format!(
-                                        "Expected {} to have type {}, but type is {}",
-                                        e,
-                                        inferred_type,
-                                        e.get_type()
-                                    ),
-                                }),
-                            }?;
-                            unwrapped_expressions.push(unwrapped_e);
-                        }
+                // we check all expressions have that same type
+                let mut unwrapped_expressions = vec![];
+
+                for e in expressions_checked {
+                    let e = coerce_literals(e, &inferred_type);
+                    if e.get_type() != inferred_type {
+                        return Err(Error {
+                            pos: Some(expr.pos()),
+
+                            message: format!(
+                                "Expected {} to have type {}, but type is {}",
+                                e,
+                                inferred_type,
+                                e.get_type()
+                            ),
+                        });
+                    }
+                    unwrapped_expressions.push(e);
+                }
+
+                Ok(ArrayExpression::Value(
+                    ArrayType::new(inferred_type, size),
+                    unwrapped_expressions,
+                )
+                .into())
+            }
+            &Expression::BitAnd(ref e1, ref e2) => {
+                let e1_checked = self.check_expression(&e1)?;
+                let e2_checked = self.check_expression(&e2)?;
+                match uint_operands(e1_checked, e2_checked) {
+                    Ok((e1, e2)) => Ok(UintExpression::And(box e1, box e2).into()),
+                    Err((e1, e2)) => Err(Error {
+                        pos: Some(expr.pos()),
+
+                        message: format!(
+                            "cannot apply bitwise operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            &Expression::BitOr(ref e1, ref e2) => {
+                let e1_checked = self.check_expression(&e1)?;
+                let e2_checked = self.check_expression(&e2)?;
+                match uint_operands(e1_checked, e2_checked) {
+                    Ok((e1, e2)) => Ok(UintExpression::Or(box e1, box e2).into()),
+                    Err((e1, e2)) => Err(Error {
+                        pos: Some(expr.pos()),
 
-                        Ok(FieldElementArrayExpression::Value(size, unwrapped_expressions).into())
+                        message: format!(
+                            "cannot apply bitwise operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            &Expression::BitXor(ref e1, ref e2) => {
+                let e1_checked = self.check_expression(&e1)?;
+                let e2_checked = self.check_expression(&e2)?;
+                match uint_operands(e1_checked, e2_checked) {
+                    Ok((e1, e2)) => Ok(UintExpression::Xor(box e1, box e2).into()),
+                    Err((e1, e2)) => Err(Error {
+                        pos: Some(expr.pos()),
+
+                        message: format!(
+                            "cannot apply bitwise operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            &Expression::LeftShift(ref e, ref by) => {
+                let e_checked = self.check_expression(&e)?;
+                let by_checked = self.check_expression(&by)?;
+                match (e_checked, by_checked) {
+                    (TypedExpression::Uint(e), TypedExpression::FieldElement(by)) => {
+                        Ok(UintExpression::LeftShift(box e, box by).into())
                     }
-                    _ => Err(Error {
+                    (e, by) => Err(Error {
+                        pos: Some(expr.pos()),
+
+                        message: format!(
+                            "cannot shift {} by {}, expected an unsigned integer and a field element",
+                            e.get_type(),
+                            by.get_type()
+                        ),
+                    }),
+                }
+            }
+            &Expression::RightShift(ref e, ref by) => {
+                let e_checked = self.check_expression(&e)?;
+                let by_checked = self.check_expression(&by)?;
+                match (e_checked, by_checked) {
+                    (TypedExpression::Uint(e), TypedExpression::FieldElement(by)) => {
+                        Ok(UintExpression::RightShift(box e, box by).into())
+                    }
+                    (e, by) => Err(Error {
                         pos: Some(expr.pos()),
 
                         message: format!(
-                            "Only arrays of {} are supported, found {}",
-                            Type::FieldElement,
-                            inferred_type
+                            "cannot shift {} by {}, expected an unsigned integer and a field element",
+                            e.get_type(),
+                            by.get_type()
                         ),
                     }),
                 }
@@ -918,6 +1394,96 @@ impl Checker {
                     }),
                 }
             }
+            &Expression::Member(ref e, ref id) => {
+                let e_checked = self.check_expression(e)?;
+                match e_checked {
+                    TypedExpression::Struct(s) => {
+                        let member_type = s.struct_type().member_type(id).cloned();
+                        match member_type {
+                            Some(ty) => Ok(member(s, id.clone(), ty)),
+                            None => Err(Error {
+                                pos: Some(expr.pos()),
+                                message: format!(
+                                    "{} of type {} has no member named {}",
+                                    e,
+                                    s.get_type(),
+                                    id
+                                ),
+                            }),
+                        }
+                    }
+                    e_checked => Err(Error {
+                        pos: Some(expr.pos()),
+                        message: format!(
+                            "Cannot access member {} of {} of type {}",
+                            id,
+                            e,
+                            e_checked.get_type()
+                        ),
+                    }),
+                }
+            }
+            &Expression::InlineStruct(ref id, ref members) => {
+                let ty = match self.types.get(id) {
+                    Some(ty) => ty.clone(),
+                    None => {
+                        return Err(Error {
+                            pos: Some(expr.pos()),
+                            message: format!("Undefined type {}", id),
+                        });
+                    }
+                };
+
+                for (i, &(ref member_id, _)) in members.iter().enumerate() {
+                    if ty.member_type(member_id).is_none() {
+                        return Err(Error {
+                            pos: Some(expr.pos()),
+                            message: format!("Struct {} has no member named {}", id, member_id),
+                        });
+                    }
+                    if members[..i].iter().any(|&(ref other, _)| other == member_id) {
+                        return Err(Error {
+                            pos: Some(expr.pos()),
+                            message: format!("Duplicate member {} in struct {}", member_id, id),
+                        });
+                    }
+                }
+
+                // members can be given in any order, the value stores them in declaration order
+                let mut values = vec![];
+                for m in ty.members.iter() {
+                    let e = match members.iter().find(|&&(ref member_id, _)| member_id == &m.id) {
+                        Some(&(_, ref e)) => e,
+                        None => {
+                            return Err(Error {
+                                pos: Some(expr.pos()),
+                                message: format!("Missing member {} in struct {}", m.id, id),
+                            });
+                        }
+                    };
+
+                    let e_checked = self.check_expression(e)?;
+                    // decimal literals can be used for unsigned integer members
+                    let e_checked = coerce_literals(e_checked, &m.ty);
+
+                    if e_checked.get_type() != m.ty {
+                        return Err(Error {
+                            pos: Some(e.pos()),
+                            message: format!(
+                                "Member {} of struct {} should have type {}, found {} of type {}",
+                                m.id,
+                                id,
+                                m.ty,
+                                e,
+                                e_checked.get_type()
+                            ),
+                        });
+                    }
+                    values.push(e_checked);
+                }
+
+                Ok(StructExpression::Value(ty, values).into())
+            }
         }
     }
 
@@ -951,6 +1517,88 @@ impl Checker {
     }
 }
 
+// decimal literals are field elements, we let them stand for unsigned integers where one is
+// expected, as long as their value fits
+fn uint_literal<T: Field>(e: TypedExpression<T>, bitwidth: usize) -> TypedExpression<T> {
+    match e {
+        TypedExpression::FieldElement(FieldElementExpression::Number(n)) => {
+            match n.to_dec_string().parse::<u64>() {
+                Ok(v) if bitwidth == 64 || v >> bitwidth == 0 => {
+                    UintExpression::Value(bitwidth, v).into()
+                }
+                _ => FieldElementExpression::Number(n).into(),
+            }
+        }
+        e => e,
+    }
+}
+
+// coerce the decimal literals in `e` to unsigned integers where `ty` expects them, including in
+// the elements of inline arrays
+fn coerce_literals<T: Field>(e: TypedExpression<T>, ty: &Type) -> TypedExpression<T> {
+    match (e, ty) {
+        (e, &Type::Uint(bitwidth)) => uint_literal(e, bitwidth),
+        (TypedExpression::Array(ArrayExpression::Value(array_type, values)), &Type::Array(ref ty))
+            if values.len() == ty.size =>
+        {
+            let coerced: Vec<_> = values
+                .iter()
+                .map(|v| coerce_literals(v.clone(), &ty.ty))
+                .collect();
+            match coerced.iter().all(|v| v.get_type() == *ty.ty) {
+                true => ArrayExpression::Value(ty.clone(), coerced).into(),
+                false => ArrayExpression::Value(array_type, values).into(),
+            }
+        }
+        (e, _) => e,
+    }
+}
+
+// the location of a statement, attached to what it compiles to so that runtime errors can be reported
+fn span<T: Field>(stat: &StatementNode<T>, function: &Option<String>) -> Span {
+    Span {
+        file: None,
+        function: function.clone(),
+        start: stat.start,
+        end: stat.end,
+    }
+}
+
+fn member<T: Field>(s: StructExpression<T>, id: String, ty: Type) -> TypedExpression<T> {
+    match ty {
+        Type::FieldElement => FieldElementExpression::Member(box s, id).into(),
+        Type::Boolean => BooleanExpression::Member(box s, id).into(),
+        Type::Array(ty) => ArrayExpression::Member(ty, box s, id).into(),
+        Type::Uint(bitwidth) => UintExpression::Member(bitwidth, box s, id).into(),
+        Type::Struct(ty) => StructExpression::Member(ty, box s, id).into(),
+    }
+}
+
+fn uint_literals<T: Field>(
+    e1: TypedExpression<T>,
+    e2: TypedExpression<T>,
+) -> (TypedExpression<T>, TypedExpression<T>) {
+    match (e1.get_type(), e2.get_type()) {
+        (Type::Uint(bitwidth), _) => (e1, uint_literal(e2, bitwidth)),
+        (_, Type::Uint(bitwidth)) => (uint_literal(e1, bitwidth), e2),
+        _ => (e1, e2),
+    }
+}
+
+fn uint_operands<T: Field>(
+    e1: TypedExpression<T>,
+    e2: TypedExpression<T>,
+) -> Result<(UintExpression<T>, UintExpression<T>), (TypedExpression<T>, TypedExpression<T>)> {
+    match uint_literals(e1, e2) {
+        (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+            if e1.bitwidth() == e2.bitwidth() =>
+        {
+            Ok((e1, e2))
+        }
+        (e1, e2) => Err((e1, e2)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // use super::*;
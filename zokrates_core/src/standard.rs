@@ -1,6 +1,6 @@
-use flat_absy::{FlatExpression, FlatExpressionList, FlatFunction, FlatStatement};
+use flat_absy::{FlatExpression, FlatExpressionList, FlatFunction, FlatStatement, SourceMap};
 use flat_absy::{FlatParameter, FlatVariable};
-use helpers::{DirectiveStatement, Helper, LibsnarkGadgetHelper};
+use helpers::{DirectiveStatement, Helper};
 use reduce::Reduce;
 use std::collections::BTreeMap;
 use types::{Signature, Type};
@@ -27,6 +27,45 @@ pub struct R1CS {
     pub constraints: Vec<Constraint>,
 }
 
+impl R1CS {
+    /// Checks that the variables refer to the witness and that the coefficients are elements of
+    /// the field `T`
+    pub fn validate<T: Field>(&self) -> Result<(), String> {
+        if self.variable_count == 0 {
+            return Err(String::from("The witness should contain at least the variable ~one"));
+        }
+
+        let indices = self
+            .constraints
+            .iter()
+            .flat_map(|c| c.a.keys().chain(c.b.keys()).chain(c.c.keys()));
+
+        if let Some(i) = self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(indices)
+            .find(|i| **i >= self.variable_count)
+        {
+            return Err(format!(
+                "Variable {} is out of the witness of {} variables",
+                i, self.variable_count
+            ));
+        }
+
+        let coefficients = self
+            .constraints
+            .iter()
+            .flat_map(|c| c.a.values().chain(c.b.values()).chain(c.c.values()));
+
+        for c in coefficients {
+            T::try_from_str(c).map_err(|_| format!("Invalid coefficient {}", c))?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Witness {
     pub variables: Vec<usize>,
@@ -39,9 +78,10 @@ pub struct Constraint {
     c: BTreeMap<usize, String>,
 }
 
+/// A R1CS along with the helper computing its whole witness from its inputs
 pub struct DirectiveR1CS {
     pub r1cs: R1CS,
-    pub directive: LibsnarkGadgetHelper,
+    pub helper: Helper,
 }
 
 impl<T: Field> Into<FlatStatement<T>> for Constraint {
@@ -100,7 +140,7 @@ impl<T: Field> Into<FlatStatement<T>> for Constraint {
             None => FlatExpression::Number(T::zero()),
         };
 
-        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b))
+        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b), None)
     }
 }
 
@@ -113,11 +153,13 @@ impl<T: Field> Into<FlatFunction<T>> for DirectiveR1CS {
         let input_binding_statements = std::iter::once(FlatStatement::Condition(
             FlatVariable::new(0).into(),
             FlatExpression::Number(T::from(1)),
+            None,
         ))
         .chain(r1cs.inputs.iter().enumerate().map(|(index, i)| {
             FlatStatement::Condition(
                 FlatVariable::new(*i).into(),
                 FlatVariable::new(index + variable_count).into(),
+                None,
             )
         }));
 
@@ -159,14 +201,13 @@ impl<T: Field> Into<FlatFunction<T>> for DirectiveR1CS {
             outputs: vec![Type::FieldElement; outputs.len()],
         };
 
-        // insert a directive to set the witness based on the libsnark gadget and  inputs
-        let directive_statement = match self.directive {
-            LibsnarkGadgetHelper::Sha256Round => FlatStatement::Directive(DirectiveStatement {
-                outputs: variables,
-                inputs: inputs,
-                helper: Helper::LibsnarkGadget(LibsnarkGadgetHelper::Sha256Round),
-            }),
-        };
+        // insert a directive to set the witness based on the helper and inputs
+        let directive_statement = FlatStatement::Directive(DirectiveStatement {
+            outputs: variables,
+            inputs: inputs,
+            helper: self.helper,
+            span: None,
+        });
 
         // insert a statement to return the subset of the witness
         let return_statement = FlatStatement::Return(FlatExpressionList {
@@ -184,6 +225,7 @@ impl<T: Field> Into<FlatFunction<T>> for DirectiveR1CS {
             arguments,
             statements,
             signature,
+            source_map: SourceMap::default(),
         }
     }
 }
@@ -200,6 +242,34 @@ mod tests {
         let _c: Constraint = serde_json::from_str(constraint).unwrap();
     }
 
+    #[test]
+    fn validate() {
+        let r1cs: R1CS = serde_json::from_str(
+            r#"{
+                "variable_count": 3,
+                "inputs": [1],
+                "outputs": [2],
+                "constraints": [[{"1": "1"}, {"1": "1"}, {"2": "1"}]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(r1cs.validate::<FieldPrime>(), Ok(()));
+
+        let r1cs: R1CS = serde_json::from_str(
+            r#"{
+                "variable_count": 2,
+                "inputs": [1],
+                "outputs": [2],
+                "constraints": [[{"1": "1"}, {"1": "1"}, {"2": "1"}]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            r1cs.validate::<FieldPrime>(),
+            Err(String::from("Variable 2 is out of the witness of 2 variables"))
+        );
+    }
+
     #[test]
     fn constraint_into_flat_statement() {
         let constraint = r#"[{"2026": "1"}, {"0": "1", "2026": "1751751751751751751751751751751751751751751"}, {"0": "0"}]"#;
@@ -208,15 +278,17 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "libsnark")]
     fn generate_sha256_constraints() {
         use flat_absy::FlatProg;
+        use helpers::LibsnarkGadgetHelper;
         use libsnark::get_sha256round_constraints;
         let r1cs: R1CS = serde_json::from_str(&get_sha256round_constraints()).unwrap();
         let v_count = r1cs.variable_count;
 
         let dr1cs: DirectiveR1CS = DirectiveR1CS {
             r1cs,
-            directive: LibsnarkGadgetHelper::Sha256Round,
+            helper: Helper::LibsnarkGadget(LibsnarkGadgetHelper::Sha256Round),
         };
         let compiled: FlatProg<FieldPrime> = FlatProg::from(dr1cs);
 
@@ -225,7 +297,8 @@ mod tests {
             compiled.functions[0].statements[1],
             FlatStatement::Condition(
                 FlatVariable::new(0).into(),
-                FlatExpression::Number(FieldPrime::from(1))
+                FlatExpression::Number(FieldPrime::from(1)),
+                None,
             )
         );
 
@@ -234,7 +307,8 @@ mod tests {
             compiled.functions[0].statements[2],
             FlatStatement::Condition(
                 FlatVariable::new(1).into(),
-                FlatVariable::new(v_count).into()
+                FlatVariable::new(v_count).into(),
+                None,
             )
         );
     }
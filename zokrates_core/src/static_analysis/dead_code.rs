@@ -76,23 +76,54 @@ impl<T: Field> Folder<T> for DeadCode {
         }
     }
 
-    fn fold_field_array_expression(
-        &mut self,
-        e: FieldElementArrayExpression<T>,
-    ) -> FieldElementArrayExpression<T> {
+    fn fold_array_expression(&mut self, e: ArrayExpression<T>) -> ArrayExpression<T> {
         match e {
-            FieldElementArrayExpression::FunctionCall(size, id, exps) => {
+            ArrayExpression::FunctionCall(ty, id, exps) => {
                 let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
 
                 let signature = Signature::new()
                     .inputs(exps.iter().map(|e| e.get_type()).collect())
-                    .outputs(vec![Type::FieldElementArray(size)]);
+                    .outputs(vec![Type::Array(ty.clone())]);
 
                 self.called
                     .insert(format!("{}_{}", id, signature.to_slug()));
-                FieldElementArrayExpression::FunctionCall(size, id, exps)
+                ArrayExpression::FunctionCall(ty, id, exps)
             }
-            e => fold_field_array_expression(self, e),
+            e => fold_array_expression(self, e),
+        }
+    }
+
+    fn fold_uint_expression(&mut self, e: UintExpression<T>) -> UintExpression<T> {
+        match e {
+            UintExpression::FunctionCall(bitwidth, id, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                let signature = Signature::new()
+                    .inputs(exps.iter().map(|e| e.get_type()).collect())
+                    .outputs(vec![Type::Uint(bitwidth)]);
+
+                self.called
+                    .insert(format!("{}_{}", id, signature.to_slug()));
+                UintExpression::FunctionCall(bitwidth, id, exps)
+            }
+            e => fold_uint_expression(self, e),
+        }
+    }
+
+    fn fold_struct_expression(&mut self, e: StructExpression<T>) -> StructExpression<T> {
+        match e {
+            StructExpression::FunctionCall(ty, id, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                let signature = Signature::new()
+                    .inputs(exps.iter().map(|e| e.get_type()).collect())
+                    .outputs(vec![Type::Struct(ty.clone())]);
+
+                self.called
+                    .insert(format!("{}_{}", id, signature.to_slug()));
+                StructExpression::FunctionCall(ty, id, exps)
+            }
+            e => fold_struct_expression(self, e),
         }
     }
 }
@@ -70,9 +70,10 @@ impl<T: Field> FlatStatement<T> {
                 }
                 e => Some(FlatStatement::Definition(var, e)),
             },
-            FlatStatement::Condition(e1, e2) => Some(FlatStatement::Condition(
+            FlatStatement::Condition(e1, e2, metadata) => Some(FlatStatement::Condition(
                 e1.propagate(constants),
                 e2.propagate(constants),
+                metadata,
             )),
             FlatStatement::Directive(d) => Some(FlatStatement::Directive(DirectiveStatement {
                 inputs: d
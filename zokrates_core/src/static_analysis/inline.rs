@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use flat_absy::Span;
+use std::collections::{HashMap, HashSet};
 use typed_absy::folder::*;
 use typed_absy::Folder;
 use typed_absy::*;
@@ -10,6 +11,10 @@ pub struct Inliner<T: Field> {
     statements_buffer: Vec<TypedStatement<T>>,
     context: Vec<(String, usize)>,
     call_count: HashMap<String, usize>,
+    // module constants are visible in every function and must not be prefixed
+    constants: HashSet<String>,
+    // the location of the statement being inlined into
+    span: Option<Span>,
 }
 
 impl<T: Field> Inliner<T> {
@@ -19,6 +24,8 @@ impl<T: Field> Inliner<T> {
             statements_buffer: vec![],
             context: vec![],
             call_count: HashMap::new(),
+            constants: HashSet::new(),
+            span: None,
         }
     }
 
@@ -37,11 +44,13 @@ impl<T: Field> Inliner<T> {
         // constant array indices
         match function {
             Some(..) => {
-                // check whether non-array arguments are constant
+                // check whether non-array, non-struct arguments are constant
                 arguments.iter().all(|e| match e {
-                    TypedExpression::FieldElementArray(..) => true,
+                    TypedExpression::Array(..) => true,
+                    TypedExpression::Struct(..) => true,
                     TypedExpression::FieldElement(FieldElementExpression::Number(..)) => true,
                     TypedExpression::Boolean(BooleanExpression::Value(..)) => true,
+                    TypedExpression::Uint(UintExpression::Value(..)) => true,
                     _ => false,
                 })
             }
@@ -79,6 +88,9 @@ impl<T: Field> Inliner<T> {
             .collect();
         self.statements_buffer.append(&mut inputs_bindings);
 
+        // the statements of the callee are located in its own body
+        let span = self.span.clone();
+
         // filter out the return statement and keep it aside
         let (mut statements, ret): (Vec<_>, Vec<_>) = function
             .statements
@@ -93,6 +105,12 @@ impl<T: Field> Inliner<T> {
         // add all statements to the buffer
         self.statements_buffer.append(&mut statements);
 
+        // the statements that follow are located at the call again
+        if let Some(ref span) = span {
+            self.statements_buffer.push(TypedStatement::Span(span.clone()));
+        }
+        self.span = span;
+
         // remove this call from the context
         self.context.pop();
 
@@ -102,8 +120,12 @@ impl<T: Field> Inliner<T> {
         }
     }
 
-    pub fn inline(prog: TypedProg<T>) -> TypedProg<T> {
-        Inliner::new().fold_program(prog)
+    // inlines calls according to the strategy and returns whether any call was inlined
+    pub fn inline(prog: TypedProg<T>) -> (TypedProg<T>, bool) {
+        let mut inliner = Inliner::new();
+        let prog = inliner.fold_program(prog);
+        let inlined = inliner.call_count.len() > 0;
+        (prog, inlined)
     }
 }
 
@@ -111,6 +133,12 @@ impl<T: Field> Folder<T> for Inliner<T> {
     // store the list of functions
     fn fold_program(&mut self, p: TypedProg<T>) -> TypedProg<T> {
         self.functions = p.functions.clone();
+        self.constants = p
+            .constants
+            .iter()
+            .map(|c| c.id.clone())
+            .chain(p.imported_constants.iter().map(|(id, _)| id.clone()))
+            .collect();
         fold_program(self, p)
     }
 
@@ -157,6 +185,10 @@ impl<T: Field> Folder<T> for Inliner<T> {
                     }
                 }
             }
+            TypedStatement::Span(span) => {
+                self.span = Some(span.clone());
+                vec![TypedStatement::Span(span)]
+            }
             s => fold_statement(self, s),
         };
 
@@ -168,6 +200,10 @@ impl<T: Field> Folder<T> for Inliner<T> {
 
     // prefix all names with the context
     fn fold_name(&mut self, n: String) -> String {
+        if self.constants.contains(&n) {
+            return n;
+        }
+
         match self.context.len() {
             0 => n,
             _ => format!(
@@ -216,18 +252,15 @@ impl<T: Field> Folder<T> for Inliner<T> {
         }
     }
 
-    // inline calls which return a field element array
-    fn fold_field_array_expression(
-        &mut self,
-        e: FieldElementArrayExpression<T>,
-    ) -> FieldElementArrayExpression<T> {
+    // inline calls which return an array
+    fn fold_array_expression(&mut self, e: ArrayExpression<T>) -> ArrayExpression<T> {
         match e {
-            FieldElementArrayExpression::FunctionCall(size, id, exps) => {
+            ArrayExpression::FunctionCall(ty, id, exps) => {
                 let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
 
                 let passed_signature = Signature::new()
                     .inputs(exps.iter().map(|e| e.get_type()).collect())
-                    .outputs(vec![Type::FieldElementArray(size)]);
+                    .outputs(vec![Type::Array(ty.clone())]);
 
                 // find the function
                 let function = self
@@ -241,15 +274,83 @@ impl<T: Field> Folder<T> for Inliner<T> {
                         let ret = self.inline_call(&function.unwrap(), exps);
                         // unwrap the result to return a field element
                         match ret[0].clone() {
-                            TypedExpression::FieldElementArray(e) => e,
+                            TypedExpression::Array(e) => e,
                             _ => panic!(""),
                         }
                     }
-                    false => FieldElementArrayExpression::FunctionCall(size, id, exps),
+                    false => ArrayExpression::FunctionCall(ty, id, exps),
                 }
             }
             // default
-            e => fold_field_array_expression(self, e),
+            e => fold_array_expression(self, e),
+        }
+    }
+
+    // inline calls which return an unsigned integer
+    fn fold_uint_expression(&mut self, e: UintExpression<T>) -> UintExpression<T> {
+        match e {
+            UintExpression::FunctionCall(bitwidth, id, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                let passed_signature = Signature::new()
+                    .inputs(exps.iter().map(|e| e.get_type()).collect())
+                    .outputs(vec![Type::Uint(bitwidth)]);
+
+                // find the function
+                let function = self
+                    .functions
+                    .iter()
+                    .find(|f| f.id == id && f.signature == passed_signature)
+                    .cloned();
+
+                match self.should_inline(&function, &exps) {
+                    true => {
+                        let ret = self.inline_call(&function.unwrap(), exps);
+                        // unwrap the result to return an unsigned integer
+                        match ret[0].clone() {
+                            TypedExpression::Uint(e) => e,
+                            _ => panic!(""),
+                        }
+                    }
+                    false => UintExpression::FunctionCall(bitwidth, id, exps),
+                }
+            }
+            // default
+            e => fold_uint_expression(self, e),
+        }
+    }
+
+    // inline calls which return a struct
+    fn fold_struct_expression(&mut self, e: StructExpression<T>) -> StructExpression<T> {
+        match e {
+            StructExpression::FunctionCall(ty, id, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                let passed_signature = Signature::new()
+                    .inputs(exps.iter().map(|e| e.get_type()).collect())
+                    .outputs(vec![Type::Struct(ty.clone())]);
+
+                // find the function
+                let function = self
+                    .functions
+                    .iter()
+                    .find(|f| f.id == id && f.signature == passed_signature)
+                    .cloned();
+
+                match self.should_inline(&function, &exps) {
+                    true => {
+                        let ret = self.inline_call(&function.unwrap(), exps);
+                        // unwrap the result to return a struct
+                        match ret[0].clone() {
+                            TypedExpression::Struct(e) => e,
+                            _ => panic!(""),
+                        }
+                    }
+                    false => StructExpression::FunctionCall(ty, id, exps),
+                }
+            }
+            // default
+            e => fold_struct_expression(self, e),
         }
     }
 }
@@ -257,6 +358,7 @@ impl<T: Field> Folder<T> for Inliner<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use types::ArrayType;
     use zokrates_field::field::FieldPrime;
 
     #[cfg(test)]
@@ -273,19 +375,26 @@ mod tests {
                 ],
                 statements: vec![TypedStatement::Return(vec![
                     FieldElementExpression::Select(
-                        box FieldElementArrayExpression::Identifier(3, String::from("b")),
+                        box ArrayExpression::Identifier(
+                            ArrayType::new(Type::FieldElement, 3),
+                            String::from("b"),
+                        ),
                         box FieldElementExpression::Identifier(String::from("a")),
                     )
                     .into(),
                 ])],
                 signature: Signature::new()
-                    .inputs(vec![Type::FieldElement, Type::FieldElementArray(3)])
+                    .inputs(vec![Type::FieldElement, Type::array(Type::FieldElement, 3)])
                     .outputs(vec![Type::FieldElement]),
             };
 
             let arguments = vec![
                 FieldElementExpression::Number(FieldPrime::from(0)).into(),
-                FieldElementArrayExpression::Identifier(3, String::from("random")).into(),
+                ArrayExpression::Identifier(
+                    ArrayType::new(Type::FieldElement, 3),
+                    String::from("random"),
+                )
+                .into(),
             ];
 
             let i = Inliner::new();
@@ -303,19 +412,26 @@ mod tests {
                 ],
                 statements: vec![TypedStatement::Return(vec![
                     FieldElementExpression::Select(
-                        box FieldElementArrayExpression::Identifier(3, String::from("b")),
+                        box ArrayExpression::Identifier(
+                            ArrayType::new(Type::FieldElement, 3),
+                            String::from("b"),
+                        ),
                         box FieldElementExpression::Identifier(String::from("a")),
                     )
                     .into(),
                 ])],
                 signature: Signature::new()
-                    .inputs(vec![Type::FieldElement, Type::FieldElementArray(3)])
+                    .inputs(vec![Type::FieldElement, Type::array(Type::FieldElement, 3)])
                     .outputs(vec![Type::FieldElement]),
             };
 
             let arguments = vec![
                 FieldElementExpression::Identifier(String::from("notconstant")).into(),
-                FieldElementArrayExpression::Identifier(3, String::from("random")).into(),
+                ArrayExpression::Identifier(
+                    ArrayType::new(Type::FieldElement, 3),
+                    String::from("random"),
+                )
+                .into(),
             ];
 
             let i = Inliner::new();
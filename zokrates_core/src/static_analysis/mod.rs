@@ -9,40 +9,131 @@ mod flat_propagation;
 mod inline;
 mod power_check;
 mod propagation;
+mod shift_check;
 mod unroll;
 
 use self::dead_code::DeadCode;
 use self::inline::Inliner;
 use self::power_check::PowerChecker;
 use self::propagation::Propagator;
-use self::unroll::Unroller;
+use self::shift_check::ShiftChecker;
+use self::unroll::{contains_loops, Unroller};
 use flat_absy::FlatProg;
-use typed_absy::TypedProg;
+use parser::Position;
+use std::fmt;
+use typed_absy::{FieldElementExpression, TypedConstant, TypedProg, TypedStatement};
 use zokrates_field::field::Field;
 
+#[derive(PartialEq, Debug)]
+pub struct Error {
+    pos: Option<(Position, Position)>,
+    message: String,
+}
+
+impl Error {
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = self
+            .pos
+            .map(|p| format!("{}", p.0))
+            .unwrap_or("?".to_string());
+        write!(f, "{}\n\t{}", location, self.message)
+    }
+}
+
 pub trait Analyse {
-    fn analyse(self) -> Self;
+    fn analyse(self) -> Result<Self, Error>
+    where
+        Self: Sized;
 }
 
 impl<T: Field> Analyse for TypedProg<T> {
-    fn analyse(self) -> Self {
-        let r = PowerChecker::check(self);
-        // unroll
-        let r = Unroller::unroll(r);
-        //propagate a first time for constants to reach function calls
-        let r = Propagator::propagate(r);
-        // apply inlining strategy
-        let r = Inliner::inline(r);
+    fn analyse(self) -> Result<Self, Error> {
+        let mut r = PowerChecker::check(self);
+
+        // loop bounds can depend on the parameters of a function, which are only known once it is
+        // inlined, so we repeat until all loops are unrolled or no progress is made
+        loop {
+            // unroll
+            let (unrolled, has_unrolled) = Unroller::unroll(r);
+            //propagate a first time for constants to reach function calls
+            let propagated = Propagator::propagate(unrolled);
+            // apply inlining strategy
+            let (inlined, has_inlined) = Inliner::inline(propagated);
+            // remove unused functions, whose loops do not need to be unrolled
+            r = DeadCode::clean(inlined);
+
+            // without progress, the loops left have bounds which cannot be known at compile time
+            if !has_unrolled && !has_inlined {
+                check_unrolled(&r)?;
+                break;
+            }
+
+            if !r.functions.iter().any(contains_loops) {
+                break;
+            }
+        }
+
         // Propagate again
         let r = Propagator::propagate(r);
         // remove unused functions
         let r = DeadCode::clean(r);
-        r
+        // shift amounts are only known once constants are propagated
+        let r = ShiftChecker::check(r)?;
+        Ok(r)
+    }
+}
+
+/// Returns the values of `constants`, which can refer to the constants before them and to
+/// `imported_constants`
+pub fn constant_values<T: Field>(
+    constants: Vec<TypedConstant<T>>,
+    imported_constants: &Vec<(String, T)>,
+) -> Vec<(String, T)> {
+    Propagator::new()
+        .reduce_constants(constants, imported_constants)
+        .into_iter()
+        .map(|c| match c.expression {
+            FieldElementExpression::Number(v) => (c.id, v),
+            e => unreachable!("constant {} should have been reduced, found {}", c.id, e),
+        })
+        .collect()
+}
+
+// returns an error for the first loop left in `p`, located at the span preceding it
+fn check_unrolled<T: Field>(p: &TypedProg<T>) -> Result<(), Error> {
+    for f in &p.functions {
+        let mut span = None;
+        for s in &f.statements {
+            match s {
+                TypedStatement::Span(s) => span = Some(s),
+                TypedStatement::For(_, from, to, _) => {
+                    return Err(Error {
+                        pos: span.map(|s| (s.start, s.end)),
+                        message: format!(
+                            "Loop bounds {}..{} in function {} are not known at compile time",
+                            from, to, f.id
+                        ),
+                    });
+                }
+                _ => {}
+            }
+        }
     }
+    Ok(())
 }
 
 impl<T: Field> Analyse for FlatProg<T> {
-    fn analyse(self) -> Self {
-        self.propagate()
+    fn analyse(self) -> Result<Self, Error> {
+        Ok(self.propagate())
     }
 }
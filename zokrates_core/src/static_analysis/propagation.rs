@@ -5,29 +5,140 @@
 //! @date 2018
 
 use std::collections::HashMap;
+use static_analysis::unroll::contains_loops;
 use typed_absy::folder::*;
 use typed_absy::*;
 use zokrates_field::field::Field;
 
 pub struct Propagator<T: Field> {
     constants: HashMap<TypedAssignee<T>, TypedExpression<T>>,
+    // values of the module-level constants, visible in every function
+    module_constants: HashMap<TypedAssignee<T>, TypedExpression<T>>,
 }
 
 impl<T: Field> Propagator<T> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Propagator {
             constants: HashMap::new(),
+            module_constants: HashMap::new(),
         }
     }
 
     pub fn propagate(p: TypedProg<T>) -> TypedProg<T> {
         Propagator::new().fold_program(p)
     }
+
+    // returns a propagator which knows the values of the constants of `p`
+    pub fn with_constants(p: &TypedProg<T>) -> Self {
+        let mut propagator = Propagator::new();
+        propagator.reduce_constants(p.constants.clone(), &p.imported_constants);
+        propagator
+    }
+
+    // forgets the variables of the previous function, keeping the module constants
+    pub fn reset(&mut self) {
+        self.constants = self.module_constants.clone();
+    }
+
+    // constants only refer to previous constants, so evaluating them in order reduces them all
+    pub fn reduce_constants(
+        &mut self,
+        constants: Vec<TypedConstant<T>>,
+        imported_constants: &Vec<(String, T)>,
+    ) -> Vec<TypedConstant<T>> {
+        self.constants = HashMap::new();
+
+        for (id, value) in imported_constants {
+            self.constants.insert(
+                TypedAssignee::Identifier(Variable::field_element(id.clone())),
+                FieldElementExpression::Number(value.clone()).into(),
+            );
+        }
+
+        let constants = constants
+            .into_iter()
+            .map(|c| {
+                let expression = match self.fold_field_expression(c.expression) {
+                    e @ FieldElementExpression::Number(..) => e,
+                    e => panic!("constant {} could not be reduced, found {}", c.id, e),
+                };
+                self.constants.insert(
+                    TypedAssignee::Identifier(Variable::field_element(c.id.clone())),
+                    expression.clone().into(),
+                );
+                TypedConstant { expression, ..c }
+            })
+            .collect();
+
+        self.module_constants = self.constants.clone();
+
+        constants
+    }
+
+    // accessing a member of a struct value yields the value of that member
+    fn fold_member(
+        &mut self,
+        s: StructExpression<T>,
+        id: &str,
+    ) -> Result<TypedExpression<T>, StructExpression<T>> {
+        match self.fold_struct_expression(s) {
+            StructExpression::Value(ty, mut values) => {
+                let index = ty.members.iter().position(|m| m.id == id).unwrap();
+                Ok(values.swap_remove(index))
+            }
+            s => Err(s),
+        }
+    }
+
+    // selecting a constant index of an array value yields the element at that index
+    fn fold_select(
+        &mut self,
+        array: ArrayExpression<T>,
+        index: FieldElementExpression<T>,
+    ) -> Result<TypedExpression<T>, (ArrayExpression<T>, FieldElementExpression<T>)> {
+        let array = self.fold_array_expression(array);
+        let index = self.fold_field_expression(index);
+
+        match (array, index) {
+            (ArrayExpression::Value(ty, mut v), FieldElementExpression::Number(n)) => {
+                let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
+                if n_as_usize < ty.size {
+                    Ok(v.swap_remove(n_as_usize))
+                } else {
+                    panic!(format!(
+                        "out of bounds index ({} >= {}) found during static analysis",
+                        n_as_usize, ty.size
+                    ));
+                }
+            }
+            (a, i) => Err((a, i)),
+        }
+    }
+}
+
+fn is_constant<T: Field>(e: &TypedExpression<T>) -> bool {
+    match e {
+        TypedExpression::FieldElement(FieldElementExpression::Number(..)) => true,
+        TypedExpression::Boolean(BooleanExpression::Value(..)) => true,
+        TypedExpression::Uint(UintExpression::Value(..)) => true,
+        TypedExpression::Array(ArrayExpression::Value(_, v)) => v.iter().all(is_constant),
+        _ => false,
+    }
 }
 
 impl<T: Field> Folder<T> for Propagator<T> {
+    fn fold_program(&mut self, p: TypedProg<T>) -> TypedProg<T> {
+        let constants = self.reduce_constants(p.constants, &p.imported_constants);
+        fold_program(self, TypedProg { constants, ..p })
+    }
+
     fn fold_function(&mut self, f: TypedFunction<T>) -> TypedFunction<T> {
-        self.constants = HashMap::new();
+        // functions whose loops could not be unrolled yet are not in SSA form, skip them
+        if contains_loops(&f) {
+            return f;
+        }
+
+        self.reset();
         fold_function(self, f)
     }
 
@@ -38,27 +149,14 @@ impl<T: Field> Folder<T> for Propagator<T> {
 			// propagation to the defined variable if rhs is a constant
 			TypedStatement::Definition(TypedAssignee::Identifier(var), expr) => {
 				match self.fold_expression(expr) {
-					e @ TypedExpression::Boolean(BooleanExpression::Value(..)) | e @ TypedExpression::FieldElement(FieldElementExpression::Number(..)) => {
-						self.constants.insert(TypedAssignee::Identifier(var), e);
-						None
-					},
-					TypedExpression::FieldElementArray(FieldElementArrayExpression::Value(size, array)) => {
-						match array.iter().all(|e| match e {
-							FieldElementExpression::Number(..) => true,
-							_ => false
-						}) {
+					e => {
+						match is_constant(&e) {
 							true => {
-								// all elements of the array are constants
-								self.constants.insert(TypedAssignee::Identifier(var), FieldElementArrayExpression::Value(size, array).into());
+								self.constants.insert(TypedAssignee::Identifier(var), e);
 								None
 							},
-							false => {
-								Some(TypedStatement::Definition(TypedAssignee::Identifier(var), FieldElementArrayExpression::Value(size, array).into()))
-							}
+							false => Some(TypedStatement::Definition(TypedAssignee::Identifier(var), e))
 						}
-					},
-					e => {
-						Some(TypedStatement::Definition(TypedAssignee::Identifier(var), e))
 					}
 				}
 			},
@@ -68,20 +166,17 @@ impl<T: Field> Folder<T> for Propagator<T> {
 				let expr = self.fold_expression(expr);
 
 				match (index, expr) {
-					(
-						FieldElementExpression::Number(n),
-						TypedExpression::FieldElement(expr @ FieldElementExpression::Number(..))
-					) => {
+					(FieldElementExpression::Number(n), expr) if is_constant(&expr) => {
 						// a[42] = 33
 						// -> store (a[42] -> 33) in the constants, possibly overwriting the previous entry
 						self.constants.entry(TypedAssignee::Identifier(var)).and_modify(|e| {
 							match *e {
-								TypedExpression::FieldElementArray(FieldElementArrayExpression::Value(size, ref mut v)) => {
+								TypedExpression::Array(ArrayExpression::Value(ref ty, ref mut v)) => {
 									let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-									if n_as_usize < size {
+									if n_as_usize < ty.size {
 										v[n_as_usize] = expr;
 									} else {
-										panic!(format!("out of bounds index ({} >= {}) found during static analysis", n_as_usize, size));
+										panic!(format!("out of bounds index ({} >= {}) found during static analysis", n_as_usize, ty.size));
 									}
 								},
 								_ => panic!("constants should only store constants")
@@ -97,13 +192,19 @@ impl<T: Field> Folder<T> for Propagator<T> {
 					}
 				}
 			},
-			TypedStatement::Definition(..) => panic!("multi dimensinal arrays are not supported, this should have been caught during semantic checking"),
+			TypedStatement::Definition(..) => panic!("nested array element assignments should have been removed during unrolling"),
 			// propagate lhs and rhs for conditions
 			TypedStatement::Condition(e1, e2) => {
 				// could stop execution here if condition is known to fail
 				Some(TypedStatement::Condition(self.fold_expression(e1), self.fold_expression(e2)))
 			},
+			// assertions which are known to hold can be removed
+			TypedStatement::Assertion(e, metadata) => match self.fold_boolean_expression(e) {
+				BooleanExpression::Value(true) => None,
+				e => Some(TypedStatement::Assertion(e, metadata)),
+			},
 			// we unrolled for loops in the previous step
+			TypedStatement::Span(span) => Some(TypedStatement::Span(span)),
 			TypedStatement::For(..) => panic!("for loop is unexpected, it should have been unrolled"),
 			TypedStatement::MultipleDefinition(variables, expression_list) => {
 				let expression_list = self.fold_expression_list(expression_list);
@@ -198,67 +299,58 @@ impl<T: Field> Folder<T> for Propagator<T> {
                 }
             }
             FieldElementExpression::Select(box array, box index) => {
-                let array = self.fold_field_array_expression(array);
-                let index = self.fold_field_expression(index);
-
-                match (array, index) {
-                    (
-                        FieldElementArrayExpression::Value(size, v),
-                        FieldElementExpression::Number(n),
-                    ) => {
-                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
-                        if n_as_usize < size {
-                            v[n_as_usize].clone()
-                        } else {
-                            panic!(format!(
-                                "out of bounds index ({} >= {}) found during static analysis",
-                                n_as_usize, size
-                            ));
-                        }
-                    }
-                    (
-                        FieldElementArrayExpression::Identifier(size, id),
-                        FieldElementExpression::Number(n),
-                    ) => match self.constants.get(&TypedAssignee::ArrayElement(
-                        box TypedAssignee::Identifier(Variable::field_array(id.clone(), size)),
-                        box FieldElementExpression::Number(n.clone()).into(),
-                    )) {
-                        Some(e) => match e {
-                            TypedExpression::FieldElement(e) => e.clone(),
-                            _ => panic!(""),
-                        },
-                        None => FieldElementExpression::Select(
-                            box FieldElementArrayExpression::Identifier(size, id),
-                            box FieldElementExpression::Number(n),
-                        ),
-                    },
-                    (a, i) => FieldElementExpression::Select(box a, box i),
+                match self.fold_select(array, index) {
+                    Ok(TypedExpression::FieldElement(e)) => e,
+                    Ok(_) => panic!("element should be a field element"),
+                    Err((a, i)) => FieldElementExpression::Select(box a, box i),
                 }
             }
+            FieldElementExpression::Member(box s, id) => match self.fold_member(s, &id) {
+                Ok(TypedExpression::FieldElement(e)) => e,
+                Ok(_) => panic!("member should be a field element"),
+                Err(s) => FieldElementExpression::Member(box s, id),
+            },
             e => fold_field_expression(self, e),
         }
     }
 
-    fn fold_field_array_expression(
-        &mut self,
-        e: FieldElementArrayExpression<T>,
-    ) -> FieldElementArrayExpression<T> {
+    fn fold_array_expression(&mut self, e: ArrayExpression<T>) -> ArrayExpression<T> {
         match e {
-            FieldElementArrayExpression::Identifier(size, id) => {
-                match self
-                    .constants
-                    .get(&TypedAssignee::Identifier(Variable::field_array(
-                        id.clone(),
-                        size,
-                    ))) {
-                    Some(e) => match e {
-                        TypedExpression::FieldElementArray(e) => e.clone(),
-                        _ => panic!("constant stored for an array should be an array"),
-                    },
-                    None => FieldElementArrayExpression::Identifier(size, id),
+            ArrayExpression::Identifier(ty, id) => match self
+                .constants
+                .get(&TypedAssignee::Identifier(Variable::array(
+                    id.clone(),
+                    *ty.ty.clone(),
+                    ty.size,
+                ))) {
+                Some(e) => match e {
+                    TypedExpression::Array(e) => e.clone(),
+                    _ => panic!("constant stored for an array should be an array"),
+                },
+                None => ArrayExpression::Identifier(ty, id),
+            },
+            ArrayExpression::IfElse(box condition, box consequence, box alternative) => {
+                let consequence = self.fold_array_expression(consequence);
+                let alternative = self.fold_array_expression(alternative);
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => consequence,
+                    BooleanExpression::Value(false) => alternative,
+                    c => ArrayExpression::IfElse(box c, box consequence, box alternative),
                 }
             }
-            e => fold_field_array_expression(self, e),
+            ArrayExpression::Member(ty, box s, id) => match self.fold_member(s, &id) {
+                Ok(TypedExpression::Array(e)) => e,
+                Ok(_) => panic!("member should be an array"),
+                Err(s) => ArrayExpression::Member(ty, box s, id),
+            },
+            ArrayExpression::Select(ty, box array, box index) => {
+                match self.fold_select(array, index) {
+                    Ok(TypedExpression::Array(e)) => e,
+                    Ok(_) => panic!("element should be an array"),
+                    Err((a, i)) => ArrayExpression::Select(ty, box a, box i),
+                }
+            }
+            e => fold_array_expression(self, e),
         }
     }
 
@@ -329,14 +421,203 @@ impl<T: Field> Folder<T> for Propagator<T> {
                     (e1, e2) => BooleanExpression::Ge(box e1, box e2),
                 }
             }
+            BooleanExpression::UintEq(box e1, box e2) => {
+                let e1 = self.fold_uint_expression(e1);
+                let e2 = self.fold_uint_expression(e2);
+
+                match (e1, e2) {
+                    (UintExpression::Value(_, v1), UintExpression::Value(_, v2)) => {
+                        BooleanExpression::Value(v1 == v2)
+                    }
+                    (e1, e2) => BooleanExpression::UintEq(box e1, box e2),
+                }
+            }
+            BooleanExpression::And(box e1, box e2) => match (
+                self.fold_boolean_expression(e1),
+                self.fold_boolean_expression(e2),
+            ) {
+                (BooleanExpression::Value(false), _) | (_, BooleanExpression::Value(false)) => {
+                    BooleanExpression::Value(false)
+                }
+                (BooleanExpression::Value(true), e) | (e, BooleanExpression::Value(true)) => e,
+                (e1, e2) => BooleanExpression::And(box e1, box e2),
+            },
+            BooleanExpression::Or(box e1, box e2) => match (
+                self.fold_boolean_expression(e1),
+                self.fold_boolean_expression(e2),
+            ) {
+                (BooleanExpression::Value(true), _) | (_, BooleanExpression::Value(true)) => {
+                    BooleanExpression::Value(true)
+                }
+                (BooleanExpression::Value(false), e) | (e, BooleanExpression::Value(false)) => e,
+                (e1, e2) => BooleanExpression::Or(box e1, box e2),
+            },
+            BooleanExpression::Not(box e) => match self.fold_boolean_expression(e) {
+                BooleanExpression::Value(v) => BooleanExpression::Value(!v),
+                e => BooleanExpression::Not(box e),
+            },
+            BooleanExpression::Member(box s, id) => match self.fold_member(s, &id) {
+                Ok(TypedExpression::Boolean(e)) => e,
+                Ok(_) => panic!("member should be a boolean"),
+                Err(s) => BooleanExpression::Member(box s, id),
+            },
+            BooleanExpression::Select(box array, box index) => {
+                match self.fold_select(array, index) {
+                    Ok(TypedExpression::Boolean(e)) => e,
+                    Ok(_) => panic!("element should be a boolean"),
+                    Err((a, i)) => BooleanExpression::Select(box a, box i),
+                }
+            }
             e => fold_boolean_expression(self, e),
         }
     }
+
+    fn fold_uint_expression(&mut self, e: UintExpression<T>) -> UintExpression<T> {
+        // all operations wrap around, so we keep the result within the bitwidth
+        let mask = |bitwidth: usize| match bitwidth {
+            64 => u64::max_value(),
+            n => (1 << n) - 1,
+        };
+
+        match e {
+            UintExpression::Identifier(bitwidth, id) => match self
+                .constants
+                .get(&TypedAssignee::Identifier(Variable::uint(id.clone(), bitwidth)))
+            {
+                Some(e) => match e {
+                    TypedExpression::Uint(e) => e.clone(),
+                    _ => panic!("constant stored for an unsigned integer should be an unsigned integer"),
+                },
+                None => UintExpression::Identifier(bitwidth, id),
+            },
+            UintExpression::Add(box e1, box e2) => match (
+                self.fold_uint_expression(e1),
+                self.fold_uint_expression(e2),
+            ) {
+                (UintExpression::Value(n, v1), UintExpression::Value(_, v2)) => {
+                    UintExpression::Value(n, v1.wrapping_add(v2) & mask(n))
+                }
+                (e1, e2) => UintExpression::Add(box e1, box e2),
+            },
+            UintExpression::Mult(box e1, box e2) => match (
+                self.fold_uint_expression(e1),
+                self.fold_uint_expression(e2),
+            ) {
+                (UintExpression::Value(n, v1), UintExpression::Value(_, v2)) => {
+                    UintExpression::Value(n, v1.wrapping_mul(v2) & mask(n))
+                }
+                (e1, e2) => UintExpression::Mult(box e1, box e2),
+            },
+            UintExpression::And(box e1, box e2) => match (
+                self.fold_uint_expression(e1),
+                self.fold_uint_expression(e2),
+            ) {
+                (UintExpression::Value(n, v1), UintExpression::Value(_, v2)) => {
+                    UintExpression::Value(n, v1 & v2)
+                }
+                (e1, e2) => UintExpression::And(box e1, box e2),
+            },
+            UintExpression::Or(box e1, box e2) => match (
+                self.fold_uint_expression(e1),
+                self.fold_uint_expression(e2),
+            ) {
+                (UintExpression::Value(n, v1), UintExpression::Value(_, v2)) => {
+                    UintExpression::Value(n, v1 | v2)
+                }
+                (e1, e2) => UintExpression::Or(box e1, box e2),
+            },
+            UintExpression::Xor(box e1, box e2) => match (
+                self.fold_uint_expression(e1),
+                self.fold_uint_expression(e2),
+            ) {
+                (UintExpression::Value(n, v1), UintExpression::Value(_, v2)) => {
+                    UintExpression::Value(n, v1 ^ v2)
+                }
+                (e1, e2) => UintExpression::Xor(box e1, box e2),
+            },
+            UintExpression::LeftShift(box e, box by) => {
+                let e = self.fold_uint_expression(e);
+                let by = self.fold_field_expression(by);
+                match (e, by) {
+                    (UintExpression::Value(n, v), FieldElementExpression::Number(by)) => {
+                        match by.to_dec_string().parse::<usize>() {
+                            Ok(by) if by < n => UintExpression::Value(n, (v << by) & mask(n)),
+                            _ => UintExpression::Value(n, 0),
+                        }
+                    }
+                    (e, by) => UintExpression::LeftShift(box e, box by),
+                }
+            }
+            UintExpression::RightShift(box e, box by) => {
+                let e = self.fold_uint_expression(e);
+                let by = self.fold_field_expression(by);
+                match (e, by) {
+                    (UintExpression::Value(n, v), FieldElementExpression::Number(by)) => {
+                        match by.to_dec_string().parse::<usize>() {
+                            Ok(by) if by < n => UintExpression::Value(n, v >> by),
+                            _ => UintExpression::Value(n, 0),
+                        }
+                    }
+                    (e, by) => UintExpression::RightShift(box e, box by),
+                }
+            }
+            UintExpression::IfElse(box condition, box consequence, box alternative) => {
+                let consequence = self.fold_uint_expression(consequence);
+                let alternative = self.fold_uint_expression(alternative);
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => consequence,
+                    BooleanExpression::Value(false) => alternative,
+                    c => UintExpression::IfElse(box c, box consequence, box alternative),
+                }
+            }
+            UintExpression::Member(bitwidth, box s, id) => match self.fold_member(s, &id) {
+                Ok(TypedExpression::Uint(e)) => e,
+                Ok(_) => panic!("member should be an unsigned integer"),
+                Err(s) => UintExpression::Member(bitwidth, box s, id),
+            },
+            UintExpression::Select(bitwidth, box array, box index) => {
+                match self.fold_select(array, index) {
+                    Ok(TypedExpression::Uint(e)) => e,
+                    Ok(_) => panic!("element should be an unsigned integer"),
+                    Err((a, i)) => UintExpression::Select(bitwidth, box a, box i),
+                }
+            }
+            e => fold_uint_expression(self, e),
+        }
+    }
+
+    fn fold_struct_expression(&mut self, e: StructExpression<T>) -> StructExpression<T> {
+        match e {
+            StructExpression::IfElse(box condition, box consequence, box alternative) => {
+                let consequence = self.fold_struct_expression(consequence);
+                let alternative = self.fold_struct_expression(alternative);
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => consequence,
+                    BooleanExpression::Value(false) => alternative,
+                    c => StructExpression::IfElse(box c, box consequence, box alternative),
+                }
+            }
+            StructExpression::Member(ty, box s, id) => match self.fold_member(s, &id) {
+                Ok(TypedExpression::Struct(e)) => e,
+                Ok(_) => panic!("member should be a struct"),
+                Err(s) => StructExpression::Member(ty, box s, id),
+            },
+            StructExpression::Select(ty, box array, box index) => {
+                match self.fold_select(array, index) {
+                    Ok(TypedExpression::Struct(e)) => e,
+                    Ok(_) => panic!("element should be a struct"),
+                    Err((a, i)) => StructExpression::Select(ty, box a, box i),
+                }
+            }
+            e => fold_struct_expression(self, e),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use types::{ArrayType, Type};
     use zokrates_field::field::FieldPrime;
 
     #[cfg(test)]
@@ -443,12 +724,12 @@ mod tests {
             #[test]
             fn select() {
                 let e = FieldElementExpression::Select(
-                    box FieldElementArrayExpression::Value(
-                        3,
+                    box ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 3),
                         vec![
-                            FieldElementExpression::Number(FieldPrime::from(1)),
-                            FieldElementExpression::Number(FieldPrime::from(2)),
-                            FieldElementExpression::Number(FieldPrime::from(3)),
+                            FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(3)).into(),
                         ],
                     ),
                     box FieldElementExpression::Add(
@@ -462,6 +743,45 @@ mod tests {
                     FieldElementExpression::Number(FieldPrime::from(3))
                 );
             }
+
+            #[test]
+            fn nested_select() {
+                // [[1, 2], [3, 4]][1][0]
+                let inner_type = ArrayType::new(Type::FieldElement, 2);
+                let e = FieldElementExpression::Select(
+                    box ArrayExpression::Select(
+                        inner_type.clone(),
+                        box ArrayExpression::Value(
+                            ArrayType::new(Type::Array(inner_type.clone()), 2),
+                            vec![
+                                ArrayExpression::Value(
+                                    inner_type.clone(),
+                                    vec![
+                                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                                        FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                                    ],
+                                )
+                                .into(),
+                                ArrayExpression::Value(
+                                    inner_type.clone(),
+                                    vec![
+                                        FieldElementExpression::Number(FieldPrime::from(3)).into(),
+                                        FieldElementExpression::Number(FieldPrime::from(4)).into(),
+                                    ],
+                                )
+                                .into(),
+                            ],
+                        ),
+                        box FieldElementExpression::Number(FieldPrime::from(1)),
+                    ),
+                    box FieldElementExpression::Number(FieldPrime::from(0)),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_field_expression(e),
+                    FieldElementExpression::Number(FieldPrime::from(3))
+                );
+            }
         }
 
         #[cfg(test)]
@@ -577,6 +897,174 @@ mod tests {
                     BooleanExpression::Value(false)
                 );
             }
+
+            #[test]
+            fn and() {
+                let e = BooleanExpression::And(
+                    box BooleanExpression::Value(true),
+                    box BooleanExpression::Identifier(String::from("a")),
+                );
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_boolean_expression(e),
+                    BooleanExpression::Identifier(String::from("a"))
+                );
+
+                let e = BooleanExpression::And(
+                    box BooleanExpression::Identifier(String::from("a")),
+                    box BooleanExpression::Value(false),
+                );
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(false)
+                );
+            }
+
+            #[test]
+            fn or() {
+                let e = BooleanExpression::Or(
+                    box BooleanExpression::Value(false),
+                    box BooleanExpression::Identifier(String::from("a")),
+                );
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_boolean_expression(e),
+                    BooleanExpression::Identifier(String::from("a"))
+                );
+
+                let e = BooleanExpression::Or(
+                    box BooleanExpression::Identifier(String::from("a")),
+                    box BooleanExpression::Value(true),
+                );
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+            }
+
+            #[test]
+            fn not() {
+                let e = BooleanExpression::Not(box BooleanExpression::Value(false));
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_boolean_expression(e),
+                    BooleanExpression::Value(true)
+                );
+            }
+        }
+
+        #[cfg(test)]
+        mod uint {
+            use super::*;
+
+            #[test]
+            fn add_wraps() {
+                let e: UintExpression<FieldPrime> = UintExpression::Add(
+                    box UintExpression::Value(8, 250),
+                    box UintExpression::Value(8, 10),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UintExpression::Value(8, 4)
+                );
+            }
+
+            #[test]
+            fn mult_wraps() {
+                let e: UintExpression<FieldPrime> = UintExpression::Mult(
+                    box UintExpression::Value(64, u64::max_value()),
+                    box UintExpression::Value(64, 2),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UintExpression::Value(64, u64::max_value() - 1)
+                );
+            }
+
+            #[test]
+            fn bitwise() {
+                let e: UintExpression<FieldPrime> = UintExpression::Xor(
+                    box UintExpression::And(
+                        box UintExpression::Value(8, 0b1100),
+                        box UintExpression::Value(8, 0b1010),
+                    ),
+                    box UintExpression::Or(
+                        box UintExpression::Value(8, 0b0001),
+                        box UintExpression::Value(8, 0b0011),
+                    ),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UintExpression::Value(8, 0b1011)
+                );
+            }
+
+            #[test]
+            fn shifts() {
+                let left: UintExpression<FieldPrime> = UintExpression::LeftShift(
+                    box UintExpression::Value(8, 0b11000011),
+                    box FieldElementExpression::Number(FieldPrime::from(2)),
+                );
+
+                let right: UintExpression<FieldPrime> = UintExpression::RightShift(
+                    box UintExpression::Value(8, 0b11000011),
+                    box FieldElementExpression::Number(FieldPrime::from(9)),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(left),
+                    UintExpression::Value(8, 0b00001100)
+                );
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(right),
+                    UintExpression::Value(8, 0)
+                );
+            }
+        }
+
+        #[cfg(test)]
+        mod structs {
+            use super::*;
+            use types::{StructMember, StructType, Type};
+
+            #[test]
+            fn member_of_value() {
+                // Point { x: 1, y: 2 }.y
+                let ty = StructType::new(
+                    String::from("Point"),
+                    vec![
+                        StructMember {
+                            id: String::from("x"),
+                            ty: Type::FieldElement,
+                        },
+                        StructMember {
+                            id: String::from("y"),
+                            ty: Type::FieldElement,
+                        },
+                    ],
+                );
+
+                let e: FieldElementExpression<FieldPrime> = FieldElementExpression::Member(
+                    box StructExpression::Value(
+                        ty,
+                        vec![
+                            FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                        ],
+                    ),
+                    String::from("y"),
+                );
+
+                assert_eq!(
+                    Propagator::new().fold_field_expression(e),
+                    FieldElementExpression::Number(FieldPrime::from(2))
+                );
+            }
         }
     }
 
@@ -598,11 +1086,11 @@ mod tests {
                 let declaration = TypedStatement::Declaration(Variable::field_array("a", 2));
                 let definition = TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_array("a", 2)),
-                    FieldElementArrayExpression::Value(
-                        2,
+                    ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 2),
                         vec![
-                            FieldElementExpression::Number(FieldPrime::from(21)),
-                            FieldElementExpression::Number(FieldPrime::from(22)),
+                            FieldElementExpression::Number(FieldPrime::from(21)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(22)).into(),
                         ],
                     )
                     .into(),
@@ -620,11 +1108,11 @@ mod tests {
                 p.fold_statement(declaration);
                 p.fold_statement(definition);
                 let expected_value: TypedExpression<FieldPrime> =
-                    FieldElementArrayExpression::Value(
-                        2,
+                    ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 2),
                         vec![
-                            FieldElementExpression::Number(FieldPrime::from(21)),
-                            FieldElementExpression::Number(FieldPrime::from(22)),
+                            FieldElementExpression::Number(FieldPrime::from(21)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(22)).into(),
                         ],
                     )
                     .into();
@@ -638,11 +1126,11 @@ mod tests {
 
                 p.fold_statement(overwrite);
                 let expected_value: TypedExpression<FieldPrime> =
-                    FieldElementArrayExpression::Value(
-                        2,
+                    ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 2),
                         vec![
-                            FieldElementExpression::Number(FieldPrime::from(21)),
-                            FieldElementExpression::Number(FieldPrime::from(42)),
+                            FieldElementExpression::Number(FieldPrime::from(21)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(42)).into(),
                         ],
                     )
                     .into();
@@ -688,4 +1176,105 @@ mod tests {
             }
         }
     }
+
+    #[cfg(test)]
+    mod program {
+        use super::*;
+        use types::Signature;
+
+        #[test]
+        fn constants() {
+            // const field N = 2
+            // const field M = N * 3
+            // def main() -> (field):
+            //   return M + N
+
+            // should be propagated to
+            // const field N = 2
+            // const field M = 6
+            // def main() -> (field):
+            //   return 8
+
+            let p: TypedProg<FieldPrime> = TypedProg {
+                functions: vec![TypedFunction {
+                    id: String::from("main"),
+                    arguments: vec![],
+                    statements: vec![TypedStatement::Return(vec![FieldElementExpression::Add(
+                        box FieldElementExpression::Identifier(String::from("M")),
+                        box FieldElementExpression::Identifier(String::from("N")),
+                    )
+                    .into()])],
+                    signature: Signature::new().outputs(vec![Type::FieldElement]),
+                }],
+                constants: vec![
+                    TypedConstant {
+                        id: String::from("N"),
+                        expression: FieldElementExpression::Number(FieldPrime::from(2)),
+                    },
+                    TypedConstant {
+                        id: String::from("M"),
+                        expression: FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier(String::from("N")),
+                            box FieldElementExpression::Number(FieldPrime::from(3)),
+                        ),
+                    },
+                ],
+                imports: vec![],
+                imported_functions: vec![],
+                imported_constants: vec![],
+            };
+
+            let p = Propagator::propagate(p);
+
+            assert_eq!(
+                p.constants
+                    .iter()
+                    .map(|c| c.expression.clone())
+                    .collect::<Vec<_>>(),
+                vec![
+                    FieldElementExpression::Number(FieldPrime::from(2)),
+                    FieldElementExpression::Number(FieldPrime::from(6))
+                ]
+            );
+            assert_eq!(
+                p.functions[0].statements,
+                vec![TypedStatement::Return(vec![FieldElementExpression::Number(
+                    FieldPrime::from(8)
+                )
+                .into()])]
+            );
+        }
+
+        #[test]
+        fn imported_constants() {
+            // imported: const field N = 5
+            // def main() -> (field):
+            //   return N
+
+            let p: TypedProg<FieldPrime> = TypedProg {
+                functions: vec![TypedFunction {
+                    id: String::from("main"),
+                    arguments: vec![],
+                    statements: vec![TypedStatement::Return(vec![
+                        FieldElementExpression::Identifier(String::from("N")).into(),
+                    ])],
+                    signature: Signature::new().outputs(vec![Type::FieldElement]),
+                }],
+                constants: vec![],
+                imports: vec![],
+                imported_functions: vec![],
+                imported_constants: vec![(String::from("N"), FieldPrime::from(5))],
+            };
+
+            let p = Propagator::propagate(p);
+
+            assert_eq!(
+                p.functions[0].statements,
+                vec![TypedStatement::Return(vec![FieldElementExpression::Number(
+                    FieldPrime::from(5)
+                )
+                .into()])]
+            );
+        }
+    }
 }
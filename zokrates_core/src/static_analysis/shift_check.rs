@@ -0,0 +1,66 @@
+use flat_absy::Span;
+use static_analysis::Error;
+use typed_absy::folder::*;
+use typed_absy::Folder;
+use typed_absy::*;
+use zokrates_field::field::Field;
+
+// shift amounts can be any field expression, they are required to reduce to constants during
+// static analysis as the flattener only shifts by known amounts
+pub struct ShiftChecker {
+    // the location of the statement being checked
+    span: Option<Span>,
+    error: Option<Error>,
+}
+
+impl ShiftChecker {
+    fn new() -> Self {
+        ShiftChecker {
+            span: None,
+            error: None,
+        }
+    }
+
+    pub fn check<T: Field>(p: TypedProg<T>) -> Result<TypedProg<T>, Error> {
+        let mut checker = ShiftChecker::new();
+        let p = checker.fold_program(p);
+        match checker.error {
+            Some(e) => Err(e),
+            None => Ok(p),
+        }
+    }
+
+    // variables are renamed during unrolling, so the amount itself is not part of the message
+    fn check_amount<T: Field>(&mut self, by: &FieldElementExpression<T>) {
+        match by {
+            FieldElementExpression::Number(..) => {}
+            _ => {
+                if self.error.is_none() {
+                    self.error = Some(Error {
+                        pos: self.span.as_ref().map(|s| (s.start, s.end)),
+                        message: String::from("Shift amount is not known at compile time"),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<T: Field> Folder<T> for ShiftChecker {
+    fn fold_statement(&mut self, s: TypedStatement<T>) -> Vec<TypedStatement<T>> {
+        if let TypedStatement::Span(ref span) = s {
+            self.span = Some(span.clone());
+        }
+        fold_statement(self, s)
+    }
+
+    fn fold_uint_expression(&mut self, e: UintExpression<T>) -> UintExpression<T> {
+        match e {
+            UintExpression::LeftShift(_, ref by) | UintExpression::RightShift(_, ref by) => {
+                self.check_amount(by)
+            }
+            _ => {}
+        };
+        fold_uint_expression(self, e)
+    }
+}
@@ -4,20 +4,33 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 
-use std::collections::HashMap;
+use static_analysis::propagation::Propagator;
+use std::collections::{HashMap, HashSet};
 use typed_absy::folder::*;
 use typed_absy::*;
 use types::Type;
 use zokrates_field::field::Field;
 
-pub struct Unroller {
+pub struct Unroller<T: Field> {
     substitution: HashMap<String, usize>,
+    // the module constants, which are not renamed
+    constants: HashSet<String>,
+    // keeps track of the constant variables of the current function to reduce loop bounds
+    propagator: Propagator<T>,
+    // whether all loops of the current function could be unrolled
+    complete: bool,
+    // whether any loop was unrolled
+    unrolled: bool,
 }
 
-impl Unroller {
-    fn new() -> Self {
+impl<T: Field> Unroller<T> {
+    fn new(constants: HashSet<String>, propagator: Propagator<T>) -> Self {
         Unroller {
             substitution: HashMap::new(),
+            constants,
+            propagator,
+            complete: true,
+            unrolled: false,
         }
     }
 
@@ -39,14 +52,37 @@ impl Unroller {
         res
     }
 
-    pub fn unroll<T: Field>(p: TypedProg<T>) -> TypedProg<T> {
-        Unroller::new().fold_program(p)
+    // unrolls the loops whose bounds are known and returns whether any loop was unrolled
+    // functions with loops whose bounds are not known yet are left as is
+    pub fn unroll(p: TypedProg<T>) -> (TypedProg<T>, bool) {
+        let constants = p
+            .constants
+            .iter()
+            .map(|c| c.id.clone())
+            .chain(p.imported_constants.iter().map(|(id, _)| id.clone()))
+            .collect();
+        let mut unroller = Unroller::new(constants, Propagator::with_constants(&p));
+        let p = unroller.fold_program(p);
+        (p, unroller.unrolled)
     }
+
+    fn reduce_bound(&mut self, bound: FieldElementExpression<T>) -> FieldElementExpression<T> {
+        let bound = self.fold_field_expression(bound);
+        self.propagator.fold_field_expression(bound)
+    }
+}
+
+// returns whether `f` contains loops which are yet to be unrolled
+pub fn contains_loops<T: Field>(f: &TypedFunction<T>) -> bool {
+    f.statements.iter().any(|s| match s {
+        TypedStatement::For(..) => true,
+        _ => false,
+    })
 }
 
-impl<T: Field> Folder<T> for Unroller {
+impl<T: Field> Folder<T> for Unroller<T> {
     fn fold_statement(&mut self, s: TypedStatement<T>) -> Vec<TypedStatement<T>> {
-        match s {
+        let res = match s {
             TypedStatement::Declaration(_) => vec![],
             TypedStatement::Definition(TypedAssignee::Identifier(variable), expr) => {
                 let expr = self.fold_expression(expr);
@@ -56,56 +92,34 @@ impl<T: Field> Folder<T> for Unroller {
                     expr,
                 )]
             }
-            TypedStatement::Definition(
-                TypedAssignee::ArrayElement(array @ box TypedAssignee::Identifier(..), box index),
-                expr,
-            ) => {
+            TypedStatement::Definition(assignee @ TypedAssignee::ArrayElement(..), expr) => {
                 let expr = self.fold_expression(expr);
-                let index = self.fold_field_expression(index);
-                let current_array = self.fold_assignee(*array.clone());
 
-                let current_ssa_variable = match current_array {
-                    TypedAssignee::Identifier(v) => v,
-                    _ => panic!("assignee should be an identifier"),
-                };
+                // a[i][j] = e redefines the whole of a
+                let (original_variable, indices) = split_assignee(assignee);
+                let indices: Vec<_> = indices
+                    .into_iter()
+                    .map(|i| self.fold_field_expression(i))
+                    .collect();
 
-                let original_variable = match *array {
+                let current_ssa_variable = match self
+                    .fold_assignee(TypedAssignee::<T>::Identifier(original_variable.clone()))
+                {
                     TypedAssignee::Identifier(v) => v,
                     _ => panic!("assignee should be an identifier"),
                 };
 
-                let array_size = match original_variable.get_type() {
-                    Type::FieldElementArray(size) => size,
-                    _ => panic!("array identifier should be a field element array"),
-                };
-
-                let expr = match expr {
-                    TypedExpression::FieldElement(e) => e,
-                    _ => panic!("right side of array element definition must be a field element"),
+                let array_type = match original_variable.get_type() {
+                    Type::Array(array_type) => array_type,
+                    _ => panic!("array identifier should be an array"),
                 };
 
                 let new_variable = self.issue_next_ssa_variable(original_variable);
 
-                let new_array = FieldElementArrayExpression::Value(
-                    array_size,
-                    (0..array_size)
-                        .map(|i| {
-                            FieldElementExpression::IfElse(
-                                box BooleanExpression::Eq(
-                                    box index.clone(),
-                                    box FieldElementExpression::Number(T::from(i)),
-                                ),
-                                box expr.clone(),
-                                box FieldElementExpression::Select(
-                                    box FieldElementArrayExpression::Identifier(
-                                        array_size,
-                                        current_ssa_variable.id.clone(),
-                                    ),
-                                    box FieldElementExpression::Number(T::from(i)),
-                                ),
-                            )
-                        })
-                        .collect(),
+                let new_array = update_element(
+                    ArrayExpression::Identifier(array_type, current_ssa_variable.id),
+                    &indices,
+                    expr,
                 );
 
                 vec![TypedStatement::Definition(
@@ -123,6 +137,19 @@ impl<T: Field> Folder<T> for Unroller {
                 vec![TypedStatement::MultipleDefinition(variables, exprs)]
             }
             TypedStatement::For(v, from, to, stats) => {
+                let (from, to) = match (self.reduce_bound(from), self.reduce_bound(to)) {
+                    (FieldElementExpression::Number(from), FieldElementExpression::Number(to)) => {
+                        (from, to)
+                    }
+                    (from, to) => {
+                        // the bounds may depend on parameters which are known once inlined
+                        self.complete = false;
+                        return vec![TypedStatement::For(v, from, to, stats)];
+                    }
+                };
+
+                self.unrolled = true;
+
                 let mut values: Vec<T> = vec![];
                 let mut current = from;
                 while current < to {
@@ -130,7 +157,8 @@ impl<T: Field> Folder<T> for Unroller {
                     current = T::one() + &current;
                 }
 
-                let res = values
+                // the statements of the unrolled loop are tracked as they get folded
+                return values
                     .into_iter()
                     .map(|index| {
                         vec![
@@ -149,11 +177,16 @@ impl<T: Field> Folder<T> for Unroller {
                     .flat_map(|x| x)
                     .flat_map(|x| self.fold_statement(x))
                     .collect();
-
-                res
             }
             s => fold_statement(self, s),
+        };
+
+        // keep track of the values of the variables, as loop bounds can depend on them
+        for s in &res {
+            self.propagator.fold_statement(s.clone());
         }
+
+        res
     }
 
     fn fold_function(&mut self, f: TypedFunction<T>) -> TypedFunction<T> {
@@ -161,18 +194,87 @@ impl<T: Field> Folder<T> for Unroller {
         for arg in &f.arguments {
             self.substitution.insert(arg.id.id.clone(), 0);
         }
-
-        fold_function(self, f)
+        self.propagator.reset();
+        self.complete = true;
+        let unrolled_before = self.unrolled;
+
+        let unrolled = fold_function(self, f.clone());
+
+        match self.complete {
+            true => unrolled,
+            // keep the function as is until all its loop bounds are known
+            false => {
+                self.unrolled = unrolled_before;
+                f
+            }
+        }
     }
 
     fn fold_name(&mut self, n: String) -> String {
-        format!("{}_{}", n, self.substitution.get(&n).unwrap())
+        match self.substitution.get(&n) {
+            Some(i) => format!("{}_{}", n, i),
+            // module constants are never defined in functions, they keep their name
+            None if self.constants.contains(&n) => n,
+            None => unreachable!("{} should be a variable of the function or a constant", n),
+        }
     }
 }
 
+// returns the variable at the root of `a` and the indices leading to the assigned element, outermost first
+fn split_assignee<T: Field>(a: TypedAssignee<T>) -> (Variable, Vec<FieldElementExpression<T>>) {
+    match a {
+        TypedAssignee::Identifier(v) => (v, vec![]),
+        TypedAssignee::ArrayElement(box a, box index) => {
+            let (v, mut indices) = split_assignee(a);
+            indices.push(index);
+            (v, indices)
+        }
+    }
+}
+
+// returns `array` with the element at `indices` replaced by `e`
+// a[i][j] = e becomes [if i == 0 then [if j == 0 then e else a[0][0], ...] else a[0], ...]
+fn update_element<T: Field>(
+    array: ArrayExpression<T>,
+    indices: &[FieldElementExpression<T>],
+    e: TypedExpression<T>,
+) -> ArrayExpression<T> {
+    let array_type = array.array_type().clone();
+    let (index, indices) = indices.split_first().unwrap();
+
+    ArrayExpression::Value(
+        array_type.clone(),
+        (0..array_type.size)
+            .map(|i| {
+                let element = array
+                    .clone()
+                    .select(FieldElementExpression::Number(T::from(i)));
+                let updated = match indices.len() {
+                    0 => e.clone(),
+                    _ => match element.clone() {
+                        TypedExpression::Array(element) => {
+                            update_element(element, indices, e.clone()).into()
+                        }
+                        _ => panic!("too many indices for array of type {}", array_type.ty),
+                    },
+                };
+                TypedExpression::if_else(
+                    BooleanExpression::Eq(
+                        box index.clone(),
+                        box FieldElementExpression::Number(T::from(i)),
+                    ),
+                    updated,
+                    element,
+                )
+            })
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use types::{ArrayType, Signature};
     use zokrates_field::field::FieldPrime;
 
     #[cfg(test)]
@@ -194,8 +296,8 @@ mod tests {
 
             let s = TypedStatement::For(
                 Variable::field_element("i"),
-                FieldPrime::from(2),
-                FieldPrime::from(5),
+                FieldElementExpression::Number(FieldPrime::from(2)),
+                FieldElementExpression::Number(FieldPrime::from(5)),
                 vec![
                     TypedStatement::Declaration(Variable::field_element("foo")),
                     TypedStatement::Definition(
@@ -232,11 +334,171 @@ mod tests {
                 ),
             ];
 
-            let mut u = Unroller::new();
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
 
             assert_eq!(u.fold_statement(s), expected);
         }
 
+        #[test]
+        fn for_loop_with_constant_bound() {
+            // field n = 1 + 1
+            // for field i in 0..n
+            //		field foo = i
+
+            // should be unrolled to
+            // n_0 = 1 + 1
+            // i_0 = 0
+            // foo_0 = i_0
+            // i_1 = 1
+            // foo_1 = i_1
+
+            let definition = TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("n")),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                )
+                .into(),
+            );
+
+            let s = TypedStatement::For(
+                Variable::field_element("i"),
+                FieldElementExpression::Number(FieldPrime::from(0)),
+                FieldElementExpression::Identifier(String::from("n")),
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("foo")),
+                    FieldElementExpression::Identifier(String::from("i")).into(),
+                )],
+            );
+
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
+
+            u.fold_statement(definition);
+
+            assert_eq!(
+                u.fold_statement(s),
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("i_0")),
+                        FieldElementExpression::Number(FieldPrime::from(0)).into(),
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("foo_0")),
+                        FieldElementExpression::Identifier(String::from("i_0")).into(),
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("i_1")),
+                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("foo_1")),
+                        FieldElementExpression::Identifier(String::from("i_1")).into(),
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn for_loop_with_unknown_bound() {
+            // def foo(field n) -> (field):
+            //   field a = 0
+            //   for field i in 0..n
+            //		a = a + i
+            //   return a
+
+            // cannot be unrolled before `n` is known, so it is left untouched
+
+            let f = TypedFunction {
+                id: String::from("foo"),
+                arguments: vec![Parameter::private(Variable::field_element("n"))],
+                statements: vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("a")),
+                        FieldElementExpression::Number(FieldPrime::from(0)).into(),
+                    ),
+                    TypedStatement::For(
+                        Variable::field_element("i"),
+                        FieldElementExpression::Number(FieldPrime::from(0)),
+                        FieldElementExpression::Identifier(String::from("n")),
+                        vec![TypedStatement::Definition(
+                            TypedAssignee::Identifier(Variable::field_element("a")),
+                            FieldElementExpression::Add(
+                                box FieldElementExpression::Identifier(String::from("a")),
+                                box FieldElementExpression::Identifier(String::from("i")),
+                            )
+                            .into(),
+                        )],
+                    ),
+                    TypedStatement::Return(vec![FieldElementExpression::Identifier(
+                        String::from("a"),
+                    )
+                    .into()]),
+                ],
+                signature: Signature::new()
+                    .inputs(vec![Type::FieldElement])
+                    .outputs(vec![Type::FieldElement]),
+            };
+
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
+
+            assert_eq!(u.fold_function(f.clone()), f);
+            assert!(!u.unrolled);
+        }
+
+        #[test]
+        fn nested_loop_with_unknown_bound() {
+            // def foo(field n) -> (field):
+            //   field a = 0
+            //   for field i in 0..2
+            //     for field j in 0..n
+            //		 a = a + j
+            //   return a
+
+            // the outer loop is not unrolled either, as the function must stay in its original form
+            // until all its bounds are known
+
+            let f = TypedFunction {
+                id: String::from("foo"),
+                arguments: vec![Parameter::private(Variable::field_element("n"))],
+                statements: vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("a")),
+                        FieldElementExpression::Number(FieldPrime::from(0)).into(),
+                    ),
+                    TypedStatement::For(
+                        Variable::field_element("i"),
+                        FieldElementExpression::Number(FieldPrime::from(0)),
+                        FieldElementExpression::Number(FieldPrime::from(2)),
+                        vec![TypedStatement::For(
+                            Variable::field_element("j"),
+                            FieldElementExpression::Number(FieldPrime::from(0)),
+                            FieldElementExpression::Identifier(String::from("n")),
+                            vec![TypedStatement::Definition(
+                                TypedAssignee::Identifier(Variable::field_element("a")),
+                                FieldElementExpression::Add(
+                                    box FieldElementExpression::Identifier(String::from("a")),
+                                    box FieldElementExpression::Identifier(String::from("j")),
+                                )
+                                .into(),
+                            )],
+                        )],
+                    ),
+                    TypedStatement::Return(vec![FieldElementExpression::Identifier(
+                        String::from("a"),
+                    )
+                    .into()]),
+                ],
+                signature: Signature::new()
+                    .inputs(vec![Type::FieldElement])
+                    .outputs(vec![Type::FieldElement]),
+            };
+
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
+
+            assert_eq!(u.fold_function(f.clone()), f);
+            assert!(!u.unrolled);
+        }
+
         #[test]
         fn definition() {
             // field a
@@ -249,7 +511,7 @@ mod tests {
             // a_1 = 6
             // a_1
 
-            let mut u = Unroller::new();
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
 
             let s: TypedStatement<FieldPrime> =
                 TypedStatement::Declaration(Variable::field_element("a"));
@@ -297,7 +559,7 @@ mod tests {
             // a_0 = 5
             // a_1 = a_0 + 1
 
-            let mut u = Unroller::new();
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
 
             let s: TypedStatement<FieldPrime> =
                 TypedStatement::Declaration(Variable::field_element("a"));
@@ -348,7 +610,7 @@ mod tests {
             // a_0 = 2
             // a_1 = foo(a_0)
 
-            let mut u = Unroller::new();
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
 
             let s: TypedStatement<FieldPrime> =
                 TypedStatement::Declaration(Variable::field_element("a"));
@@ -396,7 +658,7 @@ mod tests {
             // a_0 = [1, 1]
             // a_1 = [if 0 == 1 then 2 else a_0[0], if 1 == 1 then 2 else a_0[1]]
 
-            let mut u = Unroller::new();
+            let mut u = Unroller::new(HashSet::new(), Propagator::new());
 
             let s: TypedStatement<FieldPrime> =
                 TypedStatement::Declaration(Variable::field_array("a", 2));
@@ -404,11 +666,11 @@ mod tests {
 
             let s = TypedStatement::Definition(
                 TypedAssignee::Identifier(Variable::field_array("a", 2)),
-                FieldElementArrayExpression::Value(
-                    2,
+                ArrayExpression::Value(
+                    ArrayType::new(Type::FieldElement, 2),
                     vec![
-                        FieldElementExpression::Number(FieldPrime::from(1)),
-                        FieldElementExpression::Number(FieldPrime::from(1)),
+                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
                     ],
                 )
                 .into(),
@@ -418,11 +680,11 @@ mod tests {
                 u.fold_statement(s),
                 vec![TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_array("a_0", 2)),
-                    FieldElementArrayExpression::Value(
-                        2,
+                    ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 2),
                         vec![
-                            FieldElementExpression::Number(FieldPrime::from(1)),
-                            FieldElementExpression::Number(FieldPrime::from(1))
+                            FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(1)).into()
                         ]
                     )
                     .into()
@@ -441,8 +703,8 @@ mod tests {
                 u.fold_statement(s),
                 vec![TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_array("a_1", 2)),
-                    FieldElementArrayExpression::Value(
-                        2,
+                    ArrayExpression::Value(
+                        ArrayType::new(Type::FieldElement, 2),
                         vec![
                             FieldElementExpression::IfElse(
                                 box BooleanExpression::Eq(
@@ -451,13 +713,14 @@ mod tests {
                                 ),
                                 box FieldElementExpression::Number(FieldPrime::from(2)),
                                 box FieldElementExpression::Select(
-                                    box FieldElementArrayExpression::Identifier(
-                                        2,
+                                    box ArrayExpression::Identifier(
+                                        ArrayType::new(Type::FieldElement, 2),
                                         String::from("a_0")
                                     ),
                                     box FieldElementExpression::Number(FieldPrime::from(0))
                                 ),
-                            ),
+                            )
+                            .into(),
                             FieldElementExpression::IfElse(
                                 box BooleanExpression::Eq(
                                     box FieldElementExpression::Number(FieldPrime::from(1)),
@@ -465,13 +728,14 @@ mod tests {
                                 ),
                                 box FieldElementExpression::Number(FieldPrime::from(2)),
                                 box FieldElementExpression::Select(
-                                    box FieldElementArrayExpression::Identifier(
-                                        2,
+                                    box ArrayExpression::Identifier(
+                                        ArrayType::new(Type::FieldElement, 2),
                                         String::from("a_0")
                                     ),
                                     box FieldElementExpression::Number(FieldPrime::from(1))
                                 ),
-                            ),
+                            )
+                            .into(),
                         ]
                     )
                     .into()
@@ -48,7 +48,9 @@ pub trait Folder<T: Field>: Sized {
         match e {
             TypedExpression::FieldElement(e) => self.fold_field_expression(e).into(),
             TypedExpression::Boolean(e) => self.fold_boolean_expression(e).into(),
-            TypedExpression::FieldElementArray(e) => self.fold_field_array_expression(e).into(),
+            TypedExpression::Array(e) => self.fold_array_expression(e).into(),
+            TypedExpression::Uint(e) => self.fold_uint_expression(e).into(),
+            TypedExpression::Struct(e) => self.fold_struct_expression(e).into(),
         }
     }
 
@@ -73,11 +75,14 @@ pub trait Folder<T: Field>: Sized {
     fn fold_boolean_expression(&mut self, e: BooleanExpression<T>) -> BooleanExpression<T> {
         fold_boolean_expression(self, e)
     }
-    fn fold_field_array_expression(
-        &mut self,
-        e: FieldElementArrayExpression<T>,
-    ) -> FieldElementArrayExpression<T> {
-        fold_field_array_expression(self, e)
+    fn fold_array_expression(&mut self, e: ArrayExpression<T>) -> ArrayExpression<T> {
+        fold_array_expression(self, e)
+    }
+    fn fold_uint_expression(&mut self, e: UintExpression<T>) -> UintExpression<T> {
+        fold_uint_expression(self, e)
+    }
+    fn fold_struct_expression(&mut self, e: StructExpression<T>) -> StructExpression<T> {
+        fold_struct_expression(self, e)
     }
 }
 
@@ -110,10 +115,14 @@ pub fn fold_statement<T: Field, F: Folder<T>>(
         TypedStatement::Condition(left, right) => {
             TypedStatement::Condition(f.fold_expression(left), f.fold_expression(right))
         }
+        TypedStatement::Assertion(e, metadata) => {
+            TypedStatement::Assertion(f.fold_boolean_expression(e), metadata)
+        }
+        TypedStatement::Span(span) => TypedStatement::Span(span),
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v),
-            from,
-            to,
+            f.fold_field_expression(from),
+            f.fold_field_expression(to),
             statements
                 .into_iter()
                 .flat_map(|s| f.fold_statement(s))
@@ -127,32 +136,35 @@ pub fn fold_statement<T: Field, F: Folder<T>>(
     vec![res]
 }
 
-pub fn fold_field_array_expression<T: Field, F: Folder<T>>(
+pub fn fold_array_expression<T: Field, F: Folder<T>>(
     f: &mut F,
-    e: FieldElementArrayExpression<T>,
-) -> FieldElementArrayExpression<T> {
+    e: ArrayExpression<T>,
+) -> ArrayExpression<T> {
     match e {
-        FieldElementArrayExpression::Identifier(size, id) => {
-            FieldElementArrayExpression::Identifier(size, f.fold_name(id))
-        }
-        FieldElementArrayExpression::Value(size, exprs) => FieldElementArrayExpression::Value(
-            size,
-            exprs
-                .into_iter()
-                .map(|e| f.fold_field_expression(e))
-                .collect(),
+        ArrayExpression::Identifier(ty, id) => ArrayExpression::Identifier(ty, f.fold_name(id)),
+        ArrayExpression::Value(ty, exprs) => ArrayExpression::Value(
+            ty,
+            exprs.into_iter().map(|e| f.fold_expression(e)).collect(),
         ),
-        FieldElementArrayExpression::FunctionCall(size, id, exps) => {
+        ArrayExpression::FunctionCall(ty, id, exps) => {
             let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
-            FieldElementArrayExpression::FunctionCall(size, id, exps)
+            ArrayExpression::FunctionCall(ty, id, exps)
         }
-        FieldElementArrayExpression::IfElse(box condition, box consequence, box alternative) => {
-            FieldElementArrayExpression::IfElse(
+        ArrayExpression::IfElse(box condition, box consequence, box alternative) => {
+            ArrayExpression::IfElse(
                 box f.fold_boolean_expression(condition),
-                box f.fold_field_array_expression(consequence),
-                box f.fold_field_array_expression(alternative),
+                box f.fold_array_expression(consequence),
+                box f.fold_array_expression(alternative),
             )
         }
+        ArrayExpression::Member(ty, box s, id) => {
+            ArrayExpression::Member(ty, box f.fold_struct_expression(s), id)
+        }
+        ArrayExpression::Select(ty, box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            ArrayExpression::Select(ty, box array, box index)
+        }
     }
 }
 
@@ -201,10 +213,13 @@ pub fn fold_field_expression<T: Field, F: Folder<T>>(
             FieldElementExpression::FunctionCall(id, exps)
         }
         FieldElementExpression::Select(box array, box index) => {
-            let array = f.fold_field_array_expression(array);
+            let array = f.fold_array_expression(array);
             let index = f.fold_field_expression(index);
             FieldElementExpression::Select(box array, box index)
         }
+        FieldElementExpression::Member(box s, id) => {
+            FieldElementExpression::Member(box f.fold_struct_expression(s), id)
+        }
     }
 }
 
@@ -254,6 +269,115 @@ pub fn fold_boolean_expression<T: Field, F: Folder<T>>(
             let e = f.fold_boolean_expression(e);
             BooleanExpression::Not(box e)
         }
+        BooleanExpression::UintEq(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            BooleanExpression::UintEq(box e1, box e2)
+        }
+        BooleanExpression::Select(box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            BooleanExpression::Select(box array, box index)
+        }
+        BooleanExpression::Member(box s, id) => {
+            BooleanExpression::Member(box f.fold_struct_expression(s), id)
+        }
+    }
+}
+
+pub fn fold_uint_expression<T: Field, F: Folder<T>>(
+    f: &mut F,
+    e: UintExpression<T>,
+) -> UintExpression<T> {
+    match e {
+        UintExpression::Value(bitwidth, v) => UintExpression::Value(bitwidth, v),
+        UintExpression::Identifier(bitwidth, id) => {
+            UintExpression::Identifier(bitwidth, f.fold_name(id))
+        }
+        UintExpression::Add(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UintExpression::Add(box e1, box e2)
+        }
+        UintExpression::Mult(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UintExpression::Mult(box e1, box e2)
+        }
+        UintExpression::And(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UintExpression::And(box e1, box e2)
+        }
+        UintExpression::Or(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UintExpression::Or(box e1, box e2)
+        }
+        UintExpression::Xor(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UintExpression::Xor(box e1, box e2)
+        }
+        UintExpression::LeftShift(box e, box by) => {
+            let e = f.fold_uint_expression(e);
+            let by = f.fold_field_expression(by);
+            UintExpression::LeftShift(box e, box by)
+        }
+        UintExpression::RightShift(box e, box by) => {
+            let e = f.fold_uint_expression(e);
+            let by = f.fold_field_expression(by);
+            UintExpression::RightShift(box e, box by)
+        }
+        UintExpression::IfElse(box cond, box cons, box alt) => {
+            let cond = f.fold_boolean_expression(cond);
+            let cons = f.fold_uint_expression(cons);
+            let alt = f.fold_uint_expression(alt);
+            UintExpression::IfElse(box cond, box cons, box alt)
+        }
+        UintExpression::FunctionCall(bitwidth, id, exps) => {
+            let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
+            UintExpression::FunctionCall(bitwidth, id, exps)
+        }
+        UintExpression::Select(bitwidth, box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            UintExpression::Select(bitwidth, box array, box index)
+        }
+        UintExpression::Member(bitwidth, box s, id) => {
+            UintExpression::Member(bitwidth, box f.fold_struct_expression(s), id)
+        }
+    }
+}
+
+pub fn fold_struct_expression<T: Field, F: Folder<T>>(
+    f: &mut F,
+    e: StructExpression<T>,
+) -> StructExpression<T> {
+    match e {
+        StructExpression::Identifier(ty, id) => StructExpression::Identifier(ty, f.fold_name(id)),
+        StructExpression::Value(ty, exprs) => StructExpression::Value(
+            ty,
+            exprs.into_iter().map(|e| f.fold_expression(e)).collect(),
+        ),
+        StructExpression::FunctionCall(ty, id, exps) => {
+            let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
+            StructExpression::FunctionCall(ty, id, exps)
+        }
+        StructExpression::IfElse(box cond, box cons, box alt) => {
+            let cond = f.fold_boolean_expression(cond);
+            let cons = f.fold_struct_expression(cons);
+            let alt = f.fold_struct_expression(alt);
+            StructExpression::IfElse(box cond, box cons, box alt)
+        }
+        StructExpression::Member(ty, box s, id) => {
+            StructExpression::Member(ty, box f.fold_struct_expression(s), id)
+        }
+        StructExpression::Select(ty, box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            StructExpression::Select(ty, box array, box index)
+        }
     }
 }
 
@@ -0,0 +1,842 @@
+//! An interpreter of the typed AST, which runs a program right after semantic checking, without
+//! unrolling, inlining or flattening it. Imported modules are compiled, so their functions are
+//! run on their flattened form.
+//!
+//! The interpreter follows the semantics of the compiled program rather than those of a
+//! conventional language, so that its results can be compared with the ones of the IR
+//! interpreter: both branches of a conditional expression are evaluated, unsigned integers wrap
+//! around and the operands of comparisons have to be small enough to be decomposed into bits.
+
+use flat_absy::Span;
+use std::collections::HashMap;
+use std::fmt;
+use typed_absy::*;
+use types::{Signature, Type};
+use zokrates_field::field::Field;
+
+/// The value of an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<T: Field> {
+    Field(T),
+    Boolean(bool),
+    Uint(u64),
+    Array(Vec<Value<T>>),
+    /// The members of a struct, in declaration order
+    Struct(Vec<(String, Value<T>)>),
+}
+
+impl<T: Field> Value<T> {
+    /// Reads a value of type `ty` from its flattened form, as passed to the compiled program
+    pub fn unflatten<I: Iterator<Item = T>>(ty: &Type, values: &mut I) -> Result<Self, String> {
+        match *ty {
+            Type::FieldElement => Ok(Value::Field(next(values)?)),
+            Type::Boolean => match next(values)? {
+                ref v if *v == T::zero() => Ok(Value::Boolean(false)),
+                ref v if *v == T::one() => Ok(Value::Boolean(true)),
+                v => Err(format!("Expected bool, found {}", v)),
+            },
+            Type::Uint(bitwidth) => {
+                let v = next(values)?;
+                match v.to_dec_string().parse::<u64>() {
+                    Ok(u) if u & !mask(bitwidth) == 0 => Ok(Value::Uint(u)),
+                    _ => Err(format!("Expected u{}, found {}", bitwidth, v)),
+                }
+            }
+            Type::Array(ref array_type) => (0..array_type.size)
+                .map(|_| Value::unflatten(&array_type.ty, values))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            Type::Struct(ref struct_type) => struct_type
+                .members
+                .iter()
+                .map(|m| Value::unflatten(&m.ty, values).map(|v| (m.id.clone(), v)))
+                .collect::<Result<_, _>>()
+                .map(Value::Struct),
+        }
+    }
+
+    /// Returns the field elements the value is made of once flattened
+    pub fn flatten(self) -> Vec<T> {
+        let mut res = vec![];
+        self.flatten_into(&mut res);
+        res
+    }
+
+    fn flatten_into(self, res: &mut Vec<T>) {
+        match self {
+            Value::Field(v) => res.push(v),
+            Value::Boolean(b) => res.push(if b { T::one() } else { T::zero() }),
+            // the value may not fit in a usize on 32 bit targets
+            Value::Uint(u) => res.push(T::from_dec_string(u.to_string())),
+            Value::Array(values) => {
+                for v in values {
+                    v.flatten_into(res);
+                }
+            }
+            Value::Struct(members) => {
+                for (_, v) in members {
+                    v.flatten_into(res);
+                }
+            }
+        }
+    }
+
+    fn into_field(self) -> T {
+        match self {
+            Value::Field(v) => v,
+            v => panic!("expected a field element, found {:?}", v),
+        }
+    }
+
+    fn into_boolean(self) -> bool {
+        match self {
+            Value::Boolean(b) => b,
+            v => panic!("expected a boolean, found {:?}", v),
+        }
+    }
+
+    fn into_uint(self) -> u64 {
+        match self {
+            Value::Uint(u) => u,
+            v => panic!("expected an unsigned integer, found {:?}", v),
+        }
+    }
+
+    fn into_array(self) -> Vec<Value<T>> {
+        match self {
+            Value::Array(values) => values,
+            v => panic!("expected an array, found {:?}", v),
+        }
+    }
+
+    fn into_struct(self) -> Vec<(String, Value<T>)> {
+        match self {
+            Value::Struct(members) => members,
+            v => panic!("expected a struct, found {:?}", v),
+        }
+    }
+}
+
+fn next<T: Field, I: Iterator<Item = T>>(values: &mut I) -> Result<T, String> {
+    values
+        .next()
+        .ok_or_else(|| String::from("Not enough values"))
+}
+
+// the values of unsigned integers of `bitwidth` bits
+fn mask(bitwidth: usize) -> u64 {
+    match bitwidth {
+        64 => u64::max_value(),
+        b => (1 << b) - 1,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl<T: Field> TypedProg<T> {
+    /// Runs the main function of the program on `inputs`. Arguments and return values are
+    /// flattened to field elements like for the compiled program, so that the return values
+    /// can be compared with the ones of its witness.
+    pub fn execute(&self, inputs: &[T]) -> Result<Vec<T>, Error> {
+        let main = self
+            .functions
+            .iter()
+            .find(|f| f.id == "main")
+            .expect("a checked program should have a main function");
+
+        let expected: usize = main
+            .arguments
+            .iter()
+            .map(|p| p.id.get_type().get_primitive_count())
+            .sum();
+
+        if inputs.len() != expected {
+            return Err(Error {
+                message: format!(
+                    "Program takes {} input{} but was passed {} value{}",
+                    expected,
+                    if expected == 1 { "" } else { "s" },
+                    inputs.len(),
+                    if inputs.len() == 1 { "" } else { "s" }
+                ),
+            });
+        }
+
+        let mut inputs = inputs.iter().cloned();
+        let arguments = main
+            .arguments
+            .iter()
+            .map(|p| {
+                Value::unflatten(&p.id.get_type(), &mut inputs).map_err(|e| Error {
+                    message: format!("Invalid value for `{}`: {}", p.id.id, e),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut interpreter = Interpreter::new(self)?;
+
+        Ok(interpreter
+            .run(main, arguments)?
+            .into_iter()
+            .flat_map(|v| v.flatten())
+            .collect())
+    }
+}
+
+// the values of the variables of a function call
+type Frame<T> = HashMap<String, Value<T>>;
+
+struct Interpreter<'ast, T: Field> {
+    program: &'ast TypedProg<T>,
+    // the values of the module constants, visible in every function
+    constants: Frame<T>,
+    // the location of the statement being run
+    span: Option<Span>,
+}
+
+impl<'ast, T: Field> Interpreter<'ast, T> {
+    fn new(program: &'ast TypedProg<T>) -> Result<Self, Error> {
+        let mut interpreter = Interpreter {
+            program,
+            constants: program
+                .imported_constants
+                .iter()
+                .map(|(id, v)| (id.clone(), Value::Field(v.clone())))
+                .collect(),
+            span: None,
+        };
+
+        // constants only refer to previous constants
+        for c in &program.constants {
+            let value = interpreter.field(&Frame::new(), &c.expression)?;
+            interpreter
+                .constants
+                .insert(c.id.clone(), Value::Field(value));
+        }
+
+        Ok(interpreter)
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error {
+            message: match self.span {
+                Some(ref span) => format!("{} at {}", message, span),
+                None => message,
+            },
+        }
+    }
+
+    fn run(
+        &mut self,
+        function: &TypedFunction<T>,
+        arguments: Vec<Value<T>>,
+    ) -> Result<Vec<Value<T>>, Error> {
+        let mut frame: Frame<T> = function
+            .arguments
+            .iter()
+            .map(|p| p.id.id.clone())
+            .zip(arguments)
+            .collect();
+
+        // errors in the function are reported at the location of the failing statement
+        let caller_span = self.span.take();
+        let returned = self.block(&mut frame, &function.statements)?;
+        self.span = caller_span;
+
+        Ok(returned.expect("a checked function should return"))
+    }
+
+    fn call(
+        &mut self,
+        frame: &Frame<T>,
+        id: &str,
+        arguments: &[TypedExpression<T>],
+        outputs: Vec<Type>,
+    ) -> Result<Vec<Value<T>>, Error> {
+        let signature = Signature::new()
+            .inputs(arguments.iter().map(|a| a.get_type()).collect())
+            .outputs(outputs);
+
+        let arguments = arguments
+            .iter()
+            .map(|a| self.expression(frame, a))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let program = self.program;
+
+        if let Some(function) = program
+            .functions
+            .iter()
+            .find(|f| f.id == id && f.signature == signature)
+        {
+            return self.run(function, arguments);
+        }
+
+        // imported functions are only available in their flattened form
+        let function = program
+            .imported_functions
+            .iter()
+            .find(|f| f.id == id && f.signature == signature)
+            .unwrap_or_else(|| panic!("function {}{} should be declared", id, signature));
+
+        let outputs = function
+            .execute(arguments.into_iter().flat_map(|v| v.flatten()).collect())
+            .map_err(|e| self.error(e.to_string()))?;
+
+        let mut outputs = outputs.into_iter();
+        Ok(signature
+            .outputs
+            .iter()
+            .map(|ty| Value::unflatten(ty, &mut outputs).unwrap())
+            .collect())
+    }
+
+    // runs `statements`, returning the return values if one of them returns
+    fn block(
+        &mut self,
+        frame: &mut Frame<T>,
+        statements: &[TypedStatement<T>],
+    ) -> Result<Option<Vec<Value<T>>>, Error> {
+        for statement in statements {
+            if let Some(values) = self.statement(frame, statement)? {
+                return Ok(Some(values));
+            }
+        }
+        Ok(None)
+    }
+
+    fn statement(
+        &mut self,
+        frame: &mut Frame<T>,
+        statement: &TypedStatement<T>,
+    ) -> Result<Option<Vec<Value<T>>>, Error> {
+        match *statement {
+            TypedStatement::Return(ref expressions) => {
+                let values = expressions
+                    .iter()
+                    .map(|e| self.expression(frame, e))
+                    .collect::<Result<_, _>>()?;
+                return Ok(Some(values));
+            }
+            TypedStatement::Definition(ref assignee, ref e) => {
+                let value = self.expression(frame, e)?;
+                self.assign(frame, assignee, value)?;
+            }
+            // variables are defined by their first assignment
+            TypedStatement::Declaration(..) => {}
+            TypedStatement::Condition(ref lhs, ref rhs) => {
+                let lhs = self.expression(frame, lhs)?.flatten();
+                let rhs = self.expression(frame, rhs)?.flatten();
+                if let Some((l, r)) = lhs.into_iter().zip(rhs).find(|(l, r)| l != r) {
+                    return Err(self.error(format!("Expected {} to equal {}", l, r)));
+                }
+            }
+            TypedStatement::Assertion(ref e, ref metadata) => {
+                let (lhs, rhs) = match *e {
+                    BooleanExpression::Eq(ref lhs, ref rhs) => {
+                        (self.field(frame, lhs)?, self.field(frame, rhs)?)
+                    }
+                    ref e => (
+                        T::one(),
+                        match self.boolean(frame, e)? {
+                            true => T::one(),
+                            false => T::zero(),
+                        },
+                    ),
+                };
+                if lhs != rhs {
+                    return Err(Error {
+                        message: metadata.describe(lhs, rhs),
+                    });
+                }
+            }
+            TypedStatement::For(ref variable, ref from, ref to, ref statements) => {
+                let mut current = self.field(frame, from)?;
+                let to = self.field(frame, to)?;
+                while current < to {
+                    frame.insert(variable.id.clone(), Value::Field(current.clone()));
+                    if let Some(values) = self.block(frame, statements)? {
+                        return Ok(Some(values));
+                    }
+                    current = current + T::one();
+                }
+            }
+            TypedStatement::MultipleDefinition(ref variables, ref e) => {
+                let values = match *e {
+                    TypedExpressionList::FunctionCall(ref id, ref arguments, ref types) => {
+                        self.call(frame, id, arguments, types.clone())?
+                    }
+                };
+                for (variable, value) in variables.iter().zip(values) {
+                    frame.insert(variable.id.clone(), value);
+                }
+            }
+            TypedStatement::Span(ref span) => self.span = Some(span.clone()),
+        }
+
+        Ok(None)
+    }
+
+    fn assign(
+        &mut self,
+        frame: &mut Frame<T>,
+        assignee: &TypedAssignee<T>,
+        value: Value<T>,
+    ) -> Result<(), Error> {
+        // a[i][j] = e is stored in a, at indices [i, j]
+        let mut indices = vec![];
+        let mut assignee = assignee;
+        while let TypedAssignee::ArrayElement(ref array, ref index) = *assignee {
+            indices.push(index);
+            assignee = array;
+        }
+        let variable = match *assignee {
+            TypedAssignee::Identifier(ref variable) => variable,
+            TypedAssignee::ArrayElement(..) => unreachable!(),
+        };
+
+        let mut ty = variable.get_type();
+        let mut positions = vec![];
+        for index in indices.into_iter().rev() {
+            let array_type = match ty {
+                Type::Array(array_type) => array_type,
+                _ => panic!("array element has to take array"),
+            };
+            positions.push(self.index(frame, index, array_type.size)?);
+            ty = *array_type.ty;
+        }
+
+        if positions.is_empty() {
+            frame.insert(variable.id.clone(), value);
+            return Ok(());
+        }
+
+        let mut target = match frame.get_mut(&variable.id) {
+            Some(target) => target,
+            None => return Err(self.error(format!("`{}` is not defined", variable.id))),
+        };
+        for position in positions {
+            target = match *target {
+                Value::Array(ref mut values) => &mut values[position],
+                _ => panic!("array element has to take array"),
+            };
+        }
+        *target = value;
+
+        Ok(())
+    }
+
+    fn lookup<'a>(&'a self, frame: &'a Frame<T>, id: &str) -> Result<&'a Value<T>, Error> {
+        frame
+            .get(id)
+            .or_else(|| self.constants.get(id))
+            .ok_or_else(|| self.error(format!("`{}` is not defined", id)))
+    }
+
+    // evaluates `index` as an index in an array of `size` elements
+    fn index(
+        &mut self,
+        frame: &Frame<T>,
+        index: &FieldElementExpression<T>,
+        size: usize,
+    ) -> Result<usize, Error> {
+        let index = self.field(frame, index)?;
+        match index.to_dec_string().parse::<usize>() {
+            Ok(i) if i < size => Ok(i),
+            _ => Err(self.error(format!(
+                "Index {} is out of bounds for an array of size {}",
+                index, size
+            ))),
+        }
+    }
+
+    fn select(
+        &mut self,
+        frame: &Frame<T>,
+        array: &ArrayExpression<T>,
+        index: &FieldElementExpression<T>,
+    ) -> Result<Value<T>, Error> {
+        let index = self.index(frame, index, array.size())?;
+        match *array {
+            // avoid copying the whole array
+            ArrayExpression::Identifier(_, ref id) => match *self.lookup(frame, id)? {
+                Value::Array(ref values) => Ok(values[index].clone()),
+                ref v => panic!("expected an array, found {:?}", v),
+            },
+            ref array => Ok(self.array(frame, array)?.swap_remove(index)),
+        }
+    }
+
+    fn member(
+        &mut self,
+        frame: &Frame<T>,
+        s: &StructExpression<T>,
+        id: &str,
+    ) -> Result<Value<T>, Error> {
+        Ok(self
+            .strukt(frame, s)?
+            .into_iter()
+            .find(|(member, _)| member == id)
+            .map(|(_, v)| v)
+            .unwrap_or_else(|| panic!("struct should have member {}", id)))
+    }
+
+    // the operands of comparisons are decomposed into bits, which requires them to be small enough
+    fn check_comparable(&self, value: &T) -> Result<(), Error> {
+        let bits = T::get_required_bits() - 2;
+        match *value < T::from(2).pow(bits) {
+            true => Ok(()),
+            false => Err(self.error(format!(
+                "{} is too large to be compared, it should be smaller than 2^{}",
+                value, bits
+            ))),
+        }
+    }
+
+    fn expression(&mut self, frame: &Frame<T>, e: &TypedExpression<T>) -> Result<Value<T>, Error> {
+        match *e {
+            TypedExpression::FieldElement(ref e) => self.field(frame, e).map(Value::Field),
+            TypedExpression::Boolean(ref e) => self.boolean(frame, e).map(Value::Boolean),
+            TypedExpression::Uint(ref e) => self.uint(frame, e).map(Value::Uint),
+            TypedExpression::Array(ref e) => self.array(frame, e).map(Value::Array),
+            TypedExpression::Struct(ref e) => self.strukt(frame, e).map(Value::Struct),
+        }
+    }
+
+    fn field(&mut self, frame: &Frame<T>, e: &FieldElementExpression<T>) -> Result<T, Error> {
+        Ok(match *e {
+            FieldElementExpression::Number(ref n) => n.clone(),
+            FieldElementExpression::Identifier(ref id) => {
+                self.lookup(frame, id)?.clone().into_field()
+            }
+            FieldElementExpression::Add(ref lhs, ref rhs) => {
+                self.field(frame, lhs)? + self.field(frame, rhs)?
+            }
+            FieldElementExpression::Sub(ref lhs, ref rhs) => {
+                self.field(frame, lhs)? - self.field(frame, rhs)?
+            }
+            FieldElementExpression::Mult(ref lhs, ref rhs) => {
+                self.field(frame, lhs)? * self.field(frame, rhs)?
+            }
+            FieldElementExpression::Div(ref lhs, ref rhs) => {
+                let lhs = self.field(frame, lhs)?;
+                let rhs = self.field(frame, rhs)?;
+                if rhs == T::zero() {
+                    return Err(self.error(String::from("Division by zero")));
+                }
+                lhs / rhs
+            }
+            FieldElementExpression::Pow(ref base, ref exponent) => {
+                self.field(frame, base)?.pow(self.field(frame, exponent)?)
+            }
+            FieldElementExpression::IfElse(ref condition, ref consequence, ref alternative) => {
+                // both branches are part of the circuit
+                let condition = self.boolean(frame, condition)?;
+                let consequence = self.field(frame, consequence)?;
+                let alternative = self.field(frame, alternative)?;
+                match condition {
+                    true => consequence,
+                    false => alternative,
+                }
+            }
+            FieldElementExpression::FunctionCall(ref id, ref arguments) => self
+                .call(frame, id, arguments, vec![Type::FieldElement])?
+                .remove(0)
+                .into_field(),
+            FieldElementExpression::Select(ref array, ref index) => {
+                self.select(frame, array, index)?.into_field()
+            }
+            FieldElementExpression::Member(ref s, ref id) => {
+                self.member(frame, s, id)?.into_field()
+            }
+        })
+    }
+
+    fn boolean(&mut self, frame: &Frame<T>, e: &BooleanExpression<T>) -> Result<bool, Error> {
+        Ok(match *e {
+            BooleanExpression::Identifier(ref id) => self.lookup(frame, id)?.clone().into_boolean(),
+            BooleanExpression::Value(b) => b,
+            BooleanExpression::Lt(ref lhs, ref rhs)
+            | BooleanExpression::Le(ref lhs, ref rhs)
+            | BooleanExpression::Gt(ref lhs, ref rhs)
+            | BooleanExpression::Ge(ref lhs, ref rhs) => {
+                let lhs = self.field(frame, lhs)?;
+                let rhs = self.field(frame, rhs)?;
+                self.check_comparable(&lhs)?;
+                self.check_comparable(&rhs)?;
+                match *e {
+                    BooleanExpression::Lt(..) => lhs < rhs,
+                    BooleanExpression::Le(..) => lhs <= rhs,
+                    BooleanExpression::Gt(..) => lhs > rhs,
+                    _ => lhs >= rhs,
+                }
+            }
+            BooleanExpression::Eq(ref lhs, ref rhs) => {
+                self.field(frame, lhs)? == self.field(frame, rhs)?
+            }
+            BooleanExpression::UintEq(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)? == self.uint(frame, rhs)?
+            }
+            // both operands are part of the circuit, so none is skipped
+            BooleanExpression::Or(ref lhs, ref rhs) => {
+                let lhs = self.boolean(frame, lhs)?;
+                self.boolean(frame, rhs)? || lhs
+            }
+            BooleanExpression::And(ref lhs, ref rhs) => {
+                let lhs = self.boolean(frame, lhs)?;
+                self.boolean(frame, rhs)? && lhs
+            }
+            BooleanExpression::Not(ref e) => !self.boolean(frame, e)?,
+            BooleanExpression::Select(ref array, ref index) => {
+                self.select(frame, array, index)?.into_boolean()
+            }
+            BooleanExpression::Member(ref s, ref id) => self.member(frame, s, id)?.into_boolean(),
+        })
+    }
+
+    fn uint(&mut self, frame: &Frame<T>, e: &UintExpression<T>) -> Result<u64, Error> {
+        let mask = mask(e.bitwidth());
+
+        Ok(match *e {
+            UintExpression::Value(_, v) => v,
+            UintExpression::Identifier(_, ref id) => self.lookup(frame, id)?.clone().into_uint(),
+            UintExpression::Add(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)?.wrapping_add(self.uint(frame, rhs)?) & mask
+            }
+            UintExpression::Mult(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)?.wrapping_mul(self.uint(frame, rhs)?) & mask
+            }
+            UintExpression::And(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)? & self.uint(frame, rhs)?
+            }
+            UintExpression::Or(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)? | self.uint(frame, rhs)?
+            }
+            UintExpression::Xor(ref lhs, ref rhs) => {
+                self.uint(frame, lhs)? ^ self.uint(frame, rhs)?
+            }
+            UintExpression::LeftShift(ref operand, ref by)
+            | UintExpression::RightShift(ref operand, ref by) => {
+                let bitwidth = operand.bitwidth();
+                let value = self.uint(frame, operand)?;
+                // shifting by more than the bitwidth drops all the bits
+                let by = self
+                    .field(frame, by)?
+                    .to_dec_string()
+                    .parse::<usize>()
+                    .unwrap_or(bitwidth);
+                match (by < bitwidth, e) {
+                    (false, _) => 0,
+                    (true, UintExpression::LeftShift(..)) => (value << by) & mask,
+                    (true, _) => value >> by,
+                }
+            }
+            UintExpression::IfElse(ref condition, ref consequence, ref alternative) => {
+                let condition = self.boolean(frame, condition)?;
+                let consequence = self.uint(frame, consequence)?;
+                let alternative = self.uint(frame, alternative)?;
+                match condition {
+                    true => consequence,
+                    false => alternative,
+                }
+            }
+            UintExpression::FunctionCall(bitwidth, ref id, ref arguments) => self
+                .call(frame, id, arguments, vec![Type::Uint(bitwidth)])?
+                .remove(0)
+                .into_uint(),
+            UintExpression::Select(_, ref array, ref index) => {
+                self.select(frame, array, index)?.into_uint()
+            }
+            UintExpression::Member(_, ref s, ref id) => self.member(frame, s, id)?.into_uint(),
+        })
+    }
+
+    fn array(&mut self, frame: &Frame<T>, e: &ArrayExpression<T>) -> Result<Vec<Value<T>>, Error> {
+        Ok(match *e {
+            ArrayExpression::Identifier(_, ref id) => self.lookup(frame, id)?.clone().into_array(),
+            ArrayExpression::Value(_, ref values) => values
+                .iter()
+                .map(|v| self.expression(frame, v))
+                .collect::<Result<_, _>>()?,
+            ArrayExpression::FunctionCall(ref ty, ref id, ref arguments) => self
+                .call(frame, id, arguments, vec![Type::Array(ty.clone())])?
+                .remove(0)
+                .into_array(),
+            ArrayExpression::IfElse(ref condition, ref consequence, ref alternative) => {
+                let condition = self.boolean(frame, condition)?;
+                let consequence = self.array(frame, consequence)?;
+                let alternative = self.array(frame, alternative)?;
+                match condition {
+                    true => consequence,
+                    false => alternative,
+                }
+            }
+            ArrayExpression::Member(_, ref s, ref id) => self.member(frame, s, id)?.into_array(),
+            ArrayExpression::Select(_, ref array, ref index) => {
+                self.select(frame, array, index)?.into_array()
+            }
+        })
+    }
+
+    fn strukt(
+        &mut self,
+        frame: &Frame<T>,
+        e: &StructExpression<T>,
+    ) -> Result<Vec<(String, Value<T>)>, Error> {
+        Ok(match *e {
+            StructExpression::Identifier(_, ref id) => {
+                self.lookup(frame, id)?.clone().into_struct()
+            }
+            StructExpression::Value(ref ty, ref values) => ty
+                .members
+                .iter()
+                .zip(values)
+                .map(|(m, v)| Ok((m.id.clone(), self.expression(frame, v)?)))
+                .collect::<Result<_, _>>()?,
+            StructExpression::FunctionCall(ref ty, ref id, ref arguments) => self
+                .call(frame, id, arguments, vec![Type::Struct(ty.clone())])?
+                .remove(0)
+                .into_struct(),
+            StructExpression::IfElse(ref condition, ref consequence, ref alternative) => {
+                let condition = self.boolean(frame, condition)?;
+                let consequence = self.strukt(frame, consequence)?;
+                let alternative = self.strukt(frame, alternative)?;
+                match condition {
+                    true => consequence,
+                    false => alternative,
+                }
+            }
+            StructExpression::Member(_, ref s, ref id) => self.member(frame, s, id)?.into_struct(),
+            StructExpression::Select(_, ref array, ref index) => {
+                self.select(frame, array, index)?.into_struct()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use compile::{check_str, compile_str};
+    use zokrates_field::field::FieldPrime;
+
+    // runs `source` on the typed AST and compiled, checking that both agree
+    fn run(source: &str, inputs: &[i32]) -> Result<Vec<FieldPrime>, String> {
+        let inputs: Vec<FieldPrime> = inputs.iter().map(|i| FieldPrime::from(*i)).collect();
+
+        let typed = check_str(source)
+            .unwrap()
+            .execute(&inputs)
+            .map_err(|e| e.to_string());
+
+        let compiled = compile_str(source)
+            .unwrap()
+            .program
+            .execute(&inputs)
+            .map(|w| w.return_values().into_iter().cloned().collect::<Vec<_>>());
+
+        assert_eq!(typed.is_ok(), compiled.is_ok());
+        if let Ok(ref values) = typed {
+            assert_eq!(values, &compiled.unwrap());
+        }
+
+        typed
+    }
+
+    #[test]
+    fn agrees_with_compiled_program() {
+        let source = r#"
+struct Pair {
+	field left
+	u8 right
+}
+
+def swap(field[3] a, field i, field j) -> (field[3]):
+	field tmp = a[i]
+	a[i] = a[j]
+	a[j] = tmp
+	return a
+
+def main(field[3] a, Pair p, bool b) -> (field[3], field, u8, field):
+	field sum = 0
+	for field i in 0..3 do
+		sum = sum + a[i] * (i + 1)
+	endfor
+	field[3] s = swap(a, 0, 2)
+	u8 c = p.right * 0x10 + (p.right << 4)
+	field smaller = if sum < p.left then 1 else 0 fi
+	return s, if b then sum else p.left / 2 fi, c ^ 0xff, smaller
+"#;
+
+        assert_eq!(
+            run(source, &[1, 2, 3, 42, 3, 1]).unwrap(),
+            vec![3, 2, 1, 14, 159, 1]
+                .into_iter()
+                .map(FieldPrime::from)
+                .collect::<Vec<_>>()
+        );
+
+        // unsigned integers wrap around
+        assert_eq!(
+            run(source, &[1, 2, 3, 42, 17, 0]).unwrap()[3..5],
+            [FieldPrime::from(21), FieldPrime::from(223)]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let source = r#"
+def main(field a, u8 b) -> (field):
+	assert(0 < a, "a should not be zero")
+	return 1 / a
+"#;
+
+        assert_eq!(
+            run(source, &[0, 1]).unwrap_err(),
+            "Assertion failed at 3:2: a should not be zero"
+        );
+        assert_eq!(
+            run(source, &[1]).unwrap_err(),
+            "Program takes 2 inputs but was passed 1 value"
+        );
+
+        let program = check_str(source).unwrap();
+        assert_eq!(
+            program
+                .execute(&[FieldPrime::from(1), FieldPrime::from(256)])
+                .unwrap_err()
+                .to_string(),
+            "Invalid value for `b`: Expected u8, found 256"
+        );
+
+        // once the file of the program is known, errors are located in it like those of the
+        // compiled program
+        let mut program = check_str(source).unwrap();
+        program.attach_file("e.code");
+        assert_eq!(
+            program
+                .execute(&[FieldPrime::from(0), FieldPrime::from(1)])
+                .unwrap_err()
+                .to_string(),
+            "Assertion failed at e.code:3:2: a should not be zero"
+        );
+
+        let mut program = check_str("\ndef main(field a) -> (field):\n\treturn 1 / a\n").unwrap();
+        program.attach_file("e.code");
+        assert_eq!(
+            program
+                .execute(&[FieldPrime::from(0)])
+                .unwrap_err()
+                .to_string(),
+            "Division by zero at e.code:3:2"
+        );
+    }
+}
@@ -6,6 +6,7 @@
 //! @date 2017
 
 pub mod folder;
+pub mod interpreter;
 mod parameter;
 mod variable;
 
@@ -16,7 +17,7 @@ use types::Signature;
 use flat_absy::*;
 use imports::Import;
 use std::fmt;
-use types::Type;
+use types::{ArrayType, StructType, Type};
 use zokrates_field::field::Field;
 
 pub use self::folder::Folder;
@@ -25,8 +26,23 @@ pub use self::folder::Folder;
 pub struct TypedProg<T: Field> {
     /// Functions of the program
     pub functions: Vec<TypedFunction<T>>,
+    /// Constants declared in the program, in declaration order
+    pub constants: Vec<TypedConstant<T>>,
     pub imports: Vec<Import>,
     pub imported_functions: Vec<FlatFunction<T>>,
+    /// Values of the constants declared in imported modules
+    pub imported_constants: Vec<(String, T)>,
+}
+
+impl<T: Field> TypedProg<T> {
+    /// Sets the file of the spans of the program which are not located yet
+    pub fn attach_file(&mut self, file: &str) {
+        for function in self.functions.iter_mut() {
+            for statement in function.statements.iter_mut() {
+                statement.attach_file(file);
+            }
+        }
+    }
 }
 
 impl<T: Field> fmt::Display for TypedProg<T> {
@@ -38,6 +54,18 @@ impl<T: Field> fmt::Display for TypedProg<T> {
                 .map(|x| format!("{}", x))
                 .collect::<Vec<_>>(),
         );
+        res.extend(
+            self.imported_constants
+                .iter()
+                .map(|(id, value)| format!("const field {} = {}", id, value))
+                .collect::<Vec<_>>(),
+        );
+        res.extend(
+            self.constants
+                .iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<_>>(),
+        );
         res.extend(
             self.imported_functions
                 .iter()
@@ -58,12 +86,22 @@ impl<T: Field> fmt::Debug for TypedProg<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "program(\n\timports:\n\t\t{}\n\tfunctions:\n\t\t{}{}\n)",
+            "program(\n\timports:\n\t\t{}\n\tconstants:\n\t\t{}{}\n\tfunctions:\n\t\t{}{}\n)",
             self.imports
                 .iter()
                 .map(|x| format!("{:?}", x))
                 .collect::<Vec<_>>()
                 .join("\n\t\t"),
+            self.imported_constants
+                .iter()
+                .map(|(id, value)| format!("{} = {}", id, value))
+                .collect::<Vec<_>>()
+                .join("\n\t\t"),
+            self.constants
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect::<Vec<_>>()
+                .join("\n\t\t"),
             self.imported_functions
                 .iter()
                 .map(|x| format!("{}", x))
@@ -78,6 +116,26 @@ impl<T: Field> fmt::Debug for TypedProg<T> {
     }
 }
 
+#[derive(Clone, PartialEq)]
+pub struct TypedConstant<T: Field> {
+    /// Name of the constant
+    pub id: String,
+    /// Expression the constant is bound to, reduced to a number during constant propagation
+    pub expression: FieldElementExpression<T>,
+}
+
+impl<T: Field> fmt::Display for TypedConstant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "const field {} = {}", self.id, self.expression)
+    }
+}
+
+impl<T: Field> fmt::Debug for TypedConstant<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TypedConstant(id: {:?}, expression: {:?})", self.id, self.expression)
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct TypedFunction<T: Field> {
     /// Name of the program
@@ -145,7 +203,7 @@ impl<T: Field> Typed for TypedAssignee<T> {
             TypedAssignee::ArrayElement(ref a, _) => {
                 let a_type = a.get_type();
                 match a_type {
-                    Type::FieldElementArray(_) => Type::FieldElement,
+                    Type::Array(array_type) => *array_type.ty,
                     _ => panic!("array element has to take array"),
                 }
             }
@@ -174,8 +232,32 @@ pub enum TypedStatement<T: Field> {
     Definition(TypedAssignee<T>, TypedExpression<T>),
     Declaration(Variable),
     Condition(TypedExpression<T>, TypedExpression<T>),
-    For(Variable, T, T, Vec<TypedStatement<T>>),
+    Assertion(BooleanExpression<T>, DebugInfo),
+    For(
+        Variable,
+        FieldElementExpression<T>,
+        FieldElementExpression<T>,
+        Vec<TypedStatement<T>>,
+    ),
     MultipleDefinition(Vec<Variable>, TypedExpressionList<T>),
+    // marks the location of the statements that follow, until the next marker
+    Span(Span),
+}
+
+impl<T: Field> TypedStatement<T> {
+    /// Sets the file of the spans of the statement if it is not known yet
+    pub fn attach_file(&mut self, file: &str) {
+        match *self {
+            TypedStatement::Span(ref mut span) => span.attach_file(file),
+            TypedStatement::Assertion(_, ref mut metadata) => metadata.span.attach_file(file),
+            TypedStatement::For(_, _, _, ref mut statements) => {
+                for statement in statements.iter_mut() {
+                    statement.attach_file(file);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 impl<T: Field> fmt::Debug for TypedStatement<T> {
@@ -198,6 +280,9 @@ impl<T: Field> fmt::Debug for TypedStatement<T> {
             TypedStatement::Condition(ref lhs, ref rhs) => {
                 write!(f, "Condition({:?}, {:?})", lhs, rhs)
             }
+            TypedStatement::Assertion(ref e, ref metadata) => {
+                write!(f, "Assertion({:?}, {:?})", e, metadata)
+            }
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 try!(write!(f, "for {:?} in {:?}..{:?} do\n", var, start, stop));
                 for l in list {
@@ -208,6 +293,7 @@ impl<T: Field> fmt::Debug for TypedStatement<T> {
             TypedStatement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
+            TypedStatement::Span(ref span) => write!(f, "Span({:?})", span),
         }
     }
 }
@@ -228,6 +314,8 @@ impl<T: Field> fmt::Display for TypedStatement<T> {
             TypedStatement::Declaration(ref var) => write!(f, "{}", var),
             TypedStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             TypedStatement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            TypedStatement::Assertion(ref e, _) => write!(f, "assert({})", e),
+            TypedStatement::Span(ref span) => write!(f, "// {}", span),
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 try!(write!(f, "for {} in {}..{} do\n", var, start, stop));
                 for l in list {
@@ -256,7 +344,9 @@ pub trait Typed {
 pub enum TypedExpression<T: Field> {
     Boolean(BooleanExpression<T>),
     FieldElement(FieldElementExpression<T>),
-    FieldElementArray(FieldElementArrayExpression<T>),
+    Array(ArrayExpression<T>),
+    Uint(UintExpression<T>),
+    Struct(StructExpression<T>),
 }
 
 impl<T: Field> From<BooleanExpression<T>> for TypedExpression<T> {
@@ -271,9 +361,54 @@ impl<T: Field> From<FieldElementExpression<T>> for TypedExpression<T> {
     }
 }
 
-impl<T: Field> From<FieldElementArrayExpression<T>> for TypedExpression<T> {
-    fn from(e: FieldElementArrayExpression<T>) -> TypedExpression<T> {
-        TypedExpression::FieldElementArray(e)
+impl<T: Field> From<ArrayExpression<T>> for TypedExpression<T> {
+    fn from(e: ArrayExpression<T>) -> TypedExpression<T> {
+        TypedExpression::Array(e)
+    }
+}
+
+impl<T: Field> From<UintExpression<T>> for TypedExpression<T> {
+    fn from(e: UintExpression<T>) -> TypedExpression<T> {
+        TypedExpression::Uint(e)
+    }
+}
+
+impl<T: Field> From<StructExpression<T>> for TypedExpression<T> {
+    fn from(e: StructExpression<T>) -> TypedExpression<T> {
+        TypedExpression::Struct(e)
+    }
+}
+
+impl<T: Field> TypedExpression<T> {
+    /// Returns `if condition then consequence else alternative fi`, typed after the branches
+    pub fn if_else(
+        condition: BooleanExpression<T>,
+        consequence: TypedExpression<T>,
+        alternative: TypedExpression<T>,
+    ) -> TypedExpression<T> {
+        match (consequence, alternative) {
+            (TypedExpression::FieldElement(c), TypedExpression::FieldElement(a)) => {
+                FieldElementExpression::IfElse(box condition, box c, box a).into()
+            }
+            // there is no conditional boolean expression, so we use (condition && c) || (!condition && a)
+            (TypedExpression::Boolean(c), TypedExpression::Boolean(a)) => {
+                BooleanExpression::Or(
+                    box BooleanExpression::And(box condition.clone(), box c),
+                    box BooleanExpression::And(box BooleanExpression::Not(box condition), box a),
+                )
+                .into()
+            }
+            (TypedExpression::Uint(c), TypedExpression::Uint(a)) => {
+                UintExpression::IfElse(box condition, box c, box a).into()
+            }
+            (TypedExpression::Struct(c), TypedExpression::Struct(a)) => {
+                StructExpression::IfElse(box condition, box c, box a).into()
+            }
+            (TypedExpression::Array(c), TypedExpression::Array(a)) => {
+                ArrayExpression::IfElse(box condition, box c, box a).into()
+            }
+            _ => panic!("branches of a conditional should have the same type"),
+        }
     }
 }
 
@@ -282,7 +417,9 @@ impl<T: Field> fmt::Display for TypedExpression<T> {
         match *self {
             TypedExpression::Boolean(ref e) => write!(f, "{}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{}", e),
-            TypedExpression::FieldElementArray(ref e) => write!(f, "{}", e),
+            TypedExpression::Array(ref e) => write!(f, "{}", e),
+            TypedExpression::Uint(ref e) => write!(f, "{}", e),
+            TypedExpression::Struct(ref e) => write!(f, "{}", e),
         }
     }
 }
@@ -292,7 +429,9 @@ impl<T: Field> fmt::Debug for TypedExpression<T> {
         match *self {
             TypedExpression::Boolean(ref e) => write!(f, "{:?}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{:?}", e),
-            TypedExpression::FieldElementArray(ref e) => write!(f, "{:?}", e),
+            TypedExpression::Array(ref e) => write!(f, "{:?}", e),
+            TypedExpression::Uint(ref e) => write!(f, "{:?}", e),
+            TypedExpression::Struct(ref e) => write!(f, "{:?}", e),
         }
     }
 }
@@ -302,19 +441,28 @@ impl<T: Field> Typed for TypedExpression<T> {
         match *self {
             TypedExpression::Boolean(_) => Type::Boolean,
             TypedExpression::FieldElement(_) => Type::FieldElement,
-            TypedExpression::FieldElementArray(ref e) => e.get_type(),
+            TypedExpression::Array(ref e) => e.get_type(),
+            TypedExpression::Uint(ref e) => e.get_type(),
+            TypedExpression::Struct(ref e) => e.get_type(),
         }
     }
 }
 
-impl<T: Field> Typed for FieldElementArrayExpression<T> {
+impl<T: Field> Typed for UintExpression<T> {
     fn get_type(&self) -> Type {
-        match *self {
-            FieldElementArrayExpression::Identifier(n, _) => Type::FieldElementArray(n),
-            FieldElementArrayExpression::Value(n, _) => Type::FieldElementArray(n),
-            FieldElementArrayExpression::FunctionCall(n, _, _) => Type::FieldElementArray(n),
-            FieldElementArrayExpression::IfElse(_, ref consequence, _) => consequence.get_type(),
-        }
+        Type::Uint(self.bitwidth())
+    }
+}
+
+impl<T: Field> Typed for ArrayExpression<T> {
+    fn get_type(&self) -> Type {
+        Type::Array(self.array_type().clone())
+    }
+}
+
+impl<T: Field> Typed for StructExpression<T> {
+    fn get_type(&self) -> Type {
+        Type::Struct(self.struct_type().clone())
     }
 }
 
@@ -365,10 +513,8 @@ pub enum FieldElementExpression<T: Field> {
         Box<FieldElementExpression<T>>,
     ),
     FunctionCall(String, Vec<TypedExpression<T>>),
-    Select(
-        Box<FieldElementArrayExpression<T>>,
-        Box<FieldElementExpression<T>>,
-    ),
+    Select(Box<ArrayExpression<T>>, Box<FieldElementExpression<T>>),
+    Member(Box<StructExpression<T>>, String),
 }
 
 #[derive(Clone, PartialEq, Hash, Eq)]
@@ -398,28 +544,136 @@ pub enum BooleanExpression<T: Field> {
     Or(Box<BooleanExpression<T>>, Box<BooleanExpression<T>>),
     And(Box<BooleanExpression<T>>, Box<BooleanExpression<T>>),
     Not(Box<BooleanExpression<T>>),
+    UintEq(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    Select(Box<ArrayExpression<T>>, Box<FieldElementExpression<T>>),
+    Member(Box<StructExpression<T>>, String),
 }
 
-// for now we store the array size in the variants
+// for now we store the array type in the variants
 #[derive(Clone, PartialEq, Hash, Eq)]
-pub enum FieldElementArrayExpression<T: Field> {
+pub enum ArrayExpression<T: Field> {
+    Identifier(ArrayType, String),
+    Value(ArrayType, Vec<TypedExpression<T>>),
+    FunctionCall(ArrayType, String, Vec<TypedExpression<T>>),
+    IfElse(
+        Box<BooleanExpression<T>>,
+        Box<ArrayExpression<T>>,
+        Box<ArrayExpression<T>>,
+    ),
+    Member(ArrayType, Box<StructExpression<T>>, String),
+    Select(
+        ArrayType,
+        Box<ArrayExpression<T>>,
+        Box<FieldElementExpression<T>>,
+    ),
+}
+
+impl<T: Field> ArrayExpression<T> {
+    pub fn array_type(&self) -> &ArrayType {
+        match *self {
+            ArrayExpression::Identifier(ref t, _)
+            | ArrayExpression::Value(ref t, _)
+            | ArrayExpression::FunctionCall(ref t, ..)
+            | ArrayExpression::Member(ref t, ..)
+            | ArrayExpression::Select(ref t, ..) => t,
+            ArrayExpression::IfElse(_, ref consequence, _) => consequence.array_type(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.array_type().size
+    }
+
+    pub fn inner_type(&self) -> &Type {
+        &self.array_type().ty
+    }
+
+    /// Returns the element of the array at `index`, typed after the elements of the array
+    pub fn select(self, index: FieldElementExpression<T>) -> TypedExpression<T> {
+        match self.inner_type().clone() {
+            Type::FieldElement => FieldElementExpression::Select(box self, box index).into(),
+            Type::Boolean => BooleanExpression::Select(box self, box index).into(),
+            Type::Uint(bitwidth) => UintExpression::Select(bitwidth, box self, box index).into(),
+            Type::Struct(ty) => StructExpression::Select(ty, box self, box index).into(),
+            Type::Array(ty) => ArrayExpression::Select(ty, box self, box index).into(),
+        }
+    }
+}
+
+// like for arrays, the bitwidth is stored in the leaf variants, operators take the bitwidth of their left operand
+#[derive(Clone, PartialEq, Hash, Eq)]
+pub enum UintExpression<T: Field> {
+    Value(usize, u64),
     Identifier(usize, String),
-    Value(usize, Vec<FieldElementExpression<T>>),
+    Add(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    Mult(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    And(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    Or(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    Xor(Box<UintExpression<T>>, Box<UintExpression<T>>),
+    LeftShift(Box<UintExpression<T>>, Box<FieldElementExpression<T>>),
+    RightShift(Box<UintExpression<T>>, Box<FieldElementExpression<T>>),
+    IfElse(
+        Box<BooleanExpression<T>>,
+        Box<UintExpression<T>>,
+        Box<UintExpression<T>>,
+    ),
     FunctionCall(usize, String, Vec<TypedExpression<T>>),
+    Select(
+        usize,
+        Box<ArrayExpression<T>>,
+        Box<FieldElementExpression<T>>,
+    ),
+    Member(usize, Box<StructExpression<T>>, String),
+}
+
+impl<T: Field> UintExpression<T> {
+    pub fn bitwidth(&self) -> usize {
+        match *self {
+            UintExpression::Value(b, _)
+            | UintExpression::Identifier(b, _)
+            | UintExpression::FunctionCall(b, ..)
+            | UintExpression::Select(b, ..)
+            | UintExpression::Member(b, ..) => b,
+            UintExpression::Add(ref e, _)
+            | UintExpression::Mult(ref e, _)
+            | UintExpression::And(ref e, _)
+            | UintExpression::Or(ref e, _)
+            | UintExpression::Xor(ref e, _)
+            | UintExpression::LeftShift(ref e, _)
+            | UintExpression::RightShift(ref e, _)
+            | UintExpression::IfElse(_, ref e, _) => e.bitwidth(),
+        }
+    }
+}
+
+// the struct type is stored in the leaf variants, and in member accesses which cannot infer it from their operand
+#[derive(Clone, PartialEq, Hash, Eq)]
+pub enum StructExpression<T: Field> {
+    Identifier(StructType, String),
+    Value(StructType, Vec<TypedExpression<T>>),
+    FunctionCall(StructType, String, Vec<TypedExpression<T>>),
     IfElse(
         Box<BooleanExpression<T>>,
-        Box<FieldElementArrayExpression<T>>,
-        Box<FieldElementArrayExpression<T>>,
+        Box<StructExpression<T>>,
+        Box<StructExpression<T>>,
+    ),
+    Member(StructType, Box<StructExpression<T>>, String),
+    Select(
+        StructType,
+        Box<ArrayExpression<T>>,
+        Box<FieldElementExpression<T>>,
     ),
 }
 
-impl<T: Field> FieldElementArrayExpression<T> {
-    pub fn size(&self) -> usize {
+impl<T: Field> StructExpression<T> {
+    pub fn struct_type(&self) -> &StructType {
         match *self {
-            FieldElementArrayExpression::Identifier(s, _)
-            | FieldElementArrayExpression::Value(s, _)
-            | FieldElementArrayExpression::FunctionCall(s, ..) => s,
-            FieldElementArrayExpression::IfElse(_, ref consequence, _) => consequence.size(),
+            StructExpression::Identifier(ref t, _)
+            | StructExpression::Value(ref t, _)
+            | StructExpression::FunctionCall(ref t, ..)
+            | StructExpression::Member(ref t, ..)
+            | StructExpression::Select(ref t, ..) => t,
+            StructExpression::IfElse(_, ref consequence, _) => consequence.struct_type(),
         }
     }
 }
@@ -452,6 +706,7 @@ impl<T: Field> fmt::Display for FieldElementExpression<T> {
                 write!(f, ")")
             }
             FieldElementExpression::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            FieldElementExpression::Member(ref s, ref id) => write!(f, "{}.{}", s, id),
         }
     }
 }
@@ -469,15 +724,18 @@ impl<T: Field> fmt::Display for BooleanExpression<T> {
             BooleanExpression::And(ref lhs, ref rhs) => write!(f, "{} && {}", lhs, rhs),
             BooleanExpression::Not(ref exp) => write!(f, "!{}", exp),
             BooleanExpression::Value(b) => write!(f, "{}", b),
+            BooleanExpression::UintEq(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            BooleanExpression::Select(ref a, ref index) => write!(f, "{}[{}]", a, index),
+            BooleanExpression::Member(ref s, ref id) => write!(f, "{}.{}", s, id),
         }
     }
 }
 
-impl<T: Field> fmt::Display for FieldElementArrayExpression<T> {
+impl<T: Field> fmt::Display for ArrayExpression<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            FieldElementArrayExpression::Identifier(_, ref var) => write!(f, "{}", var),
-            FieldElementArrayExpression::Value(_, ref values) => write!(
+            ArrayExpression::Identifier(_, ref var) => write!(f, "{}", var),
+            ArrayExpression::Value(_, ref values) => write!(
                 f,
                 "[{}]",
                 values
@@ -486,7 +744,7 @@ impl<T: Field> fmt::Display for FieldElementArrayExpression<T> {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
-            FieldElementArrayExpression::FunctionCall(_, ref i, ref p) => {
+            ArrayExpression::FunctionCall(_, ref i, ref p) => {
                 try!(write!(f, "{}(", i,));
                 for (i, param) in p.iter().enumerate() {
                     try!(write!(f, "{}", param));
@@ -496,13 +754,141 @@ impl<T: Field> fmt::Display for FieldElementArrayExpression<T> {
                 }
                 write!(f, ")")
             }
-            FieldElementArrayExpression::IfElse(ref condition, ref consequent, ref alternative) => {
+            ArrayExpression::IfElse(ref condition, ref consequent, ref alternative) => {
                 write!(
                     f,
                     "if {} then {} else {} fi",
                     condition, consequent, alternative
                 )
             }
+            ArrayExpression::Member(_, ref s, ref id) => write!(f, "{}.{}", s, id),
+            ArrayExpression::Select(_, ref a, ref index) => write!(f, "{}[{}]", a, index),
+        }
+    }
+}
+
+impl<T: Field> fmt::Display for UintExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UintExpression::Value(bitwidth, v) => write!(f, "0x{:01$x}", v, bitwidth / 4),
+            UintExpression::Identifier(_, ref var) => write!(f, "{}", var),
+            UintExpression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
+            UintExpression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
+            UintExpression::And(ref lhs, ref rhs) => write!(f, "({} & {})", lhs, rhs),
+            UintExpression::Or(ref lhs, ref rhs) => write!(f, "({} | {})", lhs, rhs),
+            UintExpression::Xor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
+            UintExpression::LeftShift(ref e, ref by) => write!(f, "({} << {})", e, by),
+            UintExpression::RightShift(ref e, ref by) => write!(f, "({} >> {})", e, by),
+            UintExpression::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "if {} then {} else {} fi",
+                condition, consequent, alternative
+            ),
+            UintExpression::FunctionCall(_, ref i, ref p) => {
+                try!(write!(f, "{}(", i,));
+                for (i, param) in p.iter().enumerate() {
+                    try!(write!(f, "{}", param));
+                    if i < p.len() - 1 {
+                        try!(write!(f, ", "));
+                    }
+                }
+                write!(f, ")")
+            }
+            UintExpression::Select(_, ref a, ref index) => write!(f, "{}[{}]", a, index),
+            UintExpression::Member(_, ref s, ref id) => write!(f, "{}.{}", s, id),
+        }
+    }
+}
+
+impl<T: Field> fmt::Display for StructExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StructExpression::Identifier(_, ref var) => write!(f, "{}", var),
+            StructExpression::Value(ref ty, ref values) => write!(
+                f,
+                "{} {{{}}}",
+                ty.id,
+                ty.members
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(m, v)| format!("{}: {}", m.id, v))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            StructExpression::FunctionCall(_, ref i, ref p) => {
+                try!(write!(f, "{}(", i,));
+                for (i, param) in p.iter().enumerate() {
+                    try!(write!(f, "{}", param));
+                    if i < p.len() - 1 {
+                        try!(write!(f, ", "));
+                    }
+                }
+                write!(f, ")")
+            }
+            StructExpression::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "if {} then {} else {} fi",
+                condition, consequent, alternative
+            ),
+            StructExpression::Member(_, ref s, ref id) => write!(f, "{}.{}", s, id),
+            StructExpression::Select(_, ref a, ref index) => write!(f, "{}[{}]", a, index),
+        }
+    }
+}
+
+impl<T: Field> fmt::Debug for UintExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UintExpression::Value(bitwidth, v) => write!(f, "Value(u{}, {})", bitwidth, v),
+            UintExpression::Identifier(_, ref var) => write!(f, "Ide({})", var),
+            UintExpression::Add(ref lhs, ref rhs) => write!(f, "Add({:?}, {:?})", lhs, rhs),
+            UintExpression::Mult(ref lhs, ref rhs) => write!(f, "Mult({:?}, {:?})", lhs, rhs),
+            UintExpression::And(ref lhs, ref rhs) => write!(f, "And({:?}, {:?})", lhs, rhs),
+            UintExpression::Or(ref lhs, ref rhs) => write!(f, "Or({:?}, {:?})", lhs, rhs),
+            UintExpression::Xor(ref lhs, ref rhs) => write!(f, "Xor({:?}, {:?})", lhs, rhs),
+            UintExpression::LeftShift(ref e, ref by) => write!(f, "LeftShift({:?}, {:?})", e, by),
+            UintExpression::RightShift(ref e, ref by) => {
+                write!(f, "RightShift({:?}, {:?})", e, by)
+            }
+            UintExpression::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "IfElse({:?}, {:?}, {:?})",
+                condition, consequent, alternative
+            ),
+            UintExpression::FunctionCall(_, ref i, ref p) => {
+                try!(write!(f, "FunctionCall({:?}, (", i));
+                try!(f.debug_list().entries(p.iter()).finish());
+                write!(f, ")")
+            }
+            UintExpression::Select(_, ref a, ref index) => {
+                write!(f, "Select({:?}, {:?})", a, index)
+            }
+            UintExpression::Member(_, ref s, ref id) => write!(f, "Member({:?}, {:?})", s, id),
+        }
+    }
+}
+
+impl<T: Field> fmt::Debug for StructExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StructExpression::Identifier(_, ref var) => write!(f, "Ide({})", var),
+            StructExpression::Value(ref ty, ref values) => {
+                write!(f, "Value({}, {:?})", ty.id, values)
+            }
+            StructExpression::FunctionCall(_, ref i, ref p) => {
+                try!(write!(f, "FunctionCall({:?}, (", i));
+                try!(f.debug_list().entries(p.iter()).finish());
+                write!(f, ")")
+            }
+            StructExpression::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "IfElse({:?}, {:?}, {:?})",
+                condition, consequent, alternative
+            ),
+            StructExpression::Member(_, ref s, ref id) => write!(f, "Member({:?}, {:?})", s, id),
+            StructExpression::Select(_, ref a, ref index) => {
+                write!(f, "Select({:?}, {:?})", a, index)
+            }
         }
     }
 }
@@ -540,27 +926,36 @@ impl<T: Field> fmt::Debug for FieldElementExpression<T> {
             FieldElementExpression::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
+            FieldElementExpression::Member(ref s, ref id) => {
+                write!(f, "Member({:?}, {:?})", s, id)
+            }
         }
     }
 }
 
-impl<T: Field> fmt::Debug for FieldElementArrayExpression<T> {
+impl<T: Field> fmt::Debug for ArrayExpression<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            FieldElementArrayExpression::Identifier(_, ref var) => write!(f, "{:?}", var),
-            FieldElementArrayExpression::Value(_, ref values) => write!(f, "{:?}", values),
-            FieldElementArrayExpression::FunctionCall(_, ref i, ref p) => {
+            ArrayExpression::Identifier(_, ref var) => write!(f, "{:?}", var),
+            ArrayExpression::Value(_, ref values) => write!(f, "{:?}", values),
+            ArrayExpression::FunctionCall(_, ref i, ref p) => {
                 try!(write!(f, "FunctionCall({:?}, (", i));
                 try!(f.debug_list().entries(p.iter()).finish());
                 write!(f, ")")
             }
-            FieldElementArrayExpression::IfElse(ref condition, ref consequent, ref alternative) => {
+            ArrayExpression::IfElse(ref condition, ref consequent, ref alternative) => {
                 write!(
                     f,
                     "IfElse({:?}, {:?}, {:?})",
                     condition, consequent, alternative
                 )
             }
+            ArrayExpression::Member(_, ref s, ref id) => {
+                write!(f, "Member({:?}, {:?})", s, id)
+            }
+            ArrayExpression::Select(_, ref a, ref index) => {
+                write!(f, "Select({:?}, {:?})", a, index)
+            }
         }
     }
 }
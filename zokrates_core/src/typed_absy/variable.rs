@@ -1,5 +1,7 @@
 use absy;
 use std::fmt;
+#[cfg(test)]
+use types::StructType;
 use types::Type;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Hash, Eq)]
@@ -23,10 +25,33 @@ impl Variable {
         }
     }
 
+    #[cfg(test)]
     pub fn field_array<S: Into<String>>(id: S, size: usize) -> Variable {
         Variable {
             id: id.into(),
-            _type: Type::FieldElementArray(size),
+            _type: Type::array(Type::FieldElement, size),
+        }
+    }
+
+    pub fn array<S: Into<String>>(id: S, ty: Type, size: usize) -> Variable {
+        Variable {
+            id: id.into(),
+            _type: Type::array(ty, size),
+        }
+    }
+
+    pub fn uint<S: Into<String>>(id: S, bitwidth: usize) -> Variable {
+        Variable {
+            id: id.into(),
+            _type: Type::Uint(bitwidth),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn structure<S: Into<String>>(id: S, ty: StructType) -> Variable {
+        Variable {
+            id: id.into(),
+            _type: Type::Struct(ty),
         }
     }
 
@@ -56,6 +56,7 @@ pub fn pack<T: Field>(nbits: usize) -> FlatProg<T> {
             arguments,
             statements,
             signature,
+            source_map: SourceMap::default(),
         }],
     }
 }
@@ -83,7 +84,7 @@ pub fn unpack<T: Field>(nbits: usize) -> FlatProg<T> {
         .map(|index| use_variable(&mut bijection, format!("o{}", index), &mut counter))
         .collect();
 
-    let helper = Helper::bits();
+    let helper = Helper::bits(T::get_required_bits());
 
     let signature = Signature {
         inputs: vec![Type::FieldElement],
@@ -104,6 +105,7 @@ pub fn unpack<T: Field>(nbits: usize) -> FlatProg<T> {
             FlatStatement::Condition(
                 bit.clone(),
                 FlatExpression::Mult(box bit.clone(), box bit.clone()),
+                None,
             )
         })
         .collect();
@@ -127,6 +129,7 @@ pub fn unpack<T: Field>(nbits: usize) -> FlatProg<T> {
             box FlatExpression::Identifier(FlatVariable::new(0)),
             box FlatExpression::Number(T::from(1)),
         ),
+        None,
     ));
 
     statements.insert(
@@ -135,6 +138,7 @@ pub fn unpack<T: Field>(nbits: usize) -> FlatProg<T> {
             inputs: directive_inputs,
             outputs: directive_outputs,
             helper: helper,
+            span: None,
         }),
     );
 
@@ -148,6 +152,7 @@ pub fn unpack<T: Field>(nbits: usize) -> FlatProg<T> {
             arguments,
             statements,
             signature,
+            source_map: SourceMap::default(),
         }],
     }
 }
@@ -203,6 +208,7 @@ pub fn cast<T: Field>(from: &Type, to: &Type) -> FlatFunction<T> {
         arguments,
         statements,
         signature,
+        source_map: SourceMap::default(),
     }
 }
 
@@ -261,7 +267,7 @@ mod tests {
                     (0..FieldPrime::get_required_bits())
                         .map(|i| FlatVariable::new(i + 1))
                         .collect(),
-                    Helper::bits(),
+                    Helper::bits(FieldPrime::get_required_bits()),
                     vec![FlatVariable::new(0)]
                 ))
             );
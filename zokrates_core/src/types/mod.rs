@@ -8,7 +8,82 @@ mod signature;
 pub enum Type {
     FieldElement,
     Boolean,
-    FieldElementArray(usize),
+    Array(ArrayType),
+    Uint(usize),
+    Struct(StructType),
+}
+
+/// An array type, holding `size` elements of type `ty`
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct ArrayType {
+    pub size: usize,
+    pub ty: Box<Type>,
+    /// The constant the size is given by, until its value is looked up during semantic checking
+    #[serde(skip)]
+    pub size_constant: Option<String>,
+}
+
+impl ArrayType {
+    pub fn new(ty: Type, size: usize) -> ArrayType {
+        ArrayType {
+            size,
+            ty: box ty,
+            size_constant: None,
+        }
+    }
+
+    /// An array type whose size is the value of the constant `id`
+    pub fn with_size_constant(ty: Type, id: String) -> ArrayType {
+        ArrayType {
+            size: 0,
+            ty: box ty,
+            size_constant: Some(id),
+        }
+    }
+
+    fn size_string(&self) -> String {
+        match self.size_constant {
+            Some(ref id) => id.clone(),
+            None => self.size.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct StructMember {
+    pub id: String,
+    pub ty: Type,
+}
+
+/// A struct type. Members are kept in declaration order, which is also the order of their
+/// primitives once flattened
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct StructType {
+    pub id: String,
+    pub members: Vec<StructMember>,
+}
+
+impl StructType {
+    pub fn new(id: String, members: Vec<StructMember>) -> StructType {
+        StructType { id, members }
+    }
+
+    pub fn member_type(&self, id: &str) -> Option<&Type> {
+        self.members.iter().find(|m| m.id == id).map(|m| &m.ty)
+    }
+
+    /// Returns the range of the primitives of member `id` in the flattened struct
+    pub fn member_range(&self, id: &str) -> Option<::std::ops::Range<usize>> {
+        let mut offset = 0;
+        for m in &self.members {
+            let count = m.ty.get_primitive_count();
+            if m.id == id {
+                return Some(offset..offset + count);
+            }
+            offset += count;
+        }
+        None
+    }
 }
 
 impl fmt::Display for Type {
@@ -16,28 +91,50 @@ impl fmt::Display for Type {
         match *self {
             Type::FieldElement => write!(f, "field"),
             Type::Boolean => write!(f, "bool"),
-            Type::FieldElementArray(size) => write!(f, "{}[{}]", Type::FieldElement, size),
+            Type::Array(ref array_type) => {
+                // nested arrays are written with the outermost dimension first
+                let mut ty = array_type;
+                let mut dimensions = vec![ty.size_string()];
+                while let Type::Array(ref inner) = *ty.ty {
+                    ty = inner;
+                    dimensions.push(ty.size_string());
+                }
+                try!(write!(f, "{}", ty.ty));
+                for size in dimensions {
+                    try!(write!(f, "[{}]", size));
+                }
+                Ok(())
+            }
+            Type::Uint(bitwidth) => write!(f, "u{}", bitwidth),
+            Type::Struct(ref ty) => write!(f, "{}", ty.id),
         }
     }
 }
 
 impl fmt::Debug for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Type::FieldElement => write!(f, "field"),
-            Type::Boolean => write!(f, "bool"),
-            Type::FieldElementArray(size) => write!(f, "{}[{}]", Type::FieldElement, size),
-        }
+        write!(f, "{}", self)
     }
 }
 
 impl Type {
+    pub fn array(ty: Type, size: usize) -> Type {
+        Type::Array(ArrayType::new(ty, size))
+    }
+
     // the number of field elements the type maps to
     pub fn get_primitive_count(&self) -> usize {
         match self {
             Type::FieldElement => 1,
             Type::Boolean => 1,
-            Type::FieldElementArray(size) => size * Type::FieldElement.get_primitive_count(),
+            Type::Array(ref array_type) => array_type.size * array_type.ty.get_primitive_count(),
+            // unsigned integers are passed around as a single field element holding their value
+            Type::Uint(_) => 1,
+            Type::Struct(ref ty) => ty
+                .members
+                .iter()
+                .map(|m| m.ty.get_primitive_count())
+                .sum(),
         }
     }
 
@@ -45,7 +142,9 @@ impl Type {
         match *self {
             Type::FieldElement => String::from("f"),
             Type::Boolean => String::from("b"),
-            Type::FieldElementArray(size) => format!("{}[{}]", Type::FieldElement.to_slug(), size), // TODO differentiate types?
+            Type::Array(ref array_type) => format!("{}[{}]", array_type.ty.to_slug(), array_type.size),
+            Type::Uint(bitwidth) => format!("u{}", bitwidth),
+            Type::Struct(ref ty) => format!("{{{}}}", ty.id),
         }
     }
 }
@@ -56,8 +155,57 @@ mod tests {
 
     #[test]
     fn array() {
-        let t = Type::FieldElementArray(42);
+        let t = Type::array(Type::FieldElement, 42);
         assert_eq!(t.get_primitive_count(), 42);
         assert_eq!(t.to_slug(), "f[42]");
     }
+
+    #[test]
+    fn nested_array() {
+        // field[3][4] holds 3 arrays of 4 field elements
+        let t = Type::array(Type::array(Type::FieldElement, 4), 3);
+        assert_eq!(t.get_primitive_count(), 12);
+        assert_eq!(t.to_slug(), "f[4][3]");
+        assert_eq!(t.to_string(), "field[3][4]");
+
+        let t = Type::array(Type::Uint(8), 2);
+        assert_eq!(t.get_primitive_count(), 2);
+        assert_eq!(t.to_string(), "u8[2]");
+    }
+
+    #[test]
+    fn structure() {
+        let point = Type::Struct(StructType::new(
+            String::from("Point"),
+            vec![
+                StructMember {
+                    id: String::from("x"),
+                    ty: Type::FieldElement,
+                },
+                StructMember {
+                    id: String::from("y"),
+                    ty: Type::array(Type::FieldElement, 2),
+                },
+            ],
+        ));
+        assert_eq!(point.get_primitive_count(), 3);
+        assert_eq!(point.to_slug(), "{Point}");
+        assert_eq!(point.to_string(), "Point");
+
+        match point {
+            Type::Struct(ty) => {
+                assert_eq!(ty.member_range("y"), Some(1..3));
+                assert_eq!(ty.member_range("z"), None);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn uint() {
+        let t = Type::Uint(32);
+        assert_eq!(t.get_primitive_count(), 1);
+        assert_eq!(t.to_slug(), "u32");
+        assert_eq!(t.to_string(), "u32");
+    }
 }
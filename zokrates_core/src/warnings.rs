@@ -0,0 +1,487 @@
+//! Module containing the warnings of the compiler, about programs which are valid but likely to be
+//! mistaken, such as private inputs which do not enter any constraint and are therefore not
+//! proven anything about.
+
+use absy::{
+    Assignee, AssigneeNode, Expression, ExpressionNode, FunctionNode, Prog, Statement,
+    StatementNode,
+};
+use flat_absy::FlatVariable;
+use ir;
+use parser::Position;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use zokrates_field::field::Field;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum WarningKind {
+    UnusedParameter(String),
+    UnusedVariable(String),
+    UnusedFunction(String),
+    UnconstrainedInput(String),
+    PartiallyConstrainedInput(String),
+    ShadowedVariable(String),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Warning {
+    pos: Option<(Position, Position)>,
+    kind: WarningKind,
+}
+
+impl Warning {
+    pub fn pos(&self) -> Option<(Position, Position)> {
+        self.pos
+    }
+
+    pub fn kind(&self) -> &WarningKind {
+        &self.kind
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            WarningKind::UnusedParameter(_) => "W0001",
+            WarningKind::UnusedVariable(_) => "W0002",
+            WarningKind::UnusedFunction(_) => "W0003",
+            WarningKind::UnconstrainedInput(_) | WarningKind::PartiallyConstrainedInput(_) => {
+                "W0004"
+            }
+            WarningKind::ShadowedVariable(_) => "W0005",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self.kind {
+            WarningKind::UnusedParameter(ref id) => format!("Parameter {} is never used", id),
+            WarningKind::UnusedVariable(ref id) => format!("Variable {} is never used", id),
+            WarningKind::UnusedFunction(ref id) => {
+                format!("Function {} is never called from main", id)
+            }
+            WarningKind::UnconstrainedInput(ref id) => format!(
+                "Private input {} does not enter any constraint, so it can take any value",
+                id
+            ),
+            WarningKind::PartiallyConstrainedInput(ref id) => format!(
+                "Some elements of private input {} do not enter any constraint, so they can take any value",
+                id
+            ),
+            WarningKind::ShadowedVariable(ref id) => {
+                format!("Loop variable {} shadows a variable of the same name", id)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = self
+            .pos
+            .map(|p| format!("{}", p.0))
+            .unwrap_or("?".to_string());
+        write!(f, "{}\n\t{}", location, self.message())
+    }
+}
+
+/// An input of the main function, which is made of `size` field elements once flattened
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub id: String,
+    pub pos: (Position, Position),
+    pub private: bool,
+    pub size: usize,
+}
+
+/// Moves the start of the position `pos` of a node of `source` past the whitespace it starts with,
+/// as the nodes of the parser start right after the previous token
+pub fn trim(source: &str, pos: (Position, Position)) -> (Position, Position) {
+    let (mut start, end) = pos;
+    for line in source.lines().skip(start.line - 1) {
+        match line
+            .chars()
+            .skip(start.col - 1)
+            .position(|c| !c.is_whitespace())
+        {
+            Some(offset) => {
+                let start = Position {
+                    line: start.line,
+                    col: start.col + offset,
+                };
+                return (start, end);
+            }
+            None => {
+                start = Position {
+                    line: start.line + 1,
+                    col: 1,
+                }
+            }
+        }
+    }
+    pos
+}
+
+/// Finds the unused parameters, variables and functions of a module parsed from `source`, as well
+/// as the loop variables which shadow other variables
+pub fn check_module<T: Field>(prog: &Prog<T>, source: &str) -> Vec<Warning> {
+    let mut warnings = vec![];
+    let mut calls = HashMap::new();
+
+    for f in &prog.functions {
+        let mut checker = FunctionChecker::new();
+        checker.check(f);
+        warnings.extend(checker.warnings);
+        calls
+            .entry(f.value.id.clone())
+            .or_insert_with(HashSet::new)
+            .extend(checker.calls);
+    }
+
+    // functions which are not reachable from main are removed from the program
+    let mut reachable = HashSet::new();
+    let mut pending = vec![String::from("main")];
+    while let Some(id) = pending.pop() {
+        if let Some(called) = calls.get(&id) {
+            if reachable.insert(id.clone()) {
+                pending.extend(called.iter().cloned());
+            }
+        }
+    }
+
+    warnings.extend(
+        prog.functions
+            .iter()
+            .filter(|f| !reachable.contains(&f.value.id))
+            .map(|f| Warning {
+                pos: Some((f.start, f.start)),
+                kind: WarningKind::UnusedFunction(f.value.id.clone()),
+            }),
+    );
+
+    for w in warnings.iter_mut() {
+        w.pos = w.pos.map(|pos| trim(source, pos));
+    }
+
+    warnings.sort_by_key(|w| w.pos.map(|(start, _)| (start.line, start.col)));
+    warnings
+}
+
+/// Finds the private inputs of a compiled program which do not appear in any of its constraints,
+/// given the `inputs` of its main function
+pub fn check_constraints<T: Field>(prog: &ir::Prog<T>, inputs: &[Input]) -> Vec<Warning> {
+    let constrained: HashSet<&FlatVariable> = prog
+        .main
+        .statements
+        .iter()
+        .flat_map(|s| match *s {
+            ir::Statement::Constraint(ref quad, ref lin, _) => quad
+                .left
+                .0
+                .keys()
+                .chain(quad.right.0.keys())
+                .chain(lin.0.keys())
+                .collect(),
+            ir::Statement::Directive(_) => vec![],
+        })
+        .collect();
+
+    let mut arguments = prog.main.arguments.iter();
+    let mut warnings = vec![];
+
+    for input in inputs {
+        let unconstrained = arguments
+            .by_ref()
+            .take(input.size)
+            .filter(|v| !constrained.contains(v))
+            .count();
+
+        if !input.private || unconstrained == 0 {
+            continue;
+        }
+
+        warnings.push(Warning {
+            pos: Some(input.pos),
+            kind: match unconstrained == input.size {
+                true => WarningKind::UnconstrainedInput(input.id.clone()),
+                false => WarningKind::PartiallyConstrainedInput(input.id.clone()),
+            },
+        });
+    }
+
+    warnings
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Binding {
+    Parameter,
+    Variable,
+    /// Variables which are not reported when unused, such as loop variables
+    Ignored,
+}
+
+struct Declared {
+    id: String,
+    pos: (Position, Position),
+    /// The start of the statement declaring the variable
+    statement: Position,
+    binding: Binding,
+    used: bool,
+}
+
+struct FunctionChecker {
+    /// Variables in scope, with the innermost scope last
+    scopes: Vec<Vec<Declared>>,
+    calls: HashSet<String>,
+    warnings: Vec<Warning>,
+}
+
+impl FunctionChecker {
+    fn new() -> FunctionChecker {
+        FunctionChecker {
+            scopes: vec![],
+            calls: HashSet::new(),
+            warnings: vec![],
+        }
+    }
+
+    fn check<T: Field>(&mut self, f: &FunctionNode<T>) {
+        self.scopes.push(vec![]);
+        for a in &f.value.arguments {
+            self.declare(&a.value.id.value.id, a.pos(), f.start, Binding::Parameter);
+        }
+        for s in &f.value.statements {
+            self.statement(s);
+        }
+        self.exit_scope();
+    }
+
+    fn is_declared(&self, id: &str) -> bool {
+        self.scopes.iter().flat_map(|s| s.iter()).any(|v| v.id == id)
+    }
+
+    fn innermost(&mut self, id: &str) -> Option<&mut Declared> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|s| s.iter_mut().rev())
+            .find(|v| v.id == id)
+    }
+
+    fn declare(
+        &mut self,
+        id: &str,
+        pos: (Position, Position),
+        statement: Position,
+        binding: Binding,
+    ) {
+        self.scopes.last_mut().unwrap().push(Declared {
+            id: id.to_string(),
+            pos,
+            statement,
+            binding,
+            used: false,
+        });
+    }
+
+    fn exit_scope(&mut self) {
+        for v in self.scopes.pop().unwrap() {
+            let kind = match (v.used, v.binding) {
+                (false, Binding::Parameter) => WarningKind::UnusedParameter(v.id),
+                (false, Binding::Variable) => WarningKind::UnusedVariable(v.id),
+                _ => continue,
+            };
+            self.warnings.push(Warning {
+                pos: Some(v.pos),
+                kind,
+            });
+        }
+    }
+
+    fn statement<T: Field>(&mut self, s: &StatementNode<T>) {
+        match s.value {
+            Statement::Return(ref list) => {
+                for e in &list.value.expressions {
+                    self.expression(e);
+                }
+            }
+            Statement::Declaration(ref v) => {
+                self.declare(&v.value.id, v.pos(), s.start, Binding::Variable)
+            }
+            Statement::Definition(ref assignee, ref e) => {
+                self.expression(e);
+                self.assignee(assignee);
+            }
+            Statement::Condition(ref lhs, ref rhs) => {
+                self.expression(lhs);
+                self.expression(rhs);
+            }
+            Statement::Assertion(ref e, _) => self.expression(e),
+            Statement::For(ref var, ref from, ref to, ref statements) => {
+                self.expression(from);
+                self.expression(to);
+
+                if self.is_declared(&var.value.id) {
+                    self.warnings.push(Warning {
+                        pos: Some(var.pos()),
+                        kind: WarningKind::ShadowedVariable(var.value.id.clone()),
+                    });
+                }
+
+                self.scopes.push(vec![]);
+                self.declare(&var.value.id, var.pos(), s.start, Binding::Ignored);
+                for s in statements {
+                    self.statement(s);
+                }
+                self.exit_scope();
+            }
+            Statement::MultipleDefinition(ref assignees, ref e) => {
+                self.expression(e);
+
+                for a in assignees {
+                    match a.value {
+                        // identifiers which are not declared yet are declared by the definition
+                        Assignee::Identifier(ref id) if !self.is_declared(id) => {
+                            self.declare(id, a.pos(), s.start, Binding::Variable)
+                        }
+                        _ => self.assignee(a),
+                    }
+
+                    // all values returned by a function have to be assigned, so some of them
+                    // are unused without it being a mistake
+                    if assignees.len() > 1 {
+                        if let Assignee::Identifier(ref id) = a.value {
+                            match self.innermost(id) {
+                                Some(v) if v.statement == s.start => v.binding = Binding::Ignored,
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn assignee<T: Field>(&mut self, a: &AssigneeNode<T>) {
+        match a.value {
+            Assignee::Identifier(_) => {}
+            Assignee::ArrayElement(ref array, ref index) => {
+                self.assignee(array);
+                self.expression(index);
+            }
+        }
+    }
+
+    fn expression<T: Field>(&mut self, e: &ExpressionNode<T>) {
+        match e.value {
+            Expression::Number(..) | Expression::HexNumber(_) => {}
+            Expression::Identifier(ref id) => {
+                if let Some(v) = self.innermost(id) {
+                    v.used = true;
+                }
+            }
+            Expression::Add(ref lhs, ref rhs)
+            | Expression::Sub(ref lhs, ref rhs)
+            | Expression::Mult(ref lhs, ref rhs)
+            | Expression::Div(ref lhs, ref rhs)
+            | Expression::Pow(ref lhs, ref rhs)
+            | Expression::Lt(ref lhs, ref rhs)
+            | Expression::Le(ref lhs, ref rhs)
+            | Expression::Eq(ref lhs, ref rhs)
+            | Expression::Ge(ref lhs, ref rhs)
+            | Expression::Gt(ref lhs, ref rhs)
+            | Expression::And(ref lhs, ref rhs)
+            | Expression::Or(ref lhs, ref rhs)
+            | Expression::BitAnd(ref lhs, ref rhs)
+            | Expression::BitOr(ref lhs, ref rhs)
+            | Expression::BitXor(ref lhs, ref rhs)
+            | Expression::LeftShift(ref lhs, ref rhs)
+            | Expression::RightShift(ref lhs, ref rhs)
+            | Expression::Select(ref lhs, ref rhs) => {
+                self.expression(lhs);
+                self.expression(rhs);
+            }
+            Expression::IfElse(ref condition, ref consequent, ref alternative) => {
+                self.expression(condition);
+                self.expression(consequent);
+                self.expression(alternative);
+            }
+            Expression::FunctionCall(ref id, ref arguments) => {
+                self.calls.insert(id.clone());
+                for a in arguments {
+                    self.expression(a);
+                }
+            }
+            Expression::Not(ref e) | Expression::Member(ref e, _) => self.expression(e),
+            Expression::InlineArray(ref elements) => {
+                for e in elements {
+                    self.expression(e);
+                }
+            }
+            Expression::InlineStruct(_, ref members) => {
+                for (_, e) in members {
+                    self.expression(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parser::parse_program;
+    use std::io::BufReader;
+    use zokrates_field::field::FieldPrime;
+
+    fn check(source: &str) -> Vec<(usize, WarningKind)> {
+        let prog: Prog<FieldPrime> =
+            parse_program(&mut BufReader::new(source.as_bytes())).unwrap();
+        check_module(&prog, source)
+            .into_iter()
+            .map(|w| (w.pos().unwrap().0.line, w.kind().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn unused() {
+        let source = r#"def unused(field a) -> (field):
+	return a
+
+def add(field a, field b) -> (field):
+	field c = a
+	field d = a + 1
+	return d
+
+def main(private field a) -> (field):
+	field x, field y = foo()
+	field z = add(a, 1)
+	return 1
+"#;
+
+        assert_eq!(
+            check(source),
+            vec![
+                (1, WarningKind::UnusedFunction(String::from("unused"))),
+                (4, WarningKind::UnusedParameter(String::from("b"))),
+                (5, WarningKind::UnusedVariable(String::from("c"))),
+                (11, WarningKind::UnusedVariable(String::from("z"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn shadowed() {
+        let source = r#"def main(field i) -> (field):
+	field sum = i
+	for field i in 0..3 do
+		for field j in 0..i do
+			sum = sum + j
+		endfor
+	endfor
+	return sum
+"#;
+
+        assert_eq!(
+            check(source),
+            vec![(3, WarningKind::ShadowedVariable(String::from("i")))]
+        );
+    }
+}
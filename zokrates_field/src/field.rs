@@ -15,14 +15,6 @@ use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::ops::{Add, Div, Mul, Sub};
 
-lazy_static! {
-    static ref P: BigInt = BigInt::parse_bytes(
-        b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
-        10
-    )
-    .unwrap();
-}
-
 pub trait Pow<RHS> {
     type Output;
     fn pow(self, _: RHS) -> Self::Output;
@@ -74,265 +66,313 @@ pub trait Field:
     /// Returns a decimal string representing a the member of the equivalence class of this `Field` in Z/pZ
     /// which lies in [-(p-1)/2, (p-1)/2]
     fn to_compact_dec_string(&self) -> String;
+    /// Returns the name of the curve this field is the scalar field of
+    fn name() -> &'static str;
 }
 
-#[derive(PartialEq, PartialOrd, Clone, Eq, Ord, Hash, Serialize, Deserialize)]
-pub struct FieldPrime {
-    value: BigInt,
-}
-
-impl Field for FieldPrime {
-    fn into_byte_vector(&self) -> Vec<u8> {
-        match self.value.to_biguint() {
-            Option::Some(val) => val.to_bytes_le(),
-            Option::None => panic!("Should never happen."),
+/// Declares a prime field `$name` whose modulus is stored in the static `$modulus`, given as
+/// a decimal byte string in `$value`, and which is named `$curve`.
+macro_rules! prime_field {
+    ($(#[$meta:meta])* $name:ident, $modulus:ident, $value:expr, $curve:expr) => {
+        lazy_static! {
+            static ref $modulus: BigInt = BigInt::parse_bytes($value, 10).unwrap();
         }
-    }
 
-    fn from_byte_vector(bytes: Vec<u8>) -> Self {
-        let uval = BigUint::from_bytes_le(bytes.as_slice());
-        FieldPrime {
-            value: BigInt::from_biguint(Sign::Plus, uval),
+        $(#[$meta])*
+        #[derive(PartialEq, PartialOrd, Clone, Eq, Ord, Hash, Serialize, Deserialize)]
+        pub struct $name {
+            value: BigInt,
         }
-    }
 
-    fn to_dec_string(&self) -> String {
-        self.value.to_str_radix(10)
-    }
+        impl Field for $name {
+            fn into_byte_vector(&self) -> Vec<u8> {
+                match self.value.to_biguint() {
+                    Option::Some(val) => val.to_bytes_le(),
+                    Option::None => panic!("Should never happen."),
+                }
+            }
 
-    fn from_dec_string(val: String) -> Self {
-        FieldPrime {
-            value: BigInt::from_str_radix(val.as_str(), 10).unwrap(),
-        }
-    }
+            fn from_byte_vector(bytes: Vec<u8>) -> Self {
+                let uval = BigUint::from_bytes_le(bytes.as_slice());
+                $name {
+                    value: BigInt::from_biguint(Sign::Plus, uval),
+                }
+            }
 
-    fn inverse_mul(&self) -> FieldPrime {
-        let (b, s, _) = extended_euclid(&self.value, &*P);
-        assert_eq!(b, BigInt::one());
-        FieldPrime {
-            value: &s - s.div_floor(&*P) * &*P,
-        }
-    }
-    fn min_value() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&0).unwrap(),
-        }
-    }
-    fn max_value() -> FieldPrime {
-        FieldPrime {
-            value: &*P - ToBigInt::to_bigint(&1).unwrap(),
-        }
-    }
-    fn get_required_bits() -> usize {
-        (*P).bits()
-    }
-    fn try_from_str<'a>(s: &'a str) -> Result<Self, ()> {
-        let x = BigInt::parse_bytes(s.as_bytes(), 10).ok_or(())?;
-        Ok(FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
-        })
-    }
-    fn to_compact_dec_string(&self) -> String {
-        // values up to (p-1)/2 included are represented as positive, values between (p+1)/2 and p-1 as represented as negative by subtracting p
-        if self.value <= FieldPrime::max_value().value / 2 {
-            format!("{}", self.value.to_str_radix(10))
-        } else {
-            format!(
-                "({})",
-                (&self.value - (FieldPrime::max_value().value + BigInt::one())).to_str_radix(10)
-            )
+            fn to_dec_string(&self) -> String {
+                self.value.to_str_radix(10)
+            }
+
+            fn from_dec_string(val: String) -> Self {
+                $name {
+                    value: BigInt::from_str_radix(val.as_str(), 10).unwrap(),
+                }
+            }
+
+            fn inverse_mul(&self) -> $name {
+                let (b, s, _) = extended_euclid(&self.value, &*$modulus);
+                assert_eq!(b, BigInt::one());
+                $name {
+                    value: &s - s.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
+            fn min_value() -> $name {
+                $name {
+                    value: ToBigInt::to_bigint(&0).unwrap(),
+                }
+            }
+            fn max_value() -> $name {
+                $name {
+                    value: &*$modulus - ToBigInt::to_bigint(&1).unwrap(),
+                }
+            }
+            fn get_required_bits() -> usize {
+                (*$modulus).bits()
+            }
+            fn try_from_str<'a>(s: &'a str) -> Result<Self, ()> {
+                let x = BigInt::parse_bytes(s.as_bytes(), 10).ok_or(())?;
+                Ok($name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                })
+            }
+            fn to_compact_dec_string(&self) -> String {
+                // values up to (p-1)/2 included are represented as positive, values between (p+1)/2 and p-1 as represented as negative by subtracting p
+                if self.value <= $name::max_value().value / 2 {
+                    format!("{}", self.value.to_str_radix(10))
+                } else {
+                    format!(
+                        "({})",
+                        (&self.value - ($name::max_value().value + BigInt::one())).to_str_radix(10)
+                    )
+                }
+            }
+            fn name() -> &'static str {
+                $curve
+            }
         }
-    }
-}
 
-impl Default for FieldPrime {
-    fn default() -> Self {
-        FieldPrime {
-            value: BigInt::default(),
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    value: BigInt::default(),
+                }
+            }
         }
-    }
-}
 
-impl Display for FieldPrime {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value.to_str_radix(10))
-    }
-}
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.value.to_str_radix(10))
+            }
+        }
 
-impl Debug for FieldPrime {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value.to_str_radix(10))
-    }
-}
+        impl Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.value.to_str_radix(10))
+            }
+        }
 
-impl From<i32> for FieldPrime {
-    fn from(num: i32) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl From<i32> for $name {
+            fn from(num: i32) -> Self {
+                let x = ToBigInt::to_bigint(&num).unwrap();
+                $name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl From<u32> for FieldPrime {
-    fn from(num: u32) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl From<u32> for $name {
+            fn from(num: u32) -> Self {
+                let x = ToBigInt::to_bigint(&num).unwrap();
+                $name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl From<usize> for FieldPrime {
-    fn from(num: usize) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl From<usize> for $name {
+            fn from(num: usize) -> Self {
+                let x = ToBigInt::to_bigint(&num).unwrap();
+                $name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl Zero for FieldPrime {
-    fn zero() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&0).unwrap(),
+        impl Zero for $name {
+            fn zero() -> $name {
+                $name {
+                    value: ToBigInt::to_bigint(&0).unwrap(),
+                }
+            }
+            fn is_zero(&self) -> bool {
+                self.value == ToBigInt::to_bigint(&0).unwrap()
+            }
         }
-    }
-    fn is_zero(&self) -> bool {
-        self.value == ToBigInt::to_bigint(&0).unwrap()
-    }
-}
 
-impl One for FieldPrime {
-    fn one() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&1).unwrap(),
+        impl One for $name {
+            fn one() -> $name {
+                $name {
+                    value: ToBigInt::to_bigint(&1).unwrap(),
+                }
+            }
         }
-    }
-}
 
-impl Add<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Add<$name> for $name {
+            type Output = $name;
 
-    fn add(self, other: FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value + other.value) % &*P,
+            fn add(self, other: $name) -> $name {
+                $name {
+                    value: (self.value + other.value) % &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl<'a> Add<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Add<&'a $name> for $name {
+            type Output = $name;
 
-    fn add(self, other: &FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value + other.value.clone()) % &*P,
+            fn add(self, other: &$name) -> $name {
+                $name {
+                    value: (self.value + other.value.clone()) % &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl Sub<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Sub<$name> for $name {
+            type Output = $name;
 
-    fn sub(self, other: FieldPrime) -> FieldPrime {
-        let x = self.value - other.value;
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+            fn sub(self, other: $name) -> $name {
+                let x = self.value - other.value;
+                $name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl<'a> Sub<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Sub<&'a $name> for $name {
+            type Output = $name;
 
-    fn sub(self, other: &FieldPrime) -> FieldPrime {
-        let x = self.value - other.value.clone();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+            fn sub(self, other: &$name) -> $name {
+                let x = self.value - other.value.clone();
+                $name {
+                    value: &x - x.div_floor(&*$modulus) * &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl Mul<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Mul<$name> for $name {
+            type Output = $name;
 
-    fn mul(self, other: FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value * other.value) % &*P,
+            fn mul(self, other: $name) -> $name {
+                $name {
+                    value: (self.value * other.value) % &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl<'a> Mul<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Mul<&'a $name> for $name {
+            type Output = $name;
 
-    fn mul(self, other: &FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value * other.value.clone()) % &*P,
+            fn mul(self, other: &$name) -> $name {
+                $name {
+                    value: (self.value * other.value.clone()) % &*$modulus,
+                }
+            }
         }
-    }
-}
 
-impl Div<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Div<$name> for $name {
+            type Output = $name;
 
-    fn div(self, other: FieldPrime) -> FieldPrime {
-        self * other.inverse_mul()
-    }
-}
+            fn div(self, other: $name) -> $name {
+                self * other.inverse_mul()
+            }
+        }
 
-impl<'a> Div<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Div<&'a $name> for $name {
+            type Output = $name;
 
-    fn div(self, other: &FieldPrime) -> FieldPrime {
-        self / other.clone()
-    }
-}
+            fn div(self, other: &$name) -> $name {
+                self / other.clone()
+            }
+        }
 
-impl Pow<usize> for FieldPrime {
-    type Output = FieldPrime;
+        impl Pow<usize> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: usize) -> FieldPrime {
-        let mut res = FieldPrime::from(1);
-        for _ in 0..exp {
-            res = res * &self;
+            fn pow(self, exp: usize) -> $name {
+                let mut res = $name::from(1);
+                for _ in 0..exp {
+                    res = res * &self;
+                }
+                res
+            }
         }
-        res
-    }
-}
 
-impl Pow<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Pow<$name> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: FieldPrime) -> FieldPrime {
-        let mut res = FieldPrime::one();
-        let mut current = FieldPrime::zero();
-        loop {
-            if current >= exp {
-                return res;
+            fn pow(self, exp: $name) -> $name {
+                let mut res = $name::one();
+                let mut current = $name::zero();
+                loop {
+                    if current >= exp {
+                        return res;
+                    }
+                    res = res * &self;
+                    current = current + $name::one();
+                }
             }
-            res = res * &self;
-            current = current + FieldPrime::one();
         }
-    }
-}
 
-impl<'a> Pow<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Pow<&'a $name> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: &'a FieldPrime) -> FieldPrime {
-        let mut res = FieldPrime::one();
-        let mut current = FieldPrime::zero();
-        loop {
-            if &current >= exp {
-                return res;
+            fn pow(self, exp: &'a $name) -> $name {
+                let mut res = $name::one();
+                let mut current = $name::zero();
+                loop {
+                    if &current >= exp {
+                        return res;
+                    }
+                    res = res * &self;
+                    current = current + $name::one();
+                }
             }
-            res = res * &self;
-            current = current + FieldPrime::one();
         }
-    }
+    };
 }
 
+prime_field!(
+    /// The scalar field of ALT_BN128
+    FieldPrime,
+    BN128_MODULUS,
+    b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    "bn128"
+);
+
+prime_field!(
+    /// The scalar field of BLS12-381
+    Bls12_381Field,
+    BLS12_381_MODULUS,
+    b"52435875175126190479447740508185965837690552500527637822603658699938581184513",
+    "bls12_381"
+);
+
+prime_field!(
+    /// The scalar field of BLS12-377
+    Bls12_377Field,
+    BLS12_377_MODULUS,
+    b"8444461749428370424248824938781546531375899335154063827935233455917409239041",
+    "bls12_377"
+);
+
+prime_field!(
+    /// A small prime field, useful to test field arithmetic by hand
+    SmallPrimeField,
+    SMALL_PRIME_MODULUS,
+    b"2147483647",
+    "small"
+);
+
 /// Calculates the gcd using an iterative implementation of the extended euclidian algorithm.
 /// Returning `(d, s, t)` so that `d = s * a + t * b`
 ///
@@ -384,7 +424,7 @@ mod tests {
         #[test]
         fn negative_number() {
             assert_eq!(
-                P.checked_sub(&"12".parse::<BigInt>().unwrap()).unwrap(),
+                BN128_MODULUS.checked_sub(&"12".parse::<BigInt>().unwrap()).unwrap(),
                 FieldPrime::from("-12").value
             );
         }
@@ -704,10 +744,10 @@ mod tests {
                 &ToBigInt::to_bigint(&46).unwrap()
             )
         );
-        let (b, s, _) = extended_euclid(&ToBigInt::to_bigint(&253).unwrap(), &*P);
+        let (b, s, _) = extended_euclid(&ToBigInt::to_bigint(&253).unwrap(), &*BN128_MODULUS);
         assert_eq!(b, BigInt::one());
         let s_field = FieldPrime {
-            value: &s - s.div_floor(&*P) * &*P,
+            value: &s - s.div_floor(&*BN128_MODULUS) * &*BN128_MODULUS,
         };
         assert_eq!(
             FieldPrime::from(
@@ -716,4 +756,49 @@ mod tests {
             s_field
         );
     }
+
+    mod other_fields {
+        use super::*;
+
+        #[test]
+        fn required_bits() {
+            assert_eq!(FieldPrime::get_required_bits(), 254);
+            assert_eq!(Bls12_381Field::get_required_bits(), 255);
+            assert_eq!(Bls12_377Field::get_required_bits(), 253);
+            assert_eq!(SmallPrimeField::get_required_bits(), 31);
+        }
+
+        #[test]
+        fn small_prime_arithmetic() {
+            assert_eq!(
+                SmallPrimeField::max_value() + SmallPrimeField::from(3),
+                SmallPrimeField::from(2)
+            );
+            assert_eq!(
+                SmallPrimeField::from(2) - SmallPrimeField::from(3),
+                SmallPrimeField::max_value()
+            );
+            assert_eq!(
+                SmallPrimeField::from(-1),
+                SmallPrimeField::try_from_str("2147483646").unwrap()
+            );
+            assert_eq!(
+                SmallPrimeField::from(7) * SmallPrimeField::from(7).inverse_mul(),
+                SmallPrimeField::one()
+            );
+        }
+
+        #[test]
+        fn bls12_381_arithmetic() {
+            let x = Bls12_381Field::try_from_str(
+                "52435875175126190479447740508185965837690552500527637822603658699938581184514",
+            )
+            .unwrap();
+            assert_eq!(x, Bls12_381Field::one());
+            assert_eq!(
+                Bls12_381Field::from(5) / Bls12_381Field::from(5),
+                Bls12_381Field::one()
+            );
+        }
+    }
 }
@@ -16,10 +16,26 @@ pub fn resolve(
     }
 }
 
+/// Returns the path of the file `source` refers to when imported from `location`
+pub fn resolve_path(location: &Option<String>, source: &String) -> Result<PathBuf, io::Error> {
+    match location {
+        Some(location) => path_with_location(location, source),
+        None => Err(io::Error::new(io::ErrorKind::Other, "No location provided")),
+    }
+}
+
 fn resolve_with_location(
     location: &String,
     source: &String,
 ) -> Result<(BufReader<File>, String, String), io::Error> {
+    let path = path_with_location(location, source)?;
+
+    let (next_location, alias) = generate_next_parameters(&path)?;
+
+    File::open(path).and_then(|f| Ok((BufReader::new(f), next_location, alias)))
+}
+
+fn path_with_location(location: &String, source: &String) -> Result<PathBuf, io::Error> {
     let source = PathBuf::from(source);
 
     // paths starting with `./` or `../` are interpreted relative to the current file
@@ -37,9 +53,7 @@ fn resolve_with_location(
         return Err(io::Error::new(io::ErrorKind::Other, "Not a file"));
     }
 
-    let (next_location, alias) = generate_next_parameters(&path)?;
-
-    File::open(path).and_then(|f| Ok((BufReader::new(f), next_location, alias)))
+    Ok(path)
 }
 
 fn generate_next_parameters(path: &PathBuf) -> Result<(String, String), io::Error> {
@@ -0,0 +1,343 @@
+//! Analysis of the source of a module, answering the queries of the language server.
+
+use std::io::BufReader;
+use std::panic;
+use std::path::{Path, PathBuf};
+use zokrates_core::absy::Prog;
+use zokrates_core::compile::CompileErrorInner;
+use zokrates_core::diagnostics::Diagnostic;
+use zokrates_core::imports::Importer;
+use zokrates_core::parser::{parse_program, Position};
+use zokrates_core::semantics::Checker;
+use zokrates_field::field::FieldPrime;
+use zokrates_fs_resolver::{resolve as fs_resolve, resolve_path};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SymbolKind {
+    Module,
+    Struct,
+    Constant,
+    Function,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Definition {
+    /// A declaration of the module itself
+    Local(Position, Position),
+    /// An imported module
+    File(PathBuf),
+}
+
+pub struct Analysis {
+    location: Option<String>,
+    /// The module as parsed, if it is syntactically valid
+    program: Option<Prog<FieldPrime>>,
+    /// Aliases of the imported functions along with their declaration
+    imported_functions: Vec<(String, String)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    /// Parses and checks `source`, which is located in the directory `location` so that its
+    /// imports can be resolved
+    pub fn new(source: &str, location: Option<String>) -> Analysis {
+        // documents are analysed as they are typed, a panic of the compiler on one of them must not
+        // stop the server
+        panic::catch_unwind(|| Analysis::analyse(source, location.clone())).unwrap_or_else(|e| {
+            let reason = match e.downcast_ref::<&str>() {
+                Some(reason) => reason.to_string(),
+                None => e.downcast_ref::<String>().cloned().unwrap_or_default(),
+            };
+            Analysis {
+                diagnostics: vec![Diagnostic::error(
+                    "E0006",
+                    format!("The compiler failed on this module: {}", reason),
+                )
+                .file(location.clone())],
+                location,
+                program: None,
+                imported_functions: vec![],
+            }
+        })
+    }
+
+    fn analyse(source: &str, location: Option<String>) -> Analysis {
+        let program = match parse_program::<FieldPrime, _>(&mut BufReader::new(source.as_bytes())) {
+            Ok(program) => program,
+            Err(errors) => {
+                return Analysis {
+                    diagnostics: errors
+                        .into_iter()
+                        .map(|e| {
+                            CompileErrorInner::from(e)
+                                .with_context(&location)
+                                .diagnostic()
+                        })
+                        .collect(),
+                    location,
+                    program: None,
+                    imported_functions: vec![],
+                }
+            }
+        };
+
+        let (imported_functions, diagnostics) = match Importer::new().apply_imports(
+            program.clone(),
+            location.clone(),
+            Some(fs_resolve),
+        ) {
+            Ok(with_imports) => {
+                let imported_functions = with_imports
+                    .imported_functions
+                    .iter()
+                    .map(|f| (f.id.clone(), format!("def {}{}", f.id, f.signature)))
+                    .collect();
+                let diagnostics = match Checker::new().check_program(with_imports) {
+                    Ok(_) => vec![],
+                    Err(errors) => errors
+                        .into_iter()
+                        .map(|e| {
+                            CompileErrorInner::<FieldPrime>::from(e)
+                                .with_context(&location)
+                                .diagnostic()
+                        })
+                        .collect(),
+                };
+                (imported_functions, diagnostics)
+            }
+            Err(errors) => (vec![], errors.diagnostics()),
+        };
+
+        let mut analysis = Analysis {
+            location,
+            program: Some(program),
+            imported_functions,
+            diagnostics: vec![],
+        };
+
+        analysis.diagnostics = diagnostics
+            .into_iter()
+            .map(|d| analysis.locate(d))
+            .collect();
+
+        analysis
+    }
+
+    // errors in imported modules are reported on the import statement
+    fn locate(&self, diagnostic: Diagnostic) -> Diagnostic {
+        if diagnostic.file == self.location {
+            return diagnostic;
+        }
+
+        let import = self.program.as_ref().and_then(|p| {
+            p.imports
+                .iter()
+                .find(|i| Some(i.value.get_source()) == diagnostic.file.as_ref())
+        });
+
+        let message = match diagnostic.file {
+            Some(ref file) => format!("{}: {}", file, diagnostic.message),
+            None => diagnostic.message.clone(),
+        };
+
+        Diagnostic {
+            message,
+            ..diagnostic
+        }
+        .file(self.location.clone())
+        .span(import.map(|i| i.pos()))
+    }
+
+    /// The declarations of the module, in source order within each kind
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let program = match self.program {
+            Some(ref program) => program,
+            None => return vec![],
+        };
+
+        let imports = program.imports.iter().map(|i| Symbol {
+            name: import_alias(i.value.get_source(), i.value.get_alias()),
+            kind: SymbolKind::Module,
+            start: i.start,
+            end: i.end,
+        });
+        let structs = program.structs.iter().map(|s| Symbol {
+            name: s.value.id.clone(),
+            kind: SymbolKind::Struct,
+            start: s.start,
+            end: s.end,
+        });
+        let constants = program.constants.iter().map(|c| Symbol {
+            name: c.value.id.clone(),
+            kind: SymbolKind::Constant,
+            start: c.start,
+            end: c.end,
+        });
+        let functions = program.functions.iter().map(|f| Symbol {
+            name: f.value.id.clone(),
+            kind: SymbolKind::Function,
+            start: f.start,
+            end: f.end,
+        });
+
+        imports
+            .chain(structs)
+            .chain(constants)
+            .chain(functions)
+            .collect()
+    }
+
+    /// Where the function or the import named `id` is declared
+    pub fn definition(&self, id: &str) -> Option<Definition> {
+        let program = self.program.as_ref()?;
+
+        if let Some(f) = program.functions.iter().find(|f| f.value.id == id) {
+            return Some(Definition::Local(f.start, f.end));
+        }
+
+        program
+            .imports
+            .iter()
+            .find(|i| import_alias(i.value.get_source(), i.value.get_alias()) == id)
+            .and_then(|i| resolve_path(&self.location, i.value.get_source()).ok())
+            .map(Definition::File)
+    }
+
+    /// The declaration of the function named `id`
+    pub fn hover(&self, id: &str) -> Option<String> {
+        let program = self.program.as_ref()?;
+
+        if let Some(f) = program.functions.iter().find(|f| f.value.id == id) {
+            return Some(format!(
+                "def {}({}) -> ({})",
+                f.value.id,
+                f.value
+                    .arguments
+                    .iter()
+                    .map(|a| a.value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                f.value
+                    .signature
+                    .outputs
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        self.imported_functions
+            .iter()
+            .find(|(alias, _)| alias == id)
+            .map(|(_, declaration)| declaration.clone())
+    }
+}
+
+// the name an import is bound to, which defaults to the name of the imported file
+fn import_alias(source: &String, alias: &Option<String>) -> String {
+    match alias {
+        Some(alias) => alias.clone(),
+        None => Path::new(source)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or(source.clone()),
+    }
+}
+
+/// The identifier around the (zero-based) `line` and `character` of `source`
+pub fn identifier_at(source: &str, line: usize, character: usize) -> Option<String> {
+    let line: Vec<char> = source.lines().nth(line)?.chars().collect();
+
+    let is_identifier = |c: &char| c.is_alphanumeric() || *c == '_';
+
+    let start = line[..character.min(line.len())]
+        .iter()
+        .rev()
+        .take_while(|c| is_identifier(c))
+        .count();
+    let end = line[character.min(line.len())..]
+        .iter()
+        .take_while(|c| is_identifier(c))
+        .count();
+
+    match start + end {
+        0 => None,
+        _ => Some(
+            line[character.min(line.len()) - start..character.min(line.len()) + end]
+                .iter()
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "struct Foo {\n\tfield a\n}\n\nconst field N = 2\n\ndef double(private field a) -> (field):\n\treturn a * N\n\ndef main(field a) -> (field):\n\treturn double(a)\n";
+
+    #[test]
+    fn symbols() {
+        let analysis = Analysis::new(SOURCE, Some(String::from(".")));
+        assert_eq!(analysis.diagnostics, vec![]);
+        assert_eq!(
+            analysis
+                .symbols()
+                .iter()
+                .map(|s| (s.name.as_str(), s.kind, s.start.line))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Foo", SymbolKind::Struct, 1),
+                ("N", SymbolKind::Constant, 5),
+                ("double", SymbolKind::Function, 7),
+                ("main", SymbolKind::Function, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn definition_and_hover() {
+        let analysis = Analysis::new(SOURCE, Some(String::from(".")));
+
+        let id = identifier_at(SOURCE, 10, 10).unwrap();
+        assert_eq!(id, "double");
+
+        match analysis.definition(&id) {
+            Some(Definition::Local(start, _)) => assert_eq!(start.line, 7),
+            d => panic!("unexpected definition {:?}", d),
+        }
+        assert_eq!(
+            analysis.hover(&id),
+            Some(String::from("def double(private field a) -> (field)"))
+        );
+        assert_eq!(analysis.hover("a"), None);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let analysis = Analysis::new(
+            "def main() -> (field):\n\tfield a = 1 +\n\treturn b\n",
+            Some(String::from(".")),
+        );
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(analysis.diagnostics[0].code, "E0001");
+        assert_eq!(analysis.symbols(), vec![]);
+
+        let analysis = Analysis::new(
+            "def main() -> (field):\n\treturn b\n",
+            Some(String::from(".")),
+        );
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(analysis.diagnostics[0].code, "E0003");
+        assert_eq!(analysis.symbols().len(), 1);
+    }
+}
@@ -0,0 +1,41 @@
+//
+// @file main.rs
+// @date 2019
+//
+// Language server for ZoKrates, speaking the language server protocol over stdio
+
+mod analysis;
+mod server;
+mod transport;
+
+use crate::server::Server;
+use crate::transport::{read_message, write_message};
+use std::io::{stdin, stdout};
+
+fn main() {
+    let stdin = stdin();
+    let stdout = stdout();
+    let mut input = stdin.lock();
+    let mut output = stdout.lock();
+
+    let mut server = Server::new();
+
+    while !server.exit {
+        let message = match read_message(&mut input) {
+            Ok(Some(message)) => message,
+            // the editor closed the connection
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("Invalid message: {}", e);
+                continue;
+            }
+        };
+
+        for response in server.handle(message) {
+            write_message(&mut output, &response).unwrap_or_else(|e| {
+                eprintln!("Unable to write message: {}", e);
+                std::process::exit(1);
+            });
+        }
+    }
+}
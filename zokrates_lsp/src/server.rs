@@ -0,0 +1,359 @@
+//! Handling of the requests and notifications sent by the editor.
+
+use crate::analysis::{identifier_at, Analysis, Definition, SymbolKind};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use zokrates_core::diagnostics::Severity;
+use zokrates_core::parser::Position;
+
+// error code of the protocol for requests which are not supported
+const METHOD_NOT_FOUND: i64 = -32601;
+
+struct Document {
+    source: String,
+    analysis: Analysis,
+}
+
+pub struct Server {
+    documents: HashMap<String, Document>,
+    /// Whether the editor asked for the server to stop
+    pub exit: bool,
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server {
+            documents: HashMap::new(),
+            exit: false,
+        }
+    }
+
+    /// Handles a message, returning the messages to send back
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let method = match message["method"].as_str() {
+            Some(method) => method.to_string(),
+            // responses to requests of the server, which it does not send
+            None => return vec![],
+        };
+        let params = &message["params"];
+
+        // requests carry an id to respond to, notifications do not
+        let result = match method.as_str() {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    // documents are sent in full on every change
+                    "textDocumentSync": 1,
+                    "definitionProvider": true,
+                    "hoverProvider": true,
+                    "documentSymbolProvider": true,
+                }
+            })),
+            "shutdown" => Some(Value::Null),
+            "exit" => {
+                self.exit = true;
+                return vec![];
+            }
+            "textDocument/didOpen" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                return vec![self.update(uri, text)];
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                    .unwrap_or("");
+                return vec![self.update(uri, text)];
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                self.documents.remove(uri);
+                return vec![publish_diagnostics(uri, vec![])];
+            }
+            "textDocument/definition" => Some(self.definition(params)),
+            "textDocument/hover" => Some(self.hover(params)),
+            "textDocument/documentSymbol" => Some(self.symbols(params)),
+            _ => None,
+        };
+
+        let id = match message.get("id") {
+            Some(id) => id.clone(),
+            None => return vec![],
+        };
+
+        match result {
+            Some(result) => vec![json!({"jsonrpc": "2.0", "id": id, "result": result})],
+            None => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": METHOD_NOT_FOUND,
+                    "message": format!("Unsupported method {}", method),
+                }
+            })],
+        }
+    }
+
+    // analyses the new version of a document and publishes its diagnostics
+    fn update(&mut self, uri: &str, text: &str) -> Value {
+        let location = uri_to_path(uri)
+            .and_then(|p| p.parent().map(|p| p.display().to_string()));
+        let analysis = Analysis::new(text, location);
+
+        let diagnostics = analysis
+            .diagnostics
+            .iter()
+            .map(|d| {
+                let (start, end) = match d.span {
+                    Some(ref span) => (span.start, span.end),
+                    None => (Position { line: 1, col: 1 }, Position { line: 1, col: 1 }),
+                };
+                json!({
+                    "range": range(start, end),
+                    "severity": match d.severity {
+                        Severity::Error => 1,
+                        Severity::Warning => 2,
+                    },
+                    "code": d.code,
+                    "source": "zokrates",
+                    "message": d.message,
+                })
+            })
+            .collect();
+
+        self.documents.insert(
+            uri.to_string(),
+            Document {
+                source: text.to_string(),
+                analysis,
+            },
+        );
+
+        publish_diagnostics(uri, diagnostics)
+    }
+
+    // the document a request refers to, and the identifier under the cursor
+    fn identifier<'a>(&'a self, params: &'a Value) -> Option<(&'a str, &'a Document, String)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let document = self.documents.get(uri)?;
+        let line = params["position"]["line"].as_u64()? as usize;
+        let character = params["position"]["character"].as_u64()? as usize;
+        let id = identifier_at(&document.source, line, character)?;
+        Some((uri, document, id))
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let (uri, document, id) = match self.identifier(params) {
+            Some(identifier) => identifier,
+            None => return Value::Null,
+        };
+
+        match document.analysis.definition(&id) {
+            Some(Definition::Local(start, end)) => json!({
+                "uri": uri,
+                "range": range(start, end),
+            }),
+            Some(Definition::File(path)) => {
+                let start = Position { line: 1, col: 1 };
+                json!({
+                    "uri": path_to_uri(&path),
+                    "range": range(start, start),
+                })
+            }
+            None => Value::Null,
+        }
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let (_, document, id) = match self.identifier(params) {
+            Some(identifier) => identifier,
+            None => return Value::Null,
+        };
+
+        match document.analysis.hover(&id) {
+            Some(declaration) => json!({
+                "contents": {
+                    "language": "zokrates",
+                    "value": declaration,
+                }
+            }),
+            None => Value::Null,
+        }
+    }
+
+    fn symbols(&self, params: &Value) -> Value {
+        let document = match params["textDocument"]["uri"]
+            .as_str()
+            .and_then(|uri| self.documents.get(uri))
+        {
+            Some(document) => document,
+            None => return Value::Null,
+        };
+
+        document
+            .analysis
+            .symbols()
+            .into_iter()
+            .map(|s| {
+                json!({
+                    "name": s.name,
+                    "kind": match s.kind {
+                        SymbolKind::Module => 2,
+                        SymbolKind::Struct => 23,
+                        SymbolKind::Constant => 14,
+                        SymbolKind::Function => 12,
+                    },
+                    "range": range(s.start, s.end),
+                    "selectionRange": range(s.start, s.start),
+                })
+            })
+            .collect()
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": uri,
+            "diagnostics": diagnostics,
+        }
+    })
+}
+
+// positions of the protocol start at zero, while ours start at one
+fn range(start: Position, end: Position) -> Value {
+    json!({
+        "start": {"line": start.line - 1, "character": start.col - 1},
+        "end": {"line": end.line - 1, "character": end.col - 1},
+    })
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    if !uri.starts_with("file://") {
+        return None;
+    }
+
+    // decode the escaped characters of the path, such as spaces
+    let bytes = uri["file://".len()..].as_bytes();
+    let mut path = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' if i + 3 <= bytes.len() => std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(b) => {
+                path.push(b);
+                i += 3;
+            }
+            None => {
+                path.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(path).ok().map(PathBuf::from)
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.display().to_string();
+    let escaped: String = path
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect();
+    format!("file://{}", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_conversion() {
+        let path = Path::new("/home/user/my circuits/main.code");
+        let uri = path_to_uri(path);
+        assert_eq!(uri, "file:///home/user/my%20circuits/main.code");
+        assert_eq!(uri_to_path(&uri).unwrap(), path);
+    }
+
+    #[test]
+    fn open_and_hover() {
+        let mut server = Server::new();
+        let uri = "file:///tmp/main.code";
+
+        let messages = server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {"textDocument": {
+                "uri": uri,
+                "languageId": "zokrates",
+                "version": 1,
+                "text": "def main(field a) -> (field):\n\treturn b\n",
+            }}
+        }));
+        assert_eq!(
+            messages[0]["params"]["diagnostics"][0]["range"]["start"],
+            json!({"line": 1, "character": 8})
+        );
+
+        let messages = server.handle(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/hover",
+            "params": {
+                "textDocument": {"uri": uri},
+                "position": {"line": 0, "character": 5},
+            }
+        }));
+        assert_eq!(
+            messages[0]["result"]["contents"]["value"],
+            "def main(field a) -> (field)"
+        );
+    }
+
+    #[test]
+    fn incomplete_documents() {
+        let mut server = Server::new();
+        let uri = "file:///tmp/main.code";
+
+        // documents are analysed as they are typed, so they are often not valid yet
+        for (version, text) in vec![
+            "def main() -> (field):\n\tfield a = 1\n",
+            "def main(field[ a)",
+            "import \"foo",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let messages = server.handle(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {"textDocument": {
+                    "uri": uri,
+                    "languageId": "zokrates",
+                    "version": version,
+                    "text": text,
+                }}
+            }));
+            assert_eq!(
+                messages[0]["params"]["diagnostics"][0]["code"], "E0001",
+                "{}",
+                text
+            );
+        }
+    }
+}
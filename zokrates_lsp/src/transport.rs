@@ -0,0 +1,61 @@
+//! Messages of the language server protocol, exchanged as JSON-RPC over stdio.
+
+use serde_json::Value;
+use std::io;
+use std::io::{BufRead, Write};
+
+/// Reads the next message, or `None` once the input is closed
+pub fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<Value>> {
+    let mut length = None;
+
+    // headers are terminated by an empty line
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if header.to_lowercase().starts_with("content-length:") {
+            length = header["content-length:".len()..].trim().parse::<usize>().ok();
+        }
+    }
+
+    let length = length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length"))?;
+
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write_message<W: Write>(output: &mut W, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let message = json!({"jsonrpc": "2.0", "method": "initialized", "params": {}});
+
+        let mut buffer = vec![];
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut input = io::BufReader::new(&buffer[..]);
+        assert_eq!(read_message(&mut input).unwrap(), Some(message));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+}